@@ -58,12 +58,49 @@ impl FromMeta for Visible {
         match value {
             Lit::Bool(LitBool { value: true, .. }) => Ok(Visible::None),
             Lit::Bool(LitBool { value: false, .. }) => Ok(Visible::HiddenAlways),
-            Lit::Str(str) => Ok(Visible::FnName(syn::parse_str::<Path>(&str.value())?)),
+            Lit::Str(str) => Ok(Visible::FnName(str.parse::<Path>().map_err(|err| {
+                darling::Error::custom(format!("Invalid visible function: {}", err)).with_span(str)
+            })?)),
             _ => Err(darling::Error::unexpected_lit_type(value)),
         }
     }
 }
 
+/// The metadata of a type or a field, declared as `meta(key = value, ...)`.
+#[derive(Default, Clone)]
+pub struct Metadata(pub Vec<(String, Lit)>);
+
+impl FromMeta for Metadata {
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        let mut res = Vec::new();
+        for item in items {
+            match item {
+                NestedMeta::Meta(Meta::NameValue(nv)) => {
+                    let key = match nv.path.get_ident() {
+                        Some(ident) => ident.to_string(),
+                        None => {
+                            return Err(
+                                darling::Error::custom("Invalid metadata key.").with_span(&nv.path)
+                            )
+                        }
+                    };
+                    match &nv.lit {
+                        Lit::Str(_) | Lit::Bool(_) | Lit::Int(_) | Lit::Float(_) => {}
+                        lit => return Err(darling::Error::unexpected_lit_type(lit)),
+                    }
+                    res.push((key, nv.lit.clone()));
+                }
+                _ => {
+                    return Err(
+                        darling::Error::custom("Metadata must be `key = value`.").with_span(item)
+                    )
+                }
+            }
+        }
+        Ok(Metadata(res))
+    }
+}
+
 pub struct PathList(pub Vec<Path>);
 
 impl FromMeta for PathList {
@@ -140,11 +177,23 @@ pub struct SimpleObjectField {
     #[darling(default)]
     pub requires: Option<String>,
     #[darling(default)]
+    pub shareable: bool,
+    #[darling(default)]
+    pub override_from: Option<String>,
+    #[darling(default)]
     pub guard: Option<Meta>,
     #[darling(default)]
+    pub public: bool,
+    #[darling(default)]
     pub visible: Option<Visible>,
+    #[darling(default)]
+    pub meta: Metadata,
+    #[darling(default)]
+    pub complexity: Option<ComplexityType>,
     #[darling(default, multiple)]
     pub derived: Vec<DerivedField>,
+    #[darling(default)]
+    pub user_errors: bool,
 }
 
 #[derive(FromDeriveInput)]
@@ -172,7 +221,11 @@ pub struct SimpleObject {
     #[darling(default)]
     pub extends: bool,
     #[darling(default)]
+    pub shareable: bool,
+    #[darling(default)]
     pub visible: Option<Visible>,
+    #[darling(default)]
+    pub meta: Metadata,
     #[darling(default, multiple, rename = "concrete")]
     pub concretes: Vec<ConcreteType>,
     #[darling(default)]
@@ -190,6 +243,8 @@ pub struct Argument {
     pub key: bool, // for entity
     pub visible: Option<Visible>,
     pub secret: bool,
+    pub ctx_data: bool,
+    pub flatten: bool,
 }
 
 #[derive(FromMeta, Default)]
@@ -201,13 +256,16 @@ pub struct Object {
     pub rename_args: Option<RenameRule>,
     pub cache_control: CacheControl,
     pub extends: bool,
+    pub shareable: bool,
     pub use_type_description: bool,
     pub visible: Option<Visible>,
+    pub meta: Metadata,
     pub serial: bool,
     #[darling(multiple, rename = "concrete")]
     pub concretes: Vec<ConcreteType>,
 }
 
+#[derive(Clone)]
 pub enum ComplexityType {
     Const(usize),
     Fn(String),
@@ -217,7 +275,7 @@ impl FromMeta for ComplexityType {
     fn from_value(value: &Lit) -> darling::Result<Self> {
         match value {
             Lit::Int(n) => {
-                let n = n.base10_parse::<i32>().unwrap();
+                let n = n.base10_parse::<i32>()?;
                 if n < 0 {
                     return Err(darling::Error::custom(
                         "The complexity must be greater than or equal to 0.",
@@ -242,9 +300,15 @@ pub struct ObjectField {
     pub external: bool,
     pub provides: Option<String>,
     pub requires: Option<String>,
+    pub shareable: bool,
+    pub override_from: Option<String>,
     pub guard: Option<Meta>,
+    pub public: bool,
     pub visible: Option<Visible>,
+    pub meta: Metadata,
     pub complexity: Option<ComplexityType>,
+    pub memoize: bool,
+    pub blocking: bool,
     #[darling(default, multiple)]
     pub derived: Vec<DerivedField>,
 }
@@ -254,7 +318,7 @@ pub struct ObjectField {
 /// Derivied fields arguments: are used to generate derivied fields.
 pub struct DerivedField {
     pub name: Option<Ident>,
-    pub into: Option<String>,
+    pub into: Option<LitStr>,
     pub with: Option<Path>,
     #[darling(default)]
     pub owned: Option<bool>,
@@ -278,6 +342,8 @@ pub struct Enum {
     pub remote: Option<String>,
     #[darling(default)]
     pub visible: Option<Visible>,
+    #[darling(default)]
+    pub meta: Metadata,
 }
 
 #[derive(FromVariant)]
@@ -309,6 +375,10 @@ pub struct Union {
     pub name: Option<String>,
     #[darling(default)]
     pub visible: Option<Visible>,
+    #[darling(default)]
+    pub meta: Metadata,
+    #[darling(default)]
+    pub resolve_type: Option<Path>,
 }
 
 #[derive(FromVariant)]
@@ -320,6 +390,8 @@ pub struct UnionItem {
     #[darling(default)]
     pub flatten: bool,
     #[darling(default)]
+    pub dynamic: bool,
+    #[darling(default)]
     pub visible: Option<Visible>,
 }
 
@@ -346,6 +418,8 @@ pub struct InputObjectField {
     #[darling(default)]
     pub visible: Option<Visible>,
     #[darling(default)]
+    pub meta: Metadata,
+    #[darling(default)]
     pub secret: bool,
 }
 
@@ -365,10 +439,91 @@ pub struct InputObject {
     pub rename_fields: Option<RenameRule>,
     #[darling(default)]
     pub visible: Option<Visible>,
+    #[darling(default)]
+    pub meta: Metadata,
+    #[darling(default)]
+    pub max_depth: Option<usize>,
     #[darling(default, multiple, rename = "concrete")]
     pub concretes: Vec<ConcreteType>,
 }
 
+#[derive(FromField)]
+#[darling(attributes(filterable), forward_attrs(graphql))]
+pub struct FilterableField {
+    pub ident: Option<Ident>,
+    pub ty: Type,
+    pub attrs: Vec<Attribute>,
+
+    #[darling(default)]
+    pub skip: bool,
+    #[darling(default)]
+    pub skip_filter: bool,
+    #[darling(default)]
+    pub skip_order: bool,
+}
+
+#[derive(FromDeriveInput)]
+#[darling(attributes(filterable), forward_attrs(graphql))]
+pub struct Filterable {
+    pub ident: Ident,
+    pub vis: Visibility,
+    pub attrs: Vec<Attribute>,
+    pub data: Data<Ignored, FilterableField>,
+}
+
+#[derive(FromField)]
+#[darling(attributes(crud), forward_attrs(graphql))]
+pub struct CrudObjectField {
+    pub ident: Option<Ident>,
+    pub ty: Type,
+    pub attrs: Vec<Attribute>,
+
+    #[darling(default)]
+    pub id: bool,
+    #[darling(default)]
+    pub skip: bool,
+}
+
+#[derive(FromDeriveInput)]
+#[darling(attributes(crud), forward_attrs(graphql))]
+pub struct CrudObject {
+    pub ident: Ident,
+    pub vis: Visibility,
+    pub attrs: Vec<Attribute>,
+    pub data: Data<Ignored, CrudObjectField>,
+}
+
+#[derive(FromVariant)]
+#[darling(attributes(graphql), forward_attrs(doc))]
+pub struct OneofObjectField {
+    pub ident: Ident,
+    pub fields: Fields<syn::Type>,
+    pub attrs: Vec<Attribute>,
+
+    #[darling(default)]
+    pub name: Option<String>,
+    #[darling(default)]
+    pub visible: Option<Visible>,
+}
+
+#[derive(FromDeriveInput)]
+#[darling(attributes(graphql), forward_attrs(doc))]
+pub struct OneofObject {
+    pub ident: Ident,
+    pub generics: Generics,
+    pub attrs: Vec<Attribute>,
+    pub data: Data<OneofObjectField, Ignored>,
+
+    #[darling(default)]
+    pub internal: bool,
+    #[darling(default)]
+    pub name: Option<String>,
+    #[darling(default)]
+    pub rename_fields: Option<RenameRule>,
+    #[darling(default)]
+    pub visible: Option<Visible>,
+}
+
 #[derive(FromMeta)]
 pub struct InterfaceFieldArgument {
     pub name: String,
@@ -407,12 +562,18 @@ pub struct InterfaceField {
     pub requires: Option<String>,
     #[darling(default)]
     pub visible: Option<Visible>,
+    #[darling(default)]
+    pub meta: Metadata,
 }
 
 #[derive(FromVariant)]
+#[darling(attributes(graphql))]
 pub struct InterfaceMember {
     pub ident: Ident,
     pub fields: Fields<syn::Type>,
+
+    #[darling(default)]
+    pub dynamic: bool,
 }
 
 #[derive(FromDeriveInput)]
@@ -437,6 +598,12 @@ pub struct Interface {
     pub extends: bool,
     #[darling(default)]
     pub visible: Option<Visible>,
+    #[darling(default)]
+    pub meta: Metadata,
+    #[darling(default)]
+    pub resolve_type: Option<Path>,
+    #[darling(default)]
+    pub node: bool,
 }
 
 #[derive(FromMeta, Default)]
@@ -479,8 +646,10 @@ pub struct SubscriptionField {
     pub name: Option<String>,
     pub deprecation: Deprecation,
     pub guard: Option<Meta>,
+    pub public: bool,
     pub visible: Option<Visible>,
     pub complexity: Option<ComplexityType>,
+    pub topic: Option<String>,
 }
 
 #[derive(FromField)]
@@ -676,7 +845,12 @@ pub struct ComplexObjectField {
     pub external: bool,
     pub provides: Option<String>,
     pub requires: Option<String>,
+    pub shareable: bool,
+    pub override_from: Option<String>,
     pub guard: Option<Meta>,
+    pub public: bool,
     pub visible: Option<Visible>,
+    pub meta: Metadata,
     pub complexity: Option<ComplexityType>,
+    pub memoize: bool,
 }
@@ -1,8 +1,9 @@
+use std::collections::HashSet;
+
 use proc_macro::TokenStream;
 use proc_macro2::Ident;
 use quote::quote;
 use std::iter::FromIterator;
-use std::str::FromStr;
 use syn::ext::IdentExt;
 use syn::{
     punctuated::Punctuated, Block, Error, FnArg, ImplItem, ItemImpl, Pat, ReturnType, Token, Type,
@@ -12,15 +13,19 @@ use syn::{
 use crate::args::{self, ComplexityType, RenameRuleExt, RenameTarget};
 use crate::output_type::OutputType;
 use crate::utils::{
-    extract_input_args, gen_deprecation, generate_default, generate_guards, generate_validator,
-    get_cfg_attrs, get_crate_name, get_param_getter_ident, get_rustdoc, get_type_path_and_name,
-    parse_complexity_expr, parse_graphql_attrs, remove_graphql_attrs, visible_fn, GeneratorResult,
+    check_unique_name, expand_graphql_cfg_attrs, extract_input_args, gen_deprecation, gen_metadata,
+    generate_blocking_method, generate_ctx_data_getter, generate_default, generate_default_guard,
+    generate_flatten_args, generate_guards, generate_validator, get_cfg_attrs, get_crate_name,
+    get_param_getter_ident, get_rustdoc, get_type_path_and_name, parse_complexity_expr,
+    parse_graphql_attrs, parse_lit_str, remove_graphql_attrs, visible_fn, GeneratorResult,
 };
 
 pub fn generate(
     object_args: &args::ComplexObject,
     item_impl: &mut ItemImpl,
 ) -> GeneratorResult<TokenStream> {
+    let mut cfg_variants = expand_graphql_cfg_attrs(item_impl)?;
+
     let crate_name = get_crate_name(object_args.internal);
     let (self_ty, _) = get_type_path_and_name(item_impl.self_ty.as_ref())?;
     let generics = &item_impl.generics;
@@ -41,9 +46,7 @@ pub fn generate(
                     let base_function_name = &method.sig.ident;
                     let name = derived.name.unwrap();
                     let with = derived.with;
-                    let into = Type::Verbatim(
-                        proc_macro2::TokenStream::from_str(&derived.into.unwrap()).unwrap(),
-                    );
+                    let into = parse_lit_str::<Type>(&derived.into.unwrap(), "type")?;
 
                     let mut new_impl = method.clone();
                     new_impl.sig.ident = name;
@@ -114,7 +117,9 @@ pub fn generate(
     }
     item_impl.items.append(&mut derived_impls);
 
-    for item in &mut item_impl.items {
+    let mut field_names = HashSet::new();
+    let mut blocking_impls = Vec::new();
+    for item in cfg_variants.items_mut(&mut item_impl.items) {
         if let ImplItem::Method(method) = item {
             let method_args: args::ObjectField =
                 parse_graphql_attrs(&method.attrs)?.unwrap_or_default();
@@ -122,12 +127,22 @@ pub fn generate(
                 remove_graphql_attrs(&mut method.attrs);
                 continue;
             }
+            if method_args.blocking {
+                blocking_impls.push(ImplItem::Method(generate_blocking_method(
+                    &crate_name,
+                    method,
+                )?));
+            }
 
             let field_name = method_args.name.clone().unwrap_or_else(|| {
                 object_args
                     .rename_fields
                     .rename(method.sig.ident.unraw().to_string(), RenameTarget::Field)
             });
+            // Methods with `cfg` attributes may share a name when only one of them is compiled.
+            if get_cfg_attrs(&method.attrs).is_empty() {
+                check_unique_name(&mut field_names, &field_name, "field", &method.sig.ident)?;
+            }
             let field_desc = get_rustdoc(&method.attrs)?
                 .map(|s| quote! { ::std::option::Option::Some(#s) })
                 .unwrap_or_else(|| quote! {::std::option::Option::None});
@@ -141,6 +156,11 @@ pub fn generate(
                 Some(provides) => quote! { ::std::option::Option::Some(#provides) },
                 None => quote! { ::std::option::Option::None },
             };
+            let shareable = method_args.shareable;
+            let override_from = match &method_args.override_from {
+                Some(from) => quote! { ::std::option::Option::Some(#from) },
+                None => quote! { ::std::option::Option::None },
+            };
             let cache_control = {
                 let public = method_args.cache_control.is_public();
                 let max_age = method_args.cache_control.max_age;
@@ -180,10 +200,29 @@ pub fn generate(
                     validator,
                     visible,
                     secret,
+                    ctx_data,
+                    flatten,
                     ..
                 },
             ) in &args
             {
+                if *ctx_data {
+                    let param_ident = &ident.ident;
+                    use_params.push(quote! { #param_ident });
+                    get_params.push(generate_ctx_data_getter(&crate_name, ident, ty));
+                    continue;
+                }
+
+                if *flatten {
+                    schema_args.push(generate_flatten_args(&crate_name, ty));
+                    let param_ident = &ident.ident;
+                    use_params.push(quote! { #param_ident });
+                    get_params.push(quote! {
+                        let #ident: #ty = ctx.flatten_param_value()?;
+                    });
+                    continue;
+                }
+
                 let name = name.clone().unwrap_or_else(|| {
                     object_args
                         .rename_args
@@ -267,10 +306,9 @@ pub fn generate(
                                     default_with,
                                     ..
                                 },
-                            )) = args
-                                .iter()
-                                .find(|(pat_ident, _, _)| pat_ident.ident == variable)
-                            {
+                            )) = args.iter().find(|(pat_ident, _, arg)| {
+                                !arg.ctx_data && !arg.flatten && pat_ident.ident == variable
+                            }) {
                                 let default = match generate_default(default, default_with)? {
                                     Some(default) => {
                                         quote! { ::std::option::Option::Some(|| -> #ty { #default }) }
@@ -300,7 +338,15 @@ pub fn generate(
                 quote! { ::std::option::Option::None }
             };
 
+            let field_metadata = gen_metadata(
+                &crate_name,
+                &method_args.meta,
+                &quote! { &<Self as #crate_name::Type>::type_name() },
+                Some(field_name.as_str()),
+            )?;
             schema_fields.push(quote! {
+                #(#cfg_attrs)*
+                #field_metadata
                 #(#cfg_attrs)*
                 fields.push((#field_name.to_string(), #crate_name::registry::MetaField {
                     name: ::std::borrow::ToOwned::to_owned(#field_name),
@@ -316,6 +362,8 @@ pub fn generate(
                     external: #external,
                     provides: #provides,
                     requires: #requires,
+                    shareable: #shareable,
+                    override_from: #override_from,
                     visible: #visible,
                     compute_complexity: #complexity,
                 }));
@@ -338,21 +386,31 @@ pub fn generate(
                         .expect("invalid result type");
             }
 
-            let resolve_obj = quote! {
-                {
-                    let res = self.#field_ident(ctx, #(#use_params),*).await;
-                    res.map_err(|err| ::std::convert::Into::<#crate_name::Error>::into(err).into_server_error(ctx.item.pos))?
+            let resolve_obj = if method_args.memoize {
+                quote! {
+                    ctx.memoize(::std::any::type_name::<Self>(), async move {
+                        let res = self.#field_ident(ctx, #(#use_params),*).await;
+                        res.map_err(|err| ::std::convert::Into::<#crate_name::Error>::into(err).into_server_error(ctx.item.pos))
+                    }).await?
+                }
+            } else {
+                quote! {
+                    {
+                        let res = self.#field_ident(ctx, #(#use_params),*).await;
+                        res.map_err(|err| ::std::convert::Into::<#crate_name::Error>::into(err).into_server_error(ctx.item.pos))?
+                    }
                 }
             };
 
             let guard = match &method_args.guard {
-                Some(meta_list) => generate_guards(&crate_name, meta_list)?,
-                None => None,
+                Some(meta_list) => generate_guards(&crate_name, meta_list)?
+                    .map(|guard| quote! { #guard.check(ctx) }),
+                None => generate_default_guard(object_args.internal, method_args.public),
             };
 
             let guard = guard.map(|guard| {
                 quote! {
-                    #guard.check(ctx).await
+                    #guard.await
                         .map_err(|err| err.into_server_error(ctx.item.pos))?;
                 }
             });
@@ -371,6 +429,8 @@ pub fn generate(
             remove_graphql_attrs(&mut method.attrs);
         }
     }
+    cfg_variants.finish(&mut item_impl.items);
+    item_impl.items.append(&mut blocking_impls);
 
     let expanded = quote! {
         #item_impl
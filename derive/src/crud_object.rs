@@ -0,0 +1,260 @@
+use darling::ast::Data;
+use inflector::Inflector;
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Error, Lit, Type, TypeGroup};
+
+use crate::args;
+use crate::utils::{
+    find_value, get_crate_name, graphql_items, has_flag, is_phantom_data, option_inner_type,
+    GeneratorResult,
+};
+
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|s| s.ident == "Option")
+            .unwrap_or_default(),
+        Type::Group(TypeGroup { elem, .. }) => is_option(elem),
+        _ => false,
+    }
+}
+
+pub fn generate(object_args: &args::CrudObject) -> GeneratorResult<TokenStream> {
+    let ident = &object_args.ident;
+    let vis = &object_args.vis;
+    let s = match &object_args.data {
+        Data::Struct(s) => s,
+        _ => {
+            return Err(
+                Error::new_spanned(ident, "CrudObject can only be applied to an struct.").into(),
+            )
+        }
+    };
+
+    let type_items = graphql_items(&object_args.attrs)?;
+    let internal = has_flag(&type_items, "internal");
+    let crate_name = get_crate_name(internal);
+    let gql_typename = match find_value(&type_items, "name") {
+        Some(Lit::Str(name)) => name.value(),
+        _ => ident.to_string(),
+    };
+
+    let mut type_attrs = Vec::new();
+    if internal {
+        type_attrs.push(quote! { internal });
+    }
+    if let Some(rename_fields) = find_value(&type_items, "rename_fields") {
+        type_attrs.push(quote! { rename_fields = #rename_fields });
+    }
+
+    // Without an explicit `id` attribute, the field named `id` is the id.
+    let has_id_attr = s.fields.iter().any(|field| field.id);
+    let mut id = None;
+    let mut create_fields = Vec::new();
+    let mut update_fields = Vec::new();
+    for field in &s.fields {
+        let field_ident = match &field.ident {
+            Some(ident) => ident,
+            None => return Err(Error::new_spanned(ident, "All fields must be named.").into()),
+        };
+        let ty = &field.ty;
+
+        if field.id || (!has_id_attr && field_ident == "id") {
+            if id.is_some() {
+                return Err(Error::new_spanned(
+                    field_ident,
+                    "A CRUD object can only have one `id` field.",
+                )
+                .into());
+            }
+            id = Some((field_ident, ty));
+            continue;
+        }
+
+        let field_items = graphql_items(&field.attrs)?;
+        if field.skip || has_flag(&field_items, "skip") || is_phantom_data(ty) {
+            continue;
+        }
+
+        let name_attr =
+            find_value(&field_items, "name").map(|name| quote! { #[graphql(name = #name)] });
+        create_fields.push(quote! {
+            #name_attr
+            pub #field_ident: #ty
+        });
+        let update_ty = if is_option(ty) {
+            let ty = option_inner_type(ty);
+            quote! { #crate_name::MaybeUndefined<#ty> }
+        } else {
+            quote! { ::std::option::Option<#ty> }
+        };
+        update_fields.push(quote! {
+            #name_attr
+            pub #field_ident: #update_ty
+        });
+    }
+
+    let (id_ident, id_ty) = match id {
+        Some(id) => id,
+        None => {
+            return Err(Error::new_spanned(ident, "A CRUD object must have an `id` field.").into())
+        }
+    };
+
+    let snake_name = ident.to_string().to_snake_case();
+    let mut list_name = snake_name.to_plural();
+    if list_name == snake_name {
+        list_name = format!("{}_list", snake_name);
+    }
+    let get_ident = format_ident!("{}", snake_name);
+    let list_ident = format_ident!("{}", list_name);
+    let create_ident = format_ident!("create_{}", snake_name);
+    let update_ident = format_ident!("update_{}", snake_name);
+    let delete_ident = format_ident!("delete_{}", snake_name);
+    let get_desc = format!(" Returns the `{}` with the id.", gql_typename);
+    let list_desc = format!(" Returns the `{}` entities.", gql_typename);
+    let create_desc = format!(" Create a `{}`.", gql_typename);
+    let update_desc = format!(
+        " Update the `{}` with the id, returns `null` if it does not exist.",
+        gql_typename
+    );
+    let delete_desc = format!(
+        " Delete the `{}` with the id, returns `false` if it does not exist.",
+        gql_typename
+    );
+
+    let create_input_ident = format_ident!("{}CreateInput", ident);
+    let create_input_name = format!("{}CreateInput", gql_typename);
+    let create_input_desc = format!(" The input to create a `{}`.", gql_typename);
+    let update_input_ident = format_ident!("{}UpdateInput", ident);
+    let update_input_name = format!("{}UpdateInput", gql_typename);
+    let update_input_desc = format!(
+        " The input to update a `{}`, the fields that are not present are unchanged.",
+        gql_typename
+    );
+    let query_ident = format_ident!("{}CrudQuery", ident);
+    let query_name = format!("{}CrudQuery", gql_typename);
+    let query_desc = format!(" The query root fields of `{}`.", gql_typename);
+    let mutation_ident = format_ident!("{}CrudMutation", ident);
+    let mutation_name = format!("{}CrudMutation", gql_typename);
+    let mutation_desc = format!(" The mutation root fields of `{}`.", gql_typename);
+    let object_internal = if internal {
+        Some(quote! { internal, })
+    } else {
+        None
+    };
+
+    let expanded = quote! {
+        #[doc = #create_input_desc]
+        #[derive(#crate_name::InputObject)]
+        #[graphql(name = #create_input_name, #(#type_attrs),*)]
+        #vis struct #create_input_ident {
+            #(#create_fields),*
+        }
+
+        #[doc = #update_input_desc]
+        #[derive(#crate_name::InputObject)]
+        #[graphql(name = #update_input_name, #(#type_attrs),*)]
+        #vis struct #update_input_ident {
+            #(#update_fields),*
+        }
+
+        #[doc = #query_desc]
+        #vis struct #query_ident {
+            store: ::std::sync::Arc<dyn #crate_name::crud::EntityStore<#ident>>,
+        }
+
+        impl #query_ident {
+            /// Create the root fields, whose resolvers call the store.
+            pub fn new(store: ::std::sync::Arc<dyn #crate_name::crud::EntityStore<#ident>>) -> Self {
+                Self { store }
+            }
+        }
+
+        #[#crate_name::Object(#object_internal name = #query_name)]
+        impl #query_ident {
+            #[doc = #get_desc]
+            async fn #get_ident(
+                &self,
+                ctx: &#crate_name::Context<'_>,
+                #id_ident: #id_ty,
+            ) -> #crate_name::Result<::std::option::Option<#ident>> {
+                self.store.get(ctx, #id_ident).await
+            }
+
+            #[doc = #list_desc]
+            async fn #list_ident(
+                &self,
+                ctx: &#crate_name::Context<'_>,
+                after: ::std::option::Option<::std::string::String>,
+                before: ::std::option::Option<::std::string::String>,
+                first: ::std::option::Option<i32>,
+                last: ::std::option::Option<i32>,
+            ) -> #crate_name::Result<#crate_name::connection::Connection<usize, #ident>> {
+                #crate_name::crud::list_connection(&*self.store, ctx, after, before, first, last).await
+            }
+        }
+
+        #[doc = #mutation_desc]
+        #vis struct #mutation_ident {
+            store: ::std::sync::Arc<dyn #crate_name::crud::EntityStore<#ident>>,
+        }
+
+        impl #mutation_ident {
+            /// Create the root fields, whose resolvers call the store.
+            pub fn new(store: ::std::sync::Arc<dyn #crate_name::crud::EntityStore<#ident>>) -> Self {
+                Self { store }
+            }
+        }
+
+        #[#crate_name::Object(#object_internal name = #mutation_name)]
+        impl #mutation_ident {
+            #[doc = #create_desc]
+            async fn #create_ident(
+                &self,
+                ctx: &#crate_name::Context<'_>,
+                input: #create_input_ident,
+            ) -> #crate_name::Result<#ident> {
+                self.store.create(ctx, input).await
+            }
+
+            #[doc = #update_desc]
+            async fn #update_ident(
+                &self,
+                ctx: &#crate_name::Context<'_>,
+                #id_ident: #id_ty,
+                input: #update_input_ident,
+            ) -> #crate_name::Result<::std::option::Option<#ident>> {
+                self.store.update(ctx, #id_ident, input).await
+            }
+
+            #[doc = #delete_desc]
+            async fn #delete_ident(
+                &self,
+                ctx: &#crate_name::Context<'_>,
+                #id_ident: #id_ty,
+            ) -> #crate_name::Result<bool> {
+                self.store.delete(ctx, #id_ident).await
+            }
+        }
+
+        impl #crate_name::crud::CrudEntity for #ident {
+            type Id = #id_ty;
+            type CreateInput = #create_input_ident;
+            type UpdateInput = #update_input_ident;
+
+            fn crud_module(
+                store: ::std::sync::Arc<dyn #crate_name::crud::EntityStore<Self>>,
+            ) -> #crate_name::SchemaModule {
+                #crate_name::SchemaModule::new(#gql_typename)
+                    .query(#query_ident::new(::std::clone::Clone::clone(&store)))
+                    .mutation(#mutation_ident::new(store))
+            }
+        }
+    };
+    Ok(expanded.into())
+}
@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use darling::ast::Data;
 use proc_macro::TokenStream;
 use quote::quote;
@@ -5,7 +7,10 @@ use syn::ext::IdentExt;
 use syn::Error;
 
 use crate::args::{self, RenameRuleExt, RenameTarget};
-use crate::utils::{gen_deprecation, get_crate_name, get_rustdoc, visible_fn, GeneratorResult};
+use crate::utils::{
+    check_unique_name, gen_deprecation, gen_metadata, get_crate_name, get_rustdoc, visible_fn,
+    GeneratorResult,
+};
 
 pub fn generate(enum_args: &args::Enum) -> GeneratorResult<TokenStream> {
     let crate_name = get_crate_name(enum_args.internal);
@@ -20,6 +25,13 @@ pub fn generate(enum_args: &args::Enum) -> GeneratorResult<TokenStream> {
         .clone()
         .unwrap_or_else(|| RenameTarget::Type.rename(ident.to_string()));
 
+    let type_metadata = gen_metadata(
+        &crate_name,
+        &enum_args.meta,
+        &quote! { #gql_typename },
+        None,
+    )?;
+
     let desc = get_rustdoc(&enum_args.attrs)?
         .map(|s| quote! { ::std::option::Option::Some(#s) })
         .unwrap_or_else(|| quote! {::std::option::Option::None});
@@ -28,6 +40,7 @@ pub fn generate(enum_args: &args::Enum) -> GeneratorResult<TokenStream> {
     let mut items = Vec::new();
     let mut schema_enum_items = Vec::new();
 
+    let mut item_names = HashSet::new();
     for variant in e {
         if !variant.fields.is_empty() {
             return Err(Error::new_spanned(
@@ -46,6 +59,7 @@ pub fn generate(enum_args: &args::Enum) -> GeneratorResult<TokenStream> {
                 .rename_items
                 .rename(variant.ident.unraw().to_string(), RenameTarget::EnumItem)
         });
+        check_unique_name(&mut item_names, &gql_item_name, "item", item_ident)?;
         let item_deprecation = gen_deprecation(&variant.deprecation, &crate_name);
         let item_desc = get_rustdoc(&variant.attrs)?
             .map(|s| quote! { ::std::option::Option::Some(#s) })
@@ -131,6 +145,7 @@ pub fn generate(enum_args: &args::Enum) -> GeneratorResult<TokenStream> {
 
             fn create_type_info(registry: &mut #crate_name::registry::Registry) -> ::std::string::String {
                 registry.create_type::<Self, _>(|registry| {
+                    #type_metadata
                     #crate_name::registry::MetaType::Enum {
                         name: ::std::borrow::ToOwned::to_owned(#gql_typename),
                         description: #desc,
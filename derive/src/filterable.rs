@@ -0,0 +1,101 @@
+use darling::ast::Data;
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Error, Lit};
+
+use crate::args;
+use crate::utils::{
+    find_value, get_crate_name, graphql_items, has_flag, is_phantom_data, option_inner_type,
+    GeneratorResult,
+};
+
+pub fn generate(filterable_args: &args::Filterable) -> GeneratorResult<TokenStream> {
+    let ident = &filterable_args.ident;
+    let vis = &filterable_args.vis;
+    let s = match &filterable_args.data {
+        Data::Struct(s) => s,
+        _ => {
+            return Err(
+                Error::new_spanned(ident, "Filterable can only be applied to an struct.").into(),
+            )
+        }
+    };
+
+    let type_items = graphql_items(&filterable_args.attrs)?;
+    let internal = has_flag(&type_items, "internal");
+    let crate_name = get_crate_name(internal);
+    let gql_typename = match find_value(&type_items, "name") {
+        Some(Lit::Str(name)) => name.value(),
+        _ => ident.to_string(),
+    };
+
+    let mut type_attrs = Vec::new();
+    if internal {
+        type_attrs.push(quote! { internal });
+    }
+    if let Some(rename_fields) = find_value(&type_items, "rename_fields") {
+        type_attrs.push(quote! { rename_fields = #rename_fields });
+    }
+
+    let mut filter_fields = Vec::new();
+    let mut order_fields = Vec::new();
+    for field in &s.fields {
+        let field_ident = match &field.ident {
+            Some(ident) => ident,
+            None => return Err(Error::new_spanned(ident, "All fields must be named.").into()),
+        };
+        let field_items = graphql_items(&field.attrs)?;
+        if field.skip || has_flag(&field_items, "skip") || is_phantom_data(&field.ty) {
+            continue;
+        }
+
+        let name_attr =
+            find_value(&field_items, "name").map(|name| quote! { #[graphql(name = #name)] });
+        let ty = option_inner_type(&field.ty);
+        if !field.skip_filter {
+            filter_fields.push(quote! {
+                #name_attr
+                pub #field_ident: ::std::option::Option<#crate_name::FieldFilter<#ty>>
+            });
+        }
+        if !field.skip_order {
+            order_fields.push(quote! {
+                #name_attr
+                pub #field_ident: ::std::option::Option<#crate_name::OrderDirection>
+            });
+        }
+    }
+
+    let filter_ident = format_ident!("{}FilterInput", ident);
+    let filter_name = format!("{}FilterInput", gql_typename);
+    let filter_desc = format!(" The filter of `{}`.", gql_typename);
+    let order_ident = format_ident!("{}OrderByInput", ident);
+    let order_name = format!("{}OrderByInput", gql_typename);
+    let order_desc = format!(
+        " The ordering of `{}`, which should set a single field.",
+        gql_typename
+    );
+
+    let expanded = quote! {
+        #[doc = #filter_desc]
+        #[derive(#crate_name::InputObject, ::std::fmt::Debug, ::std::clone::Clone, ::std::default::Default)]
+        #[graphql(name = #filter_name, #(#type_attrs),*)]
+        #vis struct #filter_ident {
+            #(#filter_fields,)*
+            /// All the filters match.
+            pub and: ::std::option::Option<::std::vec::Vec<#filter_ident>>,
+            /// Any of the filters matches.
+            pub or: ::std::option::Option<::std::vec::Vec<#filter_ident>>,
+            /// The filter does not match.
+            pub not: ::std::option::Option<::std::boxed::Box<#filter_ident>>,
+        }
+
+        #[doc = #order_desc]
+        #[derive(#crate_name::InputObject, ::std::fmt::Debug, ::std::clone::Clone, ::std::default::Default)]
+        #[graphql(name = #order_name, #(#type_attrs),*)]
+        #vis struct #order_ident {
+            #(#order_fields),*
+        }
+    };
+    Ok(expanded.into())
+}
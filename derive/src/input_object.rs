@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use darling::ast::Data;
 use proc_macro::TokenStream;
 use quote::quote;
@@ -6,7 +8,8 @@ use syn::Error;
 
 use crate::args::{self, RenameRuleExt, RenameTarget};
 use crate::utils::{
-    generate_default, generate_validator, get_crate_name, get_rustdoc, visible_fn, GeneratorResult,
+    check_unique_name, gen_metadata, generate_default, generate_validator, get_crate_name,
+    get_rustdoc, is_phantom_data, visible_fn, GeneratorResult,
 };
 
 pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream> {
@@ -46,6 +49,16 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
         .clone()
         .unwrap_or_else(|| RenameTarget::Type.rename(ident.to_string()));
 
+    let type_name = if object_args.concretes.is_empty() {
+        quote! { #gql_typename }
+    } else {
+        quote! { name }
+    };
+    let type_metadata = gen_metadata(&crate_name, &object_args.meta, &type_name, None)?;
+    let depth_limit = object_args.max_depth.map(|max_depth| {
+        quote! { registry.set_input_depth_limit(#type_name, #max_depth); }
+    });
+
     let desc = get_rustdoc(&object_args.attrs)?
         .map(|s| quote! { ::std::option::Option::Some(#s) })
         .unwrap_or_else(|| quote! {::std::option::Option::None});
@@ -57,6 +70,7 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
     let mut flatten_fields = Vec::new();
     let mut federation_fields = Vec::new();
 
+    let mut field_names = HashSet::new();
     for field in &s.fields {
         let ident = field.ident.as_ref().unwrap();
         let ty = &field.ty;
@@ -66,7 +80,7 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
                 .rename(ident.unraw().to_string(), RenameTarget::Field)
         });
 
-        if field.skip {
+        if field.skip || is_phantom_data(ty) {
             get_fields.push(quote! {
                 let #ident: #ty = ::std::default::Default::default();
             });
@@ -74,6 +88,10 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
             continue;
         }
 
+        if !field.flatten {
+            check_unique_name(&mut field_names, &name, "field", ident)?;
+        }
+
         federation_fields.push((ty, name.clone()));
 
         if field.flatten {
@@ -157,7 +175,10 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
 
         fields.push(ident);
         let visible = visible_fn(&field.visible);
+        let field_metadata =
+            gen_metadata(&crate_name, &field.meta, &type_name, Some(name.as_str()))?;
         schema_fields.push(quote! {
+            #field_metadata
             fields.insert(::std::borrow::ToOwned::to_owned(#name), #crate_name::registry::MetaInputValue {
                 name: #name,
                 description: #desc,
@@ -212,10 +233,13 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
                         input_fields: {
                             let mut fields = #crate_name::indexmap::IndexMap::new();
                             #(#schema_fields)*
+                            #type_metadata
+                            #depth_limit
                             fields
                         },
                         visible: #visible,
                         rust_typename: ::std::any::type_name::<Self>(),
+                        oneof: false,
                     })
                 }
             }
@@ -257,10 +281,13 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
                         input_fields: {
                             let mut fields = #crate_name::indexmap::IndexMap::new();
                             #(#schema_fields)*
+                            #type_metadata
+                            #depth_limit
                             fields
                         },
                         visible: #visible,
                         rust_typename: ::std::any::type_name::<Self>(),
+                        oneof: false,
                     })
                 }
 
@@ -5,12 +5,13 @@ use proc_macro::TokenStream;
 use proc_macro2::{Ident, Span};
 use quote::quote;
 use syn::visit_mut::VisitMut;
-use syn::{visit_mut, Error, Lifetime, Type};
+use syn::{visit_mut, Error, Lifetime, LitStr, Type};
 
 use crate::args::{self, InterfaceField, InterfaceFieldArgument, RenameRuleExt, RenameTarget};
 use crate::output_type::OutputType;
 use crate::utils::{
-    gen_deprecation, generate_default, get_crate_name, get_rustdoc, visible_fn, GeneratorResult,
+    gen_deprecation, gen_metadata, generate_default, get_crate_name, get_rustdoc, parse_lit_str,
+    visible_fn, GeneratorResult,
 };
 
 pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream> {
@@ -27,6 +28,7 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
     };
     let extends = interface_args.extends;
     let mut enum_names = Vec::new();
+    let mut dynamic_names = Vec::new();
     let mut enum_items = HashSet::new();
     let mut type_into_impls = Vec::new();
     let gql_typename = interface_args
@@ -34,6 +36,9 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
         .clone()
         .unwrap_or_else(|| RenameTarget::Type.rename(ident.to_string()));
 
+    let type_name = quote! { #gql_typename };
+    let type_metadata = gen_metadata(&crate_name, &interface_args.meta, &type_name, None)?;
+
     let desc = get_rustdoc(&interface_args.attrs)?
         .map(|s| quote! { ::std::option::Option::Some(#s) })
         .unwrap_or_else(|| quote! {::std::option::Option::None});
@@ -42,6 +47,7 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
     let mut possible_types = Vec::new();
     let mut get_introspection_typename = Vec::new();
     let mut collect_all_fields = Vec::new();
+    let mut load_nodes = Vec::new();
 
     for variant in s {
         let enum_name = &variant.ident;
@@ -87,6 +93,25 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
             let mut assert_ty = p.clone();
             RemoveLifetime.visit_type_path_mut(&mut assert_ty);
 
+            if variant.dynamic {
+                dynamic_names.push(enum_name);
+                type_into_impls.push(quote! {
+                    #[allow(clippy::all, clippy::pedantic)]
+                    impl #impl_generics ::std::convert::From<#p> for #ident #ty_generics #where_clause {
+                        fn from(obj: #p) -> Self {
+                            #ident::#enum_name(obj)
+                        }
+                    }
+                });
+                get_introspection_typename.push(quote! {
+                    #ident::#enum_name(obj) => #crate_name::DynObject::introspection_type_name(obj)
+                });
+                collect_all_fields.push(quote! {
+                    #ident::#enum_name(obj) => #crate_name::DynObject::collect_all_fields(obj, ctx, fields)
+                });
+                continue;
+            }
+
             type_into_impls.push(quote! {
                 #crate_name::static_assertions::assert_impl_any!(#assert_ty: #crate_name::ObjectType, #crate_name::InterfaceType);
 
@@ -109,12 +134,24 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
             });
 
             get_introspection_typename.push(quote! {
-                #ident::#enum_name(obj) => <#p as #crate_name::Type>::type_name()
+                #ident::#enum_name(obj) => <#p as #crate_name::Type>::introspection_type_name(obj)
             });
 
             collect_all_fields.push(quote! {
                 #ident::#enum_name(obj) => obj.collect_all_fields(ctx, fields)
             });
+
+            if interface_args.node {
+                load_nodes.push(quote! {
+                    if global_id.type_name() == <#p as #crate_name::Type>::type_name() {
+                        return ::std::result::Result::Ok(
+                            <#p as #crate_name::NodeObject>::load(ctx, global_id.id())
+                                .await?
+                                .map(::std::convert::Into::into),
+                        );
+                    }
+                });
+            }
         } else {
             return Err(Error::new_spanned(ty, "Invalid type").into());
         }
@@ -124,7 +161,27 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
     let mut schema_fields = Vec::new();
     let mut resolvers = Vec::new();
 
-    if interface_args.fields.is_empty() {
+    // A node interface has an `id: ID!` field, which is added if it is not declared.
+    let node_id_field =
+        if interface_args.node && !interface_args.fields.iter().any(|field| field.name == "id") {
+            Some(InterfaceField {
+                name: "id".to_string(),
+                ty: LitStr::new("ID", Span::call_site()),
+                method: None,
+                desc: None,
+                args: Vec::new(),
+                deprecation: Default::default(),
+                external: false,
+                provides: None,
+                requires: None,
+                visible: None,
+                meta: Default::default(),
+            })
+        } else {
+            None
+        };
+
+    if interface_args.fields.is_empty() && node_id_field.is_none() {
         return Err(Error::new_spanned(
             &ident,
             "A GraphQL Interface type must define one or more fields.",
@@ -143,7 +200,8 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
         provides,
         requires,
         visible,
-    } in &interface_args.fields
+        meta,
+    } in node_id_field.iter().chain(&interface_args.fields)
     {
         let (name, method_name) = if let Some(method) = method {
             (name.to_string(), Ident::new(method, Span::call_site()))
@@ -156,10 +214,7 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
                 method_name,
             )
         };
-        let ty = match syn::parse_str::<syn::Type>(&ty.value()) {
-            Ok(ty) => ty,
-            Err(_) => return Err(Error::new_spanned(&ty, "Expect type").into()),
-        };
+        let ty = parse_lit_str::<syn::Type>(ty, "type")?;
         let mut calls = Vec::new();
         let mut use_params = Vec::new();
         let mut decl_params = Vec::new();
@@ -191,10 +246,7 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
             let name = interface_args
                 .rename_args
                 .rename(name, RenameTarget::Argument);
-            let ty = match syn::parse_str::<syn::Type>(&ty.value()) {
-                Ok(ty) => ty,
-                Err(_) => return Err(Error::new_spanned(&ty, "Expect type").into()),
-            };
+            let ty = parse_lit_str::<syn::Type>(ty, "type")?;
             decl_params.push(quote! { #ident: #ty });
             use_params.push(quote! { #ident });
 
@@ -242,6 +294,13 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
                     .map(::std::convert::Into::into)
             });
         }
+        for enum_name in &dynamic_names {
+            calls.push(quote! {
+                #ident::#enum_name(_) => ::std::result::Result::Err(#crate_name::Error::new(
+                    "Interface methods cannot be called on a dynamic object."
+                ))
+            });
+        }
 
         let desc = desc
             .as_ref()
@@ -266,7 +325,9 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
         });
 
         let visible = visible_fn(visible);
+        let field_metadata = gen_metadata(&crate_name, meta, &type_name, Some(name.as_str()))?;
         schema_fields.push(quote! {
+            #field_metadata
             fields.insert(::std::string::ToString::to_string(#name), #crate_name::registry::MetaField {
                 name: ::std::string::ToString::to_string(#name),
                 description: #desc,
@@ -281,6 +342,8 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
                 external: #external,
                 provides: #provides,
                 requires: #requires,
+                shareable: false,
+                override_from: ::std::option::Option::None,
                 visible: #visible,
                 compute_complexity: ::std::option::Option::None,
             });
@@ -301,16 +364,41 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
         });
     }
 
+    let resolve_type = interface_args.resolve_type.as_ref().map(|resolve_type| {
+        quote! {
+            if let ::std::option::Option::Some(name) = #resolve_type(self) {
+                return ::std::convert::Into::into(name);
+            }
+        }
+    });
+
     let introspection_type_name = if get_introspection_typename.is_empty() {
         quote! { ::std::unreachable!() }
     } else {
         quote! {
+            #resolve_type
             match self {
             #(#get_introspection_typename),*
             }
         }
     };
 
+    let load_node = if interface_args.node {
+        Some(quote! {
+            #[allow(clippy::all, clippy::pedantic)]
+            #[#crate_name::async_trait::async_trait]
+            impl #impl_generics #crate_name::NodeInterface for #ident #ty_generics #where_clause {
+                async fn load_node(ctx: &#crate_name::Context<'_>, id: &::std::primitive::str) -> #crate_name::Result<::std::option::Option<Self>> {
+                    let global_id = ctx.decode_global_id(id)?;
+                    #(#load_nodes)*
+                    ::std::result::Result::Ok(::std::option::Option::None)
+                }
+            }
+        })
+    } else {
+        None
+    };
+
     let visible = visible_fn(&interface_args.visible);
     let expanded = quote! {
         #(#type_into_impls)*
@@ -320,6 +408,8 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
             #(#methods)*
         }
 
+        #load_node
+
         #[allow(clippy::all, clippy::pedantic)]
         impl #impl_generics #crate_name::Type for #ident #ty_generics #where_clause {
             fn type_name() -> ::std::borrow::Cow<'static, ::std::primitive::str> {
@@ -340,6 +430,7 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
                         fields: {
                             let mut fields = #crate_name::indexmap::IndexMap::new();
                             #(#schema_fields)*
+                            #type_metadata
                             fields
                         },
                         possible_types: {
@@ -360,6 +451,11 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
         #[#crate_name::async_trait::async_trait]
         impl #impl_generics #crate_name::resolver_utils::ContainerType for #ident #ty_generics #where_clause {
             async fn resolve_field(&self, ctx: &#crate_name::Context<'_>) -> #crate_name::ServerResult<::std::option::Option<#crate_name::Value>> {
+                #(
+                    if let #ident::#dynamic_names(obj) = self {
+                        return #crate_name::DynObject::resolve_field(obj, ctx).await;
+                    }
+                )*
                 #(#resolvers)*
                 ::std::result::Result::Ok(::std::option::Option::None)
             }
@@ -6,14 +6,18 @@ extern crate proc_macro;
 
 mod args;
 mod complex_object;
+mod crud_object;
 mod description;
 mod r#enum;
+mod filterable;
 mod input_object;
 mod interface;
 mod merged_object;
 mod merged_subscription;
+mod mutation_payload;
 mod newtype;
 mod object;
+mod oneof_object;
 mod output_type;
 mod scalar;
 mod simple_object;
@@ -53,6 +57,19 @@ pub fn derive_simple_object(input: TokenStream) -> TokenStream {
     }
 }
 
+#[proc_macro_derive(MutationPayload, attributes(graphql))]
+pub fn derive_mutation_payload(input: TokenStream) -> TokenStream {
+    let mut object_args =
+        match args::SimpleObject::from_derive_input(&parse_macro_input!(input as DeriveInput)) {
+            Ok(object_args) => object_args,
+            Err(err) => return TokenStream::from(err.write_errors()),
+        };
+    match mutation_payload::generate(&mut object_args) {
+        Ok(expanded) => expanded,
+        Err(err) => err.write_errors().into(),
+    }
+}
+
 #[proc_macro_attribute]
 #[allow(non_snake_case)]
 pub fn ComplexObject(args: TokenStream, input: TokenStream) -> TokenStream {
@@ -93,6 +110,45 @@ pub fn derive_input_object(input: TokenStream) -> TokenStream {
     }
 }
 
+#[proc_macro_derive(CrudObject, attributes(crud))]
+pub fn derive_crud_object(input: TokenStream) -> TokenStream {
+    let object_args =
+        match args::CrudObject::from_derive_input(&parse_macro_input!(input as DeriveInput)) {
+            Ok(object_args) => object_args,
+            Err(err) => return TokenStream::from(err.write_errors()),
+        };
+    match crud_object::generate(&object_args) {
+        Ok(expanded) => expanded,
+        Err(err) => err.write_errors().into(),
+    }
+}
+
+#[proc_macro_derive(Filterable, attributes(filterable))]
+pub fn derive_filterable(input: TokenStream) -> TokenStream {
+    let filterable_args =
+        match args::Filterable::from_derive_input(&parse_macro_input!(input as DeriveInput)) {
+            Ok(filterable_args) => filterable_args,
+            Err(err) => return TokenStream::from(err.write_errors()),
+        };
+    match filterable::generate(&filterable_args) {
+        Ok(expanded) => expanded,
+        Err(err) => err.write_errors().into(),
+    }
+}
+
+#[proc_macro_derive(OneofObject, attributes(graphql))]
+pub fn derive_oneof_object(input: TokenStream) -> TokenStream {
+    let object_args =
+        match args::OneofObject::from_derive_input(&parse_macro_input!(input as DeriveInput)) {
+            Ok(object_args) => object_args,
+            Err(err) => return TokenStream::from(err.write_errors()),
+        };
+    match oneof_object::generate(&object_args) {
+        Ok(expanded) => expanded,
+        Err(err) => err.write_errors().into(),
+    }
+}
+
 #[proc_macro_derive(Interface, attributes(graphql))]
 pub fn derive_interface(input: TokenStream) -> TokenStream {
     let interface_args =
@@ -0,0 +1,53 @@
+use darling::ast::Data;
+use proc_macro::TokenStream;
+use syn::Error;
+
+use crate::args;
+use crate::simple_object;
+use crate::utils::GeneratorResult;
+
+pub fn generate(object_args: &mut args::SimpleObject) -> GeneratorResult<TokenStream> {
+    let ident = &object_args.ident;
+    let s = match &mut object_args.data {
+        Data::Struct(e) => e,
+        _ => {
+            return Err(Error::new_spanned(
+                ident,
+                "MutationPayload can only be applied to an struct.",
+            )
+            .into())
+        }
+    };
+
+    // Without an explicit `user_errors` attribute, the field named `user_errors` is used.
+    if !s.fields.iter().any(|field| field.user_errors) {
+        if let Some(field) = s
+            .fields
+            .iter_mut()
+            .find(|field| matches!(&field.ident, Some(ident) if ident == "user_errors"))
+        {
+            field.user_errors = true;
+        }
+    }
+
+    let mut user_errors = s.fields.iter().filter(|field| field.user_errors);
+    match (user_errors.next(), user_errors.next()) {
+        (Some(_), None) => {}
+        (None, _) => {
+            return Err(Error::new_spanned(
+                ident,
+                "A mutation payload must have a `user_errors` field.",
+            )
+            .into())
+        }
+        (Some(_), Some(field)) => {
+            return Err(Error::new_spanned(
+                &field.ident,
+                "A mutation payload can only have one `user_errors` field.",
+            )
+            .into())
+        }
+    }
+
+    simple_object::generate(object_args)
+}
@@ -66,6 +66,10 @@ pub fn generate(newtype_args: &args::NewType) -> GeneratorResult<TokenStream> {
             fn to_value(&self) -> #crate_name::Value {
                 <#inner_ty as #crate_name::ScalarType>::to_value(&self.0)
             }
+
+            fn to_output_value(&self, policy: #crate_name::NumberPolicy) -> #crate_name::Value {
+                <#inner_ty as #crate_name::ScalarType>::to_output_value(&self.0, policy)
+            }
         }
 
         impl #impl_generics ::std::convert::From<#inner_ty> for #ident #ty_generics #where_clause {
@@ -107,10 +111,13 @@ pub fn generate(newtype_args: &args::NewType) -> GeneratorResult<TokenStream> {
         impl #impl_generics #crate_name::OutputType for #ident #ty_generics #where_clause {
             async fn resolve(
                 &self,
-                _: &#crate_name::ContextSelectionSet<'_>,
+                ctx: &#crate_name::ContextSelectionSet<'_>,
                 _field: &#crate_name::Positioned<#crate_name::parser::types::Field>
             ) -> #crate_name::ServerResult<#crate_name::Value> {
-                Ok(#crate_name::ScalarType::to_value(self))
+                Ok(#crate_name::ScalarType::to_output_value(
+                    self,
+                    ctx.schema_env.registry.number_policy,
+                ))
             }
         }
     };
@@ -1,26 +1,32 @@
+use std::collections::HashSet;
+
 use proc_macro::TokenStream;
-use proc_macro2::Ident;
+use proc_macro2::{Ident, TokenStream as TokenStream2};
 use quote::quote;
 use std::iter::FromIterator;
-use std::str::FromStr;
 use syn::ext::IdentExt;
 use syn::{
-    punctuated::Punctuated, Block, Error, FnArg, ImplItem, ItemImpl, Pat, ReturnType, Token, Type,
+    parse_quote, punctuated::Punctuated, Block, Error, FnArg, GenericArgument, ImplItem,
+    ImplItemMethod, ItemImpl, Pat, PathArguments, ReturnType, Token, Type, TypeParamBound,
     TypeReference,
 };
 
 use crate::args::{self, ComplexityType, RenameRuleExt, RenameTarget};
 use crate::output_type::OutputType;
 use crate::utils::{
-    extract_input_args, gen_deprecation, generate_default, generate_guards, generate_validator,
-    get_cfg_attrs, get_crate_name, get_param_getter_ident, get_rustdoc, get_type_path_and_name,
-    parse_complexity_expr, parse_graphql_attrs, remove_graphql_attrs, visible_fn, GeneratorResult,
+    check_unique_name, expand_graphql_cfg_attrs, extract_input_args, gen_deprecation, gen_metadata,
+    generate_blocking_method, generate_ctx_data_getter, generate_default, generate_default_guard,
+    generate_flatten_args, generate_guards, generate_validator, get_cfg_attrs, get_crate_name,
+    get_param_getter_ident, get_rustdoc, get_type_path_and_name, parse_complexity_expr,
+    parse_graphql_attrs, parse_lit_str, remove_graphql_attrs, visible_fn, GeneratorResult,
 };
 
 pub fn generate(
     object_args: &args::Object,
     item_impl: &mut ItemImpl,
 ) -> GeneratorResult<TokenStream> {
+    let mut cfg_variants = expand_graphql_cfg_attrs(item_impl)?;
+
     let crate_name = get_crate_name(object_args.internal);
     let (self_ty, self_name) = get_type_path_and_name(item_impl.self_ty.as_ref())?;
     let (impl_generics, _, where_clause) = item_impl.generics.split_for_impl();
@@ -30,6 +36,13 @@ pub fn generate(
         .clone()
         .unwrap_or_else(|| RenameTarget::Type.rename(self_name.clone()));
 
+    let type_name = if object_args.concretes.is_empty() {
+        quote! { #gql_typename }
+    } else {
+        quote! { name }
+    };
+    let type_metadata = gen_metadata(&crate_name, &object_args.meta, &type_name, None)?;
+
     let desc = if object_args.use_type_description {
         quote! { ::std::option::Option::Some(<Self as #crate_name::Description>::description()) }
     } else {
@@ -56,9 +69,7 @@ pub fn generate(
                     let base_function_name = &method.sig.ident;
                     let name = derived.name.unwrap();
                     let with = derived.with;
-                    let into = Type::Verbatim(
-                        proc_macro2::TokenStream::from_str(&derived.into.unwrap()).unwrap(),
-                    );
+                    let into = parse_lit_str::<Type>(&derived.into.unwrap(), "type")?;
 
                     let mut new_impl = method.clone();
                     new_impl.sig.ident = name;
@@ -129,7 +140,9 @@ pub fn generate(
     }
     item_impl.items.append(&mut derived_impls);
 
-    for item in &mut item_impl.items {
+    let mut field_names = HashSet::new();
+    let mut blocking_impls = Vec::new();
+    for item in cfg_variants.items_mut(&mut item_impl.items) {
         if let ImplItem::Method(method) = item {
             let method_args: args::ObjectField =
                 parse_graphql_attrs(&method.attrs)?.unwrap_or_default();
@@ -160,9 +173,12 @@ pub fn generate(
                 let mut use_keys = Vec::new();
                 let mut get_federation_key = Vec::new();
                 let mut requires_getter = Vec::new();
-                let all_key = args.iter().all(|(_, _, arg)| !arg.key);
+                let all_key = args
+                    .iter()
+                    .filter(|(_, _, arg)| !arg.ctx_data)
+                    .all(|(_, _, arg)| !arg.key);
 
-                if args.is_empty() {
+                if args.iter().all(|(_, _, arg)| arg.ctx_data) {
                     return Err(Error::new_spanned(
                         method,
                         "Entity need to have at least one key.",
@@ -170,7 +186,23 @@ pub fn generate(
                     .into());
                 }
 
-                for (ident, ty, args::Argument { name, key, .. }) in &args {
+                for (
+                    ident,
+                    ty,
+                    args::Argument {
+                        name,
+                        key,
+                        ctx_data,
+                        ..
+                    },
+                ) in &args
+                {
+                    if *ctx_data {
+                        requires_getter.push(generate_ctx_data_getter(&crate_name, ident, ty));
+                        use_keys.push(ident);
+                        continue;
+                    }
+
                     let is_key = all_key || *key;
                     let name = name.clone().unwrap_or_else(|| {
                         object_args
@@ -255,15 +287,26 @@ pub fn generate(
                     },
                 ));
             } else if !method_args.skip {
+                if method_args.blocking {
+                    blocking_impls.push(ImplItem::Method(generate_blocking_method(
+                        &crate_name,
+                        method,
+                    )?));
+                }
                 if method.sig.asyncness.is_none() {
                     return Err(Error::new_spanned(&method, "Must be asynchronous").into());
                 }
+                convert_stream_output(&crate_name, method);
 
                 let field_name = method_args.name.clone().unwrap_or_else(|| {
                     object_args
                         .rename_fields
                         .rename(method.sig.ident.unraw().to_string(), RenameTarget::Field)
                 });
+                // Methods with `cfg` attributes may share a name when only one of them is compiled.
+                if get_cfg_attrs(&method.attrs).is_empty() {
+                    check_unique_name(&mut field_names, &field_name, "field", &method.sig.ident)?;
+                }
                 let field_desc = get_rustdoc(&method.attrs)?
                     .map(|s| quote! { ::std::option::Option::Some(#s) })
                     .unwrap_or_else(|| quote! {::std::option::Option::None});
@@ -277,6 +320,11 @@ pub fn generate(
                     Some(provides) => quote! { ::std::option::Option::Some(#provides) },
                     None => quote! { ::std::option::Option::None },
                 };
+                let shareable = object_args.shareable || method_args.shareable;
+                let override_from = match &method_args.override_from {
+                    Some(from) => quote! { ::std::option::Option::Some(#from) },
+                    None => quote! { ::std::option::Option::None },
+                };
                 let cache_control = {
                     let public = method_args.cache_control.is_public();
                     let max_age = method_args.cache_control.max_age;
@@ -315,10 +363,29 @@ pub fn generate(
                         validator,
                         visible,
                         secret,
+                        ctx_data,
+                        flatten,
                         ..
                     },
                 ) in &args
                 {
+                    if *ctx_data {
+                        let param_ident = &ident.ident;
+                        use_params.push(quote! { #param_ident });
+                        get_params.push(generate_ctx_data_getter(&crate_name, ident, ty));
+                        continue;
+                    }
+
+                    if *flatten {
+                        schema_args.push(generate_flatten_args(&crate_name, ty));
+                        let param_ident = &ident.ident;
+                        use_params.push(quote! { #param_ident });
+                        get_params.push(quote! {
+                            let #ident: #ty = ctx.flatten_param_value()?;
+                        });
+                        continue;
+                    }
+
                     let name = name.clone().unwrap_or_else(|| {
                         object_args
                             .rename_args
@@ -403,10 +470,9 @@ pub fn generate(
                                         default_with,
                                         ..
                                     },
-                                )) = args
-                                    .iter()
-                                    .find(|(pat_ident, _, _)| pat_ident.ident == variable)
-                                {
+                                )) = args.iter().find(|(pat_ident, _, arg)| {
+                                    !arg.ctx_data && !arg.flatten && pat_ident.ident == variable
+                                }) {
                                     let default = match generate_default(default, default_with)? {
                                         Some(default) => {
                                             quote! { ::std::option::Option::Some(|| -> #ty { #default }) }
@@ -436,7 +502,15 @@ pub fn generate(
                     quote! { ::std::option::Option::None }
                 };
 
+                let field_metadata = gen_metadata(
+                    &crate_name,
+                    &method_args.meta,
+                    &type_name,
+                    Some(field_name.as_str()),
+                )?;
                 schema_fields.push(quote! {
+                    #(#cfg_attrs)*
+                    #field_metadata
                     #(#cfg_attrs)*
                     fields.insert(::std::borrow::ToOwned::to_owned(#field_name), #crate_name::registry::MetaField {
                         name: ::std::borrow::ToOwned::to_owned(#field_name),
@@ -452,6 +526,8 @@ pub fn generate(
                         external: #external,
                         provides: #provides,
                         requires: #requires,
+                        shareable: #shareable,
+                        override_from: #override_from,
                         visible: #visible,
                         compute_complexity: #complexity,
                     });
@@ -480,15 +556,23 @@ pub fn generate(
                         res.map_err(|err| ::std::convert::Into::<#crate_name::Error>::into(err).into_server_error(ctx.item.pos))
                     }
                 };
+                let resolve_obj = if method_args.memoize {
+                    quote! {
+                        ctx.memoize(::std::any::type_name::<Self>(), async move #resolve_obj).await
+                    }
+                } else {
+                    resolve_obj
+                };
 
                 let guard = match &method_args.guard {
-                    Some(meta_list) => generate_guards(&crate_name, meta_list)?,
-                    None => None,
+                    Some(meta_list) => generate_guards(&crate_name, meta_list)?
+                        .map(|guard| quote! { #guard.check(ctx) }),
+                    None => generate_default_guard(object_args.internal, method_args.public),
                 };
 
                 let guard = guard.map(|guard| {
                     quote! {
-                        #guard.check(ctx).await.map_err(|err| err.into_server_error(ctx.item.pos))?;
+                        #guard.await.map_err(|err| err.into_server_error(ctx.item.pos))?;
                     }
                 });
 
@@ -510,6 +594,8 @@ pub fn generate(
             remove_graphql_attrs(&mut method.attrs);
         }
     }
+    cfg_variants.finish(&mut item_impl.items);
+    item_impl.items.append(&mut blocking_impls);
 
     let cache_control = {
         let public = object_args.cache_control.is_public();
@@ -557,6 +643,7 @@ pub fn generate(
                         fields: {
                             let mut fields = #crate_name::indexmap::IndexMap::new();
                             #(#schema_fields)*
+                            #type_metadata
                             fields
                         },
                         cache_control: #cache_control,
@@ -626,6 +713,7 @@ pub fn generate(
                         fields: {
                             let mut fields = #crate_name::indexmap::IndexMap::new();
                             #(#schema_fields)*
+                            #type_metadata
                             fields
                         },
                         cache_control: #cache_control,
@@ -718,3 +806,106 @@ pub fn generate(
 
     Ok(expanded.into())
 }
+
+/// Returns the item type and the trait bounds of `impl Stream<Item = T>`.
+fn stream_item_type(ty: &Type) -> Option<(Type, Vec<TypeParamBound>)> {
+    let impl_trait = match ty {
+        Type::ImplTrait(impl_trait) => impl_trait,
+        _ => return None,
+    };
+    let item_ty = impl_trait.bounds.iter().find_map(|bound| {
+        let segment = match bound {
+            TypeParamBound::Trait(bound) => bound.path.segments.last()?,
+            _ => return None,
+        };
+        if segment.ident != "Stream" {
+            return None;
+        }
+        match &segment.arguments {
+            PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+                GenericArgument::Binding(binding) if binding.ident == "Item" => {
+                    Some(binding.ty.clone())
+                }
+                _ => None,
+            }),
+            _ => None,
+        }
+    })?;
+    let trait_bounds = impl_trait
+        .bounds
+        .iter()
+        .filter(|bound| matches!(bound, TypeParamBound::Trait(_)))
+        .cloned()
+        .collect();
+    Some((item_ty, trait_bounds))
+}
+
+/// Convert a resolver returning `impl Stream<Item = T>`, or a `Result` of it, to a resolver
+/// returning a `ListStream`, which is resolved as a `[T]` field.
+fn convert_stream_output(crate_name: &TokenStream2, method: &mut ImplItemMethod) {
+    let ty = match &mut method.sig.output {
+        ReturnType::Type(_, ty) => ty,
+        ReturnType::Default => return,
+    };
+    let block = &method.block;
+
+    if let Some((item_ty, bounds)) = stream_item_type(ty) {
+        **ty = parse_quote! {
+            #crate_name::ListStream<#crate_name::futures_util::stream::BoxStream<'_, #item_ty>>
+        };
+        method.block = parse_quote!({
+            // Keep checking the bounds declared by the resolver.
+            fn __stream<S: #(#bounds)+*>(stream: S) -> S {
+                stream
+            }
+            // The body is run in an inner block, so that it can return early.
+            let stream = async move #block.await;
+            #crate_name::ListStream::new(::std::boxed::Box::pin(__stream(stream)))
+        });
+        return;
+    }
+
+    // The type of the result of the body, whose value type is inferred.
+    let mut res_ty = (**ty).clone();
+    if let Some(value_ty) = result_value_type(ty) {
+        if let Some((item_ty, bounds)) = stream_item_type(value_ty) {
+            *value_ty = parse_quote! {
+                #crate_name::ListStream<#crate_name::futures_util::stream::BoxStream<'_, #item_ty>>
+            };
+            if let Some(value_ty) = result_value_type(&mut res_ty) {
+                *value_ty = parse_quote!(_);
+            }
+            method.block = parse_quote!({
+                // Keep checking the bounds declared by the resolver.
+                fn __stream<S: #(#bounds)+*>(stream: S) -> S {
+                    stream
+                }
+                // The body is run in an inner block, so that it can return early.
+                let res: #res_ty = async move #block.await;
+                res.map(|stream| {
+                    let stream: #crate_name::futures_util::stream::BoxStream<'_, #item_ty> =
+                        ::std::boxed::Box::pin(__stream(stream));
+                    #crate_name::ListStream::new(stream)
+                })
+            });
+        }
+    }
+}
+
+/// Returns the value type of a `Result` or `FieldResult` type.
+fn result_value_type(ty: &mut Type) -> Option<&mut Type> {
+    let segment = match ty {
+        Type::Path(path) => match path.path.segments.last_mut() {
+            Some(segment) if segment.ident == "Result" || segment.ident == "FieldResult" => segment,
+            _ => return None,
+        },
+        _ => return None,
+    };
+    match &mut segment.arguments {
+        PathArguments::AngleBracketed(args) => match args.args.first_mut() {
+            Some(GenericArgument::Type(value_ty)) => Some(value_ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
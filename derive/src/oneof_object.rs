@@ -0,0 +1,155 @@
+use std::collections::HashSet;
+
+use darling::ast::{Data, Style};
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::Error;
+
+use crate::args::{self, RenameRuleExt, RenameTarget};
+use crate::utils::{check_unique_name, get_crate_name, get_rustdoc, visible_fn, GeneratorResult};
+
+pub fn generate(object_args: &args::OneofObject) -> GeneratorResult<TokenStream> {
+    let crate_name = get_crate_name(object_args.internal);
+    let (impl_generics, ty_generics, where_clause) = object_args.generics.split_for_impl();
+    let ident = &object_args.ident;
+    let e = match &object_args.data {
+        Data::Enum(e) => e,
+        _ => {
+            return Err(
+                Error::new_spanned(ident, "OneofObject can only be applied to an enum.").into(),
+            )
+        }
+    };
+
+    let gql_typename = object_args
+        .name
+        .clone()
+        .unwrap_or_else(|| RenameTarget::Type.rename(ident.to_string()));
+    let desc = get_rustdoc(&object_args.attrs)?
+        .map(|s| quote! { ::std::option::Option::Some(#s) })
+        .unwrap_or_else(|| quote! {::std::option::Option::None});
+    let visible = visible_fn(&object_args.visible);
+
+    let mut schema_fields = Vec::new();
+    let mut parse_fields = Vec::new();
+    let mut put_fields = Vec::new();
+    let mut field_names = HashSet::new();
+    for variant in e {
+        let enum_name = &variant.ident;
+        let ty = match variant.fields.style {
+            Style::Tuple if variant.fields.fields.len() == 1 => &variant.fields.fields[0],
+            _ => {
+                return Err(Error::new_spanned(
+                    enum_name,
+                    "Only single value variants are supported",
+                )
+                .into())
+            }
+        };
+        let name = variant.name.clone().unwrap_or_else(|| {
+            object_args
+                .rename_fields
+                .rename(enum_name.to_string(), RenameTarget::Field)
+        });
+        check_unique_name(&mut field_names, &name, "field", enum_name)?;
+
+        let desc = get_rustdoc(&variant.attrs)?
+            .map(|s| quote! { ::std::option::Option::Some(#s) })
+            .unwrap_or_else(|| quote! {::std::option::Option::None});
+        let field_visible = visible_fn(&variant.visible);
+
+        schema_fields.push(quote! {
+            fields.insert(::std::borrow::ToOwned::to_owned(#name), #crate_name::registry::MetaInputValue {
+                name: #name,
+                description: #desc,
+                ty: <::std::option::Option<#ty> as #crate_name::Type>::create_type_info(registry),
+                default_value: ::std::option::Option::None,
+                validator: ::std::option::Option::None,
+                visible: #field_visible,
+                is_secret: false,
+            });
+        });
+
+        parse_fields.push(quote! {
+            #name => ::std::result::Result::Ok(Self::#enum_name(
+                #crate_name::InputType::parse(::std::option::Option::Some(value))
+                    .map_err(#crate_name::InputValueError::propagate)?
+            )),
+        });
+
+        put_fields.push(quote! {
+            Self::#enum_name(value) => {
+                map.insert(#crate_name::Name::new(#name), #crate_name::InputType::to_value(value));
+            }
+        });
+    }
+
+    if schema_fields.is_empty() {
+        return Err(Error::new_spanned(
+            ident,
+            "A GraphQL Oneof Input Object type must define one or more input fields.",
+        )
+        .into());
+    }
+
+    let expanded = quote! {
+        #[allow(clippy::all, clippy::pedantic)]
+        impl #impl_generics #crate_name::Type for #ident #ty_generics #where_clause {
+            fn type_name() -> ::std::borrow::Cow<'static, ::std::primitive::str> {
+                ::std::borrow::Cow::Borrowed(#gql_typename)
+            }
+
+            fn create_type_info(registry: &mut #crate_name::registry::Registry) -> ::std::string::String {
+                registry.create_type::<Self, _>(|registry| #crate_name::registry::MetaType::InputObject {
+                    name: ::std::borrow::ToOwned::to_owned(#gql_typename),
+                    description: #desc,
+                    input_fields: {
+                        let mut fields = #crate_name::indexmap::IndexMap::new();
+                        #(#schema_fields)*
+                        fields
+                    },
+                    visible: #visible,
+                    rust_typename: ::std::any::type_name::<Self>(),
+                    oneof: true,
+                })
+            }
+        }
+
+        #[allow(clippy::all, clippy::pedantic)]
+        impl #impl_generics #crate_name::InputType for #ident #ty_generics #where_clause {
+            fn parse(value: ::std::option::Option<#crate_name::Value>) -> #crate_name::InputValueResult<Self> {
+                match value {
+                    ::std::option::Option::Some(#crate_name::Value::Object(obj)) if obj.len() == 1 => {
+                        let (name, value) = ::std::iter::IntoIterator::into_iter(obj).next().unwrap();
+                        if let #crate_name::Value::Null = value {
+                            return ::std::result::Result::Err(#crate_name::InputValueError::custom(
+                                ::std::format!("The field \"{}\" of a oneof input object must not be null.", name),
+                            ));
+                        }
+                        match name.as_str() {
+                            #(#parse_fields)*
+                            _ => ::std::result::Result::Err(#crate_name::InputValueError::custom(
+                                ::std::format!("Unknown field \"{}\".", name),
+                            )),
+                        }
+                    }
+                    ::std::option::Option::Some(#crate_name::Value::Object(_)) => {
+                        ::std::result::Result::Err(#crate_name::InputValueError::custom(
+                            "A oneof input object requires exactly one field.",
+                        ))
+                    }
+                    value => ::std::result::Result::Err(#crate_name::InputValueError::expected_type(value.unwrap_or_default())),
+                }
+            }
+
+            fn to_value(&self) -> #crate_name::Value {
+                let mut map = #crate_name::indexmap::IndexMap::new();
+                match self {
+                    #(#put_fields)*
+                }
+                #crate_name::Value::Object(map)
+            }
+        }
+    };
+    Ok(expanded.into())
+}
@@ -71,10 +71,13 @@ pub fn generate(
         impl #generic #crate_name::OutputType for #self_ty #where_clause {
             async fn resolve(
                 &self,
-                _: &#crate_name::ContextSelectionSet<'_>,
+                ctx: &#crate_name::ContextSelectionSet<'_>,
                 _field: &#crate_name::Positioned<#crate_name::parser::types::Field>
             ) -> #crate_name::ServerResult<#crate_name::Value> {
-                ::std::result::Result::Ok(#crate_name::ScalarType::to_value(self))
+                ::std::result::Result::Ok(#crate_name::ScalarType::to_output_value(
+                    self,
+                    ctx.schema_env.registry.number_policy,
+                ))
             }
         }
     };
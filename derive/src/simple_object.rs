@@ -1,13 +1,17 @@
+use std::collections::HashSet;
+
 use darling::ast::Data;
 use proc_macro::TokenStream;
 use quote::quote;
-use std::str::FromStr;
 use syn::ext::IdentExt;
-use syn::{Error, Ident, Path, Type};
+use syn::spanned::Spanned;
+use syn::{parse_quote, Error, Ident, Index, Member, Path, Type};
 
-use crate::args::{self, RenameRuleExt, RenameTarget, SimpleObjectField};
+use crate::args::{self, ComplexityType, RenameRuleExt, RenameTarget, SimpleObjectField};
 use crate::utils::{
-    gen_deprecation, generate_guards, get_crate_name, get_rustdoc, visible_fn, GeneratorResult,
+    check_unique_name, gen_deprecation, gen_metadata, generate_default_guard, generate_guards,
+    get_crate_name, get_rustdoc, is_phantom_data, parse_complexity_expr, parse_lit_str, visible_fn,
+    GeneratorResult,
 };
 
 #[derive(Debug)]
@@ -19,6 +23,7 @@ struct DerivedFieldMetadata {
 }
 
 struct SimpleObjectFieldGenerator<'a> {
+    index: usize,
     field: &'a SimpleObjectField,
     derived: Option<DerivedFieldMetadata>,
 }
@@ -33,6 +38,13 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
         .clone()
         .unwrap_or_else(|| RenameTarget::Type.rename(ident.to_string()));
 
+    let type_name = if object_args.concretes.is_empty() {
+        quote! { #gql_typename }
+    } else {
+        quote! { name }
+    };
+    let type_metadata = gen_metadata(&crate_name, &object_args.meta, &type_name, None)?;
+
     let desc = get_rustdoc(&object_args.attrs)?
         .map(|s| quote! { ::std::option::Option::Some(#s) })
         .unwrap_or_else(|| quote! {::std::option::Option::None});
@@ -54,36 +66,26 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
     let mut processed_fields: Vec<SimpleObjectFieldGenerator> = vec![];
 
     // Before processing the fields, we generate the derivated fields
-    for field in &s.fields {
+    for (index, field) in s.fields.iter().enumerate() {
         processed_fields.push(SimpleObjectFieldGenerator {
+            index,
             field,
             derived: None,
         });
 
         for derived in &field.derived {
-            if derived.name.is_some() && derived.into.is_some() {
-                let name = derived.name.clone().unwrap();
-                let into = match syn::parse2::<Type>(
-                    proc_macro2::TokenStream::from_str(&derived.into.clone().unwrap()).unwrap(),
-                ) {
-                    Ok(e) => e,
-                    _ => {
-                        return Err(Error::new_spanned(
-                            &name,
-                            "derived into must be a valid type.",
-                        )
-                        .into());
-                    }
-                };
+            if let (Some(name), Some(into)) = (&derived.name, &derived.into) {
+                let into = parse_lit_str::<Type>(into, "type")?;
 
                 let derived = DerivedFieldMetadata {
-                    ident: name,
+                    ident: name.clone(),
                     into,
                     owned: derived.owned,
                     with: derived.with.clone(),
                 };
 
                 processed_fields.push(SimpleObjectFieldGenerator {
+                    index,
                     field,
                     derived: Some(derived),
                 })
@@ -91,20 +93,50 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
         }
     }
 
-    for SimpleObjectFieldGenerator { field, derived } in &processed_fields {
-        if field.skip {
+    let mut field_names = HashSet::new();
+    for SimpleObjectFieldGenerator {
+        index,
+        field,
+        derived,
+    } in &processed_fields
+    {
+        if field.skip || is_phantom_data(&field.ty) {
             continue;
         }
 
-        let base_ident = match &field.ident {
-            Some(ident) => ident,
-            None => return Err(Error::new_spanned(&ident, "All fields must be named.").into()),
+        // The fields of a tuple struct are accessed by index, and their getters are named after
+        // the GraphQL field.
+        let (base_ident, tuple_ident) = match &field.ident {
+            Some(ident) => (Member::Named(ident.clone()), None),
+            None => match &field.name {
+                Some(name) => {
+                    // A keyword is used as a raw identifier, such as `r#type`.
+                    let mut getter = syn::parse_str::<Ident>(name)
+                        .or_else(|_| syn::parse_str::<Ident>(&format!("r#{}", name)))
+                        .map_err(|_| {
+                            Error::new_spanned(
+                                &field.ty,
+                                format!("The name `{}` cannot be used for the getter of a tuple struct field.", name),
+                            )
+                        })?;
+                    getter.set_span(field.ty.span());
+                    (Member::Unnamed(Index::from(*index)), Some(getter))
+                }
+                None => {
+                    return Err(Error::new_spanned(
+                        &field.ty,
+                        "The fields of a tuple struct must have a name, for example `#[graphql(name = \"x\")]`.",
+                    )
+                    .into())
+                }
+            },
         };
 
-        let ident = if let Some(derived) = derived {
-            &derived.ident
-        } else {
-            base_ident
+        let ident = match (derived, &field.ident, &tuple_ident) {
+            (Some(derived), _, _) => &derived.ident,
+            (None, Some(ident), _) => ident,
+            (None, None, Some(ident)) => ident,
+            (None, None, None) => unreachable!(),
         };
 
         let field_name = field.name.clone().unwrap_or_else(|| {
@@ -112,6 +144,7 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                 .rename_fields
                 .rename(ident.unraw().to_string(), RenameTarget::Field)
         });
+        check_unique_name(&mut field_names, &field_name, "field", ident)?;
         let field_desc = get_rustdoc(&field.attrs)?
             .map(|s| quote! {::std::option::Option::Some(#s)})
             .unwrap_or_else(|| quote! {::std::option::Option::None});
@@ -125,10 +158,19 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
             Some(provides) => quote! { ::std::option::Option::Some(#provides) },
             None => quote! { ::std::option::Option::None },
         };
+        let shareable = object_args.shareable || field.shareable;
+        let override_from = match &field.override_from {
+            Some(from) => quote! { ::std::option::Option::Some(#from) },
+            None => quote! { ::std::option::Option::None },
+        };
         let vis = &field.vis;
 
+        let user_error_ty;
         let ty = if let Some(derived) = derived {
             &derived.into
+        } else if field.user_errors {
+            user_error_ty = parse_quote! { ::std::vec::Vec<#crate_name::UserError> };
+            &user_error_ty
         } else {
             &field.ty
         };
@@ -152,7 +194,39 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
 
         let visible = visible_fn(&field.visible);
 
+        let field_metadata = gen_metadata(
+            &crate_name,
+            &field.meta,
+            &type_name,
+            Some(field_name.as_str()),
+        )?;
+        let complexity = match &field.complexity {
+            Some(ComplexityType::Const(n)) => {
+                quote! { ::std::option::Option::Some(#crate_name::registry::ComplexityType::Const(#n)) }
+            }
+            Some(ComplexityType::Fn(s)) => {
+                let (variables, expr) = parse_complexity_expr(s)?;
+                if let Some(variable) = variables.into_iter().next() {
+                    return Err(Error::new_spanned(
+                        &field.ty,
+                        format!(
+                            "The complexity of a field of a simple object can only use `child_complexity`, but `{}` is used.",
+                            variable
+                        ),
+                    )
+                    .into());
+                }
+                quote! {
+                    ::std::option::Option::Some(#crate_name::registry::ComplexityType::Fn(|__ctx, __variables_definition, __field, child_complexity| {
+                        Ok(#expr)
+                    }))
+                }
+            }
+            None => quote! { ::std::option::Option::None },
+        };
+
         schema_fields.push(quote! {
+            #field_metadata
             fields.insert(::std::borrow::ToOwned::to_owned(#field_name), #crate_name::registry::MetaField {
                 name: ::std::borrow::ToOwned::to_owned(#field_name),
                 description: #field_desc,
@@ -163,26 +237,38 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                 external: #external,
                 provides: #provides,
                 requires: #requires,
+                shareable: #shareable,
+                override_from: #override_from,
                 visible: #visible,
-                compute_complexity: ::std::option::Option::None,
+                compute_complexity: #complexity,
             });
         });
 
         let guard = match &field.guard {
-            Some(meta) => generate_guards(&crate_name, meta)?,
-            None => None,
+            Some(meta) => {
+                generate_guards(&crate_name, meta)?.map(|guard| quote! { #guard.check(ctx) })
+            }
+            None => generate_default_guard(object_args.internal, field.public),
         };
         let guard = guard.map(
-            |guard| quote! { #guard.check(ctx).await.map_err(|err| err.into_server_error(ctx.item.pos))?; },
+            |guard| quote! { #guard.await.map_err(|err| err.into_server_error(ctx.item.pos))?; },
         );
 
         let with_function = derived.as_ref().and_then(|x| x.with.as_ref());
 
-        let mut block = match !owned {
-            true => quote! {
+        let owned = owned || (field.user_errors && derived.is_none());
+
+        let mut block = match (!owned, field.user_errors) {
+            (_, true) if derived.is_none() => quote! {
+                ::std::iter::Iterator::collect(::std::iter::Iterator::map(
+                    ::std::iter::IntoIterator::into_iter(&self.#base_ident),
+                    #crate_name::UserErrorType::user_error,
+                ))
+            },
+            (true, _) => quote! {
                 &self.#base_ident
             },
-            false => quote! {
+            (false, _) => quote! {
                 ::std::clone::Clone::clone(&self.#base_ident)
             },
         };
@@ -205,7 +291,7 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
         getters.push(
             quote! {
                  #[inline]
-                 #[allow(missing_docs)]
+                 #[allow(missing_docs, non_snake_case)]
                  #vis async fn #ident(&self, ctx: &#crate_name::Context<'_>) -> #crate_name::Result<#ty> {
                      ::std::result::Result::Ok(#block)
                  }
@@ -225,7 +311,7 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
         });
     }
 
-    if !object_args.dummy && resolvers.is_empty() {
+    if !object_args.dummy && !object_args.complex && resolvers.is_empty() {
         return Err(Error::new_spanned(
             &ident,
             "A GraphQL Object type must define one or more fields.",
@@ -286,6 +372,7 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                         fields: {
                             let mut fields = #crate_name::indexmap::IndexMap::new();
                             #(#schema_fields)*
+                            #type_metadata
                             #concat_complex_fields
                             fields
                         },
@@ -338,6 +425,7 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                         fields: {
                             let mut fields = #crate_name::indexmap::IndexMap::new();
                             #(#schema_fields)*
+                            #type_metadata
                             ::std::iter::Extend::extend(&mut fields, complex_fields.clone());
                             fields
                         },
@@ -1,23 +1,28 @@
+use std::collections::HashSet;
+
 use proc_macro::TokenStream;
 use quote::quote;
 use syn::ext::IdentExt;
 use syn::{
-    Block, Error, FnArg, ImplItem, ItemImpl, Pat, ReturnType, Type, TypeImplTrait, TypeParamBound,
-    TypeReference,
+    Block, Error, FnArg, Ident, ImplItem, ItemImpl, Pat, PatIdent, ReturnType, Type, TypeImplTrait,
+    TypeParamBound, TypePath, TypeReference,
 };
 
 use crate::args::{self, ComplexityType, RenameRuleExt, RenameTarget, SubscriptionField};
 use crate::output_type::OutputType;
 use crate::utils::{
-    gen_deprecation, generate_default, generate_guards, generate_validator, get_cfg_attrs,
-    get_crate_name, get_param_getter_ident, get_rustdoc, get_type_path_and_name,
-    parse_complexity_expr, parse_graphql_attrs, remove_graphql_attrs, visible_fn, GeneratorResult,
+    check_unique_name, expand_graphql_cfg_attrs, gen_deprecation, generate_default,
+    generate_default_guard, generate_guards, generate_validator, get_cfg_attrs, get_crate_name,
+    get_param_getter_ident, get_rustdoc, get_type_path_and_name, parse_complexity_expr,
+    parse_graphql_attrs, remove_graphql_attrs, visible_fn, GeneratorResult,
 };
 
 pub fn generate(
     subscription_args: &args::Subscription,
     item_impl: &mut ItemImpl,
 ) -> GeneratorResult<TokenStream> {
+    let mut cfg_variants = expand_graphql_cfg_attrs(item_impl)?;
+
     let crate_name = get_crate_name(subscription_args.internal);
     let (self_ty, self_name) = get_type_path_and_name(item_impl.self_ty.as_ref())?;
     let generics = &item_impl.generics;
@@ -40,7 +45,8 @@ pub fn generate(
     let mut create_stream = Vec::new();
     let mut schema_fields = Vec::new();
 
-    for item in &mut item_impl.items {
+    let mut field_names = HashSet::new();
+    for item in cfg_variants.items_mut(&mut item_impl.items) {
         if let ImplItem::Method(method) = item {
             let field: SubscriptionField = parse_graphql_attrs(&method.attrs)?.unwrap_or_default();
             if field.skip {
@@ -54,6 +60,10 @@ pub fn generate(
                     .rename_fields
                     .rename(method.sig.ident.unraw().to_string(), RenameTarget::Field)
             });
+            // Methods with `cfg` attributes may share a name when only one of them is compiled.
+            if get_cfg_attrs(&method.attrs).is_empty() {
+                check_unique_name(&mut field_names, &field_name, "field", ident)?;
+            }
             let field_desc = get_rustdoc(&method.attrs)?
                 .map(|s| quote! {::std::option::Option::Some(#s)})
                 .unwrap_or_else(|| quote! {::std::option::Option::None});
@@ -134,8 +144,15 @@ pub fn generate(
             }
 
             if create_ctx {
-                let arg = syn::parse2::<FnArg>(quote! { _: &#crate_name::Context<'_> }).unwrap();
-                method.sig.inputs.insert(1, arg);
+                let arg = if field.topic.is_some() {
+                    quote! { ctx: &#crate_name::Context<'_> }
+                } else {
+                    quote! { _: &#crate_name::Context<'_> }
+                };
+                method
+                    .sig
+                    .inputs
+                    .insert(1, syn::parse2::<FnArg>(arg).unwrap());
             }
 
             let mut schema_args = Vec::new();
@@ -227,7 +244,40 @@ pub fn generate(
                 quote! { #res_ty }
             };
 
-            if let OutputType::Value(inner_ty) = &ty {
+            if let Some(topic) = &field.topic {
+                if !method.block.stmts.is_empty() {
+                    return Err(Error::new_spanned(
+                        &method.block,
+                        "The body of a subscription with a topic must be empty.",
+                    )
+                    .into());
+                }
+                let ctx_ident = match &method.sig.inputs[1] {
+                    FnArg::Typed(pat) => match &*pat.pat {
+                        Pat::Ident(ident) => ident.ident.clone(),
+                        pat => {
+                            return Err(Error::new_spanned(
+                                pat,
+                                "The context of a subscription with a topic must be named.",
+                            )
+                            .into())
+                        }
+                    },
+                    FnArg::Receiver(_) => unreachable!(),
+                };
+                let (format, topic_args) = parse_topic(topic, &args, ident)?;
+                method.block = syn::parse2::<Block>(quote!({
+                    let topic = ::std::format!(#format, #(#topic_args),*);
+                    let pubsub = #ctx_ident.data::<#crate_name::pubsub::PubSub>()?;
+                    ::std::result::Result::Ok(pubsub.subscribe::<<#stream_ty as #crate_name::futures_util::stream::Stream>::Item>(topic))
+                }))
+                .expect("invalid block");
+                if let OutputType::Value(inner_ty) = &ty {
+                    method.sig.output =
+                        syn::parse2::<ReturnType>(quote! { -> #crate_name::Result<#inner_ty> })
+                            .expect("invalid result type");
+                }
+            } else if let OutputType::Value(inner_ty) = &ty {
                 let block = &method.block;
                 let new_block = quote!({
                     {
@@ -309,6 +359,8 @@ pub fn generate(
                     external: false,
                     requires: ::std::option::Option::None,
                     provides: ::std::option::Option::None,
+                    shareable: false,
+                    override_from: ::std::option::Option::None,
                     visible: #visible,
                     compute_complexity: #complexity,
                 });
@@ -324,11 +376,12 @@ pub fn generate(
             };
 
             let guard = match &field.guard {
-                Some(meta_list) => generate_guards(&crate_name, meta_list)?,
-                None => None,
+                Some(meta_list) => generate_guards(&crate_name, meta_list)?
+                    .map(|guard| quote! { #guard.check(ctx) }),
+                None => generate_default_guard(subscription_args.internal, field.public),
             };
             let guard = guard.map(|guard| quote! {
-                #guard.check(ctx).await.map_err(|err| {
+                #guard.await.map_err(|err| {
                     err.into_server_error(ctx.item.pos)
                         .with_path(::std::vec![#crate_name::PathSegment::Field(::std::borrow::ToOwned::to_owned(&*field_name))])
                 })?;
@@ -424,6 +477,7 @@ pub fn generate(
             remove_graphql_attrs(&mut method.attrs);
         }
     }
+    cfg_variants.finish(&mut item_impl.items);
 
     if create_stream.is_empty() {
         return Err(Error::new_spanned(
@@ -477,3 +531,64 @@ pub fn generate(
 
     Ok(expanded.into())
 }
+
+/// Convert the topic of a field to a format string, whose placeholders such as `{merchant_id}`
+/// are the arguments of the field.
+fn parse_topic(
+    topic: &str,
+    args: &[(PatIdent, TypePath, args::SubscriptionFieldArgument)],
+    method: &Ident,
+) -> GeneratorResult<(String, Vec<Ident>)> {
+    let mut format = String::new();
+    let mut topic_args = Vec::new();
+    let mut chars = topic.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                format.push_str("{{");
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                format.push_str("}}");
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => {
+                            return Err(Error::new_spanned(
+                                method,
+                                format!("Unclosed placeholder in the topic \"{}\".", topic),
+                            )
+                            .into())
+                        }
+                    }
+                }
+                let name = name.trim();
+                match args.iter().find(|(ident, _, _)| ident.ident == name) {
+                    Some((ident, _, _)) => topic_args.push(ident.ident.clone()),
+                    None => {
+                        return Err(Error::new_spanned(
+                            method,
+                            format!("The topic refers to an unknown argument `{}`.", name),
+                        )
+                        .into())
+                    }
+                }
+                format.push_str("{}");
+            }
+            '}' => {
+                return Err(Error::new_spanned(
+                    method,
+                    format!("Unmatched `}}` in the topic \"{}\".", topic),
+                )
+                .into())
+            }
+            c => format.push(c),
+        }
+    }
+    Ok((format, topic_args))
+}
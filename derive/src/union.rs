@@ -7,7 +7,7 @@ use syn::visit_mut::VisitMut;
 use syn::{visit_mut, Error, Lifetime, Type};
 
 use crate::args::{self, RenameTarget};
-use crate::utils::{get_crate_name, get_rustdoc, visible_fn, GeneratorResult};
+use crate::utils::{gen_metadata, get_crate_name, get_rustdoc, visible_fn, GeneratorResult};
 
 pub fn generate(union_args: &args::Union) -> GeneratorResult<TokenStream> {
     let crate_name = get_crate_name(union_args.internal);
@@ -27,6 +27,13 @@ pub fn generate(union_args: &args::Union) -> GeneratorResult<TokenStream> {
         .clone()
         .unwrap_or_else(|| RenameTarget::Type.rename(ident.to_string()));
 
+    let type_metadata = gen_metadata(
+        &crate_name,
+        &union_args.meta,
+        &quote! { #gql_typename },
+        None,
+    )?;
+
     let desc = get_rustdoc(&union_args.attrs)?
         .map(|s| quote! { ::std::option::Option::Some(#s) })
         .unwrap_or_else(|| quote! {::std::option::Option::None});
@@ -36,6 +43,7 @@ pub fn generate(union_args: &args::Union) -> GeneratorResult<TokenStream> {
     let mut union_values = Vec::new();
     let mut get_introspection_typename = Vec::new();
     let mut collect_all_fields = Vec::new();
+    let mut has_dynamic = false;
 
     for variant in s {
         let enum_name = &variant.ident;
@@ -72,6 +80,33 @@ pub fn generate(union_args: &args::Union) -> GeneratorResult<TokenStream> {
             }
 
             enum_names.push(enum_name);
+
+            if variant.dynamic {
+                if variant.flatten {
+                    return Err(Error::new_spanned(
+                        enum_name,
+                        "A dynamic variant cannot be flattened",
+                    )
+                    .into());
+                }
+                has_dynamic = true;
+                type_into_impls.push(quote! {
+                    #[allow(clippy::all, clippy::pedantic)]
+                    impl #impl_generics ::std::convert::From<#p> for #ident #ty_generics #where_clause {
+                        fn from(obj: #p) -> Self {
+                            #ident::#enum_name(obj)
+                        }
+                    }
+                });
+                get_introspection_typename.push(quote! {
+                    #ident::#enum_name(obj) => #crate_name::DynObject::introspection_type_name(obj)
+                });
+                collect_all_fields.push(quote! {
+                    #ident::#enum_name(obj) => #crate_name::DynObject::collect_all_fields(obj, ctx, fields)
+                });
+                continue;
+            }
+
             union_values.push(quote! {
                 union_values.insert(
                     <#p as #crate_name::Type>::type_name().into_owned(),
@@ -133,15 +168,9 @@ pub fn generate(union_args: &args::Union) -> GeneratorResult<TokenStream> {
                 });
             }
 
-            if !variant.flatten {
-                get_introspection_typename.push(quote! {
-                    #ident::#enum_name(obj) => <#p as #crate_name::Type>::type_name()
-                });
-            } else {
-                get_introspection_typename.push(quote! {
-                    #ident::#enum_name(obj) => <#p as #crate_name::Type>::introspection_type_name(obj)
-                });
-            }
+            get_introspection_typename.push(quote! {
+                #ident::#enum_name(obj) => <#p as #crate_name::Type>::introspection_type_name(obj)
+            });
 
             collect_all_fields.push(quote! {
                 #ident::#enum_name(obj) => obj.collect_all_fields(ctx, fields)
@@ -151,7 +180,7 @@ pub fn generate(union_args: &args::Union) -> GeneratorResult<TokenStream> {
         }
     }
 
-    if possible_types.is_empty() {
+    if possible_types.is_empty() && !has_dynamic {
         return Err(Error::new_spanned(
             &ident,
             "A GraphQL Union type must include one or more unique member types.",
@@ -159,6 +188,14 @@ pub fn generate(union_args: &args::Union) -> GeneratorResult<TokenStream> {
         .into());
     }
 
+    let resolve_type = union_args.resolve_type.as_ref().map(|resolve_type| {
+        quote! {
+            if let ::std::option::Option::Some(name) = #resolve_type(self) {
+                return ::std::convert::Into::into(name);
+            }
+        }
+    });
+
     let visible = visible_fn(&union_args.visible);
     let expanded = quote! {
         #(#type_into_impls)*
@@ -170,6 +207,7 @@ pub fn generate(union_args: &args::Union) -> GeneratorResult<TokenStream> {
             }
 
             fn introspection_type_name(&self) -> ::std::borrow::Cow<'static, ::std::primitive::str> {
+                #resolve_type
                 match self {
                     #(#get_introspection_typename),*
                 }
@@ -178,6 +216,7 @@ pub fn generate(union_args: &args::Union) -> GeneratorResult<TokenStream> {
             fn create_type_info(registry: &mut #crate_name::registry::Registry) -> ::std::string::String {
                 registry.create_type::<Self, _>(|registry| {
                     #(#registry_types)*
+                    #type_metadata
 
                     #crate_name::registry::MetaType::Union {
                         name: ::std::borrow::ToOwned::to_owned(#gql_typename),
@@ -3,16 +3,19 @@ use std::collections::HashSet;
 use darling::FromMeta;
 use proc_macro2::{Span, TokenStream, TokenTree};
 use proc_macro_crate::{crate_name, FoundCrate};
-use quote::quote;
-use syn::visit::Visit;
+use quote::{format_ident, quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::parse::Parse;
+use syn::visit::{self, Visit};
 use syn::{
-    Attribute, Error, Expr, ExprPath, FnArg, Ident, ImplItemMethod, Lit, LitStr, Meta, NestedMeta,
-    Pat, PatIdent, Type, TypeGroup, TypeParamBound, TypeReference,
+    parse_quote, Attribute, Error, Expr, ExprPath, FnArg, GenericArgument, Ident, ImplItem,
+    ImplItemMethod, ItemImpl, Lifetime, Lit, LitStr, Meta, MetaList, NestedMeta, Pat, PatIdent,
+    PathArguments, Type, TypeGroup, TypeParamBound, TypeReference,
 };
 use thiserror::Error;
 
 use crate::args;
-use crate::args::{Argument, Deprecation, Visible};
+use crate::args::{Argument, Deprecation, Metadata, Visible};
 
 #[derive(Error, Debug)]
 pub enum GeneratorError {
@@ -145,6 +148,16 @@ pub fn generate_validator(crate_name: &TokenStream, args: &Meta) -> GeneratorRes
     }
 }
 
+/// Returns the check of the default guard of the schema, which guards the fields that have neither
+/// a guard nor the `public` attribute, except the fields of the types of this crate.
+pub fn generate_default_guard(internal: bool, public: bool) -> Option<TokenStream> {
+    if internal || public {
+        None
+    } else {
+        Some(quote! { ctx.check_default_guard() })
+    }
+}
+
 pub fn generate_guards(
     crate_name: &TokenStream,
     args: &Meta,
@@ -280,6 +293,20 @@ pub fn generate_guards(
                 Ok(Some(quote! { #ty { #(#params),* } }))
             }
         },
+        Meta::NameValue(nv) if nv.path.is_ident("guard") => {
+            if let Lit::Str(value) = &nv.lit {
+                let expr = value.parse::<Expr>()?;
+                Ok(Some(quote! {
+                    ({
+                        #[allow(unused_imports)]
+                        use #crate_name::guard::GuardExt as _;
+                        #expr
+                    })
+                }))
+            } else {
+                Err(Error::new_spanned(&nv.lit, "Value must be string literal").into())
+            }
+        }
         _ => Err(Error::new_spanned(args, "Invalid guards").into()),
     }
 }
@@ -319,8 +346,8 @@ fn generate_default_value(lit: &Lit) -> GeneratorResult<TokenStream> {
             Ok(quote!({ ::std::convert::TryInto::try_into(#value).unwrap() }))
         }
         Lit::Float(value) => {
-            let value = value.base10_parse::<f64>()?;
-            Ok(quote!({ ::std::convert::TryInto::try_into(#value) }))
+            let value = proc_macro2::Literal::f64_unsuffixed(value.base10_parse::<f64>()?);
+            Ok(quote!({ #value }))
         }
         Lit::Bool(value) => {
             let value = value.value;
@@ -334,11 +361,8 @@ fn generate_default_value(lit: &Lit) -> GeneratorResult<TokenStream> {
 }
 
 fn generate_default_with(lit: &LitStr) -> GeneratorResult<TokenStream> {
-    let str = lit.value();
-    let tokens: TokenStream = str
-        .parse()
-        .map_err(|err| GeneratorError::Syn(syn::Error::from(err)))?;
-    Ok(quote! { (#tokens) })
+    let expr = parse_lit_str::<Expr>(lit, "default_with expression")?;
+    Ok(quote! { (#expr) })
 }
 
 pub fn generate_default(
@@ -355,6 +379,27 @@ pub fn generate_default(
     }
 }
 
+/// Parses the content of a string literal, reporting errors at the literal.
+pub fn parse_lit_str<T: Parse>(lit: &LitStr, what: &str) -> GeneratorResult<T> {
+    lit.parse::<T>()
+        .map_err(|err| Error::new_spanned(lit, format!("Invalid {}: {}", what, err)).into())
+}
+
+/// Returns an error pointing at `tokens` if `name` has already been used in the same type.
+pub fn check_unique_name(
+    names: &mut HashSet<String>,
+    name: &str,
+    kind: &str,
+    tokens: impl ToTokens,
+) -> GeneratorResult<()> {
+    if !names.insert(name.to_string()) {
+        return Err(
+            Error::new_spanned(tokens, format!("Duplicate {} name \"{}\".", kind, name)).into(),
+        );
+    }
+    Ok(())
+}
+
 pub fn get_param_getter_ident(name: &str) -> Ident {
     Ident::new(&format!("__{}_getter", name), Span::call_site())
 }
@@ -367,26 +412,246 @@ pub fn get_cfg_attrs(attrs: &[Attribute]) -> Vec<Attribute> {
         .collect()
 }
 
+/// Parses the `graphql` attributes, merging them if there are several of them, which happens when
+/// some of them come from `cfg_attr`.
 pub fn parse_graphql_attrs<T: FromMeta>(attrs: &[Attribute]) -> GeneratorResult<Option<T>> {
+    let mut merged: Option<MetaList> = None;
     for attr in attrs {
         if attr.path.is_ident("graphql") {
-            let meta = attr.parse_meta()?;
-            return Ok(Some(T::from_meta(&meta)?));
+            match (&mut merged, attr.parse_meta()?) {
+                (None, Meta::List(list)) => merged = Some(list),
+                (Some(merged), Meta::List(list)) => merged.nested.extend(list.nested),
+                (_, meta) => return Ok(Some(T::from_meta(&meta)?)),
+            }
         }
     }
-    Ok(None)
+    match merged {
+        Some(list) => Ok(Some(T::from_meta(&Meta::List(list))?)),
+        None => Ok(None),
+    }
 }
 
 pub fn remove_graphql_attrs(attrs: &mut Vec<Attribute>) {
-    if let Some((idx, _)) = attrs
-        .iter()
-        .enumerate()
-        .find(|(_, a)| a.path.is_ident("graphql"))
-    {
-        attrs.remove(idx);
+    attrs.retain(|attr| !attr.path.is_ident("graphql"));
+}
+
+/// Splits the `cfg_attr` attributes of an argument or a method into their predicate and the
+/// attributes they apply, for the ones that apply `graphql` attributes.
+fn graphql_cfg_attrs(attrs: &[Attribute]) -> GeneratorResult<Vec<(NestedMeta, Vec<NestedMeta>)>> {
+    let mut res = Vec::new();
+    for attr in attrs {
+        if !attr.path.is_ident("cfg_attr") {
+            continue;
+        }
+        if let Meta::List(list) = attr.parse_meta()? {
+            let mut nested = list.nested.into_iter();
+            let predicate = match nested.next() {
+                Some(predicate) => predicate,
+                None => continue,
+            };
+            let attrs = nested.collect::<Vec<_>>();
+            if attrs.iter().any(
+                |attr| matches!(attr, NestedMeta::Meta(meta) if meta.path().is_ident("graphql")),
+            ) {
+                res.push((predicate, attrs));
+            }
+        }
+    }
+    Ok(res)
+}
+
+/// Applies or drops the `graphql` attributes inside the `cfg_attr` attributes, according to the
+/// next value of `enabled`.
+fn resolve_graphql_cfg_attrs(
+    attrs: &mut Vec<Attribute>,
+    enabled: &mut impl Iterator<Item = bool>,
+) -> GeneratorResult<()> {
+    let mut res = Vec::new();
+    for attr in attrs.drain(..) {
+        if !attr.path.is_ident("cfg_attr") {
+            res.push(attr);
+            continue;
+        }
+        let (predicate, nested) = match graphql_cfg_attrs(std::slice::from_ref(&attr))?.pop() {
+            Some(item) => item,
+            None => {
+                res.push(attr);
+                continue;
+            }
+        };
+        let (graphql, others): (Vec<_>, Vec<_>) = nested.into_iter().partition(
+            |attr| matches!(attr, NestedMeta::Meta(meta) if meta.path().is_ident("graphql")),
+        );
+        if enabled.next().unwrap_or_default() {
+            res.extend(
+                graphql
+                    .iter()
+                    .map(|meta| -> Attribute { parse_quote!(#[#meta]) }),
+            );
+        }
+        if !others.is_empty() {
+            res.push(parse_quote!(#[cfg_attr(#predicate, #(#others),*)]));
+        }
+    }
+    *attrs = res;
+    Ok(())
+}
+
+/// The combinations of the predicates of the `cfg_attr` attributes that apply `graphql`
+/// attributes, expanded by [`expand_graphql_cfg_attrs`].
+pub struct GraphqlCfgVariants {
+    /// The methods of the combinations other than the one kept in the impl block, for each item
+    /// of the impl block. They only generate the registration and the resolver code, and are not
+    /// emitted.
+    variants: Vec<Vec<ImplItem>>,
+    conditions: Vec<(usize, Attribute)>,
+}
+
+impl GraphqlCfgVariants {
+    /// Iterates over the items of the impl block, each followed by its variants.
+    pub fn items_mut<'a>(
+        &'a mut self,
+        items: &'a mut [ImplItem],
+    ) -> impl Iterator<Item = &'a mut ImplItem> {
+        let variants = self
+            .variants
+            .iter_mut()
+            .map(Some)
+            .chain(std::iter::repeat_with(|| None));
+        items.iter_mut().zip(variants).flat_map(|(item, variants)| {
+            std::iter::once(item).chain(variants.into_iter().flatten())
+        })
+    }
+
+    /// Removes the `cfg` attributes from the methods kept in the impl block, once their
+    /// registration and resolver code is generated, so that they are compiled with every
+    /// combination.
+    pub fn finish(self, items: &mut [ImplItem]) {
+        for (idx, condition) in self.conditions {
+            if let ImplItem::Method(method) = &mut items[idx] {
+                if let Some(pos) = method.attrs.iter().rposition(|attr| *attr == condition) {
+                    method.attrs.remove(pos);
+                }
+            }
+        }
     }
 }
 
+/// Expands the `cfg_attr` attributes that apply `graphql` attributes to the methods of an impl
+/// block or to their arguments.
+///
+/// Unlike derive macros, attribute macros receive their input before `cfg_attr` is evaluated.
+/// The method kept in the impl block is the combination where none of the predicates hold, and
+/// the other combinations are returned as variants. Each of them has a `cfg` attribute that
+/// gates its registration and resolver code, while the method body is only emitted once.
+pub fn expand_graphql_cfg_attrs(item_impl: &mut ItemImpl) -> GeneratorResult<GraphqlCfgVariants> {
+    let mut variants = GraphqlCfgVariants {
+        variants: Vec::new(),
+        conditions: Vec::new(),
+    };
+    for (idx, item) in item_impl.items.iter_mut().enumerate() {
+        variants.variants.push(Vec::new());
+        let method = match item {
+            ImplItem::Method(method) => method,
+            _ => continue,
+        };
+
+        let method_cfg_attrs = graphql_cfg_attrs(&method.attrs)?;
+        let mut predicates = method_cfg_attrs
+            .iter()
+            .map(|(predicate, _)| predicate.clone())
+            .collect::<Vec<_>>();
+        for arg in &method.sig.inputs {
+            if let FnArg::Typed(pat) = arg {
+                predicates.extend(
+                    graphql_cfg_attrs(&pat.attrs)?
+                        .into_iter()
+                        .map(|(predicate, _)| predicate),
+                );
+            }
+        }
+        if predicates.is_empty() {
+            continue;
+        }
+        if predicates.len() > 8 {
+            return Err(Error::new_spanned(
+                &method.sig.ident,
+                "Too many `cfg_attr` attributes with `graphql` attributes.",
+            )
+            .into());
+        }
+
+        // These attributes change the method itself or add other methods, which must be the
+        // same for every combination.
+        let method_attrs = method
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("graphql"))
+            .map(|attr| attr.parse_meta())
+            .collect::<Result<Vec<_>, _>>()?;
+        let nested = method_attrs
+            .iter()
+            .chain(method_cfg_attrs.iter().flat_map(|(_, attrs)| {
+                attrs.iter().filter_map(|attr| match attr {
+                    NestedMeta::Meta(meta) if meta.path().is_ident("graphql") => Some(meta),
+                    _ => None,
+                })
+            }))
+            .filter_map(|meta| match meta {
+                Meta::List(list) => Some(list.nested.iter()),
+                _ => None,
+            })
+            .flatten();
+        for meta in nested {
+            if let NestedMeta::Meta(meta) = meta {
+                for name in ["entity", "blocking", "derived"] {
+                    if meta.path().is_ident(name) {
+                        return Err(Error::new_spanned(
+                            meta,
+                            format!(
+                                "The `{}` attribute can't be used on a method with `cfg_attr` attributes that apply `graphql` attributes.",
+                                name
+                            ),
+                        )
+                        .into());
+                    }
+                }
+            }
+        }
+
+        let original = method.clone();
+        for mask in 0..1u32 << predicates.len() {
+            let flags = (0..predicates.len())
+                .map(|i| mask & (1 << i) != 0)
+                .collect::<Vec<_>>();
+            let mut enabled = flags.iter().copied();
+            let mut variant = original.clone();
+            resolve_graphql_cfg_attrs(&mut variant.attrs, &mut enabled)?;
+            for arg in &mut variant.sig.inputs {
+                if let FnArg::Typed(pat) = arg {
+                    resolve_graphql_cfg_attrs(&mut pat.attrs, &mut enabled)?;
+                }
+            }
+            let conditions = predicates.iter().zip(&flags).map(|(predicate, enabled)| {
+                if *enabled {
+                    quote! { #predicate }
+                } else {
+                    quote! { not(#predicate) }
+                }
+            });
+            let condition: Attribute = parse_quote!(#[cfg(all(#(#conditions),*))]);
+            variant.attrs.push(condition.clone());
+            if mask == 0 {
+                variants.conditions.push((idx, condition));
+                *method = variant;
+            } else {
+                variants.variants[idx].push(ImplItem::Method(variant));
+            }
+        }
+    }
+    Ok(variants)
+}
+
 pub fn get_type_path_and_name(ty: &Type) -> GeneratorResult<(&Type, String)> {
     match ty {
         Type::Path(path) => Ok((
@@ -415,6 +680,63 @@ pub fn get_type_path_and_name(ty: &Type) -> GeneratorResult<(&Type, String)> {
     }
 }
 
+/// Returns `true` if the type is `PhantomData<T>`, such fields are never exposed in the schema.
+pub fn is_phantom_data(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|s| s.ident == "PhantomData")
+            .unwrap_or_default(),
+        Type::Group(TypeGroup { elem, .. }) => is_phantom_data(elem),
+        _ => false,
+    }
+}
+
+/// The items of the `#[graphql(...)]` attributes, which belong to the other derives of the type.
+pub fn graphql_items(attrs: &[Attribute]) -> GeneratorResult<Vec<NestedMeta>> {
+    let mut items = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("graphql")) {
+        if let Meta::List(list) = attr.parse_meta()? {
+            items.extend(list.nested);
+        }
+    }
+    Ok(items)
+}
+
+pub fn has_flag(items: &[NestedMeta], name: &str) -> bool {
+    items
+        .iter()
+        .any(|item| matches!(item, NestedMeta::Meta(Meta::Path(path)) if path.is_ident(name)))
+}
+
+pub fn find_value<'a>(items: &'a [NestedMeta], name: &str) -> Option<&'a Lit> {
+    items.iter().find_map(|item| match item {
+        NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident(name) => Some(&nv.lit),
+        _ => None,
+    })
+}
+
+/// Returns `T` for `Option<T>`, because a missing value can only be compared by omitting the
+/// filter.
+pub fn option_inner_type(ty: &Type) -> &Type {
+    match ty {
+        Type::Path(path) => match path.path.segments.last() {
+            Some(segment) if segment.ident == "Option" => match &segment.arguments {
+                PathArguments::AngleBracketed(args) => match args.args.first() {
+                    Some(GenericArgument::Type(ty)) => ty,
+                    _ => ty,
+                },
+                _ => ty,
+            },
+            _ => ty,
+        },
+        Type::Group(TypeGroup { elem, .. }) => option_inner_type(elem),
+        _ => ty,
+    }
+}
+
 pub fn visible_fn(visible: &Option<Visible>) -> TokenStream {
     match visible {
         None | Some(Visible::None) => quote! { ::std::option::Option::None },
@@ -425,6 +747,46 @@ pub fn visible_fn(visible: &Option<Visible>) -> TokenStream {
     }
 }
 
+/// Generates a statement that stores the metadata of the type named `ty`, or of its field if
+/// `field` is `Some`.
+///
+/// The statement refers to the `registry` in scope.
+pub fn gen_metadata(
+    crate_name: &TokenStream,
+    metadata: &Metadata,
+    ty: &TokenStream,
+    field: Option<&str>,
+) -> GeneratorResult<TokenStream> {
+    if metadata.0.is_empty() {
+        return Ok(quote! {});
+    }
+
+    let mut entries = Vec::new();
+    for (key, lit) in &metadata.0 {
+        let value = match lit {
+            Lit::Str(s) => quote! { ::std::string::String::from(#s) },
+            Lit::Bool(b) => quote! { #b },
+            Lit::Int(n) => {
+                let n = n.base10_parse::<i64>()?;
+                quote! { #n }
+            }
+            Lit::Float(f) => {
+                let f = f.base10_parse::<f64>()?;
+                quote! { #f }
+            }
+            _ => return Err(Error::new_spanned(lit, "Invalid metadata value.").into()),
+        };
+        entries.push(quote! { (#key, #crate_name::Value::from(#value)) });
+    }
+    let metadata = quote! {
+        <#crate_name::Metadata as ::std::iter::FromIterator<_>>::from_iter(::std::vec![#(#entries),*])
+    };
+    Ok(match field {
+        Some(field) => quote! { registry.set_field_metadata(#ty, #field, #metadata); },
+        None => quote! { registry.set_type_metadata(#ty, #metadata); },
+    })
+}
+
 pub fn parse_complexity_expr(s: &str) -> GeneratorResult<(HashSet<String>, Expr)> {
     #[derive(Default)]
     struct VisitComplexityExpr {
@@ -506,6 +868,7 @@ pub fn extract_input_args(
                                 parse_graphql_attrs::<args::Argument>(&pat.attrs)?
                                     .unwrap_or_default(),
                             ));
+                            remove_graphql_attrs(&mut pat.attrs);
                         } else {
                             create_ctx = false;
                         }
@@ -533,3 +896,162 @@ pub fn extract_input_args(
 
     Ok(args)
 }
+
+/// Turns a synchronous resolver with the `blocking` attribute into an asynchronous one, and
+/// returns a hidden copy of the original method, which the new one runs with
+/// `Context::spawn_blocking`.
+pub fn generate_blocking_method(
+    crate_name: &TokenStream,
+    method: &mut ImplItemMethod,
+) -> GeneratorResult<ImplItemMethod> {
+    if let Some(asyncness) = &method.sig.asyncness {
+        return Err(
+            Error::new_spanned(asyncness, "A blocking resolver must be synchronous.").into(),
+        );
+    }
+    if !matches!(method.sig.inputs.first(), Some(FnArg::Receiver(_))) {
+        return Err(Error::new_spanned(
+            &method.sig,
+            "The self receiver must be the first parameter.",
+        )
+        .into());
+    }
+
+    let mut inner = method.clone();
+    inner.sig.ident = format_ident!("__{}_blocking", method.sig.ident.unraw());
+    inner
+        .attrs
+        .retain(|attr| !attr.path.is_ident("graphql") && !attr.path.is_ident("doc"));
+    inner.attrs.push(parse_quote!(#[doc(hidden)]));
+    for arg in &mut inner.sig.inputs {
+        if let FnArg::Typed(pat) = arg {
+            remove_graphql_attrs(&mut pat.attrs);
+        }
+    }
+
+    let mut ctx = None;
+    let mut call_args = Vec::new();
+    for (idx, arg) in method.sig.inputs.iter_mut().enumerate() {
+        if let FnArg::Typed(pat) = arg {
+            let pat_ident = match &mut *pat.pat {
+                Pat::Ident(pat_ident) => pat_ident,
+                _ => return Err(Error::new_spanned(&pat, "Must be a simple argument").into()),
+            };
+            // The arguments are moved to the hidden method, so they don't need to be mutable here.
+            pat_ident.mutability = None;
+            let ctx_type = match &*pat.ty {
+                Type::Reference(TypeReference { elem, .. }) => match elem.as_ref() {
+                    Type::Path(path) => Some(path.path.segments.last().unwrap().ident.to_string()),
+                    _ => None,
+                },
+                _ => None,
+            };
+            if idx == 1 && ctx_type.as_deref() == Some("Context") {
+                return Err(Error::new_spanned(
+                    &pat.ty,
+                    "A blocking resolver takes a `&BlockingContext` instead of a `&Context`.",
+                )
+                .into());
+            }
+            if idx == 1 && ctx_type.as_deref() == Some("BlockingContext") {
+                *pat.ty = parse_quote!(&#crate_name::Context<'_>);
+                ctx = Some(pat_ident.ident.clone());
+            } else {
+                if !is_static_type(&pat.ty) {
+                    return Err(Error::new_spanned(
+                        &pat.ty,
+                        "The arguments of a blocking resolver are moved to another thread, so they must be owned `'static` types.",
+                    )
+                    .into());
+                }
+                call_args.push(pat_ident.ident.clone());
+            }
+        }
+    }
+
+    let inner_ident = &inner.sig.ident;
+    let (ctx, use_ctx) = match ctx {
+        Some(ctx) => (ctx, quote! { __blocking_ctx, }),
+        None => {
+            method
+                .sig
+                .inputs
+                .insert(1, parse_quote!(__ctx: &#crate_name::Context<'_>));
+            (format_ident!("__ctx"), quote! {})
+        }
+    };
+    method.sig.asyncness = Some(Default::default());
+    method.block = parse_quote!({
+        let __self = ::std::clone::Clone::clone(self);
+        #ctx.spawn_blocking(move |__blocking_ctx| {
+            __self.#inner_ident(#use_ctx #(#call_args),*)
+        })
+        .await
+    });
+
+    Ok(inner)
+}
+
+/// Returns `false` if the type contains a reference or a lifetime other than `'static`.
+fn is_static_type(ty: &Type) -> bool {
+    #[derive(Default)]
+    struct VisitNonStatic {
+        found: bool,
+    }
+
+    impl<'a> Visit<'a> for VisitNonStatic {
+        fn visit_type_reference(&mut self, i: &'a TypeReference) {
+            if i.lifetime.is_none() {
+                self.found = true;
+            }
+            visit::visit_type_reference(self, i);
+        }
+
+        fn visit_lifetime(&mut self, i: &'a Lifetime) {
+            if i.ident != "static" {
+                self.found = true;
+            }
+        }
+    }
+
+    let mut visit = VisitNonStatic::default();
+    visit.visit_type(ty);
+    !visit.found
+}
+
+pub fn generate_ctx_data_getter(
+    crate_name: &TokenStream,
+    ident: &PatIdent,
+    ty: &Type,
+) -> TokenStream {
+    let (data_ty, is_ref) = match ty {
+        Type::Reference(TypeReference { elem, .. }) => (elem.as_ref(), true),
+        _ => (ty, false),
+    };
+    let get_data = quote! {
+        ctx.data::<#data_ty>().map_err(|err| #crate_name::Error::into_server_error(err, ctx.item.pos))?
+    };
+    if is_ref {
+        quote! {
+            let #ident: #ty = #get_data;
+        }
+    } else {
+        quote! {
+            let #ident: #ty = ::std::clone::Clone::clone(#get_data);
+        }
+    }
+}
+
+pub fn generate_flatten_args(crate_name: &TokenStream, ty: &Type) -> TokenStream {
+    quote! {
+        #crate_name::static_assertions::assert_impl_one!(#ty: #crate_name::InputObjectType);
+        if let #crate_name::registry::MetaType::InputObject { input_fields, .. } =
+            registry.create_dummy_type::<#ty>() {
+            registry.add_flattened_input_fields(
+                &<#ty as #crate_name::Type>::type_name(),
+                input_fields.keys().cloned(),
+            );
+            args.extend(input_fields.into_iter().map(|(_, arg)| (arg.name, arg)));
+        }
+    }
+}
@@ -77,6 +77,10 @@ where
 }
 
 /// Extractor for GraphQL batch request.
+///
+/// The limits of the request body can be configured by adding a
+/// `async_graphql::http::MultipartOptions` to the request extensions, for example with
+/// `axum::AddExtensionLayer`.
 pub struct GraphQLBatchRequest(pub async_graphql::BatchRequest);
 
 impl GraphQLBatchRequest {
@@ -111,6 +115,11 @@ where
                 .and_then(|headers| headers.get(http::header::CONTENT_TYPE))
                 .and_then(|value| value.to_str().ok())
                 .map(ToString::to_string);
+            let opts = req
+                .extensions()
+                .and_then(|extensions| extensions.get::<MultipartOptions>())
+                .copied()
+                .unwrap_or_default();
             let body_stream = BodyStream::from_request(req)
                 .await
                 .map_err(|_| {
@@ -122,12 +131,7 @@ where
                 .map_err(|err| std::io::Error::new(ErrorKind::Other, err.to_string()));
             let body_reader = tokio_util::io::StreamReader::new(body_stream).compat();
             Ok(Self(
-                async_graphql::http::receive_batch_body(
-                    content_type,
-                    body_reader,
-                    MultipartOptions::default(),
-                )
-                .await?,
+                async_graphql::http::receive_batch_body(content_type, body_reader, opts).await?,
             ))
         }
     }
@@ -1,5 +1,6 @@
 use async_graphql::http::MultipartOptions;
-use poem::error::BadRequest;
+use async_graphql::ParseRequestError;
+use poem::error::{BadRequest, PayloadTooLarge};
 use poem::http::{header, Method};
 use poem::web::Query;
 use poem::{async_trait, Error, FromRequest, Request, RequestBody, Result};
@@ -56,6 +57,10 @@ impl<'a> FromRequest<'a> for GraphQLRequest {
 }
 
 /// An extractor for GraphQL batch request.
+///
+/// The limits of the request body can be configured by adding a
+/// `async_graphql::http::MultipartOptions` to the request data, for example with
+/// `poem::middleware::AddData`.
 pub struct GraphQLBatchRequest(pub async_graphql::BatchRequest);
 
 #[async_trait]
@@ -72,13 +77,18 @@ impl<'a> FromRequest<'a> for GraphQLBatchRequest {
                 .get(header::CONTENT_TYPE)
                 .and_then(|value| value.to_str().ok())
                 .map(ToString::to_string);
+            let opts = req.data::<MultipartOptions>().copied().unwrap_or_default();
             Ok(Self(
                 async_graphql::http::receive_batch_body(
                     content_type,
                     body.take()?.into_async_read().compat(),
-                    MultipartOptions::default(),
+                    opts,
                 )
-                .await?,
+                .await
+                .map_err(|err| match err {
+                    ParseRequestError::PayloadTooLarge => PayloadTooLarge(err),
+                    err => BadRequest(err),
+                })?,
             ))
         }
     }
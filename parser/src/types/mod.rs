@@ -20,7 +20,7 @@ pub use service::*;
 /// The type of an operation; `query`, `mutation` or `subscription`.
 ///
 /// [Reference](https://spec.graphql.org/October2021/#OperationType).
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
 pub enum OperationType {
     /// A query.
     Query,
@@ -1,14 +1,21 @@
 //! Query context.
 
 use std::any::{Any, TypeId};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::{self, Debug, Display, Formatter};
+use std::future::Future;
 use std::ops::Deref;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 
 use async_graphql_value::{Value as InputValue, Variables};
 use fnv::FnvHashMap;
+#[cfg(feature = "timeout")]
+use futures_timer::Delay;
+#[cfg(feature = "timeout")]
+use futures_util::future::Shared;
 use http::header::{AsHeaderName, HeaderMap, IntoHeaderName};
+use indexmap::IndexMap;
 use serde::ser::{SerializeSeq, Serializer};
 use serde::Serialize;
 
@@ -18,8 +25,8 @@ use crate::parser::types::{
 };
 use crate::schema::SchemaEnv;
 use crate::{
-    Error, InputType, Lookahead, Name, PathSegment, Pos, Positioned, Result, ServerError,
-    ServerResult, UploadValue, Value,
+    Error, GlobalId, InputType, Lookahead, Name, OnError, PathSegment, Pos, Positioned, Response,
+    Result, ServerError, ServerResult, UploadValue, Value, ID,
 };
 
 /// Schema/Context data.
@@ -41,6 +48,10 @@ impl Data {
     pub fn insert<D: Any + Send + Sync>(&mut self, data: D) {
         self.0.insert(TypeId::of::<D>(), Box::new(data));
     }
+
+    pub(crate) fn merge(&mut self, other: Data) {
+        self.0.extend(other.0);
+    }
 }
 
 impl Debug for Data {
@@ -203,6 +214,69 @@ pub struct ContextBase<'a, T> {
     pub query_env: &'a QueryEnv,
 }
 
+/// Gets the data of the query, of the session or of the schema, in that order of priority.
+fn find_data<'a, D: Any + Send + Sync>(
+    query_env: &'a QueryEnv,
+    schema_env: &'a SchemaEnv,
+) -> Option<&'a D> {
+    query_env
+        .ctx_data
+        .0
+        .get(&TypeId::of::<D>())
+        .map(AsRef::as_ref)
+        .or_else(|| {
+            query_env
+                .session_data
+                .0
+                .get(&TypeId::of::<D>())
+                .map(AsRef::as_ref)
+        })
+        .or_else(|| schema_env.data_for(Some(query_env.operation.node.ty), &TypeId::of::<D>()))
+        .and_then(|d| d.downcast_ref::<D>())
+}
+
+/// The data of a context, which can be moved to another thread.
+///
+/// See [`ContextBase::spawn_blocking`].
+#[cfg(feature = "unblock")]
+#[cfg_attr(docsrs, doc(cfg(feature = "unblock")))]
+pub struct BlockingContext {
+    query_env: QueryEnv,
+    schema_env: SchemaEnv,
+}
+
+#[cfg(feature = "unblock")]
+impl BlockingContext {
+    /// Gets the global data defined in the `Context` or `Schema`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Error` if the specified type data does not exist.
+    pub fn data<D: Any + Send + Sync>(&self) -> Result<&D> {
+        self.data_opt::<D>().ok_or_else(|| {
+            Error::new(format!(
+                "Data `{}` does not exist.",
+                std::any::type_name::<D>()
+            ))
+        })
+    }
+
+    /// Gets the global data defined in the `Context` or `Schema`.
+    ///
+    /// # Panics
+    ///
+    /// It will panic if the specified data type does not exist.
+    pub fn data_unchecked<D: Any + Send + Sync>(&self) -> &D {
+        self.data_opt::<D>()
+            .unwrap_or_else(|| panic!("Data `{}` does not exist.", std::any::type_name::<D>()))
+    }
+
+    /// Gets the global data defined in the `Context` or `Schema` or `None` if the specified type data does not exist.
+    pub fn data_opt<D: Any + Send + Sync>(&self) -> Option<&D> {
+        find_data(&self.query_env, &self.schema_env)
+    }
+}
+
 #[doc(hidden)]
 pub struct QueryEnvInner {
     pub extensions: Extensions,
@@ -215,7 +289,19 @@ pub struct QueryEnvInner {
     pub ctx_data: Arc<Data>,
     pub http_headers: Mutex<HeaderMap<String>>,
     pub disable_introspection: bool,
+    pub on_error: OnError,
+    pub max_list_elements: Option<usize>,
+    pub list_elements: AtomicUsize,
+    pub max_response_size: Option<usize>,
+    pub response_size: AtomicUsize,
+    pub response_too_large: Mutex<Option<ServerError>>,
     pub errors: Mutex<Vec<ServerError>>,
+    pub memoize_cache: Mutex<HashMap<String, Arc<dyn Any + Send + Sync>>>,
+    #[cfg(feature = "timeout")]
+    pub deadline: Option<Shared<Delay>>,
+    pub incremental_delivery: bool,
+    pub incremental_payloads: Mutex<Vec<Response>>,
+    pub disabled_fields: HashSet<Pos>,
 }
 
 #[doc(hidden)]
@@ -252,6 +338,12 @@ impl QueryEnv {
     }
 }
 
+/// The arguments of an enabled `@defer` or `@stream` directive.
+pub(crate) struct IncrementalDirective {
+    pub(crate) label: Option<String>,
+    pub(crate) initial_count: usize,
+}
+
 impl<'a, T> ContextBase<'a, T> {
     #[doc(hidden)]
     pub fn with_field(
@@ -284,18 +376,49 @@ impl<'a, T> ContextBase<'a, T> {
 
     #[doc(hidden)]
     pub fn set_error_path(&self, error: ServerError) -> ServerError {
+        if self.path_node.is_some() {
+            ServerError {
+                path: self.path(),
+                ..error
+            }
+        } else {
+            error
+        }
+    }
+
+    pub(crate) fn path(&self) -> Vec<PathSegment> {
+        let mut path = Vec::new();
         if let Some(node) = self.path_node {
-            let mut path = Vec::new();
             node.for_each(|current_node| {
                 path.push(match current_node {
                     QueryPathSegment::Name(name) => PathSegment::Field((*name).to_string()),
                     QueryPathSegment::Index(idx) => PathSegment::Index(*idx),
                 })
             });
-            ServerError { path, ..error }
-        } else {
-            error
         }
+        path
+    }
+
+    /// Record the result of a deferred fragment or a streamed list item, which is sent after the
+    /// initial response.
+    pub(crate) fn add_incremental_payload(
+        &self,
+        directive: &IncrementalDirective,
+        res: ServerResult<Value>,
+    ) {
+        let resp = match res {
+            Ok(value) => Response::new(value),
+            Err(err) => Response::from_errors(vec![err]),
+        };
+        self.query_env
+            .incremental_payloads
+            .lock()
+            .unwrap()
+            .push(Response {
+                path: self.path(),
+                label: directive.label.clone(),
+                ..resp
+            });
     }
 
     /// Report a resolver error.
@@ -305,6 +428,84 @@ impl<'a, T> ContextBase<'a, T> {
         self.query_env.errors.lock().unwrap().push(error);
     }
 
+    /// Count the elements of a resolved list, and abort the execution if the total number
+    /// exceeds [`SchemaBuilder::limit_list_elements`](crate::SchemaBuilder::limit_list_elements).
+    pub(crate) fn add_list_elements(&self, count: usize) -> ServerResult<()> {
+        if let Some(max_list_elements) = self.query_env.max_list_elements {
+            let total = self
+                .query_env
+                .list_elements
+                .fetch_add(count, Ordering::Relaxed)
+                + count;
+            if total > max_list_elements {
+                return Err(self.response_too_large(format!(
+                    "The response contains more than {} list elements.",
+                    max_list_elements
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Count the size of a resolved field in the JSON encoding of the response, and abort the
+    /// execution if the total size exceeds
+    /// [`SchemaBuilder::limit_response_size`](crate::SchemaBuilder::limit_response_size).
+    ///
+    /// The values of objects are not counted, because their fields are counted when they are
+    /// resolved.
+    pub(crate) fn add_response_size(&self, name: &str, value: &Value) -> ServerResult<()> {
+        fn shallow_size(value: &Value) -> usize {
+            match value {
+                Value::Object(_) => 2,
+                Value::List(items) => {
+                    2 + items.len().saturating_sub(1)
+                        + items.iter().map(shallow_size).sum::<usize>()
+                }
+                value => crate::schema::json_size(value),
+            }
+        }
+
+        if let Some(max_response_size) = self.query_env.max_response_size {
+            // The name, quoted and followed by a colon, and the separating comma.
+            let size = name.len() + 4 + shallow_size(value);
+            let total = self
+                .query_env
+                .response_size
+                .fetch_add(size, Ordering::Relaxed)
+                + size;
+            if total > max_response_size {
+                return Err(self.response_too_large(format!(
+                    "The response is larger than {} bytes.",
+                    max_response_size
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Creates a `RESPONSE_TOO_LARGE` error, which also replaces the whole response.
+    fn response_too_large(&self, message: String) -> ServerError {
+        let err = crate::schema::response_too_large_error(message);
+        self.query_env
+            .response_too_large
+            .lock()
+            .unwrap()
+            .get_or_insert_with(|| err.clone());
+        err
+    }
+
+    /// Report the error of a nullable field, or return it to abort the execution if the request
+    /// uses [`OnError::Abort`].
+    pub(crate) fn add_nullable_error(&self, error: ServerError) -> ServerResult<()> {
+        match self.query_env.on_error {
+            OnError::Propagate => {
+                self.add_error(error);
+                Ok(())
+            }
+            OnError::Abort => Err(error),
+        }
+    }
+
     /// Gets the global data defined in the `Context` or `Schema`.
     ///
     /// If both `Schema` and `Query` have the same data type, the data in the `Query` is obtained.
@@ -333,13 +534,117 @@ impl<'a, T> ContextBase<'a, T> {
 
     /// Gets the global data defined in the `Context` or `Schema` or `None` if the specified type data does not exist.
     pub fn data_opt<D: Any + Send + Sync>(&self) -> Option<&'a D> {
-        self.query_env
-            .ctx_data
-            .0
-            .get(&TypeId::of::<D>())
-            .or_else(|| self.query_env.session_data.0.get(&TypeId::of::<D>()))
-            .or_else(|| self.schema_env.data.0.get(&TypeId::of::<D>()))
-            .and_then(|d| d.downcast_ref::<D>())
+        find_data(self.query_env, self.schema_env)
+    }
+
+    /// Run a blocking function on a thread pool, so that it does not block the executor of the
+    /// resolvers, and returns its result.
+    ///
+    /// The function cannot borrow the context, which is bound to the executing query, so it is
+    /// given a [`BlockingContext`] which gives access to the same data.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_graphql::*;
+    ///
+    /// struct Query;
+    ///
+    /// #[Object]
+    /// impl Query {
+    ///     async fn checksum(&self, ctx: &Context<'_>) -> Result<u64> {
+    ///         ctx.spawn_blocking(|ctx| {
+    ///             let bytes = ctx.data::<Vec<u8>>()?;
+    ///             Ok(bytes.iter().map(|b| *b as u64).sum())
+    ///         })
+    ///         .await
+    ///     }
+    /// }
+    ///
+    /// tokio::runtime::Runtime::new().unwrap().block_on(async move {
+    ///     let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+    ///         .data(vec![1u8, 2, 3])
+    ///         .finish();
+    ///     let res = schema.execute("{ checksum }").await.into_result().unwrap().data;
+    ///     assert_eq!(res, value!({ "checksum": 6 }));
+    /// });
+    /// ```
+    #[cfg(feature = "unblock")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "unblock")))]
+    pub async fn spawn_blocking<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&BlockingContext) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let ctx = BlockingContext {
+            query_env: self.query_env.clone(),
+            schema_env: self.schema_env.clone(),
+        };
+        blocking::unblock(move || f(&ctx)).await
+    }
+
+    /// Check that the [`Scopes`](crate::guard::Scopes) in the data of the request contain the
+    /// scope.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error with the `UNAUTHORIZED` code and the missing scope in the `scope`
+    /// extension if the scope is not granted, or if there are no scopes in the data.
+    pub fn require_scope(&self, scope: &str) -> Result<()> {
+        use crate::ErrorExtensions;
+
+        if let Some(scopes) = self.data_opt::<crate::guard::Scopes>() {
+            if scopes.contains(scope) {
+                return Ok(());
+            }
+        }
+        Err(
+            Error::new(format!("The scope \"{}\" is required.", scope)).extend_with(|_, e| {
+                e.set("code", "UNAUTHORIZED");
+                e.set("scope", scope);
+            }),
+        )
+    }
+
+    /// Encode a global ID with the codec of the schema.
+    ///
+    /// The global ID is signed if the schema has an HMAC key.
+    ///
+    /// See [`SchemaBuilder::global_id_codec`](crate::SchemaBuilder::global_id_codec).
+    pub fn encode_global_id(&self, type_name: &str, id: impl Display) -> ID {
+        let id = GlobalId::new(type_name, id).encode_with(self.schema_env.global_id_codec.as_ref());
+        ID(self.sign(id.0))
+    }
+
+    /// Decode a global ID with the codec of the schema.
+    ///
+    /// The signature of the global ID is verified if the schema has an HMAC key.
+    ///
+    /// See [`SchemaBuilder::global_id_codec`](crate::SchemaBuilder::global_id_codec).
+    pub fn decode_global_id(&self, id: &str) -> Result<GlobalId> {
+        GlobalId::decode_with(self.schema_env.global_id_codec.as_ref(), self.verify(id)?)
+    }
+
+    /// Sign a value with the HMAC key of the schema, if there is one.
+    pub(crate) fn sign(&self, value: String) -> String {
+        #[cfg(feature = "hmac_signing")]
+        if let Some(signer) = &self.schema_env.signer {
+            return signer.sign(&value);
+        }
+        value
+    }
+
+    /// Verify a value signed with [`ContextBase::sign`] and remove the signature.
+    pub(crate) fn verify<'b>(&self, value: &'b str) -> Result<&'b str> {
+        #[cfg(feature = "hmac_signing")]
+        if let Some(signer) = &self.schema_env.signer {
+            use crate::ErrorExtensions;
+
+            return signer.verify(value).ok_or_else(|| {
+                Error::new("Invalid signature.").extend_with(|_, e| e.set("code", "BAD_USER_INPUT"))
+            });
+        }
+        Ok(value)
     }
 
     /// Returns whether the HTTP header `key` is currently set on the response
@@ -487,10 +792,46 @@ impl<'a, T> ContextBase<'a, T> {
             })
     }
 
-    fn resolve_input_value(&self, value: Positioned<InputValue>) -> ServerResult<Value> {
+    /// Returns `true` if the variable was provided in the request or has a default value.
+    fn is_var_provided(&self, name: &str) -> bool {
+        self.query_env.variables.contains_key(name)
+            || self
+                .query_env
+                .operation
+                .node
+                .variable_definitions
+                .iter()
+                .any(|def| def.node.name.node == name && def.node.default_value.is_some())
+    }
+
+    /// Returns `true` if the value is a variable that was not provided, in which case the
+    /// argument or input object field it is used for is treated as absent.
+    fn is_absent(&self, value: &InputValue) -> bool {
+        matches!(value, InputValue::Variable(name) if !self.is_var_provided(name))
+    }
+
+    fn remove_absent_fields(&self, value: InputValue) -> InputValue {
+        match value {
+            InputValue::List(values) => InputValue::List(
+                values
+                    .into_iter()
+                    .map(|value| self.remove_absent_fields(value))
+                    .collect(),
+            ),
+            InputValue::Object(fields) => InputValue::Object(
+                fields
+                    .into_iter()
+                    .filter(|(_, value)| !self.is_absent(value))
+                    .map(|(name, value)| (name, self.remove_absent_fields(value)))
+                    .collect(),
+            ),
+            value => value,
+        }
+    }
+
+    pub(crate) fn resolve_input_value(&self, value: Positioned<InputValue>) -> ServerResult<Value> {
         let pos = value.pos;
-        value
-            .node
+        self.remove_absent_fields(value.node)
             .into_const_with(|name| self.var_value(&name, pos))
     }
 
@@ -501,6 +842,61 @@ impl<'a, T> ContextBase<'a, T> {
             .any(|directive| directive.node.name.node == "ifdef")
     }
 
+    /// Returns the arguments of the `@defer` or `@stream` directive if it is enabled.
+    ///
+    /// The directives are ignored unless the response is delivered incrementally.
+    pub(crate) fn incremental_directive(
+        &self,
+        directives: &[Positioned<Directive>],
+        name: &str,
+    ) -> ServerResult<Option<IncrementalDirective>> {
+        if !self.query_env.incremental_delivery {
+            return Ok(None);
+        }
+        let directive = match directives
+            .iter()
+            .find(|directive| directive.node.name.node == name)
+        {
+            Some(directive) => directive,
+            None => return Ok(None),
+        };
+
+        let mut res = IncrementalDirective {
+            label: None,
+            initial_count: 0,
+        };
+        for (arg_name, value) in &directive.node.arguments {
+            let pos = value.pos;
+            let value = Some(self.resolve_input_value(value.clone())?);
+            match arg_name.node.as_str() {
+                "if" => {
+                    let enabled =
+                        <bool as InputType>::parse(value).map_err(|e| e.into_server_error(pos))?;
+                    if !enabled {
+                        return Ok(None);
+                    }
+                }
+                "label" => {
+                    res.label = <Option<String> as InputType>::parse(value)
+                        .map_err(|e| e.into_server_error(pos))?;
+                }
+                "initialCount" => {
+                    let initial_count =
+                        <i32 as InputType>::parse(value).map_err(|e| e.into_server_error(pos))?;
+                    if initial_count < 0 {
+                        return Err(ServerError::new(
+                            "initialCount must be a positive integer.",
+                            Some(pos),
+                        ));
+                    }
+                    res.initial_count = initial_count as usize;
+                }
+                _ => {}
+            }
+        }
+        Ok(Some(res))
+    }
+
     #[doc(hidden)]
     pub fn is_skip(&self, directives: &[Positioned<Directive>]) -> ServerResult<bool> {
         for directive in directives {
@@ -547,13 +943,26 @@ impl<'a> ContextBase<'a, &'a Positioned<SelectionSet>> {
 }
 
 impl<'a> ContextBase<'a, &'a Positioned<Field>> {
+    #[doc(hidden)]
+    pub async fn check_default_guard(&self) -> Result<()> {
+        match &self.schema_env.default_guard {
+            Some(guard) => guard.check(self).await,
+            None => Ok(()),
+        }
+    }
+
     #[doc(hidden)]
     pub fn param_value<T: InputType>(
         &self,
         name: &str,
         default: Option<fn() -> T>,
     ) -> ServerResult<T> {
-        let value = self.item.node.get_argument(name).cloned();
+        let value = self
+            .item
+            .node
+            .get_argument(name)
+            .filter(|value| !self.is_absent(&value.node))
+            .cloned();
         if value.is_none() {
             if let Some(default) = default {
                 return Ok(default());
@@ -566,6 +975,80 @@ impl<'a> ContextBase<'a, &'a Positioned<Field>> {
         InputType::parse(value).map_err(|e| e.into_server_error(pos))
     }
 
+    #[doc(hidden)]
+    pub fn flatten_param_value<T: InputType>(&self) -> ServerResult<T> {
+        let input_fields = self
+            .schema_env
+            .registry
+            .flattened_input_fields
+            .get(&*T::type_name());
+        let mut arguments = IndexMap::new();
+        for (name, value) in &self.item.node.arguments {
+            if !input_fields.map_or(false, |fields| fields.contains(name.node.as_str()))
+                || self.is_absent(&value.node)
+            {
+                continue;
+            }
+            arguments.insert(name.node.clone(), self.resolve_input_value(value.clone())?);
+        }
+        InputType::parse(Some(Value::Object(arguments)))
+            .map_err(|e| e.into_server_error(self.item.pos))
+    }
+
+    #[doc(hidden)]
+    pub async fn memoize<T, Fut>(&self, parent_type: &str, fut: Fut) -> ServerResult<T>
+    where
+        T: Clone + Send + Sync + 'static,
+        Fut: Future<Output = ServerResult<T>>,
+    {
+        // The parent is identified by its path, so the same field of different list items or of
+        // objects returned by different fields is never shared.
+        let mut key = String::new();
+        if let Some(parent) = self.path_node.and_then(|path_node| path_node.parent) {
+            key.push_str(&parent.to_string());
+        }
+        key.push('\0');
+        key.push_str(parent_type);
+        key.push('\0');
+        key.push_str(&self.item.node.name.node);
+        let mut arguments = BTreeMap::new();
+        for (name, value) in &self.item.node.arguments {
+            if self.is_absent(&value.node) {
+                continue;
+            }
+            arguments.insert(&name.node, self.resolve_input_value(value.clone())?);
+        }
+        for (name, value) in arguments {
+            key.push('\0');
+            key.push_str(name);
+            key.push('=');
+            key.push_str(&value.to_string());
+        }
+
+        let cell = self
+            .query_env
+            .memoize_cache
+            .lock()
+            .unwrap()
+            .entry(key)
+            .or_insert_with(|| Arc::new(futures_util::lock::Mutex::new(None::<T>)))
+            .clone()
+            .downcast::<futures_util::lock::Mutex<Option<T>>>();
+        let cell = match cell {
+            Ok(cell) => cell,
+            Err(_) => return fut.await,
+        };
+
+        // Holding the lock while resolving makes concurrent invocations wait for the first one.
+        let mut value = cell.lock().await;
+        if let Some(value) = &*value {
+            return Ok(value.clone());
+        }
+        let res = fut.await?;
+        *value = Some(res.clone());
+        Ok(res)
+    }
+
     /// Creates a uniform interface to inspect the forthcoming selections.
     ///
     /// # Examples
@@ -646,6 +1129,39 @@ impl<'a> ContextBase<'a, &'a Positioned<Field>> {
             context: self,
         }
     }
+
+    /// Returns `true` if the argument was explicitly provided for the current field.
+    ///
+    /// An argument is not provided if it is omitted, or if it is set to a variable that was
+    /// neither provided in the request nor has a default value. In both cases the default value
+    /// of the argument is used, while an explicit `null` is passed to the resolver as is.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_graphql::*;
+    ///
+    /// struct Query;
+    ///
+    /// #[Object]
+    /// impl Query {
+    ///     async fn value(&self, ctx: &Context<'_>, #[graphql(default = 10)] a: Option<i32>) -> String {
+    ///         format!("{:?} {}", a, ctx.args_provided("a"))
+    ///     }
+    /// }
+    ///
+    /// tokio::runtime::Runtime::new().unwrap().block_on(async move {
+    ///     let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    ///     let res = schema.execute("{ a: value b: value(a: null) }").await.into_result().unwrap();
+    ///     assert_eq!(res.data, value!({ "a": "Some(10) false", "b": "None true" }));
+    /// });
+    /// ```
+    pub fn args_provided(&self, name: &str) -> bool {
+        self.item
+            .node
+            .get_argument(name)
+            .map_or(false, |value| !self.is_absent(&value.node))
+    }
 }
 
 /// Selection field.
@@ -673,6 +1189,9 @@ impl<'a> SelectionField<'a> {
     pub fn arguments(&self) -> ServerResult<Vec<(Name, Value)>> {
         let mut arguments = Vec::with_capacity(self.field.arguments.len());
         for (name, value) in &self.field.arguments {
+            if self.context.is_absent(&value.node) {
+                continue;
+            }
             let pos = name.pos;
             arguments.push((
                 name.node.clone(),
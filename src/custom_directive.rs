@@ -0,0 +1,244 @@
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use futures_util::future::FutureExt;
+use indexmap::IndexMap;
+
+use crate::parser::types::Directive;
+use crate::registry::Registry;
+use crate::{Context, InputType, Name, Pos, Positioned, ServerError, ServerResult, Value};
+
+type ResolveFut<'a> = &'a mut (dyn Future<Output = ServerResult<Option<Value>>> + Send + Unpin);
+
+/// An argument of a [`CustomDirective`].
+pub struct DirectiveArgument {
+    pub(crate) name: &'static str,
+    pub(crate) description: Option<&'static str>,
+    pub(crate) ty: fn(&mut Registry) -> String,
+    pub(crate) default_value: Option<Value>,
+}
+
+impl DirectiveArgument {
+    /// Create an argument of the type `T`.
+    pub fn new<T: InputType>(name: &'static str) -> Self {
+        Self {
+            name,
+            description: None,
+            ty: T::create_type_info,
+            default_value: None,
+        }
+    }
+
+    /// Set the description of the argument.
+    #[must_use]
+    pub fn description(self, description: &'static str) -> Self {
+        Self {
+            description: Some(description),
+            ..self
+        }
+    }
+
+    /// Set the default value of the argument.
+    #[must_use]
+    pub fn default_value<T: InputType>(self, value: T) -> Self {
+        Self {
+            default_value: Some(value.to_value()),
+            ..self
+        }
+    }
+}
+
+/// The values of the arguments of a [`CustomDirective`] applied to a field.
+pub struct DirectiveArgs {
+    name: &'static str,
+    pos: Pos,
+    values: IndexMap<Name, Value>,
+}
+
+impl DirectiveArgs {
+    /// Parse the value of an argument, or of its default value if it is not provided.
+    pub fn get<T: InputType>(&self, name: &str) -> ServerResult<T> {
+        T::parse(self.values.get(name).cloned()).map_err(|err| {
+            let err = err.into_server_error(self.pos);
+            ServerError::new(
+                format!(
+                    "Invalid value for argument \"{}\" of directive \"@{}\": {}",
+                    name, self.name, err.message
+                ),
+                Some(self.pos),
+            )
+        })
+    }
+}
+
+/// A custom executable directive, which can be applied to the fields of a query.
+///
+/// The directive is added with [`SchemaBuilder::directive`](crate::SchemaBuilder::directive),
+/// which defines it in the schema at the `FIELD` location. When a field with the directive is
+/// executed, [`resolve_field`](CustomDirective::resolve_field) is called with the remainder of
+/// the chain, so it can transform the value of the field or return without resolving it. The
+/// directives of a field are called in the order they are applied in the query.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// struct Lowercase;
+///
+/// #[async_trait::async_trait]
+/// impl CustomDirective for Lowercase {
+///     fn name(&self) -> &'static str {
+///         "lowercase"
+///     }
+///
+///     async fn resolve_field(
+///         &self,
+///         ctx: &Context<'_>,
+///         _args: &DirectiveArgs,
+///         next: NextDirective<'_>,
+///     ) -> ServerResult<Option<Value>> {
+///         Ok(match next.run(ctx).await? {
+///             Some(Value::String(s)) => Some(Value::String(s.to_lowercase())),
+///             value => value,
+///         })
+///     }
+/// }
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn value(&self) -> &str {
+///         "HELLO"
+///     }
+/// }
+///
+/// tokio::runtime::Runtime::new().unwrap().block_on(async move {
+///     let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///         .directive(Lowercase)
+///         .finish();
+///     let res = schema.execute("{ value @lowercase }").await.into_result().unwrap().data;
+///     assert_eq!(res, value!({ "value": "hello" }));
+/// });
+/// ```
+#[async_trait::async_trait]
+pub trait CustomDirective: Send + Sync + 'static {
+    /// The name of the directive, without the `@`.
+    fn name(&self) -> &'static str;
+
+    /// The description of the directive.
+    fn description(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// The arguments of the directive.
+    fn arguments(&self) -> Vec<DirectiveArgument> {
+        Vec::new()
+    }
+
+    /// Called when a field with the directive is resolved, `next.run(ctx)` resolves the field.
+    async fn resolve_field(
+        &self,
+        ctx: &Context<'_>,
+        args: &DirectiveArgs,
+        next: NextDirective<'_>,
+    ) -> ServerResult<Option<Value>>;
+}
+
+/// A custom directive added to a schema, with the default values of its arguments.
+pub(crate) struct RegisteredDirective {
+    pub(crate) directive: Arc<dyn CustomDirective>,
+    pub(crate) defaults: Vec<(&'static str, Value)>,
+}
+
+/// The remainder of the custom directives of a field.
+pub struct NextDirective<'a> {
+    chain: &'a [(&'a dyn CustomDirective, DirectiveArgs)],
+    resolve_fut: ResolveFut<'a>,
+}
+
+impl<'a> NextDirective<'a> {
+    /// Call the [CustomDirective::resolve_field] function of the next directive, or resolve the
+    /// field.
+    pub async fn run(self, ctx: &Context<'_>) -> ServerResult<Option<Value>> {
+        if let Some(((first, args), next)) = self.chain.split_first() {
+            first
+                .resolve_field(
+                    ctx,
+                    args,
+                    NextDirective {
+                        chain: next,
+                        resolve_fut: self.resolve_fut,
+                    },
+                )
+                .await
+        } else {
+            self.resolve_fut.await
+        }
+    }
+}
+
+/// Resolve a field through the custom directives applied to it.
+pub(crate) async fn resolve_with_directives(
+    ctx: &Context<'_>,
+    directives: &[Positioned<Directive>],
+    resolve_fut: impl Future<Output = ServerResult<Option<Value>>> + Send,
+) -> ServerResult<Option<Value>> {
+    let custom_directives = &ctx.schema_env.custom_directives;
+    let mut chain = Vec::new();
+    if !custom_directives.is_empty() {
+        for directive in directives {
+            let registered = match custom_directives.get(directive.node.name.node.as_str()) {
+                Some(registered) => registered,
+                None => continue,
+            };
+            let mut values = IndexMap::new();
+            for (name, value) in &registered.defaults {
+                values.insert(Name::new(name), value.clone());
+            }
+            for (name, value) in &directive.node.arguments {
+                let value = ctx
+                    .resolve_input_value(value.clone())
+                    .map_err(|err| ctx.set_error_path(err))?;
+                values.insert(name.node.clone(), value);
+            }
+            chain.push((
+                registered.directive.as_ref(),
+                DirectiveArgs {
+                    name: registered.directive.name(),
+                    pos: directive.pos,
+                    values,
+                },
+            ));
+        }
+    }
+
+    if chain.is_empty() {
+        return resolve_fut.await;
+    }
+
+    // The errors of the field are passed through the directives unchanged.
+    let field_error = Mutex::new(None);
+    let resolve_fut = resolve_fut.inspect(|res| {
+        if let Err(err) = res {
+            *field_error.lock().unwrap() = Some(err.clone());
+        }
+    });
+    futures_util::pin_mut!(resolve_fut);
+    NextDirective {
+        chain: &chain,
+        resolve_fut: &mut resolve_fut,
+    }
+    .run(ctx)
+    .await
+    .map_err(|err| {
+        let is_field_error = field_error.lock().unwrap().as_ref() == Some(&err);
+        // The errors returned by the directives are located at the field.
+        if !is_field_error && err.path.is_empty() {
+            ctx.set_error_path(err)
+        } else {
+            err
+        }
+    })
+}
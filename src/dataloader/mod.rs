@@ -1,5 +1,14 @@
 //! Batch loading support, used to solve N+1 problem.
 //!
+//! A [`DataLoader`] collects the keys that are loaded by concurrent resolvers during the
+//! [`delay`](DataLoader::delay), and loads them with a single call to [`Loader::load`], in batches
+//! of up to [`max_batch_size`](DataLoader::max_batch_size) keys.
+//!
+//! Resolvers get the loader from the context data. A loader that is added with
+//! [`Request::data`](crate::Request::data) is created for every request, so the values cached by
+//! [`DataLoader::with_cache`] are only shared by the resolvers of the same request. A loader that
+//! is added with [`SchemaBuilder::data`](crate::SchemaBuilder::data) shares them across requests.
+//!
 //! # Examples
 //!
 //! ```rust
@@ -150,6 +159,21 @@ pub trait Loader<K: Send + Sync + Hash + Eq + Clone + 'static>: Send + Sync + 's
     async fn load(&self, keys: &[K]) -> Result<HashMap<K, Self::Value>, Self::Error>;
 }
 
+/// Add an event to the OpenTelemetry span of the resolver which is calling the loader.
+#[cfg(feature = "opentelemetry")]
+fn add_opentelemetry_event<T>(name: &'static str, num_keys: usize) {
+    use opentelemetry::trace::TraceContextExt;
+    use opentelemetry::Key;
+
+    opentelemetry::Context::current().span().add_event(
+        name.to_string(),
+        vec![
+            Key::from_static_str("graphql.dataloader.loader").string(std::any::type_name::<T>()),
+            Key::from_static_str("graphql.dataloader.keys").i64(num_keys as i64),
+        ],
+    );
+}
+
 /// Data loader.
 ///
 /// Reference: <https://github.com/facebook/dataloader>
@@ -332,6 +356,9 @@ impl<T, C: CacheFactory> DataLoader<T, C> {
                 return Ok(Default::default());
             }
 
+            #[cfg(feature = "opentelemetry")]
+            add_opentelemetry_event::<T>("dataloader.wait", keys_set.len());
+
             typed_requests.keys.extend(keys_set.clone());
             let (tx, rx) = oneshot::channel();
             typed_requests.pending.push((
@@ -365,7 +392,13 @@ impl<T, C: CacheFactory> DataLoader<T, C> {
             Action::Delay => {}
         }
 
-        rx.await.unwrap()
+        let res = rx.await.unwrap();
+        #[cfg(feature = "opentelemetry")]
+        add_opentelemetry_event::<T>(
+            "dataloader.ready",
+            res.as_ref().map(HashMap::len).unwrap_or_default(),
+        );
+        res
     }
 
     /// Feed some data into the cache.
@@ -193,6 +193,10 @@ impl<T: InputType> InputValueError<T> {
     pub fn into_server_error(self, pos: Pos) -> ServerError {
         ServerError::new(self.message, Some(pos))
     }
+
+    pub(crate) fn into_message(self) -> String {
+        self.message
+    }
 }
 
 impl<T: InputType, E: Display> From<E> for InputValueError<T> {
@@ -321,6 +325,10 @@ pub enum ParseRequestError {
     /// The request is a batch request, but the server does not support batch requests.
     #[error("Batch requests are not supported")]
     UnsupportedBatch,
+
+    /// The batch request contains more operations than the server allows.
+    #[error("Batch requests can contain at most {0} operations")]
+    BatchTooLarge(usize),
 }
 
 impl From<multer::Error> for ParseRequestError {
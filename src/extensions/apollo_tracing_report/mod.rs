@@ -0,0 +1,495 @@
+//! Report traces to [Apollo Studio](https://www.apollographql.com/docs/studio/).
+
+mod proto;
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use futures_channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use futures_timer::Delay;
+use futures_util::future::{select, Either};
+use futures_util::lock::Mutex;
+use futures_util::StreamExt;
+use http::HeaderMap;
+
+use self::proto::{Location, Node, NodeId, ReportHeader, Trace, TraceError};
+use crate::extensions::{
+    Extension, ExtensionContext, ExtensionFactory, NextExecute, NextParseQuery, NextRequest,
+    NextResolve, ResolveInfo,
+};
+use crate::parser::types::ExecutableDocument;
+use crate::{PathSegment, QueryPathSegment, Response, ServerResult, Value, Variables};
+
+/// The endpoint of Apollo Studio that receives the reports.
+pub const APOLLO_REPORT_ENDPOINT: &str =
+    "https://usage-reporting.api.apollographql.com/api/ingress/traces";
+
+/// Sends the reports to Apollo Studio.
+///
+/// A report is an encoded `Report` protobuf message. It should be posted to
+/// [`APOLLO_REPORT_ENDPOINT`] with the `X-Api-Key` header set to the API key and the
+/// `Content-Type` header set to `application/protobuf`. Failures should be handled by the sender,
+/// since the reporting task drops the report after calling it.
+#[async_trait::async_trait]
+pub trait ApolloReportSender: Send + Sync + 'static {
+    /// Send a report.
+    async fn send(&self, api_key: &str, report: Vec<u8>);
+}
+
+/// The name and the version of the client that sent a request.
+///
+/// Add it to the data of a request to include it in the trace of the request.
+#[derive(Debug, Clone, Default)]
+pub struct ApolloClientInfo {
+    /// The name of the client.
+    pub name: Option<String>,
+
+    /// The version of the client.
+    pub version: Option<String>,
+}
+
+impl ApolloClientInfo {
+    /// Read the client information from the `apollographql-client-name` and
+    /// `apollographql-client-version` headers.
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(ToString::to_string)
+        };
+        Self {
+            name: header("apollographql-client-name"),
+            version: header("apollographql-client-version"),
+        }
+    }
+}
+
+struct TraceMessage {
+    key: String,
+    schema_id: String,
+    trace: Trace,
+}
+
+/// Apollo Studio trace reporting extension
+///
+/// Unlike [`ApolloTracing`](crate::extensions::ApolloTracing), which includes the timings in the
+/// response, this extension records a trace of each operation, with the timings and the errors of
+/// the fields, and reports the traces to Apollo Studio in batches.
+///
+/// The reports are sent by a background task, which must be spawned on the runtime of the
+/// application. The task exits after the schema is dropped and the remaining traces are sent.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::extensions::apollo_tracing_report::{ApolloReportSender, ApolloTracingReport};
+///
+/// struct Sender;
+///
+/// #[async_trait::async_trait]
+/// impl ApolloReportSender for Sender {
+///     async fn send(&self, api_key: &str, report: Vec<u8>) {
+///         // Post the report to `APOLLO_REPORT_ENDPOINT` with an HTTP client.
+///     }
+/// }
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn value(&self) -> i32 {
+///         100
+///     }
+/// }
+///
+/// tokio::runtime::Runtime::new().unwrap().block_on(async move {
+///     let (extension, task) = ApolloTracingReport::build("api-key", "my-graph@current", Sender)
+///         .batch_size(100)
+///         .finish();
+///     tokio::spawn(task);
+///
+///     let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///         .extension(extension)
+///         .finish();
+///     schema.execute("{ value }").await;
+/// });
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "apollo_tracing_report")))]
+pub struct ApolloTracingReport {
+    sender: UnboundedSender<TraceMessage>,
+}
+
+impl ApolloTracingReport {
+    /// Create a builder of the extension, which reports the traces of the graph `graph_ref`
+    /// (e.g. `my-graph@current`) with `sender`.
+    pub fn build(
+        api_key: impl Into<String>,
+        graph_ref: impl Into<String>,
+        sender: impl ApolloReportSender,
+    ) -> ApolloTracingReportBuilder {
+        ApolloTracingReportBuilder {
+            api_key: api_key.into(),
+            graph_ref: graph_ref.into(),
+            sender: Box::new(sender),
+            batch_size: 100,
+            flush_interval: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Builder of [`ApolloTracingReport`].
+#[cfg_attr(docsrs, doc(cfg(feature = "apollo_tracing_report")))]
+pub struct ApolloTracingReportBuilder {
+    api_key: String,
+    graph_ref: String,
+    sender: Box<dyn ApolloReportSender>,
+    batch_size: usize,
+    flush_interval: Duration,
+}
+
+impl ApolloTracingReportBuilder {
+    /// Send a report when this number of traces is collected. Default is 100.
+    #[must_use]
+    pub fn batch_size(self, batch_size: usize) -> Self {
+        Self {
+            batch_size: batch_size.max(1),
+            ..self
+        }
+    }
+
+    /// Send the collected traces at least this often. Default is 10 seconds.
+    #[must_use]
+    pub fn flush_interval(self, flush_interval: Duration) -> Self {
+        Self {
+            flush_interval,
+            ..self
+        }
+    }
+
+    /// Returns the extension and the task that sends the reports.
+    pub fn finish(
+        self,
+    ) -> (
+        ApolloTracingReport,
+        impl Future<Output = ()> + Send + 'static,
+    ) {
+        let (tx, rx) = mpsc::unbounded();
+        (ApolloTracingReport { sender: tx }, report_task(self, rx))
+    }
+}
+
+async fn report_task(builder: ApolloTracingReportBuilder, mut rx: UnboundedReceiver<TraceMessage>) {
+    let hostname = std::env::var("HOSTNAME").unwrap_or_default();
+    let agent_version = format!("async-graphql {}", env!("CARGO_PKG_VERSION"));
+    let mut batch = Vec::new();
+    let mut delay = Delay::new(builder.flush_interval);
+
+    loop {
+        let closed = match select(rx.next(), &mut delay).await {
+            Either::Left((Some(message), _)) => {
+                batch.push(message);
+                if batch.len() < builder.batch_size {
+                    continue;
+                }
+                false
+            }
+            Either::Left((None, _)) => true,
+            Either::Right(_) => {
+                delay = Delay::new(builder.flush_interval);
+                false
+            }
+        };
+
+        if !batch.is_empty() {
+            let schema_id = batch
+                .last()
+                .map(|message: &TraceMessage| message.schema_id.clone())
+                .unwrap_or_default();
+            let mut traces: BTreeMap<String, Vec<Trace>> = BTreeMap::new();
+            for message in batch.drain(..) {
+                traces.entry(message.key).or_default().push(message.trace);
+            }
+            let report = proto::encode_report(
+                &ReportHeader {
+                    graph_ref: &builder.graph_ref,
+                    hostname: &hostname,
+                    agent_version: &agent_version,
+                    executable_schema_id: &schema_id,
+                },
+                traces
+                    .iter()
+                    .map(|(key, traces)| (key.as_str(), traces.as_slice())),
+            );
+            builder.sender.send(&builder.api_key, report).await;
+        }
+
+        if closed {
+            return;
+        }
+    }
+}
+
+impl ExtensionFactory for ApolloTracingReport {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(ApolloTracingReportExtension {
+            sender: self.sender.clone(),
+            inner: Mutex::new(Inner {
+                start: Instant::now(),
+                start_time: SystemTime::now(),
+                signature: None,
+                operation_name: None,
+                client: Default::default(),
+                fields: Vec::new(),
+            }),
+        })
+    }
+}
+
+struct ResolvedField {
+    path: Vec<NodeId>,
+    field_name: String,
+    parent_type: String,
+    return_type: String,
+    start_time: u64,
+    end_time: u64,
+}
+
+struct Inner {
+    start: Instant,
+    start_time: SystemTime,
+    signature: Option<String>,
+    operation_name: Option<String>,
+    client: ApolloClientInfo,
+    fields: Vec<ResolvedField>,
+}
+
+struct ApolloTracingReportExtension {
+    sender: UnboundedSender<TraceMessage>,
+    inner: Mutex<Inner>,
+}
+
+impl ApolloTracingReportExtension {
+    fn elapsed_ns(start: Instant) -> u64 {
+        start.elapsed().as_nanos() as u64
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for ApolloTracingReportExtension {
+    async fn request(&self, ctx: &ExtensionContext<'_>, next: NextRequest<'_>) -> Response {
+        {
+            let mut inner = self.inner.lock().await;
+            inner.start = Instant::now();
+            inner.start_time = SystemTime::now();
+        }
+        let resp = next.run(ctx).await;
+
+        let mut inner = self.inner.lock().await;
+        let signature = match inner.signature.take() {
+            Some(signature) if ctx.operation_type().is_some() => signature,
+            _ => return resp,
+        };
+
+        let mut root = Node::new(NodeId::Root);
+        for field in inner.fields.drain(..) {
+            let node = field
+                .path
+                .into_iter()
+                .fold(&mut root, |node, id| node.child(id));
+            node.original_field_name = field.field_name;
+            node.parent_type = field.parent_type;
+            node.ty = field.return_type;
+            node.start_time = field.start_time;
+            node.end_time = field.end_time;
+        }
+        for error in &resp.errors {
+            let node = error.path.iter().fold(&mut root, |node, segment| {
+                node.child(match segment {
+                    PathSegment::Field(name) => NodeId::ResponseName(name.clone()),
+                    PathSegment::Index(idx) => NodeId::Index(*idx as u32),
+                })
+            });
+            node.errors.push(TraceError {
+                message: error.message.clone(),
+                locations: error
+                    .locations
+                    .iter()
+                    .map(|pos| Location {
+                        line: pos.line as u32,
+                        column: pos.column as u32,
+                    })
+                    .collect(),
+                json: serde_json::to_string(error).unwrap_or_default(),
+            });
+        }
+
+        let key = format!(
+            "# {}\n{}",
+            inner.operation_name.as_deref().unwrap_or("-"),
+            signature
+        );
+        let trace = Trace {
+            start_time: inner.start_time,
+            end_time: SystemTime::now(),
+            duration_ns: Self::elapsed_ns(inner.start),
+            root,
+            client_name: inner.client.name.clone().unwrap_or_default(),
+            client_version: inner.client.version.clone().unwrap_or_default(),
+        };
+        let _ = self.sender.unbounded_send(TraceMessage {
+            key,
+            schema_id: ctx.schema_env.schema_hash.to_string(),
+            trace,
+        });
+        resp
+    }
+
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        let document = next.run(ctx, query, variables).await?;
+        self.inner.lock().await.signature = Some(ctx.stringify_execute_doc(&document, variables));
+        Ok(document)
+    }
+
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        {
+            let mut inner = self.inner.lock().await;
+            inner.operation_name = operation_name.map(ToString::to_string);
+            if let Some(client) = ctx.data_opt::<ApolloClientInfo>() {
+                inner.client = client.clone();
+            }
+        }
+        next.run(ctx, operation_name).await
+    }
+
+    async fn resolve(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        info: ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> ServerResult<Option<Value>> {
+        let mut path = Vec::new();
+        info.path_node.for_each(|segment| {
+            path.push(match segment {
+                QueryPathSegment::Index(idx) => NodeId::Index(*idx as u32),
+                QueryPathSegment::Name(name) => NodeId::ResponseName(name.to_string()),
+            })
+        });
+        let field_name = info.name.to_string();
+        let parent_type = info.parent_type.to_string();
+        let return_type = info.return_type.to_string();
+        let start = self.inner.lock().await.start;
+
+        let start_time = Self::elapsed_ns(start);
+        let res = next.run(ctx, info).await;
+        let end_time = Self::elapsed_ns(start);
+
+        self.inner.lock().await.fields.push(ResolvedField {
+            path,
+            field_name,
+            parent_type,
+            return_type,
+            start_time,
+            end_time,
+        });
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    struct ChannelSender(UnboundedSender<(String, Vec<u8>)>);
+
+    #[async_trait::async_trait]
+    impl ApolloReportSender for ChannelSender {
+        async fn send(&self, api_key: &str, report: Vec<u8>) {
+            self.0
+                .unbounded_send((api_key.to_string(), report))
+                .unwrap();
+        }
+    }
+
+    fn contains(report: &[u8], s: &str) -> bool {
+        report.windows(s.len()).any(|window| window == s.as_bytes())
+    }
+
+    #[tokio::test]
+    async fn test_apollo_tracing_report() {
+        struct Query;
+
+        #[Object(internal)]
+        impl Query {
+            async fn value(&self) -> i32 {
+                100
+            }
+
+            async fn fail(&self) -> Result<i32> {
+                Err("oops".into())
+            }
+        }
+
+        let (tx, mut rx) = mpsc::unbounded();
+        let (extension, task) =
+            ApolloTracingReport::build("key", "graph@current", ChannelSender(tx))
+                .batch_size(2)
+                .finish();
+        let task = tokio::spawn(task);
+
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(extension)
+            .finish();
+        schema
+            .execute(
+                Request::new("query A { value fail }").data(ApolloClientInfo {
+                    name: Some("web-client".to_string()),
+                    version: Some("1.2.3".to_string()),
+                }),
+            )
+            .await;
+        schema.execute("{ value }").await;
+
+        let (api_key, report) = rx.next().await.unwrap();
+        assert_eq!(api_key, "key");
+        assert!(contains(&report, "graph@current"));
+        assert!(contains(&report, "# A\nquery A"));
+        assert!(contains(&report, "# -\n"));
+        assert!(contains(&report, "web-client"));
+        assert!(contains(&report, "1.2.3"));
+        assert!(contains(&report, "oops"));
+        assert!(contains(&report, "Query"));
+
+        // The remaining traces are sent when the schema is dropped.
+        schema.execute("{ value }").await;
+        drop(schema);
+        task.await.unwrap();
+        let (_, report) = rx.next().await.unwrap();
+        assert!(contains(&report, "# -\n"));
+        assert!(rx.next().await.is_none());
+    }
+
+    #[test]
+    fn test_client_info_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("apollographql-client-name", "ios".parse().unwrap());
+        let client = ApolloClientInfo::from_headers(&headers);
+        assert_eq!(client.name.as_deref(), Some("ios"));
+        assert_eq!(client.version, None);
+    }
+}
@@ -0,0 +1,227 @@
+//! An encoder for the subset of Apollo's `reports.proto` that is used to report traces.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const WIRE_VARINT: u64 = 0;
+const WIRE_LEN: u64 = 2;
+
+#[derive(Default)]
+pub(super) struct Encoder(Vec<u8>);
+
+impl Encoder {
+    pub(super) fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+
+    fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.0.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.0.push(value as u8);
+    }
+
+    fn key(&mut self, field: u32, wire_type: u64) {
+        self.varint(((field as u64) << 3) | wire_type);
+    }
+
+    fn uint64(&mut self, field: u32, value: u64) {
+        if value != 0 {
+            self.key(field, WIRE_VARINT);
+            self.varint(value);
+        }
+    }
+
+    fn string(&mut self, field: u32, value: &str) {
+        if !value.is_empty() {
+            self.key(field, WIRE_LEN);
+            self.varint(value.len() as u64);
+            self.0.extend_from_slice(value.as_bytes());
+        }
+    }
+
+    fn message(&mut self, field: u32, f: impl FnOnce(&mut Encoder)) {
+        let mut inner = Encoder::default();
+        f(&mut inner);
+        self.key(field, WIRE_LEN);
+        self.varint(inner.0.len() as u64);
+        self.0.extend(inner.0);
+    }
+
+    fn timestamp(&mut self, field: u32, time: SystemTime) {
+        let duration = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+        self.message(field, |e| {
+            e.uint64(1, duration.as_secs());
+            e.uint64(2, duration.subsec_nanos() as u64);
+        });
+    }
+}
+
+/// `Trace.Location`
+pub(super) struct Location {
+    pub(super) line: u32,
+    pub(super) column: u32,
+}
+
+/// `Trace.Error`
+pub(super) struct TraceError {
+    pub(super) message: String,
+    pub(super) locations: Vec<Location>,
+    pub(super) json: String,
+}
+
+/// The id of a `Trace.Node`, which is a oneof of `response_name` and `index`.
+#[derive(PartialEq)]
+pub(super) enum NodeId {
+    Root,
+    ResponseName(String),
+    Index(u32),
+}
+
+/// `Trace.Node`
+pub(super) struct Node {
+    pub(super) id: NodeId,
+    pub(super) original_field_name: String,
+    pub(super) ty: String,
+    pub(super) parent_type: String,
+    pub(super) errors: Vec<TraceError>,
+    pub(super) start_time: u64,
+    pub(super) end_time: u64,
+    pub(super) children: Vec<Node>,
+}
+
+impl Node {
+    pub(super) fn new(id: NodeId) -> Self {
+        Self {
+            id,
+            original_field_name: String::new(),
+            ty: String::new(),
+            parent_type: String::new(),
+            errors: Vec::new(),
+            start_time: 0,
+            end_time: 0,
+            children: Vec::new(),
+        }
+    }
+
+    /// Returns the child with the specified id, which is created if it does not exist.
+    pub(super) fn child(&mut self, id: NodeId) -> &mut Node {
+        match self.children.iter().position(|child| child.id == id) {
+            Some(idx) => &mut self.children[idx],
+            None => {
+                self.children.push(Node::new(id));
+                self.children.last_mut().unwrap()
+            }
+        }
+    }
+
+    fn encode(&self, e: &mut Encoder) {
+        match &self.id {
+            NodeId::Root => {}
+            NodeId::ResponseName(name) => e.string(1, name),
+            NodeId::Index(index) => {
+                // A oneof field is always encoded, even if it has the default value.
+                e.key(2, WIRE_VARINT);
+                e.varint(*index as u64);
+            }
+        }
+        e.string(3, &self.ty);
+        e.uint64(8, self.start_time);
+        e.uint64(9, self.end_time);
+        for error in &self.errors {
+            e.message(11, |e| {
+                e.string(1, &error.message);
+                for location in &error.locations {
+                    e.message(2, |e| {
+                        e.uint64(1, location.line as u64);
+                        e.uint64(2, location.column as u64);
+                    });
+                }
+                e.string(4, &error.json);
+            });
+        }
+        for child in &self.children {
+            e.message(12, |e| child.encode(e));
+        }
+        e.string(13, &self.parent_type);
+        e.string(14, &self.original_field_name);
+    }
+}
+
+/// `Trace`
+pub(super) struct Trace {
+    pub(super) start_time: SystemTime,
+    pub(super) end_time: SystemTime,
+    pub(super) duration_ns: u64,
+    pub(super) root: Node,
+    pub(super) client_name: String,
+    pub(super) client_version: String,
+}
+
+impl Trace {
+    fn encode(&self, e: &mut Encoder) {
+        e.timestamp(3, self.end_time);
+        e.timestamp(4, self.start_time);
+        e.string(7, &self.client_name);
+        e.string(8, &self.client_version);
+        e.uint64(11, self.duration_ns);
+        e.message(14, |e| self.root.encode(e));
+    }
+}
+
+/// `ReportHeader`
+pub(super) struct ReportHeader<'a> {
+    pub(super) graph_ref: &'a str,
+    pub(super) hostname: &'a str,
+    pub(super) agent_version: &'a str,
+    pub(super) executable_schema_id: &'a str,
+}
+
+/// Encode a `Report` with the traces grouped by their stats report key.
+pub(super) fn encode_report<'a>(
+    header: &ReportHeader<'_>,
+    traces: impl IntoIterator<Item = (&'a str, &'a [Trace])>,
+) -> Vec<u8> {
+    let mut e = Encoder::default();
+    e.message(1, |e| {
+        e.string(5, header.hostname);
+        e.string(6, header.agent_version);
+        e.string(8, "rust");
+        e.string(11, header.executable_schema_id);
+        e.string(12, header.graph_ref);
+    });
+    e.timestamp(2, SystemTime::now());
+    for (key, traces) in traces {
+        // `traces_per_query` is a map, which is encoded as repeated key-value entries.
+        e.message(5, |e| {
+            e.string(1, key);
+            e.message(2, |e| {
+                for trace in traces {
+                    e.message(1, |e| trace.encode(e));
+                }
+            });
+        });
+    }
+    e.into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        let mut e = Encoder::default();
+        e.uint64(1, 300);
+        e.uint64(2, 0);
+        e.string(3, "ab");
+        e.message(4, |e| {
+            e.key(2, WIRE_VARINT);
+            e.varint(0);
+        });
+        assert_eq!(
+            e.into_bytes(),
+            vec![0x08, 0xac, 0x02, 0x1a, 0x02, b'a', b'b', 0x22, 0x02, 0x10, 0x00]
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use std::fmt::{self, Display, Formatter};
+use std::sync::Arc;
+
+use futures_util::lock::Mutex;
+
+use crate::extensions::{
+    Extension, ExtensionContext, ExtensionFactory, NextExecute, NextParseQuery,
+};
+use crate::parser::types::ExecutableDocument;
+use crate::validation::collect_deprecated_usages;
+use crate::{Pos, Response, ServerResult, Variables};
+
+/// The kind of a deprecated schema element.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum DeprecatedUsageKind {
+    /// A field of an object or an interface.
+    Field,
+
+    /// A value of an enum.
+    EnumValue,
+}
+
+/// A usage of a deprecated field or enum value in a query.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DeprecatedUsage {
+    /// The kind of the deprecated element.
+    pub kind: DeprecatedUsageKind,
+
+    /// The name of the type that the field or enum value belongs to.
+    pub type_name: String,
+
+    /// The name of the field or enum value.
+    pub name: String,
+
+    /// The deprecation reason.
+    pub reason: Option<String>,
+
+    /// The position of the usage in the query.
+    pub pos: Pos,
+}
+
+impl DeprecatedUsage {
+    /// Returns the schema coordinate of the deprecated element, such as `User.name`.
+    pub fn coordinate(&self) -> String {
+        format!("{}.{}", self.type_name, self.name)
+    }
+}
+
+impl Display for DeprecatedUsage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{} at {}", self.type_name, self.name, self.pos)?;
+        if let Some(reason) = &self.reason {
+            write!(f, " ({})", reason)?;
+        }
+        Ok(())
+    }
+}
+
+type ReportFn = Arc<dyn Fn(&ExtensionContext<'_>, &[DeprecatedUsage]) + Send + Sync>;
+
+/// Deprecation reporter extension
+///
+/// This extension detects the deprecated fields and enum values used by the incoming queries, and
+/// reports them with the name of the operation. This tells when a deprecated field is no longer
+/// used and can be removed.
+///
+/// The callback receives the [`ExtensionContext`], so the identity of the client can be taken from
+/// the request or session data, for example with [`ExtensionContext::data_opt`].
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::extensions::DeprecationReporter;
+///
+/// struct ClientName(String);
+///
+/// let reporter = DeprecationReporter::new(|ctx, usages| {
+///     let client = ctx.data_opt::<ClientName>().map(|name| name.0.as_str());
+///     for usage in usages {
+///         println!("{:?} used {}", client, usage.coordinate());
+///     }
+/// });
+/// ```
+#[derive(Clone)]
+pub struct DeprecationReporter {
+    report: ReportFn,
+}
+
+impl DeprecationReporter {
+    /// Create a deprecation reporter that calls the specified function with the deprecated
+    /// fields and enum values used by each query.
+    ///
+    /// The function is not called for queries that do not use any deprecated element.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(&ExtensionContext<'_>, &[DeprecatedUsage]) + Send + Sync + 'static,
+    {
+        Self {
+            report: Arc::new(f),
+        }
+    }
+
+    /// Create a deprecation reporter that writes the deprecated fields and enum values used by
+    /// each query to the log.
+    #[cfg(feature = "log")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "log")))]
+    pub fn log() -> Self {
+        Self::new(|ctx, usages| {
+            for usage in usages {
+                log::warn!(
+                    target: "async-graphql",
+                    "[Deprecated] operation={} {}",
+                    ctx.operation_name().unwrap_or("<anonymous>"),
+                    usage,
+                );
+            }
+        })
+    }
+}
+
+impl ExtensionFactory for DeprecationReporter {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(DeprecationReporterExtension {
+            report: self.report.clone(),
+            usages: Default::default(),
+        })
+    }
+}
+
+struct DeprecationReporterExtension {
+    report: ReportFn,
+    usages: Mutex<Vec<DeprecatedUsage>>,
+}
+
+#[async_trait::async_trait]
+impl Extension for DeprecationReporterExtension {
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        let document = next.run(ctx, query, variables).await?;
+        *self.usages.lock().await =
+            collect_deprecated_usages(&ctx.schema_env.registry, &document, Some(variables));
+        Ok(document)
+    }
+
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        let usages = std::mem::take(&mut *self.usages.lock().await);
+        if !usages.is_empty() {
+            (self.report)(ctx, &usages);
+        }
+        next.run(ctx, operation_name).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::*;
+
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    #[graphql(internal)]
+    enum Color {
+        Red,
+        #[graphql(deprecation = "Use `Red` instead.")]
+        Crimson,
+    }
+
+    struct Query;
+
+    #[Object(internal)]
+    impl Query {
+        async fn value(&self) -> i32 {
+            1
+        }
+
+        #[graphql(deprecation = "Use `value` instead.")]
+        async fn old_value(&self) -> i32 {
+            1
+        }
+
+        async fn color(&self, colors: Vec<Color>) -> Color {
+            colors[0]
+        }
+    }
+
+    struct ClientName(&'static str);
+
+    #[tokio::test]
+    async fn deprecation_reporter() {
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(extensions::DeprecationReporter::new({
+                let reports = reports.clone();
+                move |ctx, usages| {
+                    reports.lock().unwrap().push((
+                        ctx.data_opt::<ClientName>().map(|client| client.0),
+                        ctx.operation_name().map(ToString::to_string),
+                        usages.to_vec(),
+                    ));
+                }
+            }))
+            .finish();
+
+        schema
+            .execute(Request::new("{ value color(colors: [RED]) }").data(ClientName("web")))
+            .await
+            .into_result()
+            .unwrap();
+        assert!(reports.lock().unwrap().is_empty());
+
+        schema
+            .execute(
+                Request::new(
+                    "query Q($c: [Color!]!) { oldValue color(colors: [RED, CRIMSON]) a: color(colors: $c) }",
+                )
+                .variables(Variables::from_json(serde_json::json!({ "c": ["CRIMSON"] })))
+                .data(ClientName("web")),
+            )
+            .await
+            .into_result()
+            .unwrap();
+
+        let reports = std::mem::take(&mut *reports.lock().unwrap());
+        assert_eq!(reports.len(), 1);
+        let (client, operation_name, usages) = &reports[0];
+        assert_eq!(*client, Some("web"));
+        assert_eq!(operation_name.as_deref(), Some("Q"));
+        assert_eq!(
+            usages
+                .iter()
+                .map(|usage| (usage.kind, usage.coordinate(), usage.reason.as_deref()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    DeprecatedUsageKind::Field,
+                    "Query.oldValue".to_string(),
+                    Some("Use `value` instead.")
+                ),
+                (
+                    DeprecatedUsageKind::EnumValue,
+                    "Color.CRIMSON".to_string(),
+                    Some("Use `Red` instead.")
+                ),
+                (
+                    DeprecatedUsageKind::EnumValue,
+                    "Color.CRIMSON".to_string(),
+                    Some("Use `Red` instead.")
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+
+use crate::extensions::{Extension, ExtensionContext, ExtensionFactory, FieldRewrite};
+use crate::parser::types::Field;
+use crate::Positioned;
+
+/// Disable fields of the schema at runtime, such as an expensive field that misbehaves.
+///
+/// The fields are identified by their coordinates, such as `Query.search`, where the type is the
+/// one in which the field is selected. A disabled field is not executed and is reported as an
+/// error with the `FEATURE_DISABLED` code, for all the clients. Clones of the extension share
+/// the disabled fields, so a clone can be kept to change them after the schema is built.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::extensions::FieldKillSwitch;
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+///
+///     async fn search(&self) -> Option<i32> {
+///         Some(20)
+///     }
+/// }
+///
+/// tokio::runtime::Runtime::new().unwrap().block_on(async move {
+///     let kill_switch = FieldKillSwitch::new();
+///     let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///         .extension(kill_switch.clone())
+///         .finish();
+///
+///     kill_switch.disable("Query.search");
+///     let resp = schema.execute("{ value search }").await;
+///     assert_eq!(resp.data, value!({ "value": 10, "search": null }));
+///     assert_eq!(resp.errors[0].message, "The field \"search\" is disabled.");
+/// });
+/// ```
+#[derive(Clone, Default)]
+pub struct FieldKillSwitch {
+    disabled: Arc<RwLock<HashSet<String>>>,
+}
+
+impl FieldKillSwitch {
+    /// Create a kill switch without disabled fields.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Disable the field with the coordinate, such as `Query.search`.
+    pub fn disable(&self, coordinate: impl Into<String>) {
+        self.disabled.write().unwrap().insert(coordinate.into());
+    }
+
+    /// Enable the field with the coordinate again.
+    pub fn enable(&self, coordinate: &str) {
+        self.disabled.write().unwrap().remove(coordinate);
+    }
+
+    /// Returns `true` if the field with the coordinate is disabled.
+    pub fn is_disabled(&self, coordinate: &str) -> bool {
+        self.disabled.read().unwrap().contains(coordinate)
+    }
+}
+
+impl ExtensionFactory for FieldKillSwitch {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(FieldKillSwitchExtension {
+            kill_switch: self.clone(),
+        })
+    }
+}
+
+struct FieldKillSwitchExtension {
+    kill_switch: FieldKillSwitch,
+}
+
+#[async_trait::async_trait]
+impl Extension for FieldKillSwitchExtension {
+    fn rewrite_field(
+        &self,
+        _ctx: &ExtensionContext<'_>,
+        parent_type: &str,
+        field: &Positioned<Field>,
+    ) -> FieldRewrite {
+        if self
+            .kill_switch
+            .is_disabled(&format!("{}.{}", parent_type, field.node.name.node))
+        {
+            FieldRewrite::Disable
+        } else {
+            FieldRewrite::Keep
+        }
+    }
+}
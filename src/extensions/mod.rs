@@ -5,33 +5,63 @@ mod analyzer;
 pub mod apollo_persisted_queries;
 #[cfg(feature = "apollo_tracing")]
 mod apollo_tracing;
+#[cfg(feature = "apollo_tracing_report")]
+pub mod apollo_tracing_report;
+mod deprecation;
+mod kill_switch;
 #[cfg(feature = "log")]
 mod logger;
 #[cfg(feature = "opentelemetry")]
 mod opentelemetry;
+mod operation_whitelist;
+mod persisted_fragments;
+mod pii_scrubbing;
+#[cfg(feature = "prometheus")]
+mod prometheus;
+mod rate_limit;
+pub mod recording;
+pub mod response_cache;
 #[cfg(feature = "tracing")]
 mod tracing;
 
 pub use self::analyzer::Analyzer;
 #[cfg(feature = "apollo_tracing")]
 pub use self::apollo_tracing::ApolloTracing;
+#[cfg(feature = "apollo_tracing_report")]
+pub use self::apollo_tracing_report::ApolloTracingReport;
+pub use self::deprecation::{DeprecatedUsage, DeprecatedUsageKind, DeprecationReporter};
+pub use self::kill_switch::FieldKillSwitch;
 #[cfg(feature = "log")]
 pub use self::logger::Logger;
 #[cfg(feature = "opentelemetry")]
-pub use self::opentelemetry::OpenTelemetry;
+pub use self::opentelemetry::{OpenTelemetry, OpenTelemetryConfig};
+pub use self::operation_whitelist::{OperationPolicy, OperationWhitelist};
+pub use self::persisted_fragments::PersistedFragments;
+pub use self::pii_scrubbing::PiiScrubbing;
+#[cfg(feature = "prometheus")]
+pub use self::prometheus::PrometheusMetrics;
+pub use self::rate_limit::RateLimit;
+pub use self::recording::Recording;
+pub use self::response_cache::ResponseCache;
 #[cfg(feature = "tracing")]
 pub use self::tracing::Tracing;
 
 use std::any::{Any, TypeId};
+use std::collections::{HashMap, HashSet};
 use std::future::Future;
 use std::sync::Arc;
 
 use futures_util::stream::BoxStream;
+use once_cell::sync::OnceCell;
 
-use crate::parser::types::ExecutableDocument;
+use crate::parser::types::{
+    DocumentOperations, ExecutableDocument, Field, FragmentDefinition, OperationDefinition,
+    OperationType, Selection, SelectionSet,
+};
+use crate::registry::MetaTypeName;
 use crate::{
-    Data, Error, QueryPathNode, Request, Response, Result, SchemaEnv, ServerError, ServerResult,
-    ValidationResult, Value, Variables,
+    Data, Error, Metadata, Name, Pos, Positioned, QueryPathNode, Request, Response, Result,
+    SchemaEnv, ServerError, ServerResult, SimpleObject, ValidationResult, Value, Variables,
 };
 
 /// Context for extension
@@ -44,12 +74,63 @@ pub struct ExtensionContext<'a> {
 
     #[doc(hidden)]
     pub query_data: Option<&'a Data>,
+
+    #[doc(hidden)]
+    pub selected_operation: &'a OnceCell<(OperationType, Option<String>)>,
+
+    #[doc(hidden)]
+    pub document: Option<&'a ExecutableDocument>,
 }
 
 impl<'a> ExtensionContext<'a> {
+    /// Returns the type of the operation being executed.
+    ///
+    /// This is `None` until the operation has been selected from the document, so it is not
+    /// available in `prepare_request`, `parse_query` and `validation`, and in `request` it is only
+    /// available after the request has been executed.
+    pub fn operation_type(&self) -> Option<OperationType> {
+        self.selected_operation.get().map(|(ty, _)| *ty)
+    }
+
+    /// Returns the name of the operation being executed, if it has one.
+    ///
+    /// This is `None` until the operation has been selected from the document.
+    pub fn operation_name(&self) -> Option<&'a str> {
+        self.selected_operation
+            .get()
+            .and_then(|(_, name)| name.as_deref())
+    }
+
+    /// Returns the document of the request.
+    ///
+    /// This is `None` until the document has been validated, so it is only available in
+    /// `execute`, which is also called for every response of a subscription, and `resolve`. The
+    /// document can be rewritten before validation by returning a new one in `parse_query`.
+    pub fn document(&self) -> Option<&'a ExecutableDocument> {
+        self.document
+    }
+
+    /// Returns the operation being executed, which is selected from the document.
+    ///
+    /// This is `None` until the document has been validated.
+    pub fn operation(&self) -> Option<&'a Positioned<OperationDefinition>> {
+        match &self.document?.operations {
+            DocumentOperations::Single(operation) => Some(operation),
+            DocumentOperations::Multiple(operations) => operations.get(self.operation_name()?),
+        }
+    }
+
+    /// Returns the fragments of the document.
+    ///
+    /// This is `None` until the document has been validated.
+    pub fn fragments(&self) -> Option<&'a HashMap<Name, Positioned<FragmentDefinition>>> {
+        self.document.map(|document| &document.fragments)
+    }
+
     /// Convert the specified [ExecutableDocument] into a query string.
     ///
-    /// Usually used for log extension, it can hide secret arguments.
+    /// Usually used for log extension, it can hide secret arguments and the PII arguments and
+    /// input fields, which are tagged with `pii = true` metadata.
     pub fn stringify_execute_doc(&self, doc: &ExecutableDocument, variables: &Variables) -> String {
         self.schema_env
             .registry
@@ -88,9 +169,23 @@ impl<'a> ExtensionContext<'a> {
         self.query_data
             .and_then(|query_data| query_data.get(&TypeId::of::<D>()))
             .or_else(|| self.session_data.get(&TypeId::of::<D>()))
-            .or_else(|| self.schema_env.data.get(&TypeId::of::<D>()))
+            .map(AsRef::as_ref)
+            .or_else(|| {
+                self.schema_env
+                    .data_for(self.operation_type(), &TypeId::of::<D>())
+            })
             .and_then(|d| d.downcast_ref::<D>())
     }
+
+    /// Returns the metadata of a type.
+    pub fn type_metadata(&self, ty: &str) -> Option<&'a Metadata> {
+        self.schema_env.registry.type_metadata(ty)
+    }
+
+    /// Returns the metadata of a field, for example of the field in [`ResolveInfo`].
+    pub fn field_metadata(&self, ty: &str, field: &str) -> Option<&'a Metadata> {
+        self.schema_env.registry.field_metadata(ty, field)
+    }
 }
 
 /// Parameters for `Extension::resolve_field_start`
@@ -111,6 +206,40 @@ pub struct ResolveInfo<'a> {
     pub alias: Option<&'a str>,
 }
 
+/// The status of a component reported by [`Extension::health`].
+#[derive(SimpleObject, Debug, Clone)]
+#[graphql(internal, name = "_ComponentStatus")]
+pub struct ComponentStatus {
+    /// The name of the component.
+    pub name: String,
+
+    /// Whether the component is healthy.
+    pub healthy: bool,
+
+    /// An optional message describing the status.
+    pub message: Option<String>,
+}
+
+impl ComponentStatus {
+    /// Create a healthy component status.
+    pub fn new_healthy(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            healthy: true,
+            message: None,
+        }
+    }
+
+    /// Create an unhealthy component status with a message.
+    pub fn new_unhealthy(name: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            healthy: false,
+            message: Some(message.into()),
+        }
+    }
+}
+
 type RequestFut<'a> = &'a mut (dyn Future<Output = Response> + Send + Unpin);
 
 type ParseFut<'a> = &'a mut (dyn Future<Output = ServerResult<ExecutableDocument>> + Send + Unpin);
@@ -350,6 +479,11 @@ pub trait Extension: Sync + Send + 'static {
     }
 
     /// Called at execute query.
+    ///
+    /// This hook wraps the execution of the whole operation, so it can be used to scope a
+    /// database transaction: begin it before calling `next`, then commit or roll back depending
+    /// on whether the returned response has errors. Use [`ExtensionContext::operation_type`] to
+    /// only do this for mutations.
     async fn execute(
         &self,
         ctx: &ExtensionContext<'_>,
@@ -368,6 +502,43 @@ pub trait Extension: Sync + Send + 'static {
     ) -> ServerResult<Option<Value>> {
         next.run(ctx, info).await
     }
+
+    /// Called when the `_health` or `_readiness` field is queried.
+    ///
+    /// Returns the status of the component managed by this extension, if any. See
+    /// [`SchemaBuilder::enable_health_check`](crate::SchemaBuilder::enable_health_check).
+    async fn health(&self, _ctx: &ExtensionContext<'_>) -> Option<ComponentStatus> {
+        None
+    }
+
+    /// Called for every field of the validated operation and of its fragments before it is
+    /// executed, to disable or replace the field.
+    ///
+    /// The first extension which does not return [`FieldRewrite::Keep`] decides. The fields of
+    /// the introspection are not rewritten.
+    fn rewrite_field(
+        &self,
+        _ctx: &ExtensionContext<'_>,
+        _parent_type: &str,
+        _field: &Positioned<Field>,
+    ) -> FieldRewrite {
+        FieldRewrite::Keep
+    }
+}
+
+/// The result of [`Extension::rewrite_field`].
+pub enum FieldRewrite {
+    /// Execute the field.
+    Keep,
+
+    /// Do not execute the field, and report it as an error with the `FEATURE_DISABLED` code.
+    ///
+    /// A nullable field is set to `null`, while the error of a non-null field is propagated to
+    /// its parent.
+    Disable,
+
+    /// Execute another field instead, which is not validated.
+    Replace(Positioned<Field>),
 }
 
 /// Extension factory
@@ -385,6 +556,8 @@ pub struct Extensions {
     schema_env: SchemaEnv,
     session_data: Arc<Data>,
     query_data: Option<Arc<Data>>,
+    operation: Arc<OnceCell<(OperationType, Option<String>)>>,
+    document: Option<Arc<ExecutableDocument>>,
 }
 
 #[doc(hidden)]
@@ -399,6 +572,8 @@ impl Extensions {
             schema_env,
             session_data,
             query_data: None,
+            operation: Default::default(),
+            document: None,
         }
     }
 
@@ -407,6 +582,17 @@ impl Extensions {
         self.query_data = Some(data);
     }
 
+    #[inline]
+    pub(crate) fn attach_operation(&mut self, ty: OperationType, name: Option<String>) {
+        // The operation is shared with the clones of the extensions, so that `request` can read it.
+        let _ = self.operation.set((ty, name));
+    }
+
+    #[inline]
+    pub(crate) fn attach_document(&mut self, document: Arc<ExecutableDocument>) {
+        self.document = Some(document);
+    }
+
     #[inline]
     pub(crate) fn is_empty(&self) -> bool {
         self.extensions.is_empty()
@@ -418,6 +604,8 @@ impl Extensions {
             schema_env: &self.schema_env,
             session_data: &self.session_data,
             query_data: self.query_data.as_deref(),
+            selected_operation: &self.operation,
+            document: self.document.as_deref(),
         }
     }
 
@@ -479,6 +667,116 @@ impl Extensions {
         next.run(&self.create_context(), operation_name).await
     }
 
+    /// Rewrite the fields of the operation and of the fragments, and returns the positions of
+    /// the disabled fields.
+    pub(crate) fn rewrite_operation(
+        &self,
+        operation: &mut Positioned<OperationDefinition>,
+        fragments: &mut HashMap<Name, Positioned<FragmentDefinition>>,
+    ) -> HashSet<Pos> {
+        let mut disabled = HashSet::new();
+        if self.extensions.is_empty() {
+            return disabled;
+        }
+
+        let ctx = self.create_context();
+        let registry = &self.schema_env.registry;
+        let root_type = match operation.node.ty {
+            OperationType::Query => Some(registry.query_type.as_str()),
+            OperationType::Mutation => registry.mutation_type.as_deref(),
+            OperationType::Subscription => registry.subscription_type.as_deref(),
+        };
+        if let Some(root_type) = root_type {
+            self.rewrite_selection_set(
+                &ctx,
+                root_type,
+                &mut operation.node.selection_set,
+                &mut disabled,
+            );
+        }
+        for fragment in fragments.values_mut() {
+            let type_condition = fragment.node.type_condition.node.on.node.to_string();
+            self.rewrite_selection_set(
+                &ctx,
+                &type_condition,
+                &mut fragment.node.selection_set,
+                &mut disabled,
+            );
+        }
+        disabled
+    }
+
+    fn rewrite_selection_set(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        parent_type: &str,
+        selection_set: &mut Positioned<SelectionSet>,
+        disabled: &mut HashSet<Pos>,
+    ) {
+        for selection in &mut selection_set.node.items {
+            match &mut selection.node {
+                Selection::Field(field) => {
+                    if field.node.name.node.starts_with("__") {
+                        continue;
+                    }
+                    let rewrite = self
+                        .extensions
+                        .iter()
+                        .map(|extension| extension.rewrite_field(ctx, parent_type, field))
+                        .find(|rewrite| !matches!(rewrite, FieldRewrite::Keep));
+                    match rewrite {
+                        Some(FieldRewrite::Disable) => {
+                            disabled.insert(field.pos);
+                            continue;
+                        }
+                        Some(FieldRewrite::Replace(new_field)) => *field = new_field,
+                        Some(FieldRewrite::Keep) | None => {}
+                    }
+
+                    let field_type = self
+                        .schema_env
+                        .registry
+                        .types
+                        .get(parent_type)
+                        .and_then(|ty| ty.field_by_name(&field.node.name.node))
+                        .map(|meta_field| MetaTypeName::concrete_typename(&meta_field.ty));
+                    if let Some(field_type) = field_type {
+                        self.rewrite_selection_set(
+                            ctx,
+                            field_type,
+                            &mut field.node.selection_set,
+                            disabled,
+                        );
+                    }
+                }
+                Selection::InlineFragment(fragment) => {
+                    let type_condition = fragment
+                        .node
+                        .type_condition
+                        .as_ref()
+                        .map(|condition| condition.node.on.node.to_string())
+                        .unwrap_or_else(|| parent_type.to_string());
+                    self.rewrite_selection_set(
+                        ctx,
+                        &type_condition,
+                        &mut fragment.node.selection_set,
+                        disabled,
+                    );
+                }
+                Selection::FragmentSpread(_) => {}
+            }
+        }
+    }
+
+    pub(crate) async fn health(&self) -> Vec<ComponentStatus> {
+        let ctx = self.create_context();
+        let mut components = Vec::new();
+        for extension in &self.extensions {
+            components.extend(extension.health(&ctx).await);
+        }
+        components
+    }
+
     pub async fn resolve(
         &self,
         info: ResolveInfo<'_>,
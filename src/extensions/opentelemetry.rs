@@ -1,17 +1,18 @@
 use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
 
-use async_graphql_parser::types::ExecutableDocument;
+use async_graphql_parser::types::{ExecutableDocument, OperationType};
 use async_graphql_value::Variables;
-use futures_util::stream::BoxStream;
+use futures_util::stream::{BoxStream, StreamExt};
 use futures_util::TryFutureExt;
-use opentelemetry::trace::{FutureExt, SpanKind, TraceContextExt, Tracer};
+use opentelemetry::trace::{FutureExt, Span, SpanKind, SpanRef, TraceContextExt, Tracer};
 use opentelemetry::{Context as OpenTelemetryContext, Key};
 
 use crate::extensions::{
     Extension, ExtensionContext, ExtensionFactory, NextExecute, NextParseQuery, NextRequest,
     NextResolve, NextSubscribe, NextValidation, ResolveInfo,
 };
-use crate::{Response, ServerError, ServerResult, ValidationResult, Value};
+use crate::{QueryPathSegment, Response, ServerError, ServerResult, ValidationResult, Value};
 
 const KEY_SOURCE: Key = Key::from_static_str("graphql.source");
 const KEY_VARIABLES: Key = Key::from_static_str("graphql.variables");
@@ -20,11 +21,66 @@ const KEY_RETURN_TYPE: Key = Key::from_static_str("graphql.returnType");
 const KEY_ERROR: Key = Key::from_static_str("graphql.error");
 const KEY_COMPLEXITY: Key = Key::from_static_str("graphql.complexity");
 const KEY_DEPTH: Key = Key::from_static_str("graphql.depth");
+const KEY_OPERATION_TYPE: Key = Key::from_static_str("graphql.operation.type");
+const KEY_OPERATION_NAME: Key = Key::from_static_str("graphql.operation.name");
+const KEY_SEQUENCE: Key = Key::from_static_str("graphql.subscription.sequence");
+
+fn set_operation_attributes(span: &SpanRef<'_>, ty: OperationType, name: Option<&str>) {
+    span.set_attribute(KEY_OPERATION_TYPE.string(ty.to_string()));
+    if let Some(name) = name {
+        span.set_attribute(KEY_OPERATION_NAME.string(name.to_string()));
+    }
+}
+
+/// Configuration of the [`OpenTelemetry`] extension.
+///
+/// By default a span is recorded for every resolved field. The resolvers of the items of large
+/// lists can produce a huge number of spans, so they can be sampled with a limit on the number
+/// of items and a duration threshold.
+#[cfg_attr(docsrs, doc(cfg(feature = "opentelemetry")))]
+#[derive(Debug, Clone, Default)]
+pub struct OpenTelemetryConfig {
+    max_list_items: Option<usize>,
+    min_list_item_duration: Option<Duration>,
+}
+
+impl OpenTelemetryConfig {
+    /// Only record the spans of the first `count` items of every list and of their fields.
+    #[must_use]
+    pub fn max_list_items(self, count: usize) -> Self {
+        Self {
+            max_list_items: Some(count),
+            ..self
+        }
+    }
+
+    /// Only record the spans of list items, and of their fields, which take at least `duration`
+    /// to resolve.
+    ///
+    /// The span is created when the resolver has finished, so the spans of the nested fields
+    /// are attached to the closest recorded parent span.
+    #[must_use]
+    pub fn min_list_item_duration(self, duration: Duration) -> Self {
+        Self {
+            min_list_item_duration: Some(duration),
+            ..self
+        }
+    }
+}
 
 /// OpenTelemetry extension
+///
+/// Besides the spans of the execution stages and of the resolvers, the extension records these
+/// events:
+///
+/// - `dataloader.wait` and `dataloader.ready` on the span of a resolver which is waiting for a
+///   batch of a [`DataLoader`](crate::dataloader::DataLoader).
+/// - `subscription.enqueued` and `subscription.flushed` on the `subscribe` span, when a
+///   subscription event is produced and when the transport has sent it.
 #[cfg_attr(docsrs, doc(cfg(feature = "opentelemetry")))]
 pub struct OpenTelemetry<T> {
     tracer: Arc<T>,
+    config: Arc<OpenTelemetryConfig>,
 }
 
 impl<T> OpenTelemetry<T> {
@@ -35,6 +91,16 @@ impl<T> OpenTelemetry<T> {
     {
         Self {
             tracer: Arc::new(tracer),
+            config: Default::default(),
+        }
+    }
+
+    /// Set the configuration of the extension.
+    #[must_use]
+    pub fn config(self, config: OpenTelemetryConfig) -> Self {
+        Self {
+            config: Arc::new(config),
+            ..self
         }
     }
 }
@@ -43,25 +109,41 @@ impl<T: Tracer + Send + Sync> ExtensionFactory for OpenTelemetry<T> {
     fn create(&self) -> Arc<dyn Extension> {
         Arc::new(OpenTelemetryExtension {
             tracer: self.tracer.clone(),
+            config: self.config.clone(),
         })
     }
 }
 
 struct OpenTelemetryExtension<T> {
     tracer: Arc<T>,
+    config: Arc<OpenTelemetryConfig>,
 }
 
 #[async_trait::async_trait]
 impl<T: Tracer + Send + Sync> Extension for OpenTelemetryExtension<T> {
     async fn request(&self, ctx: &ExtensionContext<'_>, next: NextRequest<'_>) -> Response {
-        next.run(ctx)
-            .with_context(OpenTelemetryContext::current_with_span(
-                self.tracer
-                    .span_builder("request")
-                    .with_kind(SpanKind::Server)
-                    .start(&*self.tracer),
-            ))
-            .await
+        let span = self
+            .tracer
+            .span_builder("request")
+            .with_kind(SpanKind::Server)
+            .start(&*self.tracer);
+
+        async move {
+            let resp = next.run(ctx).await;
+            if let Some(ty) = ctx.operation_type() {
+                // Name the span after the operation, e.g. `query GetUser`.
+                let current_cx = OpenTelemetryContext::current();
+                let span = current_cx.span();
+                span.update_name(match ctx.operation_name() {
+                    Some(name) => format!("{} {}", ty, name),
+                    None => ty.to_string(),
+                });
+                set_operation_attributes(&span, ty, ctx.operation_name());
+            }
+            resp
+        }
+        .with_context(OpenTelemetryContext::current_with_span(span))
+        .await
     }
 
     fn subscribe<'s>(
@@ -70,15 +152,31 @@ impl<T: Tracer + Send + Sync> Extension for OpenTelemetryExtension<T> {
         stream: BoxStream<'s, Response>,
         next: NextSubscribe<'_>,
     ) -> BoxStream<'s, Response> {
-        Box::pin(
-            next.run(ctx, stream)
-                .with_context(OpenTelemetryContext::current_with_span(
-                    self.tracer
-                        .span_builder("subscribe")
-                        .with_kind(SpanKind::Server)
-                        .start(&*self.tracer),
-                )),
-        )
+        let cx = OpenTelemetryContext::current_with_span(
+            self.tracer
+                .span_builder("subscribe")
+                .with_kind(SpanKind::Server)
+                .start(&*self.tracer),
+        );
+        let stream = next.run(ctx, stream).with_context(cx.clone());
+
+        Box::pin(async_stream::stream! {
+            futures_util::pin_mut!(stream);
+            let mut sequence = 0i64;
+            while let Some(resp) = stream.next().await {
+                cx.span().add_event(
+                    "subscription.enqueued".to_string(),
+                    vec![KEY_SEQUENCE.i64(sequence)],
+                );
+                yield resp;
+                // The stream is polled again when the transport has sent the previous event.
+                cx.span().add_event(
+                    "subscription.flushed".to_string(),
+                    vec![KEY_SEQUENCE.i64(sequence)],
+                );
+                sequence += 1;
+            }
+        })
     }
 
     async fn parse_query(
@@ -145,9 +243,11 @@ impl<T: Tracer + Send + Sync> Extension for OpenTelemetryExtension<T> {
             .span_builder("execute")
             .with_kind(SpanKind::Server)
             .start(&*self.tracer);
-        next.run(ctx, operation_name)
-            .with_context(OpenTelemetryContext::current_with_span(span))
-            .await
+        let cx = OpenTelemetryContext::current_with_span(span);
+        if let Some(ty) = ctx.operation_type() {
+            set_operation_attributes(&cx.span(), ty, operation_name);
+        }
+        next.run(ctx, operation_name).with_context(cx).await
     }
 
     async fn resolve(
@@ -156,24 +256,59 @@ impl<T: Tracer + Send + Sync> Extension for OpenTelemetryExtension<T> {
         info: ResolveInfo<'_>,
         next: NextResolve<'_>,
     ) -> ServerResult<Option<Value>> {
+        let mut in_list = false;
+        for node in std::iter::once(info.path_node).chain(info.path_node.parents()) {
+            if let QueryPathSegment::Index(idx) = node.segment {
+                if matches!(self.config.max_list_items, Some(max) if idx >= max) {
+                    return next.run(ctx, info).await;
+                }
+                in_list = true;
+            }
+        }
+
         let attributes = vec![
             KEY_PARENT_TYPE.string(info.parent_type.to_string()),
             KEY_RETURN_TYPE.string(info.return_type.to_string()),
         ];
-        let span = self
+        let span_builder = self
             .tracer
             .span_builder(info.path_node.to_string())
             .with_kind(SpanKind::Server)
-            .with_attributes(attributes)
-            .start(&*self.tracer);
-        next.run(ctx, info)
-            .with_context(OpenTelemetryContext::current_with_span(span))
-            .inspect_err(|err| {
-                let current_cx = OpenTelemetryContext::current();
-                current_cx
-                    .span()
-                    .add_event("error".to_string(), vec![KEY_ERROR.string(err.to_string())]);
-            })
-            .await
+            .with_attributes(attributes);
+
+        match self.config.min_list_item_duration {
+            Some(min_duration) if in_list => {
+                // The span is only known to be recorded after the resolver has finished.
+                let start_time = SystemTime::now();
+                let start = Instant::now();
+                let res = next.run(ctx, info).await;
+                if start.elapsed() >= min_duration {
+                    let mut span = span_builder
+                        .with_start_time(start_time)
+                        .start(&*self.tracer);
+                    if let Err(err) = &res {
+                        span.add_event(
+                            "error".to_string(),
+                            vec![KEY_ERROR.string(err.to_string())],
+                        );
+                    }
+                    span.end();
+                }
+                res
+            }
+            _ => {
+                let span = span_builder.start(&*self.tracer);
+                next.run(ctx, info)
+                    .with_context(OpenTelemetryContext::current_with_span(span))
+                    .inspect_err(|err| {
+                        let current_cx = OpenTelemetryContext::current();
+                        current_cx.span().add_event(
+                            "error".to_string(),
+                            vec![KEY_ERROR.string(err.to_string())],
+                        );
+                    })
+                    .await
+            }
+        }
     }
 }
@@ -0,0 +1,513 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::{Arc, Mutex, RwLock};
+
+use async_graphql_value::Value as InputValue;
+
+use crate::extensions::{
+    Extension, ExtensionContext, ExtensionFactory, NextParseQuery, NextPrepareRequest,
+};
+use crate::parser::types::{
+    Directive, DocumentOperations, ExecutableDocument, FragmentDefinition, OperationDefinition,
+    Selection, SelectionSet,
+};
+use crate::parser::{parse_query, Error as ParseError};
+use crate::{
+    ErrorExtensionValues, Name, Pos, Positioned, Request, ServerError, ServerResult, Value,
+    Variables,
+};
+
+const DIRECTIVE_NAME: &str = "operation";
+const EXTENSION_NAME: &str = "operationId";
+
+/// The policy of an operation registered in an [`OperationWhitelist`].
+#[derive(Debug, Clone, Default)]
+pub struct OperationPolicy {
+    max_arguments: HashMap<String, f64>,
+}
+
+impl OperationPolicy {
+    /// Create a policy without constraints.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Limit the value of the arguments with the name in all the fields of the operation, for
+    /// example the page size argument `first`.
+    ///
+    /// The limit is checked against the literal values and the values of the variables, including
+    /// the numbers in lists, and the fields with the name in input objects.
+    #[must_use]
+    pub fn max_argument(mut self, name: impl Into<String>, max: i64) -> Self {
+        self.max_arguments.insert(name.into(), max as f64);
+        self
+    }
+}
+
+/// Only allow the operations registered by id.
+///
+/// The client sends the id of the operation with the `@operation(id: "...")` directive on the
+/// operation, or in the `operationId` extension of the request. The directive is removed from the
+/// document before it is validated, so it does not need to be declared in the schema.
+///
+/// The executed operation and the fragments it spreads must be the same as the registered ones,
+/// ignoring the formatting, the name of the operation and the `@operation` directive. A request
+/// without an id, with an unknown id, with another operation, or whose arguments exceed the limits
+/// of the [`OperationPolicy`] of the operation fails with an `OPERATION_NOT_ALLOWED` error. Clones
+/// of the extension share the registered operations, so a clone can be kept to change them after
+/// the schema is built.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::extensions::{OperationPolicy, OperationWhitelist};
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn users(&self, first: i32) -> Vec<i32> {
+///         (0..first).collect()
+///     }
+/// }
+///
+/// tokio::runtime::Runtime::new().unwrap().block_on(async move {
+///     let whitelist = OperationWhitelist::new();
+///     whitelist
+///         .register(
+///             "listUsers",
+///             "query($first: Int!) { users(first: $first) }",
+///             OperationPolicy::new().max_argument("first", 3),
+///         )
+///         .unwrap();
+///     let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///         .extension(whitelist)
+///         .finish();
+///
+///     let query = r#"query($first: Int!) @operation(id: "listUsers") { users(first: $first) }"#;
+///     let request = Request::new(query).variables(Variables::from_value(value!({ "first": 2 })));
+///     assert_eq!(
+///         schema.execute(request).await.into_result().unwrap().data,
+///         value!({ "users": [0, 1] })
+///     );
+///
+///     let request = Request::new(query).variables(Variables::from_value(value!({ "first": 10 })));
+///     assert!(schema.execute(request).await.is_err());
+///
+///     // Other operations are rejected.
+///     let query = r#"query @operation(id: "listUsers") { users(first: 2) }"#;
+///     assert!(schema.execute(query).await.is_err());
+///     assert!(schema.execute("{ users(first: 2) }").await.is_err());
+/// });
+/// ```
+#[derive(Clone, Default)]
+pub struct OperationWhitelist {
+    operations: Arc<RwLock<HashMap<String, RegisteredOperation>>>,
+}
+
+struct RegisteredOperation {
+    /// The normalized operations of the registered document.
+    operations: HashSet<String>,
+    policy: OperationPolicy,
+}
+
+impl OperationWhitelist {
+    /// Create a whitelist without operations.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Register the operation defined in the source with the id, or replace the operation with
+    /// the id.
+    ///
+    /// If the source defines several operations, any of them can be executed with the id.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source cannot be parsed.
+    pub fn register(
+        &self,
+        id: impl Into<String>,
+        source: &str,
+        policy: OperationPolicy,
+    ) -> Result<(), ParseError> {
+        let mut document = parse_query(source)?;
+        let operations = match &mut document.operations {
+            DocumentOperations::Single(operation) => vec![operation],
+            DocumentOperations::Multiple(operations) => operations.values_mut().collect(),
+        }
+        .into_iter()
+        .map(|operation| {
+            take_directive(operation);
+            normalize(&operation.node, &document.fragments)
+        })
+        .collect();
+        self.operations
+            .write()
+            .unwrap()
+            .insert(id.into(), RegisteredOperation { operations, policy });
+        Ok(())
+    }
+
+    /// Remove the operation with the id.
+    pub fn unregister(&self, id: &str) {
+        self.operations.write().unwrap().remove(id);
+    }
+
+    /// Returns `true` if the operation with the id is registered.
+    pub fn is_registered(&self, id: &str) -> bool {
+        self.operations.read().unwrap().contains_key(id)
+    }
+}
+
+impl ExtensionFactory for OperationWhitelist {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(OperationWhitelistExtension {
+            whitelist: self.clone(),
+            request: Default::default(),
+        })
+    }
+}
+
+#[derive(Default)]
+struct RequestInfo {
+    operation_name: Option<String>,
+    operation_id: Option<Value>,
+}
+
+struct OperationWhitelistExtension {
+    whitelist: OperationWhitelist,
+    request: Mutex<RequestInfo>,
+}
+
+fn not_allowed_error(message: impl Into<String>, pos: Option<Pos>) -> ServerError {
+    let mut extensions = ErrorExtensionValues::default();
+    extensions.set("code", "OPERATION_NOT_ALLOWED");
+    let mut err = ServerError::new(message, pos);
+    err.extensions = Some(extensions);
+    err
+}
+
+/// Removes the `@operation` directive from the operation and returns it.
+fn take_directive(
+    operation: &mut Positioned<OperationDefinition>,
+) -> Option<Positioned<Directive>> {
+    let directives = &mut operation.node.directives;
+    let idx = directives
+        .iter()
+        .position(|directive| directive.node.name.node == DIRECTIVE_NAME)?;
+    Some(directives.remove(idx))
+}
+
+fn resolve_value(
+    operation: &OperationDefinition,
+    variables: &Variables,
+    value: &InputValue,
+) -> Option<Value> {
+    value
+        .clone()
+        .into_const_with(|name| {
+            variables
+                .get(&name)
+                .or_else(|| {
+                    operation
+                        .variable_definitions
+                        .iter()
+                        .find(|def| def.node.name.node == name)
+                        .and_then(|def| def.node.default_value())
+                })
+                .cloned()
+                .ok_or(())
+        })
+        .ok()
+}
+
+fn write_arguments(output: &mut String, arguments: &[(Positioned<Name>, Positioned<InputValue>)]) {
+    if arguments.is_empty() {
+        return;
+    }
+    output.push('(');
+    for (idx, (name, value)) in arguments.iter().enumerate() {
+        if idx > 0 {
+            output.push_str(", ");
+        }
+        output.push_str(&format!("{}: {}", name.node, value.node));
+    }
+    output.push(')');
+}
+
+fn write_directives(output: &mut String, directives: &[Positioned<Directive>]) {
+    for directive in directives {
+        output.push_str(&format!(" @{}", directive.node.name.node));
+        write_arguments(output, &directive.node.arguments);
+    }
+}
+
+fn write_selection_set<'a>(
+    output: &mut String,
+    selection_set: &'a SelectionSet,
+    spreads: &mut BTreeSet<&'a Name>,
+) {
+    output.push_str(" {");
+    for selection in &selection_set.items {
+        output.push(' ');
+        match &selection.node {
+            Selection::Field(field) => {
+                if let Some(alias) = &field.node.alias {
+                    output.push_str(&format!("{}: ", alias.node));
+                }
+                output.push_str(&field.node.name.node);
+                write_arguments(output, &field.node.arguments);
+                write_directives(output, &field.node.directives);
+                if !field.node.selection_set.node.items.is_empty() {
+                    write_selection_set(output, &field.node.selection_set.node, spreads);
+                }
+            }
+            Selection::FragmentSpread(spread) => {
+                output.push_str(&format!("...{}", spread.node.fragment_name.node));
+                write_directives(output, &spread.node.directives);
+                spreads.insert(&spread.node.fragment_name.node);
+            }
+            Selection::InlineFragment(fragment) => {
+                output.push_str("...");
+                if let Some(type_condition) = &fragment.node.type_condition {
+                    output.push_str(&format!(" on {}", type_condition.node.on.node));
+                }
+                write_directives(output, &fragment.node.directives);
+                write_selection_set(output, &fragment.node.selection_set.node, spreads);
+            }
+        }
+    }
+    output.push_str(" }");
+}
+
+/// Writes the operation and the fragments it spreads in a canonical form, without the name of
+/// the operation.
+fn normalize(
+    operation: &OperationDefinition,
+    fragments: &HashMap<Name, Positioned<FragmentDefinition>>,
+) -> String {
+    let mut output = operation.ty.to_string();
+    if !operation.variable_definitions.is_empty() {
+        output.push('(');
+        for (idx, definition) in operation.variable_definitions.iter().enumerate() {
+            if idx > 0 {
+                output.push_str(", ");
+            }
+            output.push_str(&format!(
+                "${}: {}",
+                definition.node.name.node, definition.node.var_type.node
+            ));
+            if let Some(default_value) = &definition.node.default_value {
+                output.push_str(&format!(" = {}", default_value.node));
+            }
+            write_directives(&mut output, &definition.node.directives);
+        }
+        output.push(')');
+    }
+    write_directives(&mut output, &operation.directives);
+
+    let mut spreads = BTreeSet::new();
+    write_selection_set(&mut output, &operation.selection_set.node, &mut spreads);
+
+    // The fragments are written in the order of their names, and each one once.
+    let mut written = HashSet::new();
+    while let Some(name) = spreads
+        .iter()
+        .find(|name| !written.contains(*name))
+        .copied()
+    {
+        written.insert(name);
+        if let Some(fragment) = fragments.get(name) {
+            output.push_str(&format!(
+                "\nfragment {} on {}",
+                name, fragment.node.type_condition.node.on.node
+            ));
+            write_directives(&mut output, &fragment.node.directives);
+            write_selection_set(&mut output, &fragment.node.selection_set.node, &mut spreads);
+        }
+    }
+    output
+}
+
+/// Returns the name and the limit of the first number in the value which exceeds its limit.
+fn exceeded_limit<'a>(
+    policy: &'a OperationPolicy,
+    name: &'a str,
+    value: &'a Value,
+) -> Option<(&'a str, f64)> {
+    match value {
+        Value::Number(n) => policy
+            .max_arguments
+            .get(name)
+            .filter(|max| n.as_f64().unwrap_or(f64::INFINITY) > **max)
+            .map(|max| (name, *max)),
+        Value::List(items) => items
+            .iter()
+            .find_map(|item| exceeded_limit(policy, name, item)),
+        Value::Object(fields) => fields
+            .iter()
+            .find_map(|(name, value)| exceeded_limit(policy, name, value)),
+        _ => None,
+    }
+}
+
+struct ArgumentChecker<'a> {
+    id: &'a str,
+    policy: &'a OperationPolicy,
+    operation: &'a OperationDefinition,
+    document: &'a ExecutableDocument,
+    variables: &'a Variables,
+    visited: HashSet<&'a Name>,
+}
+
+impl<'a> ArgumentChecker<'a> {
+    fn check(&mut self, selection_set: &'a SelectionSet) -> ServerResult<()> {
+        for selection in &selection_set.items {
+            match &selection.node {
+                Selection::Field(field) => {
+                    for (name, value) in &field.node.arguments {
+                        let exceeded = resolve_value(self.operation, self.variables, &value.node)
+                            .and_then(|value| {
+                                exceeded_limit(self.policy, &name.node, &value)
+                                    .map(|(name, max)| (name.to_string(), max))
+                            });
+                        if let Some((name, max)) = exceeded {
+                            return Err(not_allowed_error(
+                                format!(
+                                    r#"The argument "{}" must not be greater than {} in the operation "{}"."#,
+                                    name, max, self.id
+                                ),
+                                Some(value.pos),
+                            ));
+                        }
+                    }
+                    self.check(&field.node.selection_set.node)?;
+                }
+                Selection::FragmentSpread(spread) => {
+                    let name = &spread.node.fragment_name.node;
+                    if self.visited.insert(name) {
+                        if let Some(fragment) = self.document.fragments.get(name) {
+                            self.check(&fragment.node.selection_set.node)?;
+                        }
+                    }
+                }
+                Selection::InlineFragment(fragment) => {
+                    self.check(&fragment.node.selection_set.node)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for OperationWhitelistExtension {
+    async fn prepare_request(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        request: Request,
+        next: NextPrepareRequest<'_>,
+    ) -> ServerResult<Request> {
+        let request = next.run(ctx, request).await?;
+        *self.request.lock().unwrap() = RequestInfo {
+            operation_name: request.operation_name.clone(),
+            operation_id: request.extensions.get(EXTENSION_NAME).cloned(),
+        };
+        Ok(request)
+    }
+
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        let mut document = next.run(ctx, query, variables).await?;
+        let info = std::mem::take(&mut *self.request.lock().unwrap());
+
+        // The directive is removed from all the operations, because it is not declared in the
+        // schema, and the one of the executed operation is kept.
+        let mut directive = None;
+        let mut selected = None;
+        match &mut document.operations {
+            DocumentOperations::Single(operation) => {
+                directive = take_directive(operation);
+                selected = Some(None);
+            }
+            DocumentOperations::Multiple(operations) => {
+                for (name, operation) in operations.iter_mut() {
+                    let d = take_directive(operation);
+                    if selected.is_none()
+                        && (info.operation_name.is_none()
+                            || info.operation_name.as_deref() == Some(name.as_str()))
+                    {
+                        directive = d;
+                        selected = Some(Some(name.clone()));
+                    }
+                }
+            }
+        }
+        let operation = match (&document.operations, selected) {
+            (DocumentOperations::Single(operation), Some(_)) => operation,
+            (DocumentOperations::Multiple(operations), Some(Some(name))) => &operations[&name],
+            // The unknown operation is reported by the execution.
+            _ => return Ok(document),
+        };
+
+        let (id, pos) = match &directive {
+            Some(directive) => (
+                directive
+                    .node
+                    .get_argument("id")
+                    .and_then(|value| resolve_value(&operation.node, variables, &value.node)),
+                directive.pos,
+            ),
+            None => (info.operation_id, operation.pos),
+        };
+        let id = match id {
+            Some(Value::String(id)) => id,
+            Some(_) => {
+                return Err(not_allowed_error(
+                    "The operation id must be a string.",
+                    Some(pos),
+                ))
+            }
+            None => {
+                return Err(not_allowed_error(
+                    "The operation id is required.",
+                    Some(pos),
+                ))
+            }
+        };
+
+        let operations = self.whitelist.operations.read().unwrap();
+        let registered = operations.get(&id).ok_or_else(|| {
+            not_allowed_error(format!(r#"Unknown operation id "{}"."#, id), Some(pos))
+        })?;
+        if !registered
+            .operations
+            .contains(&normalize(&operation.node, &document.fragments))
+        {
+            return Err(not_allowed_error(
+                format!(r#"The operation is not the registered operation "{}"."#, id),
+                Some(operation.pos),
+            ));
+        }
+        if registered.policy.max_arguments.is_empty() {
+            return Ok(document);
+        }
+        ArgumentChecker {
+            id: &id,
+            policy: &registered.policy,
+            operation: &operation.node,
+            document: &document,
+            variables,
+            visited: HashSet::new(),
+        }
+        .check(&operation.node.selection_set.node)?;
+
+        Ok(document)
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use crate::extensions::{Extension, ExtensionContext, ExtensionFactory, NextParseQuery};
+use crate::parser::types::{ExecutableDocument, FragmentDefinition, Selection, SelectionSet};
+use crate::parser::{parse_query, Error as ParseError};
+use crate::{Name, Positioned, ServerResult, Variables};
+
+/// Fragments registered on the server, which the clients can spread without sending their
+/// definitions.
+///
+/// The registered fragments spread by a query, and the ones they spread, are added to its
+/// document before it is validated, unless the query defines a fragment with the same name. Clones
+/// of the extension share the registered fragments, so a clone can be kept to change them after
+/// the schema is built.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::extensions::PersistedFragments;
+///
+/// #[derive(SimpleObject)]
+/// struct User {
+///     id: i32,
+///     name: String,
+/// }
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn user(&self) -> User {
+///         User { id: 1, name: "Alice".to_string() }
+///     }
+/// }
+///
+/// tokio::runtime::Runtime::new().unwrap().block_on(async move {
+///     let fragments = PersistedFragments::new();
+///     fragments.register("fragment UserFields on User { id name }").unwrap();
+///     let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///         .extension(fragments)
+///         .finish();
+///
+///     assert_eq!(
+///         schema.execute("{ user { ...UserFields } }").await.into_result().unwrap().data,
+///         value!({ "user": { "id": 1, "name": "Alice" } })
+///     );
+/// });
+/// ```
+#[derive(Clone, Default)]
+pub struct PersistedFragments {
+    fragments: Arc<RwLock<HashMap<Name, Positioned<FragmentDefinition>>>>,
+}
+
+impl PersistedFragments {
+    /// Create an extension without fragments.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Register the fragments defined in the source, which must only contain fragments. A
+    /// registered fragment with the same name is replaced.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source cannot be parsed.
+    pub fn register(&self, source: &str) -> Result<(), ParseError> {
+        // A document must contain an operation, so one is added after the fragments to keep their
+        // positions.
+        let document = parse_query(format!("{}\n{{ __typename }}", source))?;
+        self.fragments.write().unwrap().extend(document.fragments);
+        Ok(())
+    }
+
+    /// Remove the fragment with the name.
+    pub fn unregister(&self, name: &str) {
+        self.fragments.write().unwrap().remove(name);
+    }
+
+    /// Returns `true` if the fragment with the name is registered.
+    pub fn contains(&self, name: &str) -> bool {
+        self.fragments.read().unwrap().contains_key(name)
+    }
+}
+
+impl ExtensionFactory for PersistedFragments {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(PersistedFragmentsExtension {
+            fragments: self.clone(),
+        })
+    }
+}
+
+struct PersistedFragmentsExtension {
+    fragments: PersistedFragments,
+}
+
+fn spread_names<'a>(selection_set: &'a SelectionSet, names: &mut Vec<&'a Name>) {
+    for selection in &selection_set.items {
+        match &selection.node {
+            Selection::Field(field) => spread_names(&field.node.selection_set.node, names),
+            Selection::FragmentSpread(spread) => names.push(&spread.node.fragment_name.node),
+            Selection::InlineFragment(fragment) => {
+                spread_names(&fragment.node.selection_set.node, names)
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for PersistedFragmentsExtension {
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        let mut document = next.run(ctx, query, variables).await?;
+
+        let persisted = self.fragments.fragments.read().unwrap();
+        let mut missing = Vec::new();
+        {
+            let mut names = Vec::new();
+            for (_, operation) in document.operations.iter() {
+                spread_names(&operation.node.selection_set.node, &mut names);
+            }
+            for fragment in document.fragments.values() {
+                spread_names(&fragment.node.selection_set.node, &mut names);
+            }
+            while let Some(name) = names.pop() {
+                if document.fragments.contains_key(name) || missing.contains(&name) {
+                    continue;
+                }
+                if let Some(fragment) = persisted.get(name) {
+                    missing.push(name);
+                    spread_names(&fragment.node.selection_set.node, &mut names);
+                }
+            }
+        }
+        let missing = missing.into_iter().cloned().collect::<Vec<_>>();
+        for name in missing {
+            let fragment = persisted[&name].clone();
+            document.fragments.insert(name, fragment);
+        }
+
+        Ok(document)
+    }
+}
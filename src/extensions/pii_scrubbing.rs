@@ -0,0 +1,266 @@
+use std::sync::Arc;
+
+use crate::extensions::{Extension, ExtensionContext, ExtensionFactory, NextResolve, ResolveInfo};
+use crate::registry::MetaTypeName;
+use crate::{
+    ErrorExtensionValues, PathSegment, QueryPathSegment, ServerError, ServerResult, Value,
+};
+
+type ScopesFn = dyn Fn(&ExtensionContext<'_>) -> Vec<String> + Send + Sync;
+
+/// PII scrubbing extension
+///
+/// Redacts the values of the fields tagged with `#[graphql(meta(pii = true))]` in the responses
+/// to the callers that lack the scope passed to [`PiiScrubbing::new`]. The resolvers of these
+/// fields are not called, and `String` and `ID` fields are replaced with `"<redacted>"`, while
+/// the nullable fields of other types are replaced with `null`. The non-null fields of other types
+/// are resolved to a `FORBIDDEN` error instead, so the `null` propagates to the nearest nullable
+/// parent.
+///
+/// The scopes of a caller are returned by the function passed to [`PiiScrubbing::scopes`]. If it
+/// is not set, the values are always redacted.
+///
+/// The arguments and the input fields that are tagged with `pii = true` are always redacted from
+/// the query strings reported by the telemetry extensions, regardless of this extension.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::extensions::PiiScrubbing;
+///
+/// struct Scopes(Vec<String>);
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     #[graphql(meta(pii = true))]
+///     async fn email(&self) -> &str {
+///         "sunli@example.com"
+///     }
+/// }
+///
+/// tokio::runtime::Runtime::new().unwrap().block_on(async move {
+///     let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///         .extension(
+///             PiiScrubbing::new("pii:read")
+///                 .scopes(|ctx| ctx.data_opt::<Scopes>().map(|scopes| scopes.0.clone()).unwrap_or_default()),
+///         )
+///         .finish();
+///
+///     assert_eq!(
+///         schema.execute("{ email }").await.data,
+///         value!({ "email": "<redacted>" })
+///     );
+///     assert_eq!(
+///         schema
+///             .execute(Request::new("{ email }").data(Scopes(vec!["pii:read".to_string()])))
+///             .await
+///             .data,
+///         value!({ "email": "sunli@example.com" })
+///     );
+/// });
+/// ```
+pub struct PiiScrubbing {
+    scope: Arc<str>,
+    scopes: Option<Arc<ScopesFn>>,
+}
+
+impl PiiScrubbing {
+    /// Create a PII scrubbing extension, which shows the PII fields only to the callers with the
+    /// `scope`.
+    pub fn new(scope: impl Into<String>) -> Self {
+        Self {
+            scope: scope.into().into(),
+            scopes: None,
+        }
+    }
+
+    /// Set the function that returns the scopes of the caller of a request.
+    #[must_use]
+    pub fn scopes(
+        self,
+        f: impl Fn(&ExtensionContext<'_>) -> Vec<String> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            scopes: Some(Arc::new(f)),
+            ..self
+        }
+    }
+}
+
+impl ExtensionFactory for PiiScrubbing {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(PiiScrubbingExtension {
+            scope: self.scope.clone(),
+            scopes: self.scopes.clone(),
+        })
+    }
+}
+
+struct PiiScrubbingExtension {
+    scope: Arc<str>,
+    scopes: Option<Arc<ScopesFn>>,
+}
+
+#[async_trait::async_trait]
+impl Extension for PiiScrubbingExtension {
+    async fn resolve(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        info: ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> ServerResult<Option<Value>> {
+        if !ctx
+            .schema_env
+            .registry
+            .is_pii_field(info.parent_type, info.name)
+        {
+            return next.run(ctx, info).await;
+        }
+
+        let allowed = self
+            .scopes
+            .as_ref()
+            .map(|f| f(ctx).iter().any(|scope| **scope == *self.scope))
+            .unwrap_or_default();
+        if allowed {
+            return next.run(ctx, info).await;
+        }
+
+        match MetaTypeName::create(info.return_type) {
+            MetaTypeName::NonNull("String" | "ID") | MetaTypeName::Named("String" | "ID") => {
+                Ok(Some(Value::String("<redacted>".to_string())))
+            }
+            MetaTypeName::NonNull(_) => {
+                let mut path = Vec::new();
+                info.path_node.for_each(|segment| {
+                    path.push(match segment {
+                        QueryPathSegment::Name(name) => PathSegment::Field((*name).to_string()),
+                        QueryPathSegment::Index(idx) => PathSegment::Index(*idx),
+                    })
+                });
+                let mut extensions = ErrorExtensionValues::default();
+                extensions.set("code", "FORBIDDEN");
+                let mut err = ServerError::new("The field is redacted.", None);
+                err.path = path;
+                err.extensions = Some(extensions);
+                Err(err)
+            }
+            _ => Ok(Some(Value::Null)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[tokio::test]
+    async fn test_pii_scrubbing() {
+        #[derive(SimpleObject)]
+        #[graphql(internal)]
+        struct User {
+            name: String,
+            #[graphql(meta(pii = true))]
+            email: String,
+            #[graphql(meta(pii = true))]
+            birth_year: Option<i32>,
+            #[graphql(meta(pii = false))]
+            id: ID,
+        }
+
+        struct Query;
+
+        #[Object(internal)]
+        impl Query {
+            async fn user(&self) -> User {
+                User {
+                    name: "sunli".to_string(),
+                    email: "sunli@example.com".to_string(),
+                    birth_year: Some(1990),
+                    id: ID::from("1"),
+                }
+            }
+        }
+
+        let query = "{ user { name email birthYear id } }";
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(PiiScrubbing::new("pii"))
+            .finish();
+        assert_eq!(
+            schema.execute(query).await.into_result().unwrap().data,
+            value!({
+                "user": {
+                    "name": "sunli",
+                    "email": "<redacted>",
+                    "birthYear": null,
+                    "id": "1",
+                }
+            })
+        );
+
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(PiiScrubbing::new("pii").scopes(|_| vec!["pii".to_string()]))
+            .finish();
+        assert_eq!(
+            schema.execute(query).await.into_result().unwrap().data,
+            value!({
+                "user": {
+                    "name": "sunli",
+                    "email": "sunli@example.com",
+                    "birthYear": 1990,
+                    "id": "1",
+                }
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pii_scrubbing_non_null() {
+        #[derive(SimpleObject)]
+        #[graphql(internal)]
+        struct User {
+            name: String,
+            #[graphql(meta(pii = true))]
+            birth_year: i32,
+        }
+
+        struct Query;
+
+        #[Object(internal)]
+        impl Query {
+            async fn user(&self) -> Option<User> {
+                Some(User {
+                    name: "sunli".to_string(),
+                    birth_year: 1990,
+                })
+            }
+        }
+
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(PiiScrubbing::new("pii"))
+            .finish();
+        let resp = schema.execute("{ user { name birthYear } }").await;
+        assert_eq!(resp.data, value!({ "user": null }));
+        assert_eq!(
+            resp.errors,
+            vec![ServerError {
+                message: "The field is redacted.".to_string(),
+                source: None,
+                locations: Vec::new(),
+                path: vec![
+                    PathSegment::Field("user".to_string()),
+                    PathSegment::Field("birthYear".to_string()),
+                ],
+                extensions: Some({
+                    let mut extensions = ErrorExtensionValues::default();
+                    extensions.set("code", "FORBIDDEN");
+                    extensions
+                }),
+            }]
+        );
+    }
+}
@@ -0,0 +1,247 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use async_graphql_parser::types::ExecutableDocument;
+use async_graphql_value::Variables;
+use prometheus::{HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry};
+
+use crate::extensions::{
+    Extension, ExtensionContext, ExtensionFactory, NextExecute, NextParseQuery, NextRequest,
+    NextResolve, NextValidation, ResolveInfo,
+};
+use crate::{Response, ServerError, ServerResult, ValidationResult, Value};
+
+struct Metrics {
+    requests: IntCounterVec,
+    errors: IntCounterVec,
+    phase_duration: HistogramVec,
+    resolver_duration: HistogramVec,
+}
+
+/// Prometheus metrics extension
+///
+/// Records the following metrics into a [`prometheus::Registry`]:
+///
+/// | Name                                | Type      | Labels                  |
+/// |-------------------------------------|-----------|-------------------------|
+/// | `graphql_requests_total`            | Counter   | `operation_type`        |
+/// | `graphql_errors_total`              | Counter   | `operation_type`        |
+/// | `graphql_phase_duration_seconds`    | Histogram | `phase`                 |
+/// | `graphql_resolver_duration_seconds` | Histogram | `parent_type`, `field`  |
+///
+/// The `phase` label is one of `parse`, `validation` and `execution`. The `operation_type` label
+/// is `unknown` if the request failed before an operation was selected.
+///
+/// # Examples
+///
+/// ```no_run
+/// use async_graphql::*;
+/// use async_graphql::extensions::PrometheusMetrics;
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn value(&self) -> i32 {
+///         100
+///     }
+/// }
+///
+/// let registry = prometheus::Registry::new();
+/// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///     .extension(PrometheusMetrics::new(&registry).unwrap())
+///     .finish();
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "prometheus")))]
+pub struct PrometheusMetrics {
+    metrics: Arc<Metrics>,
+}
+
+impl PrometheusMetrics {
+    /// Create a Prometheus metrics extension and register its metrics into `registry`.
+    pub fn new(registry: &Registry) -> prometheus::Result<Self> {
+        let requests = IntCounterVec::new(
+            Opts::new("graphql_requests_total", "Number of GraphQL requests."),
+            &["operation_type"],
+        )?;
+        let errors = IntCounterVec::new(
+            Opts::new(
+                "graphql_errors_total",
+                "Number of errors in GraphQL responses.",
+            ),
+            &["operation_type"],
+        )?;
+        let phase_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "graphql_phase_duration_seconds",
+                "Duration of parsing, validating and executing GraphQL requests.",
+            ),
+            &["phase"],
+        )?;
+        let resolver_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "graphql_resolver_duration_seconds",
+                "Duration of GraphQL field resolvers.",
+            ),
+            &["parent_type", "field"],
+        )?;
+
+        registry.register(Box::new(requests.clone()))?;
+        registry.register(Box::new(errors.clone()))?;
+        registry.register(Box::new(phase_duration.clone()))?;
+        registry.register(Box::new(resolver_duration.clone()))?;
+
+        Ok(Self {
+            metrics: Arc::new(Metrics {
+                requests,
+                errors,
+                phase_duration,
+                resolver_duration,
+            }),
+        })
+    }
+}
+
+impl ExtensionFactory for PrometheusMetrics {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(PrometheusMetricsExtension {
+            metrics: self.metrics.clone(),
+        })
+    }
+}
+
+struct PrometheusMetricsExtension {
+    metrics: Arc<Metrics>,
+}
+
+impl PrometheusMetricsExtension {
+    fn observe_phase(&self, phase: &str, start: Instant) {
+        self.metrics
+            .phase_duration
+            .with_label_values(&[phase])
+            .observe(start.elapsed().as_secs_f64());
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for PrometheusMetricsExtension {
+    async fn request(&self, ctx: &ExtensionContext<'_>, next: NextRequest<'_>) -> Response {
+        let resp = next.run(ctx).await;
+        let operation_type = ctx
+            .operation_type()
+            .map(|ty| ty.to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+        self.metrics
+            .requests
+            .with_label_values(&[&operation_type])
+            .inc();
+        if !resp.errors.is_empty() {
+            self.metrics
+                .errors
+                .with_label_values(&[&operation_type])
+                .inc_by(resp.errors.len() as u64);
+        }
+        resp
+    }
+
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        let start = Instant::now();
+        let res = next.run(ctx, query, variables).await;
+        self.observe_phase("parse", start);
+        res
+    }
+
+    async fn validation(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        next: NextValidation<'_>,
+    ) -> Result<ValidationResult, Vec<ServerError>> {
+        let start = Instant::now();
+        let res = next.run(ctx).await;
+        self.observe_phase("validation", start);
+        res
+    }
+
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        let start = Instant::now();
+        let resp = next.run(ctx, operation_name).await;
+        self.observe_phase("execution", start);
+        resp
+    }
+
+    async fn resolve(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        info: ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> ServerResult<Option<Value>> {
+        let parent_type = info.parent_type;
+        let field = info.name;
+        let start = Instant::now();
+        let res = next.run(ctx, info).await;
+        self.metrics
+            .resolver_duration
+            .with_label_values(&[parent_type, field])
+            .observe(start.elapsed().as_secs_f64());
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[tokio::test]
+    async fn test_prometheus_metrics() {
+        struct Query;
+
+        #[Object(internal)]
+        impl Query {
+            async fn value(&self) -> i32 {
+                100
+            }
+        }
+
+        let registry = Registry::new();
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(PrometheusMetrics::new(&registry).unwrap())
+            .finish();
+        assert!(schema.execute("{ value }").await.is_ok());
+        assert!(schema.execute("{ value1 }").await.is_err());
+
+        let families = registry.gather();
+        let family = |name: &str| families.iter().find(|f| f.get_name() == name).unwrap();
+
+        let requests = family("graphql_requests_total");
+        assert_eq!(requests.get_metric().len(), 2);
+        let errors = family("graphql_errors_total").get_metric();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].get_label()[0].get_value(), "unknown");
+        assert_eq!(errors[0].get_counter().get_value() as u64, 1);
+
+        let resolver = family("graphql_resolver_duration_seconds").get_metric();
+        assert_eq!(resolver.len(), 1);
+        let labels: Vec<_> = resolver[0]
+            .get_label()
+            .iter()
+            .map(|label| (label.get_name(), label.get_value()))
+            .collect();
+        assert_eq!(labels, vec![("field", "value"), ("parent_type", "Query")]);
+        assert_eq!(resolver[0].get_histogram().get_sample_count(), 1);
+
+        let phases = family("graphql_phase_duration_seconds").get_metric();
+        assert_eq!(phases.len(), 3);
+    }
+}
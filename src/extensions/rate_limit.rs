@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::extensions::{Extension, ExtensionContext, ExtensionFactory, NextValidation};
+use crate::{ErrorExtensionValues, ServerError, ValidationResult};
+
+type KeyFn = dyn Fn(&ExtensionContext<'_>) -> Option<String> + Send + Sync;
+
+struct Bucket {
+    points: f64,
+    updated_at: Instant,
+}
+
+struct Limiter {
+    capacity: f64,
+    points_per_second: f64,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl Limiter {
+    /// Take points from the bucket of the client, or returns the duration after which the points
+    /// are available.
+    fn take(&self, key: &str, points: f64) -> Result<(), Option<Duration>> {
+        if points > self.capacity {
+            return Err(None);
+        }
+
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap();
+        if !buckets.contains_key(key) {
+            // Forget the clients whose buckets are full again.
+            let (capacity, points_per_second) = (self.capacity, self.points_per_second);
+            buckets.retain(|_, bucket| {
+                bucket.points
+                    + now.duration_since(bucket.updated_at).as_secs_f64() * points_per_second
+                    < capacity
+            });
+        }
+
+        let bucket = buckets.entry(key.to_string()).or_insert(Bucket {
+            points: self.capacity,
+            updated_at: now,
+        });
+        bucket.points = (bucket.points
+            + now.duration_since(bucket.updated_at).as_secs_f64() * self.points_per_second)
+            .min(self.capacity);
+        bucket.updated_at = now;
+
+        if bucket.points >= points {
+            bucket.points -= points;
+            Ok(())
+        } else {
+            Err(Some(Duration::from_secs_f64(
+                (points - bucket.points) / self.points_per_second,
+            )))
+        }
+    }
+}
+
+/// Rate limit extension
+///
+/// Limits the total complexity of the queries of each client with a token bucket. The bucket of a
+/// client holds up to `capacity` points and is refilled at `capacity` points per `period`, and a
+/// query takes as many points as its complexity.
+///
+/// A query that exceeds the limit fails with a `RATE_LIMITED` error, whose `retryAfter` extension
+/// is the number of seconds after which the query is allowed.
+///
+/// The clients are identified by the key returned by the function passed to [`RateLimit::key`].
+/// All requests without a key share the same bucket.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use async_graphql::*;
+/// use async_graphql::extensions::RateLimit;
+///
+/// struct ClientIp(String);
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn value(&self) -> i32 {
+///         100
+///     }
+/// }
+///
+/// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///     .extension(
+///         RateLimit::new(1000, Duration::from_secs(60))
+///             .key(|ctx| ctx.data_opt::<ClientIp>().map(|ip| ip.0.clone())),
+///     )
+///     .finish();
+/// ```
+pub struct RateLimit {
+    limiter: Arc<Limiter>,
+    key: Option<Arc<KeyFn>>,
+}
+
+impl RateLimit {
+    /// Create a rate limit extension that allows `capacity` complexity points per `period`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` or `period` is zero.
+    pub fn new(capacity: usize, period: Duration) -> Self {
+        assert!(capacity > 0, "The capacity must be greater than zero.");
+        assert!(
+            period > Duration::from_secs(0),
+            "The period must be greater than zero."
+        );
+        Self {
+            limiter: Arc::new(Limiter {
+                capacity: capacity as f64,
+                points_per_second: capacity as f64 / period.as_secs_f64(),
+                buckets: Default::default(),
+            }),
+            key: None,
+        }
+    }
+
+    /// Set the function that returns the key of the client of a request, for example its IP
+    /// address or user id.
+    #[must_use]
+    pub fn key(
+        self,
+        f: impl Fn(&ExtensionContext<'_>) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            key: Some(Arc::new(f)),
+            ..self
+        }
+    }
+}
+
+impl ExtensionFactory for RateLimit {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(RateLimitExtension {
+            limiter: self.limiter.clone(),
+            key: self.key.clone(),
+        })
+    }
+}
+
+struct RateLimitExtension {
+    limiter: Arc<Limiter>,
+    key: Option<Arc<KeyFn>>,
+}
+
+#[async_trait::async_trait]
+impl Extension for RateLimitExtension {
+    async fn validation(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        next: NextValidation<'_>,
+    ) -> Result<ValidationResult, Vec<ServerError>> {
+        let res = next.run(ctx).await?;
+        let key = self.key.as_ref().and_then(|f| f(ctx)).unwrap_or_default();
+        match self.limiter.take(&key, res.complexity as f64) {
+            Ok(()) => Ok(res),
+            Err(retry_after) => {
+                let mut extensions = ErrorExtensionValues::default();
+                extensions.set("code", "RATE_LIMITED");
+                let message = match retry_after {
+                    Some(retry_after) => {
+                        extensions.set("retryAfter", retry_after.as_secs_f64().ceil() as u64);
+                        "Rate limit exceeded."
+                    }
+                    None => "The query complexity exceeds the rate limit.",
+                };
+                let mut err = ServerError::new(message, None);
+                err.extensions = Some(extensions);
+                Err(vec![err])
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[tokio::test]
+    async fn test_rate_limit() {
+        struct ClientId(i32);
+
+        struct Query;
+
+        #[Object(internal)]
+        impl Query {
+            async fn value(&self) -> i32 {
+                100
+            }
+        }
+
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(
+                RateLimit::new(5, Duration::from_secs(3600))
+                    .key(|ctx| ctx.data_opt::<ClientId>().map(|id| id.0.to_string())),
+            )
+            .finish();
+        let execute = |query: &'static str, client_id: Option<i32>| {
+            let schema = schema.clone();
+            async move {
+                let mut request = Request::new(query);
+                if let Some(client_id) = client_id {
+                    request = request.data(ClientId(client_id));
+                }
+                schema.execute(request).await
+            }
+        };
+
+        assert!(execute("{ a: value b: value c: value }", Some(1))
+            .await
+            .is_ok());
+        let resp = execute("{ a: value b: value c: value }", Some(1)).await;
+        assert_eq!(resp.errors.len(), 1);
+        assert_eq!(resp.errors[0].message, "Rate limit exceeded.");
+        // One point is refilled every 12 minutes.
+        let extensions = resp.errors[0].extensions.clone().unwrap();
+        let mut expected = ErrorExtensionValues::default();
+        expected.set("code", "RATE_LIMITED");
+        expected.set("retryAfter", 720);
+        assert_eq!(extensions, expected);
+
+        assert!(execute("{ a: value b: value }", Some(1)).await.is_ok());
+        assert!(execute("{ value }", Some(1)).await.is_err());
+
+        // The buckets of the clients are separate.
+        assert!(execute("{ a: value b: value c: value }", Some(2))
+            .await
+            .is_ok());
+        assert!(execute("{ a: value b: value c: value }", None)
+            .await
+            .is_ok());
+
+        let resp = execute(
+            "{ a: value b: value c: value d: value e: value f: value }",
+            None,
+        )
+        .await;
+        assert_eq!(
+            resp.errors[0].message,
+            "The query complexity exceeds the rate limit."
+        );
+    }
+}
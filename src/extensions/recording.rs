@@ -0,0 +1,267 @@
+//! Recording extension and replay of the recorded fixtures.
+
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::extensions::{
+    Extension, ExtensionContext, ExtensionFactory, NextPrepareRequest, NextRequest,
+};
+use crate::{ObjectType, Request, Response, Schema, ServerResult, SubscriptionType, Variables};
+
+/// A recorded request and its response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Fixture {
+    /// The query source of the request.
+    pub query: String,
+
+    /// The operation name of the request.
+    #[serde(default)]
+    pub operation_name: Option<String>,
+
+    /// The variables of the request.
+    #[serde(default)]
+    pub variables: Variables,
+
+    /// The serialized response.
+    pub response: serde_json::Value,
+}
+
+impl Fixture {
+    fn file_name(&self) -> String {
+        let mut hasher = DefaultHasher::new();
+        self.query.hash(&mut hasher);
+        self.operation_name.hash(&mut hasher);
+        self.variables.to_string().hash(&mut hasher);
+        format!(
+            "{}-{:016x}.json",
+            self.operation_name.as_deref().unwrap_or("anonymous"),
+            hasher.finish()
+        )
+    }
+}
+
+/// Recording extension
+///
+/// Writes every query and mutation, with its variables and response, as a [`Fixture`] to a JSON
+/// file in the directory. The same request is always written to the same file, so recording the
+/// traffic of a server builds a suite of unique requests, which can be executed again by
+/// [`replay`] to check that the responses are unchanged after a refactoring.
+///
+/// The files are written with blocking I/O after the response is created, so this extension is
+/// meant for development and test environments. Subscriptions are not recorded.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::extensions::Recording;
+/// use async_graphql::extensions::recording::replay;
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn add(&self, a: i32, b: i32) -> i32 {
+///         a + b
+///     }
+/// }
+///
+/// tokio::runtime::Runtime::new().unwrap().block_on(async move {
+///     let dir = tempfile::tempdir().unwrap();
+///     let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///         .extension(Recording::new(dir.path()))
+///         .finish();
+///     schema.execute("{ add(a: 1, b: 2) }").await;
+///
+///     let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+///     assert!(replay(&schema, dir.path()).await.unwrap().is_empty());
+/// });
+/// ```
+pub struct Recording {
+    dir: Arc<Path>,
+}
+
+impl Recording {
+    /// Create a recording extension, which writes the fixtures to `dir`.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into().into(),
+        }
+    }
+}
+
+impl ExtensionFactory for Recording {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(RecordingExtension {
+            dir: self.dir.clone(),
+            request: Default::default(),
+        })
+    }
+}
+
+struct RecordingExtension {
+    dir: Arc<Path>,
+    request: Mutex<Option<(String, Option<String>, Variables)>>,
+}
+
+impl RecordingExtension {
+    fn write(&self, fixture: &Fixture) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let data = serde_json::to_vec_pretty(fixture)?;
+        fs::write(self.dir.join(fixture.file_name()), data)
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for RecordingExtension {
+    async fn request(&self, ctx: &ExtensionContext<'_>, next: NextRequest<'_>) -> Response {
+        let resp = next.run(ctx).await;
+        let request = self.request.lock().unwrap().take();
+        if let Some((query, operation_name, variables)) = request {
+            if let Ok(response) = serde_json::to_value(&resp) {
+                // A fixture that can not be written must not fail the request.
+                let _ = self.write(&Fixture {
+                    query,
+                    operation_name,
+                    variables,
+                    response,
+                });
+            }
+        }
+        resp
+    }
+
+    async fn prepare_request(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        request: Request,
+        next: NextPrepareRequest<'_>,
+    ) -> ServerResult<Request> {
+        *self.request.lock().unwrap() = Some((
+            request.query.clone(),
+            request.operation_name.clone(),
+            request.variables.clone(),
+        ));
+        next.run(ctx, request).await
+    }
+}
+
+/// A replayed fixture whose response differs from the recorded one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayMismatch {
+    /// The path of the fixture.
+    pub path: PathBuf,
+
+    /// The recorded response.
+    pub expected: serde_json::Value,
+
+    /// The response of the schema.
+    pub actual: serde_json::Value,
+}
+
+/// Execute the fixtures in `dir`, which were written by the [`Recording`] extension, against the
+/// schema, and return the fixtures whose responses differ from the recorded ones.
+///
+/// Fails if the directory or one of its `.json` files can not be read as fixtures.
+pub async fn replay<Query, Mutation, Subscription>(
+    schema: &Schema<Query, Mutation, Subscription>,
+    dir: impl AsRef<Path>,
+) -> io::Result<Vec<ReplayMismatch>>
+where
+    Query: ObjectType + 'static,
+    Mutation: ObjectType + 'static,
+    Subscription: SubscriptionType + 'static,
+{
+    let mut paths = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    paths.retain(|path| {
+        path.extension()
+            .map(|ext| ext == "json")
+            .unwrap_or_default()
+    });
+    paths.sort();
+
+    let mut mismatches = Vec::new();
+    for path in paths {
+        let fixture: Fixture = serde_json::from_slice(&fs::read(&path)?)?;
+        let mut request = Request::new(fixture.query).variables(fixture.variables);
+        if let Some(operation_name) = fixture.operation_name {
+            request = request.operation_name(operation_name);
+        }
+        let actual = serde_json::to_value(&schema.execute(request).await)?;
+        if actual != fixture.response {
+            mismatches.push(ReplayMismatch {
+                path,
+                expected: fixture.response,
+                actual,
+            });
+        }
+    }
+    Ok(mismatches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[tokio::test]
+    async fn test_record_and_replay() {
+        struct Query;
+
+        #[Object(internal)]
+        impl Query {
+            async fn value(&self, n: i32) -> i32 {
+                n
+            }
+        }
+
+        struct ChangedQuery;
+
+        #[Object(internal, name = "Query")]
+        impl ChangedQuery {
+            async fn value(&self, n: i32) -> i32 {
+                n * 2
+            }
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(Recording::new(dir.path()))
+            .finish();
+        let query = "query Value($n: Int!) { value(n: $n) }";
+        for n in [1, 1, 0] {
+            schema
+                .execute(
+                    Request::new(query).variables(Variables::from_json(serde_json::json!({
+                        "n": n
+                    }))),
+                )
+                .await;
+        }
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+
+        let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+        assert!(replay(&schema, dir.path()).await.unwrap().is_empty());
+
+        let schema = Schema::new(ChangedQuery, EmptyMutation, EmptySubscription);
+        let mismatches = replay(&schema, dir.path()).await.unwrap();
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(
+            mismatches[0].expected,
+            serde_json::json!({ "data": { "value": 1 } })
+        );
+        assert_eq!(
+            mismatches[0].actual,
+            serde_json::json!({ "data": { "value": 2 } })
+        );
+    }
+}
@@ -0,0 +1,343 @@
+//! Response cache extension.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::extensions::{
+    Extension, ExtensionContext, ExtensionFactory, NextExecute, NextPrepareRequest,
+};
+use crate::parser::types::OperationType;
+use crate::{CacheControl, Request, Response, ServerResult, Value};
+
+/// A cached response.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedResponse {
+    /// The data of the response.
+    pub data: Value,
+
+    /// The cache control of the response.
+    pub cache_control: CacheControl,
+}
+
+/// Cache storage for responses.
+#[async_trait::async_trait]
+pub trait CacheStorage: Send + Sync + 'static {
+    /// Load the response by `key`.
+    async fn get(&self, key: &str) -> Option<CachedResponse>;
+
+    /// Save the response by `key`, which expires after `ttl`.
+    async fn set(&self, key: String, response: CachedResponse, ttl: Duration);
+}
+
+/// Memory-based cache, which removes the responses when they expire.
+#[derive(Default)]
+pub struct MemoryCacheStorage(Mutex<HashMap<String, (Instant, CachedResponse)>>);
+
+impl MemoryCacheStorage {
+    /// Creates a new memory cache.
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+#[async_trait::async_trait]
+impl CacheStorage for MemoryCacheStorage {
+    async fn get(&self, key: &str) -> Option<CachedResponse> {
+        let mut cache = self.0.lock().unwrap();
+        match cache.get(key) {
+            Some((expires_at, response)) if *expires_at > Instant::now() => Some(response.clone()),
+            Some(_) => {
+                cache.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    async fn set(&self, key: String, response: CachedResponse, ttl: Duration) {
+        let mut cache = self.0.lock().unwrap();
+        let now = Instant::now();
+        cache.retain(|_, (expires_at, _)| *expires_at > now);
+        cache.insert(key, (now + ttl, response));
+    }
+}
+
+type SessionKeyFn = dyn Fn(&ExtensionContext<'_>) -> Option<String> + Send + Sync;
+
+/// Response cache extension.
+///
+/// Caches the responses of queries for the `max_age` of their [`CacheControl`], keyed by the
+/// query, the operation name and the variables. Responses with errors and the responses of
+/// mutations are never cached.
+///
+/// Public responses are shared by all requests. Private responses are only cached if a session
+/// key is set with [`ResponseCache::session_key`], and are only shared by the requests of the same
+/// session.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::extensions::response_cache::{MemoryCacheStorage, ResponseCache};
+///
+/// struct Query;
+///
+/// #[Object(cache_control(max_age = 60))]
+/// impl Query {
+///     async fn value(&self) -> i32 {
+///         100
+///     }
+/// }
+///
+/// struct UserId(String);
+///
+/// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///     .extension(
+///         ResponseCache::new(MemoryCacheStorage::new())
+///             .session_key(|ctx| ctx.data_opt::<UserId>().map(|id| id.0.clone())),
+///     )
+///     .finish();
+/// ```
+pub struct ResponseCache {
+    storage: Arc<dyn CacheStorage>,
+    session_key: Option<Arc<SessionKeyFn>>,
+}
+
+impl ResponseCache {
+    /// Creates a response cache extension with the storage.
+    pub fn new(storage: impl CacheStorage) -> Self {
+        Self {
+            storage: Arc::new(storage),
+            session_key: None,
+        }
+    }
+
+    /// Set the function that returns the session key of a request, for example the id of the
+    /// current user.
+    #[must_use]
+    pub fn session_key(
+        self,
+        f: impl Fn(&ExtensionContext<'_>) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            session_key: Some(Arc::new(f)),
+            ..self
+        }
+    }
+}
+
+impl ExtensionFactory for ResponseCache {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(ResponseCacheExtension {
+            storage: self.storage.clone(),
+            session_key: self.session_key.clone(),
+            request_key: Mutex::new(None),
+        })
+    }
+}
+
+struct ResponseCacheExtension {
+    storage: Arc<dyn CacheStorage>,
+    session_key: Option<Arc<SessionKeyFn>>,
+    request_key: Mutex<Option<String>>,
+}
+
+#[async_trait::async_trait]
+impl Extension for ResponseCacheExtension {
+    async fn prepare_request(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        request: Request,
+        next: NextPrepareRequest<'_>,
+    ) -> ServerResult<Request> {
+        let request = next.run(ctx, request).await?;
+        *self.request_key.lock().unwrap() = Some(format!(
+            "{}\n{}\n{}",
+            request.operation_name.as_deref().unwrap_or_default(),
+            serde_json::to_string(&request.variables).unwrap_or_default(),
+            request.query
+        ));
+        Ok(request)
+    }
+
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        let request_key = self.request_key.lock().unwrap().take();
+        let request_key = match request_key {
+            Some(request_key) if ctx.operation_type() == Some(OperationType::Query) => request_key,
+            _ => return next.run(ctx, operation_name).await,
+        };
+        let public_key = format!("public\n{}", request_key);
+        let private_key = self
+            .session_key
+            .as_ref()
+            .and_then(|f| f(ctx))
+            .map(|session_key| format!("private\n{}\n{}", session_key, request_key));
+
+        let mut cached = self.storage.get(&public_key).await;
+        if cached.is_none() {
+            if let Some(private_key) = &private_key {
+                cached = self.storage.get(private_key).await;
+            }
+        }
+        if let Some(cached) = cached {
+            return Response::new(cached.data).cache_control(cached.cache_control);
+        }
+
+        let resp = next.run(ctx, operation_name).await;
+        let cache_control = resp.cache_control;
+        let key = if cache_control.public {
+            Some(public_key)
+        } else {
+            private_key
+        };
+        if let Some(key) = key.filter(|_| resp.is_ok() && cache_control.max_age > 0) {
+            self.storage
+                .set(
+                    key,
+                    CachedResponse {
+                        data: resp.data.clone(),
+                        cache_control,
+                    },
+                    Duration::from_secs(cache_control.max_age as u64),
+                )
+                .await;
+        }
+        resp
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicI32, Ordering};
+
+    use super::*;
+    use crate::*;
+
+    #[tokio::test]
+    async fn test_response_cache() {
+        struct Counter(AtomicI32);
+
+        struct UserId(i32);
+
+        struct Query;
+
+        #[Object(internal)]
+        impl Query {
+            #[graphql(cache_control(max_age = 60))]
+            async fn public_value(&self, ctx: &Context<'_>, n: i32) -> i32 {
+                ctx.data_unchecked::<Counter>()
+                    .0
+                    .fetch_add(1, Ordering::SeqCst)
+                    + n
+            }
+
+            #[graphql(cache_control(max_age = 60, private))]
+            async fn private_value(&self, ctx: &Context<'_>) -> i32 {
+                ctx.data_unchecked::<Counter>()
+                    .0
+                    .fetch_add(1, Ordering::SeqCst)
+            }
+
+            async fn uncached_value(&self, ctx: &Context<'_>) -> i32 {
+                ctx.data_unchecked::<Counter>()
+                    .0
+                    .fetch_add(1, Ordering::SeqCst)
+            }
+        }
+
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .data(Counter(AtomicI32::new(0)))
+            .extension(
+                ResponseCache::new(MemoryCacheStorage::new())
+                    .session_key(|ctx| ctx.data_opt::<UserId>().map(|id| id.0.to_string())),
+            )
+            .finish();
+
+        let execute = |query: &'static str, user_id: Option<i32>| {
+            let schema = schema.clone();
+            async move {
+                let mut request = Request::new(query);
+                if let Some(user_id) = user_id {
+                    request = request.data(UserId(user_id));
+                }
+                let resp = schema.execute(request).await;
+                assert!(resp.is_ok());
+                (resp.data, resp.cache_control)
+            }
+        };
+
+        // Public responses are shared.
+        let (data, cache_control) = execute("{ publicValue(n: 0) }", None).await;
+        assert_eq!(data, value!({ "publicValue": 0 }));
+        assert_eq!(
+            cache_control,
+            CacheControl {
+                public: true,
+                max_age: 60
+            }
+        );
+        let (data, cache_control) = execute("{ publicValue(n: 0) }", Some(1)).await;
+        assert_eq!(data, value!({ "publicValue": 0 }));
+        assert_eq!(cache_control.max_age, 60);
+        assert_eq!(
+            execute("{ publicValue(n: 10) }", None).await.0,
+            value!({ "publicValue": 11 })
+        );
+
+        // Private responses are cached per session.
+        assert_eq!(
+            execute("{ privateValue }", None).await.0,
+            value!({ "privateValue": 2 })
+        );
+        assert_eq!(
+            execute("{ privateValue }", None).await.0,
+            value!({ "privateValue": 3 })
+        );
+        assert_eq!(
+            execute("{ privateValue }", Some(1)).await.0,
+            value!({ "privateValue": 4 })
+        );
+        assert_eq!(
+            execute("{ privateValue }", Some(1)).await.0,
+            value!({ "privateValue": 4 })
+        );
+        assert_eq!(
+            execute("{ privateValue }", Some(2)).await.0,
+            value!({ "privateValue": 5 })
+        );
+
+        // Responses without max age are not cached.
+        assert_eq!(
+            execute("{ uncachedValue }", None).await.0,
+            value!({ "uncachedValue": 6 })
+        );
+        assert_eq!(
+            execute("{ uncachedValue }", None).await.0,
+            value!({ "uncachedValue": 7 })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_memory_cache_storage_expires() {
+        let storage = MemoryCacheStorage::new();
+        let response = CachedResponse {
+            data: value!(1),
+            cache_control: Default::default(),
+        };
+        storage
+            .set("a".to_string(), response.clone(), Duration::from_secs(60))
+            .await;
+        storage
+            .set("b".to_string(), response.clone(), Duration::from_secs(0))
+            .await;
+        assert_eq!(storage.get("a").await, Some(response));
+        assert_eq!(storage.get("b").await, None);
+        assert_eq!(storage.get("c").await, None);
+    }
+}
@@ -52,13 +52,26 @@ struct TracingExtension;
 #[async_trait::async_trait]
 impl Extension for TracingExtension {
     async fn request(&self, ctx: &ExtensionContext<'_>, next: NextRequest<'_>) -> Response {
-        next.run(ctx)
-            .instrument(span!(
-                target: "async_graphql::graphql",
-                Level::INFO,
-                "request",
-            ))
-            .await
+        let span = span!(
+            target: "async_graphql::graphql",
+            Level::INFO,
+            "request",
+            operation_type = tracinglib::field::Empty,
+            operation_name = tracinglib::field::Empty,
+        );
+        async move {
+            let resp = next.run(ctx).await;
+            let span = tracinglib::Span::current();
+            if let Some(ty) = ctx.operation_type() {
+                span.record("operation_type", tracinglib::field::display(ty));
+            }
+            if let Some(name) = ctx.operation_name() {
+                span.record("operation_name", name);
+            }
+            resp
+        }
+        .instrument(span)
+        .await
     }
 
     fn subscribe<'s>(
@@ -122,7 +135,9 @@ impl Extension for TracingExtension {
         let span = span!(
             target: "async_graphql::graphql",
             Level::INFO,
-            "execute"
+            "execute",
+            operation_type = ctx.operation_type().map(tracinglib::field::display),
+            operation_name = operation_name,
         );
         next.run(ctx, operation_name).instrument(span).await
     }
@@ -0,0 +1,182 @@
+//! C ABI to execute a schema from other languages
+//!
+//! A schema is generic over its root types, so the library embedding it creates the
+//! [`SchemaHandle`] in its own exported function. The host language then executes requests with
+//! [`async_graphql_execute`], and frees the responses and the handle with
+//! [`async_graphql_free_string`] and [`async_graphql_free_schema`].
+//!
+//! The requests are executed on the calling thread, which is blocked until the response is ready,
+//! so the resolvers must not depend on the runtime of a specific executor, such as the timers of
+//! `tokio`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use async_graphql::ffi::SchemaHandle;
+//! use async_graphql::*;
+//!
+//! struct Query;
+//!
+//! #[Object]
+//! impl Query {
+//!     async fn add(&self, a: i32, b: i32) -> i32 {
+//!         a + b
+//!     }
+//! }
+//!
+//! #[no_mangle]
+//! pub extern "C" fn my_schema_new() -> *mut SchemaHandle {
+//!     SchemaHandle::new(Schema::new(Query, EmptyMutation, EmptySubscription)).into_raw()
+//! }
+//! ```
+//!
+//! From C:
+//!
+//! ```c
+//! SchemaHandle *schema = my_schema_new();
+//! char *response = async_graphql_execute(schema, "{\"query\": \"{ add(a: 1, b: 2) }\"}");
+//! /* response is {"data":{"add":3}} */
+//! async_graphql_free_string(response);
+//! async_graphql_free_schema(schema);
+//! ```
+
+#![allow(unsafe_code)]
+
+use std::ffi::{CStr, CString};
+use std::future::Future;
+use std::os::raw::c_char;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+use futures_util::future::{FutureExt, LocalBoxFuture};
+
+use crate::{ObjectType, Schema, SubscriptionType};
+
+type ExecuteFn =
+    dyn Fn(serde_json::Value) -> LocalBoxFuture<'static, serde_json::Value> + Send + Sync;
+
+/// A schema whose root types are erased, so that it can be passed through the C ABI.
+pub struct SchemaHandle {
+    execute: Box<ExecuteFn>,
+}
+
+impl SchemaHandle {
+    /// Create a handle to the schema.
+    pub fn new<Query, Mutation, Subscription>(schema: Schema<Query, Mutation, Subscription>) -> Self
+    where
+        Query: ObjectType + 'static,
+        Mutation: ObjectType + 'static,
+        Subscription: SubscriptionType + 'static,
+    {
+        Self {
+            execute: Box::new(move |request| {
+                let schema = schema.clone();
+                async move { schema.execute_json(&request).await }.boxed_local()
+            }),
+        }
+    }
+
+    /// Move the handle to the heap and returns a pointer to it, which must be freed with
+    /// [`async_graphql_free_schema`].
+    pub fn into_raw(self) -> *mut SchemaHandle {
+        Box::into_raw(Box::new(self))
+    }
+
+    /// Execute a request in the JSON format on the current thread, and returns the response in
+    /// the JSON format.
+    ///
+    /// See [`Schema::execute_json`].
+    pub fn execute(&self, request: &str) -> String {
+        let resp = match serde_json::from_str(request) {
+            Ok(request) => block_on((self.execute)(request)),
+            Err(err) => serde_json::json!({
+                "errors": [{ "message": format!("Invalid request: {}", err) }]
+            }),
+        };
+        resp.to_string()
+    }
+}
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+fn block_on<F: Future>(fut: F) -> F::Output {
+    futures_util::pin_mut!(fut);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        match fut.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+/// Execute a request in the JSON format, and returns the response in the JSON format, which must
+/// be freed with [`async_graphql_free_string`].
+///
+/// Returns a null pointer if `schema` or `request` is null. A panic of a resolver does not unwind
+/// into the caller, and is returned as an error response instead.
+///
+/// # Safety
+///
+/// `schema` must be a pointer returned by [`SchemaHandle::into_raw`] that has not been freed, and
+/// `request` must be a nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn async_graphql_execute(
+    schema: *const SchemaHandle,
+    request: *const c_char,
+) -> *mut c_char {
+    if schema.is_null() || request.is_null() {
+        return std::ptr::null_mut();
+    }
+    let schema = &*schema;
+    let request = CStr::from_ptr(request);
+    let resp = panic::catch_unwind(AssertUnwindSafe(|| match request.to_str() {
+        Ok(request) => schema.execute(request),
+        Err(_) => serde_json::json!({
+            "errors": [{ "message": "Invalid request: the request is not valid UTF-8." }]
+        })
+        .to_string(),
+    }))
+    .unwrap_or_else(|_| {
+        serde_json::json!({
+            "errors": [{ "message": "Internal error: the execution panicked." }]
+        })
+        .to_string()
+    });
+    // A JSON document never contains a nul byte, which is escaped in strings.
+    CString::new(resp).unwrap_or_default().into_raw()
+}
+
+/// Free a string returned by [`async_graphql_execute`].
+///
+/// # Safety
+///
+/// `s` must be null, or a pointer returned by [`async_graphql_execute`] that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn async_graphql_free_string(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// Free a schema handle returned by [`SchemaHandle::into_raw`].
+///
+/// # Safety
+///
+/// `schema` must be null, or a pointer returned by [`SchemaHandle::into_raw`] that has not been
+/// freed.
+#[no_mangle]
+pub unsafe extern "C" fn async_graphql_free_schema(schema: *mut SchemaHandle) {
+    if !schema.is_null() {
+        drop(Box::from_raw(schema));
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use crate::registry::{MetaType, Registry};
+
+/// Counts how many times each field of the schema has been resolved.
+///
+/// The counters are created when the schema is built, so counting a field is a lookup and an
+/// atomic increment, without any lock. A field selected on an object also counts as a usage of
+/// the same field of the interfaces it implements.
+pub(crate) struct FieldUsage {
+    counters: BTreeMap<String, Arc<AtomicU64>>,
+    fields: HashMap<String, HashMap<String, Vec<Arc<AtomicU64>>>>,
+}
+
+impl FieldUsage {
+    pub(crate) fn new(registry: &Registry) -> Self {
+        let mut counters = BTreeMap::new();
+        for ty in registry.types.values() {
+            let fields = match ty {
+                MetaType::Object { fields, .. } | MetaType::Interface { fields, .. } => fields,
+                _ => continue,
+            };
+            if ty.name().starts_with("__") {
+                continue;
+            }
+            for field in fields.values() {
+                if !field.name.starts_with("__") {
+                    counters.insert(format!("{}.{}", ty.name(), field.name), Default::default());
+                }
+            }
+        }
+
+        let mut fields: HashMap<String, HashMap<String, Vec<Arc<AtomicU64>>>> = HashMap::new();
+        for (type_name, ty) in &registry.types {
+            if let MetaType::Object {
+                fields: type_fields,
+                ..
+            }
+            | MetaType::Interface {
+                fields: type_fields,
+                ..
+            } = ty
+            {
+                let interfaces = registry.implements.get(type_name);
+                for field in type_fields.values() {
+                    let field_counters = std::iter::once(type_name)
+                        .chain(interfaces.into_iter().flatten())
+                        .filter_map(|ty| counters.get(&format!("{}.{}", ty, field.name)))
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    if !field_counters.is_empty() {
+                        fields
+                            .entry(type_name.clone())
+                            .or_default()
+                            .insert(field.name.clone(), field_counters);
+                    }
+                }
+            }
+        }
+
+        Self { counters, fields }
+    }
+
+    pub(crate) fn increment(&self, type_name: &str, field_name: &str) {
+        if let Some(counters) = self
+            .fields
+            .get(type_name)
+            .and_then(|fields| fields.get(field_name))
+        {
+            for counter in counters {
+                counter.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    pub(crate) fn snapshot(&self) -> FieldUsageSnapshot {
+        FieldUsageSnapshot {
+            counts: self
+                .counters
+                .iter()
+                .map(|(coordinate, counter)| (coordinate.clone(), counter.load(Ordering::Relaxed)))
+                .collect(),
+        }
+    }
+}
+
+/// A snapshot of the field usage counters of a schema.
+///
+/// See [`SchemaBuilder::enable_field_usage`](crate::SchemaBuilder::enable_field_usage).
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct FieldUsageSnapshot {
+    /// The number of times each field has been resolved, keyed by its schema coordinate, such as
+    /// `User.name`.
+    pub counts: BTreeMap<String, u64>,
+}
+
+impl FieldUsageSnapshot {
+    /// Returns the number of times the field with the specified schema coordinate has been
+    /// resolved.
+    pub fn count(&self, coordinate: &str) -> u64 {
+        self.counts.get(coordinate).copied().unwrap_or_default()
+    }
+
+    /// Returns the schema coordinates of the fields that have never been resolved.
+    pub fn unused_fields(&self) -> impl Iterator<Item = &str> {
+        self.counts
+            .iter()
+            .filter(|(_, count)| **count == 0)
+            .map(|(coordinate, _)| coordinate.as_str())
+    }
+}
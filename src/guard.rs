@@ -1,5 +1,7 @@
 //! Field guards
 
+use std::collections::BTreeSet;
+
 use crate::{Context, Result};
 
 /// Field guard
@@ -51,3 +53,64 @@ impl<A: Guard + Send + Sync, B: Guard + Send + Sync> Guard for Or<A, B> {
         self.1.check(ctx).await
     }
 }
+
+/// The scopes granted to a request, such as the scopes of an OAuth access token.
+///
+/// Put the scopes in the data of the request, and check them in the resolvers with
+/// [`Context::require_scope`](crate::ContextBase::require_scope).
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::guard::Scopes;
+/// use async_graphql::*;
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn orders(&self, ctx: &Context<'_>) -> Result<Vec<i32>> {
+///         ctx.require_scope("orders:read")?;
+///         Ok(vec![1, 2, 3])
+///     }
+/// }
+///
+/// tokio::runtime::Runtime::new().unwrap().block_on(async move {
+///     let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+///     let request =
+///         Request::new("{ orders }").data(Scopes::from_space_separated("orders:read users:read"));
+///     assert!(schema.execute(request).await.is_ok());
+///     assert!(schema.execute("{ orders }").await.is_err());
+/// });
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Scopes(BTreeSet<String>);
+
+impl Scopes {
+    /// Create the scopes from a space-separated list, such as the `scope` claim of an OAuth
+    /// access token.
+    pub fn from_space_separated(scopes: &str) -> Self {
+        scopes.split_whitespace().collect()
+    }
+
+    /// Grant a scope.
+    pub fn insert(&mut self, scope: impl Into<String>) {
+        self.0.insert(scope.into());
+    }
+
+    /// Returns `true` if the scope is granted.
+    pub fn contains(&self, scope: &str) -> bool {
+        self.0.contains(scope)
+    }
+
+    /// Returns an iterator over the granted scopes, in sorted order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+}
+
+impl<S: Into<String>> FromIterator<S> for Scopes {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        Self(iter.into_iter().map(Into::into).collect())
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+/// Generate the page for the Altair GraphQL client.
+///
+/// The assets of Altair are loaded from a CDN with a `<base>` element, so the endpoints must be
+/// absolute URLs or paths, such as `/graphql`.
+///
+/// # Example
+///
+/// ```rust
+/// use async_graphql::http::*;
+///
+/// altair_source(
+///     AltairConfig::new("/graphql")
+///         .subscriptions_endpoint("ws://localhost:8000/ws")
+///         .initial_query("{ value }")
+///         .with_header("Authorization", "Bearer token"),
+/// );
+/// ```
+pub fn altair_source(config: AltairConfig) -> String {
+    r#"<!DOCTYPE html>
+<html>
+  <head>
+    <meta charset="utf-8" />
+    <title>Altair</title>
+    <base href="https://cdn.jsdelivr.net/npm/altair-static@5/build/dist/" />
+    <meta name="viewport" content="width=device-width, initial-scale=1" />
+    <link rel="icon" type="image/x-icon" href="favicon.ico" />
+    <link rel="stylesheet" href="styles.css" />
+  </head>
+  <body>
+    <app-root>
+      <div class="loading-screen styled">
+        <div class="loading-screen-inner">
+          <div class="loading-screen-logo-container">
+            <img src="assets/img/logo_350.svg" alt="Altair" />
+          </div>
+          <div class="loading-screen-loading-indicator">
+            <span class="loading-indicator-dot"></span>
+            <span class="loading-indicator-dot"></span>
+            <span class="loading-indicator-dot"></span>
+          </div>
+        </div>
+      </div>
+    </app-root>
+    <script type="text/javascript" src="runtime.js"></script>
+    <script type="text/javascript" src="polyfills.js"></script>
+    <script type="text/javascript" src="main.js"></script>
+    <script>
+      document.addEventListener('DOMContentLoaded', function () {
+        AltairGraphQL.init(ALTAIR_CONFIG);
+      });
+    </script>
+  </body>
+</html>
+"#
+    .replace(
+        "ALTAIR_CONFIG",
+        &serde_json::to_string(&config)
+            .unwrap_or_else(|_| "{}".to_string())
+            .replace("</", "<\\/"),
+    )
+}
+
+/// Config for the Altair GraphQL client
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AltairConfig<'a> {
+    #[serde(rename = "endpointURL")]
+    endpoint_url: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    subscriptions_endpoint: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    initial_query: Option<&'a str>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    initial_headers: HashMap<&'a str, &'a str>,
+}
+
+impl<'a> AltairConfig<'a> {
+    /// Create a config for the Altair GraphQL client.
+    pub fn new(endpoint: &'a str) -> Self {
+        Self {
+            endpoint_url: endpoint,
+            subscriptions_endpoint: None,
+            initial_query: None,
+            initial_headers: Default::default(),
+        }
+    }
+
+    /// Set subscriptions endpoint, for example: `ws://localhost:8000/ws`.
+    #[must_use]
+    pub fn subscriptions_endpoint(mut self, endpoint: &'a str) -> Self {
+        self.subscriptions_endpoint = Some(endpoint);
+        self
+    }
+
+    /// Set the query shown when the page is opened.
+    #[must_use]
+    pub fn initial_query(mut self, query: &'a str) -> Self {
+        self.initial_query = Some(query);
+        self
+    }
+
+    /// Set HTTP header sent with the queries.
+    #[must_use]
+    pub fn with_header(mut self, name: &'a str, value: &'a str) -> Self {
+        self.initial_headers.insert(name, value);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_altair_source() {
+        let source = altair_source(
+            AltairConfig::new("/graphql")
+                .subscriptions_endpoint("ws://localhost:8000/ws")
+                .initial_query("{ value }")
+                .with_header("Authorization", "Bearer </script>"),
+        );
+        let config = source
+            .split("AltairGraphQL.init(")
+            .nth(1)
+            .and_then(|s| s.split(");").next())
+            .unwrap();
+        assert!(!config.contains("</"));
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(config).unwrap(),
+            serde_json::json!({
+                "endpointURL": "/graphql",
+                "subscriptionsEndpoint": "ws://localhost:8000/ws",
+                "initialQuery": "{ value }",
+                "initialHeaders": { "Authorization": "Bearer </script>" },
+            })
+        );
+
+        let source = altair_source(AltairConfig::new("/graphql"));
+        assert!(source.contains(r#"AltairGraphQL.init({"endpointURL":"/graphql"});"#));
+    }
+}
@@ -0,0 +1,108 @@
+/// Generate the page for GraphiQL 2.
+///
+/// The subscriptions are sent to `subscription_endpoint` with the `graphql-ws` protocol.
+pub fn graphiql_source_v2(
+    graphql_endpoint_url: &str,
+    subscription_endpoint: Option<&str>,
+) -> String {
+    r#"<!DOCTYPE html>
+<html lang="en">
+  <head>
+    <meta charset="utf-8" />
+    <meta name="viewport" content="width=device-width, initial-scale=1" />
+    <title>GraphiQL</title>
+    <style>
+      body {
+        height: 100%;
+        margin: 0;
+        width: 100%;
+        overflow: hidden;
+      }
+
+      #graphiql {
+        height: 100vh;
+      }
+    </style>
+    <script crossorigin src="https://unpkg.com/react@18/umd/react.production.min.js"></script>
+    <script crossorigin src="https://unpkg.com/react-dom@18/umd/react-dom.production.min.js"></script>
+    <script crossorigin src="https://unpkg.com/graphql-ws@5/umd/graphql-ws.min.js"></script>
+    <link rel="stylesheet" href="https://unpkg.com/graphiql@2/graphiql.min.css" />
+  </head>
+  <body>
+    <div id="graphiql">Loading...</div>
+    <script crossorigin src="https://unpkg.com/graphiql@2/graphiql.min.js"></script>
+    <script>
+      var subscriptionUrl = GRAPHQL_SUBSCRIPTION_URL;
+      var fetcher = GraphiQL.createFetcher({
+        url: GRAPHQL_URL,
+        wsClient: subscriptionUrl ? graphqlWs.createClient({ url: subscriptionUrl }) : undefined,
+      });
+
+      ReactDOM.createRoot(document.getElementById('graphiql')).render(
+        React.createElement(GraphiQL, {
+          fetcher: fetcher,
+          defaultEditorToolsVisibility: true,
+        }),
+      );
+    </script>
+  </body>
+</html>
+"#
+    .replace("GRAPHQL_URL", &js_string(graphql_endpoint_url))
+    .replace(
+        "GRAPHQL_SUBSCRIPTION_URL",
+        &match subscription_endpoint {
+            Some(url) => js_string(url),
+            None => "null".to_string(),
+        },
+    )
+}
+
+/// Generate the page for the Apollo Sandbox.
+pub fn apollo_sandbox_source(graphql_endpoint_url: &str) -> String {
+    r#"<!DOCTYPE html>
+<html lang="en">
+  <head>
+    <meta charset="utf-8" />
+    <title>Apollo Sandbox</title>
+  </head>
+  <body style="margin: 0; overflow-x: hidden; overflow-y: hidden">
+    <div style="width: 100%; height: 100vh;" id="sandbox"></div>
+    <script src="https://embeddable-sandbox.cdn.apollographql.com/_latest/embeddable-sandbox.umd.production.min.js"></script>
+    <script>
+      new window.EmbeddedSandbox({
+        target: '#sandbox',
+        initialEndpoint: GRAPHQL_URL,
+      });
+    </script>
+  </body>
+</html>
+"#
+    .replace("GRAPHQL_URL", &js_string(graphql_endpoint_url))
+}
+
+/// Encode an URL as a JavaScript string literal.
+fn js_string(s: &str) -> String {
+    serde_json::to_string(s).unwrap().replace("</", "<\\/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_graphiql_source_v2() {
+        let source = graphiql_source_v2("/graphql", Some("ws://localhost:8000/ws"));
+        assert!(source.contains(r#"url: "/graphql","#));
+        assert!(source.contains(r#"var subscriptionUrl = "ws://localhost:8000/ws";"#));
+
+        let source = graphiql_source_v2("/graphql", None);
+        assert!(source.contains("var subscriptionUrl = null;"));
+    }
+
+    #[test]
+    fn test_apollo_sandbox_source() {
+        let source = apollo_sandbox_source("http://localhost:8000/'</script>");
+        assert!(source.contains(r#"initialEndpoint: "http://localhost:8000/'<\/script>","#));
+    }
+}
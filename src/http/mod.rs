@@ -1,19 +1,30 @@
 //! A helper module that supports HTTP
 
+mod altair_source;
 mod graphiql_source;
+mod graphiql_v2_source;
 mod multipart;
+mod multipart_mixed;
 mod playground_source;
+mod response_encoding;
+mod sse;
 mod websocket;
 
+pub use altair_source::{altair_source, AltairConfig};
 pub use graphiql_source::graphiql_source;
+pub use graphiql_v2_source::{apollo_sandbox_source, graphiql_source_v2};
 pub use multipart::MultipartOptions;
+pub use multipart_mixed::{create_multipart_mixed_stream, MULTIPART_MIXED_CONTENT_TYPE};
 pub use playground_source::{playground_source, GraphQLPlaygroundConfig};
+pub use response_encoding::ResponseEncoding;
+pub use sse::{create_sse_stream, SSE_CONTENT_TYPE};
 pub use websocket::{
     ClientMessage, Protocols as WebSocketProtocols, WebSocket, WsMessage, ALL_WEBSOCKET_PROTOCOLS,
 };
 
 use futures_util::io::{AsyncRead, AsyncReadExt};
 use mime;
+use serde::Deserialize;
 
 use crate::{BatchRequest, ParseRequestError, Request};
 
@@ -57,28 +68,106 @@ pub async fn receive_batch_body(
         // cbor is in application/octet-stream.
         // Note: cbor will only match if feature ``cbor`` is active
         // TODO: wait for mime to add application/cbor and match against that too
-        _ => receive_batch_body_no_multipart(&content_type, body).await,
+        _ => receive_batch_body_no_multipart(&content_type, body, opts).await,
     }
 }
 
-/// Recieves a GraphQL query which is either cbor or json but NOT multipart
+/// Recieves a GraphQL query which is either cbor, urlencoded or json but NOT multipart
 /// This method is only to avoid recursive calls with [``receive_batch_body``] and [``multipart::receive_batch_multipart``]
 pub(super) async fn receive_batch_body_no_multipart(
     content_type: &mime::Mime,
     body: impl AsyncRead + Send,
+    opts: MultipartOptions,
 ) -> Result<BatchRequest, ParseRequestError> {
     assert_ne!(content_type.type_(), mime::MULTIPART, "received multipart");
-    match (content_type.type_(), content_type.subtype()) {
+    let data = read_body(body, opts.max_body_size).await?;
+    let batch_request = match (content_type.type_(), content_type.subtype()) {
         #[cfg(feature = "cbor")]
         // cbor is in application/octet-stream.
         // TODO: wait for mime to add application/cbor and match against that too
         (mime::OCTET_STREAM, _) | (mime::APPLICATION, mime::OCTET_STREAM) => {
-            receive_batch_cbor(body).await
+            receive_batch_cbor(data.as_slice()).await?
+        }
+        (mime::APPLICATION, mime::WWW_FORM_URLENCODED) => {
+            BatchRequest::Single(receive_urlencoded(data.as_slice()).await?)
         }
         // default to json
-        _ => receive_batch_json(body).await,
+        _ => receive_batch_json(data.as_slice()).await?,
+    };
+    match &batch_request {
+        BatchRequest::Single(request) => check_variables(request, &opts)?,
+        BatchRequest::Batch(requests) => {
+            if let Some(max_batch_size) = opts.max_batch_size {
+                if requests.len() > max_batch_size {
+                    return Err(ParseRequestError::BatchTooLarge(max_batch_size));
+                }
+            }
+            for request in requests {
+                check_variables(request, &opts)?;
+            }
+        }
+    }
+    Ok(batch_request)
+}
+
+pub(super) async fn read_body(
+    body: impl AsyncRead,
+    max_size: Option<usize>,
+) -> Result<Vec<u8>, ParseRequestError> {
+    let mut data = Vec::new();
+    futures_util::pin_mut!(body);
+    match max_size {
+        Some(max_size) => {
+            // Read one byte past the limit to detect an oversized body without buffering it.
+            body.take(max_size as u64 + 1)
+                .read_to_end(&mut data)
+                .await
+                .map_err(ParseRequestError::Io)?;
+            if data.len() > max_size {
+                return Err(ParseRequestError::PayloadTooLarge);
+            }
+        }
+        None => {
+            body.read_to_end(&mut data)
+                .await
+                .map_err(ParseRequestError::Io)?;
+        }
+    }
+    Ok(data)
+}
+
+fn check_variables(request: &Request, opts: &MultipartOptions) -> Result<(), ParseRequestError> {
+    if let Some(max_num_variables) = opts.max_num_variables {
+        if request.variables.len() > max_num_variables {
+            return Err(ParseRequestError::PayloadTooLarge);
+        }
     }
+
+    if let Some(max_variables_size) = opts.max_variables_size {
+        struct CountWriter(usize);
+
+        impl std::io::Write for CountWriter {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0 += buf.len();
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut writer = CountWriter(0);
+        serde_json::to_writer(&mut writer, &request.variables)
+            .map_err(|e| ParseRequestError::InvalidRequest(Box::new(e)))?;
+        if writer.0 > max_variables_size {
+            return Err(ParseRequestError::PayloadTooLarge);
+        }
+    }
+
+    Ok(())
 }
+
 /// Receive a GraphQL request from a body as JSON.
 pub async fn receive_json(body: impl AsyncRead) -> Result<Request, ParseRequestError> {
     receive_batch_json(body).await?.into_single()
@@ -95,6 +184,42 @@ pub async fn receive_batch_json(body: impl AsyncRead) -> Result<BatchRequest, Pa
         .map_err(|e| ParseRequestError::InvalidRequest(Box::new(e)))?)
 }
 
+/// Receive a GraphQL request from a body as `application/x-www-form-urlencoded`.
+///
+/// The `query` and `operationName` fields are plain strings, while `variables` and `extensions`
+/// are JSON encoded, the same as in a `GET` request.
+pub async fn receive_urlencoded(body: impl AsyncRead) -> Result<Request, ParseRequestError> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct UrlEncodedRequest {
+        #[serde(default)]
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<String>,
+        extensions: Option<String>,
+    }
+
+    let mut data = Vec::new();
+    futures_util::pin_mut!(body);
+    body.read_to_end(&mut data)
+        .await
+        .map_err(ParseRequestError::Io)?;
+    let form = serde_urlencoded::from_bytes::<UrlEncodedRequest>(&data)
+        .map_err(|e| ParseRequestError::InvalidRequest(Box::new(e)))?;
+
+    let mut request = Request::new(form.query);
+    request.operation_name = form.operation_name;
+    if let Some(variables) = form.variables.filter(|s| !s.is_empty()) {
+        request.variables = serde_json::from_str(&variables)
+            .map_err(|e| ParseRequestError::InvalidRequest(Box::new(e)))?;
+    }
+    if let Some(extensions) = form.extensions.filter(|s| !s.is_empty()) {
+        request.extensions = serde_json::from_str(&extensions)
+            .map_err(|e| ParseRequestError::InvalidRequest(Box::new(e)))?;
+    }
+    Ok(request)
+}
+
 /// Receive a GraphQL request from a body as CBOR.
 #[cfg(feature = "cbor")]
 #[cfg_attr(docsrs, doc(cfg(feature = "cbor")))]
@@ -114,3 +239,169 @@ pub async fn receive_batch_cbor(body: impl AsyncRead) -> Result<BatchRequest, Pa
     Ok(serde_cbor::from_slice::<BatchRequest>(&data)
         .map_err(|e| ParseRequestError::InvalidRequest(Box::new(e)))?)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    #[tokio::test]
+    async fn test_receive_urlencoded() {
+        let body = "query=query+Q%28%24id%3A+Int%21%29+%7B+user%28id%3A+%24id%29+%7D\
+                    &operationName=Q&variables=%7B%22id%22%3A10%7D";
+        let request = receive_body(
+            Some("application/x-www-form-urlencoded"),
+            body.as_bytes(),
+            MultipartOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(request.query, "query Q($id: Int!) { user(id: $id) }");
+        assert_eq!(request.operation_name.as_deref(), Some("Q"));
+        assert_eq!(request.variables.into_value(), value!({ "id": 10 }));
+
+        let request = receive_urlencoded("query=%7B+a+%7D&variables=".as_bytes())
+            .await
+            .unwrap();
+        assert_eq!(request.query, "{ a }");
+        assert!(request.variables.is_empty());
+
+        assert!(matches!(
+            receive_urlencoded("query=%7B+a+%7D&variables=abc".as_bytes()).await,
+            Err(ParseRequestError::InvalidRequest(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_receive_body_limits() {
+        let body = r#"{"query": "{ a }", "variables": {"a": 1, "b": "abcdef"}}"#;
+        let receive = |opts| receive_body(Some("application/json"), body.as_bytes(), opts);
+
+        assert!(receive(MultipartOptions::default()).await.is_ok());
+        assert!(
+            receive(MultipartOptions::default().max_body_size(body.len()))
+                .await
+                .is_ok()
+        );
+        assert!(matches!(
+            receive(MultipartOptions::default().max_body_size(body.len() - 1)).await,
+            Err(ParseRequestError::PayloadTooLarge)
+        ));
+
+        assert!(receive(MultipartOptions::default().max_num_variables(2))
+            .await
+            .is_ok());
+        assert!(matches!(
+            receive(MultipartOptions::default().max_num_variables(1)).await,
+            Err(ParseRequestError::PayloadTooLarge)
+        ));
+
+        // {"a":1,"b":"abcdef"}
+        assert!(receive(MultipartOptions::default().max_variables_size(20))
+            .await
+            .is_ok());
+        assert!(matches!(
+            receive(MultipartOptions::default().max_variables_size(19)).await,
+            Err(ParseRequestError::PayloadTooLarge)
+        ));
+
+        let batch_body = r#"[{"query": "{ a }"}, {"query": "{ a }", "variables": {"a": 1}}]"#;
+        assert!(matches!(
+            receive_batch_body(
+                Some("application/json"),
+                batch_body.as_bytes(),
+                MultipartOptions::default().max_num_variables(0),
+            )
+            .await,
+            Err(ParseRequestError::PayloadTooLarge)
+        ));
+
+        let receive_batch =
+            |opts| receive_batch_body(Some("application/json"), batch_body.as_bytes(), opts);
+        assert!(receive_batch(MultipartOptions::default().max_batch_size(2))
+            .await
+            .is_ok());
+        assert!(matches!(
+            receive_batch(MultipartOptions::default().max_batch_size(1)).await,
+            Err(ParseRequestError::BatchTooLarge(1))
+        ));
+    }
+
+    fn multipart_body(files: &[&str]) -> String {
+        let mut body = String::new();
+        body.push_str("--boundary\r\nContent-Disposition: form-data; name=\"operations\"\r\n\r\n");
+        body.push_str(&format!(
+            r#"{{ "query": "mutation ($files: [Upload!]!) {{ upload(files: $files) }}", "variables": {{ "files": [{}] }} }}"#,
+            vec!["null"; files.len()].join(",")
+        ));
+        body.push_str("\r\n--boundary\r\nContent-Disposition: form-data; name=\"map\"\r\n\r\n{");
+        for idx in 0..files.len() {
+            if idx > 0 {
+                body.push(',');
+            }
+            body.push_str(&format!(r#""{0}": ["variables.files.{0}"]"#, idx));
+        }
+        body.push('}');
+        for (idx, content) in files.iter().enumerate() {
+            body.push_str(&format!(
+                "\r\n--boundary\r\nContent-Disposition: form-data; name=\"{0}\"; filename=\"{0}.txt\"\r\nContent-Type: text/plain\r\n\r\n{1}",
+                idx, content
+            ));
+        }
+        body.push_str("\r\n--boundary--\r\n");
+        body
+    }
+
+    #[tokio::test]
+    async fn test_receive_multipart() {
+        use std::io::Read;
+
+        let body = multipart_body(&["abc", "abcdefgh"]);
+        let receive = |opts| {
+            receive_body(
+                Some("multipart/form-data; boundary=boundary"),
+                body.as_bytes(),
+                opts,
+            )
+        };
+
+        let request = receive(MultipartOptions::default().spool_threshold(4))
+            .await
+            .unwrap();
+        assert_eq!(request.uploads.len(), 2);
+        let mut contents = Vec::new();
+        for upload in request.uploads {
+            assert_eq!(upload.content_type.as_deref(), Some("text/plain"));
+            let in_memory = upload.content.is_in_memory();
+            let mut content = String::new();
+            upload.into_read().read_to_string(&mut content).unwrap();
+            contents.push((content, in_memory));
+        }
+        assert_eq!(
+            contents,
+            vec![("abc".to_string(), true), ("abcdefgh".to_string(), false)]
+        );
+
+        let request = receive(MultipartOptions::default()).await.unwrap();
+        assert!(request
+            .uploads
+            .iter()
+            .all(|upload| !upload.content.is_in_memory()));
+
+        assert!(receive(MultipartOptions::default().max_num_files(2))
+            .await
+            .is_ok());
+        assert!(matches!(
+            receive(MultipartOptions::default().max_num_files(1)).await,
+            Err(ParseRequestError::PayloadTooLarge)
+        ));
+        assert!(matches!(
+            receive(MultipartOptions::default().max_file_size(4)).await,
+            Err(ParseRequestError::PayloadTooLarge)
+        ));
+        assert!(matches!(
+            receive(MultipartOptions::default().max_body_size(20)).await,
+            Err(ParseRequestError::PayloadTooLarge)
+        ));
+    }
+}
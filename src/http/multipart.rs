@@ -1,16 +1,17 @@
 use std::collections::HashMap;
+use std::fs::File;
 use std::io::{self, Seek, SeekFrom, Write};
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use futures_util::io::AsyncRead;
-use futures_util::stream::Stream;
-use multer::{Constraints, Multipart, SizeLimit};
+use futures_util::stream::{Stream, TryStreamExt};
+use multer::{Constraints, Field, Multipart, SizeLimit};
 use pin_project_lite::pin_project;
 
-use crate::{BatchRequest, ParseRequestError, UploadValue};
+use crate::{BatchRequest, ParseRequestError, UploadContent, UploadValue};
 
-/// Options for `receive_multipart`.
+/// Options for `receive_body` and `receive_batch_body`.
 #[derive(Default, Clone, Copy)]
 #[non_exhaustive]
 pub struct MultipartOptions {
@@ -18,6 +19,18 @@ pub struct MultipartOptions {
     pub max_file_size: Option<usize>,
     /// The maximum number of files.
     pub max_num_files: Option<usize>,
+    /// The size above which an uploaded file is written to a temporary file instead of being
+    /// kept in memory.
+    pub spool_threshold: Option<usize>,
+    /// The maximum size of the request body, excluding uploaded files.
+    pub max_body_size: Option<usize>,
+    /// The maximum number of variables of a request.
+    pub max_num_variables: Option<usize>,
+    /// The maximum size of the variables of a request, measured as the length of their JSON
+    /// encoding.
+    pub max_variables_size: Option<usize>,
+    /// The maximum number of requests of a batch request.
+    pub max_batch_size: Option<usize>,
 }
 
 impl MultipartOptions {
@@ -36,6 +49,77 @@ impl MultipartOptions {
             ..self
         }
     }
+
+    /// Set the size above which an uploaded file is written to a temporary file.
+    ///
+    /// By default every uploaded file is written to a temporary file.
+    pub fn spool_threshold(self, size: usize) -> Self {
+        MultipartOptions {
+            spool_threshold: Some(size),
+            ..self
+        }
+    }
+
+    /// Set maximum size of the request body.
+    ///
+    /// For multipart requests this applies to the `operations` and `map` fields, the size of the
+    /// uploaded files is limited by [`max_file_size`](Self::max_file_size).
+    pub fn max_body_size(self, size: usize) -> Self {
+        MultipartOptions {
+            max_body_size: Some(size),
+            ..self
+        }
+    }
+
+    /// Set maximum number of variables of a request.
+    pub fn max_num_variables(self, n: usize) -> Self {
+        MultipartOptions {
+            max_num_variables: Some(n),
+            ..self
+        }
+    }
+
+    /// Set maximum size of the variables of a request.
+    pub fn max_variables_size(self, size: usize) -> Self {
+        MultipartOptions {
+            max_variables_size: Some(size),
+            ..self
+        }
+    }
+
+    /// Set maximum number of requests of a batch request.
+    pub fn max_batch_size(self, n: usize) -> Self {
+        MultipartOptions {
+            max_batch_size: Some(n),
+            ..self
+        }
+    }
+}
+
+/// Reads a field which is not a file, without buffering more than `max_size` bytes.
+async fn read_field(
+    field: Field<'_>,
+    max_size: Option<usize>,
+) -> Result<Vec<u8>, ParseRequestError> {
+    let body = field
+        .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+        .into_async_read();
+    super::read_body(body, max_size)
+        .await
+        .map_err(|err| match err {
+            // Keep the errors of the multipart stream, such as an exceeded size limit.
+            ParseRequestError::Io(err)
+                if matches!(err.get_ref(), Some(err) if err.is::<multer::Error>()) =>
+            {
+                (*err
+                    .into_inner()
+                    .unwrap()
+                    .downcast::<multer::Error>()
+                    .unwrap())
+                .into()
+            }
+            err => err,
+        })
 }
 
 pub(super) async fn receive_batch_multipart(
@@ -74,13 +158,14 @@ pub(super) async fn receive_batch_multipart(
             .clone();
         match field.name() {
             Some("operations") => {
-                let body = field.bytes().await?;
+                let body = read_field(field, opts.max_body_size).await?;
                 request = Some(
-                    super::receive_batch_body_no_multipart(&content_type, body.as_ref()).await?,
+                    super::receive_batch_body_no_multipart(&content_type, body.as_slice(), opts)
+                        .await?,
                 )
             }
             Some("map") => {
-                let map_bytes = field.bytes().await?;
+                let map_bytes = read_field(field, opts.max_body_size).await?;
 
                 match (content_type.type_(), content_type.subtype()) {
                     // cbor is in application/octet-stream.
@@ -110,13 +195,35 @@ pub(super) async fn receive_batch_multipart(
             _ => {
                 if let Some(name) = field.name().map(ToString::to_string) {
                     if let Some(filename) = field.file_name().map(ToString::to_string) {
+                        if matches!(opts.max_num_files, Some(max_num_files) if files.len() >= max_num_files)
+                        {
+                            return Err(ParseRequestError::PayloadTooLarge);
+                        }
                         let content_type = field.content_type().map(ToString::to_string);
-                        let mut file = tempfile::tempfile().map_err(ParseRequestError::Io)?;
+                        let mut data = Vec::new();
+                        let mut file: Option<File> = None;
                         while let Some(chunk) = field.chunk().await? {
-                            file.write(&chunk).map_err(ParseRequestError::Io)?;
+                            match &mut file {
+                                Some(file) => file.write_all(&chunk)?,
+                                None => {
+                                    data.extend_from_slice(&chunk);
+                                    if opts.spool_threshold.unwrap_or(0) < data.len() {
+                                        let mut new_file = tempfile::tempfile()?;
+                                        new_file.write_all(&data)?;
+                                        data = Vec::new();
+                                        file = Some(new_file);
+                                    }
+                                }
+                            }
                         }
-                        file.seek(SeekFrom::Start(0))?;
-                        files.push((name, filename, content_type, file));
+                        let content: UploadContent = match file {
+                            Some(mut file) => {
+                                file.seek(SeekFrom::Start(0))?;
+                                file.into()
+                            }
+                            None => data.into(),
+                        };
+                        files.push((name, filename, content_type, content));
                     }
                 }
             }
@@ -126,12 +233,12 @@ pub(super) async fn receive_batch_multipart(
     let mut request: BatchRequest = request.ok_or(ParseRequestError::MissingOperatorsPart)?;
     let map = map.as_mut().ok_or(ParseRequestError::MissingMapPart)?;
 
-    for (name, filename, content_type, file) in files {
+    for (name, filename, content_type, content) in files {
         if let Some(var_paths) = map.remove(&name) {
             let upload = UploadValue {
                 filename,
                 content_type,
-                content: file,
+                content,
             };
 
             for var_path in var_paths {
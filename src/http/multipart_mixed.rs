@@ -0,0 +1,68 @@
+//! Incremental delivery over HTTP with `multipart/mixed` responses
+
+use futures_util::future;
+use futures_util::stream::{self, Stream, StreamExt};
+
+use crate::Response;
+
+/// The value of the `Content-Type` header of a `multipart/mixed` response.
+pub const MULTIPART_MIXED_CONTENT_TYPE: &str = "multipart/mixed; boundary=\"-\"";
+
+/// Create a `multipart/mixed` response body from a stream of responses.
+///
+/// This is how the payloads of an operation with `@defer` or `@stream` directives, which are
+/// produced by [`Schema::execute_stream`](crate::Schema::execute_stream), are sent over HTTP.
+/// Every response is sent in a JSON part, and the body ends with the closing delimiter after the
+/// last response.
+///
+/// The chunks should be written to the response body as they are, with the `Content-Type` header
+/// set to [`MULTIPART_MIXED_CONTENT_TYPE`].
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::http::create_multipart_mixed_stream;
+/// use futures_util::stream::StreamExt;
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn a(&self) -> i32 {
+///         1
+///     }
+///
+///     async fn b(&self) -> i32 {
+///         2
+///     }
+/// }
+///
+/// tokio::runtime::Runtime::new().unwrap().block_on(async move {
+///     let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+///     let body = create_multipart_mixed_stream(schema.execute_stream("{ a ... @defer { b } }"))
+///         .collect::<Vec<_>>()
+///         .await
+///         .concat();
+///     assert_eq!(
+///         body,
+///         "\r\n---\r\nContent-Type: application/json; charset=utf-8\r\n\r\n\
+///          {\"data\":{\"a\":1},\"hasNext\":true}\
+///          \r\n---\r\nContent-Type: application/json; charset=utf-8\r\n\r\n\
+///          {\"data\":{\"b\":2},\"hasNext\":false}\
+///          \r\n-----\r\n"
+///     );
+/// });
+/// ```
+pub fn create_multipart_mixed_stream(
+    stream: impl Stream<Item = Response> + Send + Unpin,
+) -> impl Stream<Item = String> + Send + Unpin {
+    stream
+        .map(|resp| {
+            format!(
+                "\r\n---\r\nContent-Type: application/json; charset=utf-8\r\n\r\n{}",
+                serde_json::to_string(&resp).unwrap_or_default()
+            )
+        })
+        .chain(stream::once(future::ready("\r\n-----\r\n".to_string())))
+}
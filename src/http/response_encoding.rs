@@ -0,0 +1,158 @@
+use crate::BatchResponse;
+
+/// The encoding of a response body.
+///
+/// Binary encodings are only available with the `cbor` and `msgpack` features, and encode the
+/// same response shape as JSON.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ResponseEncoding {
+    /// `application/json`
+    Json,
+
+    /// `application/cbor`
+    #[cfg(feature = "cbor")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbor")))]
+    Cbor,
+
+    /// `application/msgpack`
+    #[cfg(feature = "msgpack")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "msgpack")))]
+    MsgPack,
+}
+
+impl ResponseEncoding {
+    /// Select the encoding from the value of an `Accept` header.
+    ///
+    /// The supported media type with the highest quality is selected. JSON is selected if there
+    /// is no header, or if none of the accepted media types is supported.
+    pub fn from_accept(accept: Option<impl AsRef<str>>) -> Self {
+        let accept = match &accept {
+            Some(accept) => accept.as_ref(),
+            None => return ResponseEncoding::Json,
+        };
+
+        let mut candidates = accept
+            .split(',')
+            .filter_map(|media_range| media_range.trim().parse::<mime::Mime>().ok())
+            .filter_map(|media_range| {
+                let quality = media_range
+                    .get_param("q")
+                    .and_then(|q| q.as_str().parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                let encoding = Self::from_media_range(&media_range)?;
+                Some((encoding, quality))
+            })
+            .filter(|(_, quality)| *quality > 0.0)
+            .collect::<Vec<_>>();
+        // The sort is stable, so the order of the header is kept for the same quality.
+        candidates.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap());
+        candidates
+            .first()
+            .map(|(encoding, _)| *encoding)
+            .unwrap_or(ResponseEncoding::Json)
+    }
+
+    fn from_media_range(media_range: &mime::Mime) -> Option<Self> {
+        match (media_range.type_().as_str(), media_range.subtype().as_str()) {
+            ("*", "*") | ("application", "*") | ("application", "json") => {
+                Some(ResponseEncoding::Json)
+            }
+            #[cfg(feature = "cbor")]
+            ("application", "cbor") => Some(ResponseEncoding::Cbor),
+            #[cfg(feature = "msgpack")]
+            ("application", "msgpack")
+            | ("application", "x-msgpack")
+            | ("application", "vnd.msgpack") => Some(ResponseEncoding::MsgPack),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of the `Content-Type` header for this encoding.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ResponseEncoding::Json => "application/json",
+            #[cfg(feature = "cbor")]
+            ResponseEncoding::Cbor => "application/cbor",
+            #[cfg(feature = "msgpack")]
+            ResponseEncoding::MsgPack => "application/msgpack",
+        }
+    }
+
+    /// Encode a response with this encoding.
+    pub fn encode(
+        &self,
+        response: &BatchResponse,
+    ) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+        match self {
+            ResponseEncoding::Json => Ok(serde_json::to_vec(response)?),
+            #[cfg(feature = "cbor")]
+            ResponseEncoding::Cbor => Ok(response.to_cbor()?),
+            #[cfg(feature = "msgpack")]
+            ResponseEncoding::MsgPack => Ok(response.to_msgpack()?),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{value, Response};
+
+    #[test]
+    fn test_from_accept() {
+        assert_eq!(
+            ResponseEncoding::from_accept(None::<&str>),
+            ResponseEncoding::Json
+        );
+        assert_eq!(
+            ResponseEncoding::from_accept(Some("text/html, image/png")),
+            ResponseEncoding::Json
+        );
+        assert_eq!(
+            ResponseEncoding::from_accept(Some("application/json, */*;q=0.5")),
+            ResponseEncoding::Json
+        );
+        #[cfg(feature = "cbor")]
+        {
+            assert_eq!(
+                ResponseEncoding::from_accept(Some("application/json;q=0.9, application/cbor")),
+                ResponseEncoding::Cbor
+            );
+            assert_eq!(
+                ResponseEncoding::from_accept(Some("application/cbor;q=0, application/json")),
+                ResponseEncoding::Json
+            );
+        }
+        #[cfg(feature = "msgpack")]
+        assert_eq!(
+            ResponseEncoding::from_accept(Some("application/x-msgpack, application/json")),
+            ResponseEncoding::MsgPack
+        );
+    }
+
+    #[test]
+    fn test_encode() {
+        let resp = BatchResponse::Single(Response::new(value!({ "a": 1 })));
+        let encoding = ResponseEncoding::Json;
+        assert_eq!(encoding.content_type(), "application/json");
+        assert_eq!(encoding.encode(&resp).unwrap(), br#"{"data":{"a":1}}"#);
+
+        #[cfg(feature = "cbor")]
+        assert_eq!(
+            serde_cbor::from_slice::<serde_json::Value>(
+                &ResponseEncoding::Cbor.encode(&resp).unwrap()
+            )
+            .unwrap(),
+            serde_json::json!({ "data": { "a": 1 } })
+        );
+
+        #[cfg(feature = "msgpack")]
+        assert_eq!(
+            rmp_serde::from_slice::<serde_json::Value>(
+                &ResponseEncoding::MsgPack.encode(&resp).unwrap()
+            )
+            .unwrap(),
+            serde_json::json!({ "data": { "a": 1 } })
+        );
+    }
+}
@@ -0,0 +1,84 @@
+//! Server-Sent Events transport for subscriptions
+
+use futures_util::future;
+use futures_util::stream::{self, Stream, StreamExt};
+
+use crate::{ObjectType, Request, Response, Schema, SubscriptionType};
+
+/// The value of the `Content-Type` header of a Server-Sent Events response.
+pub const SSE_CONTENT_TYPE: &str = "text/event-stream";
+
+/// Create a stream of `text/event-stream` events for a GraphQL request.
+///
+/// This follows the distinct connections mode of the
+/// [GraphQL over Server-Sent Events](https://github.com/enisdenjo/graphql-sse/blob/master/PROTOCOL.md)
+/// protocol: every response is sent in a `next` event, and a `complete` event is sent when the
+/// operation ends. Queries and mutations produce a single `next` event.
+///
+/// The events should be written to the response body as they are, with the `Content-Type` header
+/// set to [`SSE_CONTENT_TYPE`].
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::http::create_sse_stream;
+/// use futures_util::stream::{self, Stream, StreamExt};
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// struct Subscription;
+///
+/// #[Subscription]
+/// impl Subscription {
+///     async fn values(&self) -> impl Stream<Item = i32> {
+///         stream::iter(vec![1, 2])
+///     }
+/// }
+///
+/// tokio::runtime::Runtime::new().unwrap().block_on(async move {
+///     let schema = Schema::new(Query, EmptyMutation, Subscription);
+///     let events = create_sse_stream(&schema, "subscription { values }")
+///         .collect::<Vec<_>>()
+///         .await;
+///     assert_eq!(
+///         events,
+///         vec![
+///             "event: next\ndata: {\"data\":{\"values\":1}}\n\n",
+///             "event: next\ndata: {\"data\":{\"values\":2}}\n\n",
+///             "event: complete\ndata:\n\n",
+///         ]
+///     );
+/// });
+/// ```
+pub fn create_sse_stream<Query, Mutation, Subscription>(
+    schema: &Schema<Query, Mutation, Subscription>,
+    request: impl Into<Request>,
+) -> impl Stream<Item = String> + Send + Unpin
+where
+    Query: ObjectType + 'static,
+    Mutation: ObjectType + 'static,
+    Subscription: SubscriptionType + 'static,
+{
+    schema
+        .execute_stream(request)
+        .map(|resp| next_event(&resp))
+        .chain(stream::once(future::ready(
+            "event: complete\ndata:\n\n".to_string(),
+        )))
+}
+
+fn next_event(resp: &Response) -> String {
+    // The JSON serializer escapes line breaks, so the payload fits in a single `data` field.
+    format!(
+        "event: next\ndata: {}\n\n",
+        serde_json::to_string(resp).unwrap_or_default()
+    )
+}
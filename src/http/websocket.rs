@@ -5,6 +5,7 @@ use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
 use futures_util::stream::Stream;
 use futures_util::FutureExt;
@@ -60,6 +61,12 @@ impl WsMessage {
     }
 }
 
+// The timers are only available with the `timeout` feature.
+#[cfg(feature = "timeout")]
+type Delay = futures_timer::Delay;
+#[cfg(not(feature = "timeout"))]
+type Delay = std::convert::Infallible;
+
 pin_project! {
     /// A GraphQL connection over websocket.
     ///
@@ -74,6 +81,8 @@ pin_project! {
         #[pin]
         stream: S,
         protocol: Protocols,
+        keepalive: Option<(Duration, Delay)>,
+        idle_timeout: Option<(Duration, Option<Delay>)>,
     }
 }
 
@@ -119,6 +128,46 @@ where
     /// This function, if present, will be called with the data sent by the client in the
     /// [`GQL_CONNECTION_INIT` message](https://github.com/apollographql/subscriptions-transport-ws/blob/master/PROTOCOL.md#gql_connection_init).
     /// From that point on the returned data will be accessible to all requests.
+    ///
+    /// The function can authenticate the connection asynchronously. If it returns an error, the
+    /// connection is closed with the `4403` code with the `graphql-ws` protocol, or a
+    /// `connection_error` message is sent with the `subscriptions-transport-ws` protocol.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_graphql::http::{WebSocket, WebSocketProtocols};
+    /// use async_graphql::*;
+    /// use futures_util::stream;
+    ///
+    /// struct Token(String);
+    ///
+    /// struct Query;
+    ///
+    /// #[Object]
+    /// impl Query {
+    ///     async fn value(&self) -> i32 {
+    ///         10
+    ///     }
+    /// }
+    ///
+    /// let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    /// let ws = WebSocket::with_data(
+    ///     schema,
+    ///     stream::empty::<String>(),
+    ///     |payload: serde_json::Value| async move {
+    ///         match payload.get("token").and_then(|token| token.as_str()) {
+    ///             Some(token) => {
+    ///                 let mut data = Data::default();
+    ///                 data.insert(Token(token.to_string()));
+    ///                 Ok(data)
+    ///             }
+    ///             None => Err(Error::new("Forbidden")),
+    ///         }
+    ///     },
+    ///     WebSocketProtocols::GraphQLWS,
+    /// );
+    /// ```
     #[must_use]
     pub fn with_data(
         schema: Schema<Query, Mutation, Subscription>,
@@ -159,10 +208,37 @@ where
             streams: HashMap::new(),
             stream,
             protocol,
+            keepalive: None,
+            idle_timeout: None,
         }
     }
 }
 
+impl<S, F, Query, Mutation, Subscription> WebSocket<S, F, Query, Mutation, Subscription> {
+    /// Send a keep-alive message at the interval once the connection is acknowledged, so the
+    /// proxies and the load balancers do not drop the silent connections.
+    ///
+    /// The message is `ka` with the `subscriptions-transport-ws` protocol, and `ping` with the
+    /// `graphql-ws` protocol.
+    #[cfg(feature = "timeout")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "timeout")))]
+    #[must_use]
+    pub fn keepalive_interval(mut self, interval: Duration) -> Self {
+        self.keepalive = Some((interval, Delay::new(interval)));
+        self
+    }
+
+    /// Close the connection with the `1000` code when it has no active subscriptions for the
+    /// duration.
+    #[cfg(feature = "timeout")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "timeout")))]
+    #[must_use]
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = Some((timeout, None));
+        self
+    }
+}
+
 impl<S, F, R, Query, Mutation, Subscription> Stream
     for WebSocket<S, F, Query, Mutation, Subscription>
 where
@@ -187,7 +263,13 @@ where
 
                 let message: ClientMessage = match message {
                     Ok(message) => message,
-                    Err(err) => return Poll::Ready(Some(WsMessage::Close(1002, err.to_string()))),
+                    Err(err) => {
+                        let code = match this.protocol {
+                            Protocols::SubscriptionsTransportWS => 1002,
+                            Protocols::GraphQLWS => 4400,
+                        };
+                        return Poll::Ready(Some(WsMessage::Close(code, err.to_string())));
+                    }
                 };
 
                 match message {
@@ -223,6 +305,14 @@ where
                         payload: request,
                     } => {
                         if let Some(data) = this.data.clone() {
+                            if *this.protocol == Protocols::GraphQLWS
+                                && this.streams.contains_key(&id)
+                            {
+                                return Poll::Ready(Some(WsMessage::Close(
+                                    4409,
+                                    format!("Subscriber for {} already exists", id),
+                                )));
+                            }
                             this.streams.insert(
                                 id,
                                 Box::pin(
@@ -267,6 +357,10 @@ where
                 return match res {
                     Ok(data) => {
                         *this.data = Some(Arc::new(data));
+                        #[cfg(feature = "timeout")]
+                        if let Some((interval, delay)) = this.keepalive {
+                            delay.reset(*interval);
+                        }
                         Poll::Ready(Some(WsMessage::Text(
                             serde_json::to_string(&ServerMessage::ConnectionAck).unwrap(),
                         )))
@@ -279,7 +373,7 @@ where
                             .unwrap(),
                         ))),
                         Protocols::GraphQLWS => {
-                            Poll::Ready(Some(WsMessage::Close(1002, err.message)))
+                            Poll::Ready(Some(WsMessage::Close(4403, err.message)))
                         }
                     },
                 };
@@ -304,6 +398,32 @@ where
             }
         }
 
+        #[cfg(feature = "timeout")]
+        if let Some((timeout, delay)) = this.idle_timeout {
+            if this.streams.is_empty() {
+                let delay = delay.get_or_insert_with(|| Delay::new(*timeout));
+                if delay.poll_unpin(cx).is_ready() {
+                    return Poll::Ready(Some(WsMessage::Close(1000, "Idle timeout.".to_string())));
+                }
+            } else {
+                *delay = None;
+            }
+        }
+
+        #[cfg(feature = "timeout")]
+        if let Some((interval, delay)) = this.keepalive {
+            if this.data.is_some() && delay.poll_unpin(cx).is_ready() {
+                delay.reset(*interval);
+                let message = match this.protocol {
+                    Protocols::SubscriptionsTransportWS => ServerMessage::KeepAlive,
+                    Protocols::GraphQLWS => ServerMessage::Ping { payload: None },
+                };
+                return Poll::Ready(Some(WsMessage::Text(
+                    serde_json::to_string(&message).unwrap(),
+                )));
+            }
+        }
+
         Poll::Pending
     }
 }
@@ -436,7 +556,16 @@ enum ServerMessage<'a> {
         #[serde(skip_serializing_if = "Option::is_none")]
         payload: Option<serde_json::Value>,
     },
-    // Not used by this library
-    // #[serde(rename = "ka")]
-    // KeepAlive
+    /// A keep-alive ping of the graphql-ws protocol.
+    ///
+    /// https://github.com/enisdenjo/graphql-ws/blob/master/PROTOCOL.md#ping
+    #[cfg(feature = "timeout")]
+    Ping {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        payload: Option<serde_json::Value>,
+    },
+    /// A keep-alive message of the subscriptions-transport-ws protocol.
+    #[cfg(feature = "timeout")]
+    #[serde(rename = "ka")]
+    KeepAlive,
 }
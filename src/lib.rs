@@ -59,20 +59,28 @@
 //! This crate offers the following features, all of which are not activated by default:
 //!
 //! - `apollo_tracing`: Enable the [Apollo tracing extension](extensions/struct.ApolloTracing.html).
+//! - `apollo_tracing_report`: Enable the [Apollo Studio trace reporting extension](extensions/apollo_tracing_report/struct.ApolloTracingReport.html).
 //! - `apollo_persisted_queries`: Enable the [Apollo persisted queries extension](extensions/apollo_persisted_queries/struct.ApolloPersistedQueries.html).
 //! - `log`: Enable the [logger extension](extensions/struct.Logger.html).
 //! - `tracing`: Enable the [tracing extension](extensions/struct.Tracing.html).
 //! - `opentelemetry`: Enable the [OpenTelemetry extension](extensions/struct.OpenTelemetry.html).
-//! - `unblock`: Support [asynchronous reader for Upload](types/struct.Upload.html)
+//! - `prometheus`: Enable the [Prometheus metrics extension](extensions/struct.PrometheusMetrics.html).
+//! - `unblock`: Support [asynchronous reader for Upload](types/struct.Upload.html) and [`Context::spawn_blocking`](struct.ContextBase.html#method.spawn_blocking)
 //! - `bson`: Integrate with the [`bson` crate](https://crates.io/crates/bson).
 //! - `chrono`: Integrate with the [`chrono` crate](https://crates.io/crates/chrono).
 //! - `chrono-tz`: Integrate with the [`chrono-tz` crate](https://crates.io/crates/chrono-tz).
+//! - `time`: Integrate with the [`time` crate](https://crates.io/crates/time).
 //! - `url`: Integrate with the [`url` crate](https://crates.io/crates/url).
 //! - `uuid`: Integrate with the [`uuid` crate](https://crates.io/crates/uuid).
 //! - `string_number`: Enable the [StringNumber](types/struct.StringNumber.html).
 //! - `dataloader`: Support [DataLoader](dataloader/struct.DataLoader.html).
 //! - `decimal`: Integrate with the [`rust_decimal` crate](https://crates.io/crates/rust_decimal).
 //! - `cbor`: Support for [serde_cbor](https://crates.io/crates/serde_cbor).
+//! - `msgpack`: Support for MessagePack responses with [rmp-serde](https://crates.io/crates/rmp-serde).
+//! - `strict_output`: Check that resolved values conform to their declared types, and panic with a description of the problem if they do not. Intended for debugging.
+//! - `hmac_signing`: Enable [`SchemaBuilder::hmac_key`](struct.SchemaBuilder.html#method.hmac_key) to sign cursors and global IDs with HMAC-SHA256.
+//! - `ffi`: Enable the [C ABI](ffi/index.html) to execute a schema from other languages. This is the only feature that uses `unsafe` code.
+//! - `timeout`: Enable [`SchemaBuilder::partial_results_timeout`](struct.SchemaBuilder.html#method.partial_results_timeout), and the keep-alive messages and the idle timeout of [`WebSocket`](http/struct.WebSocket.html).
 //!
 //! ## Integrations
 //!
@@ -157,16 +165,23 @@
 #![allow(clippy::trivially_copy_pass_by_ref)]
 #![allow(clippy::upper_case_acronyms)]
 #![recursion_limit = "256"]
-#![forbid(unsafe_code)]
+#![cfg_attr(not(feature = "ffi"), forbid(unsafe_code))]
+#![cfg_attr(feature = "ffi", deny(unsafe_code))]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 mod base;
+mod custom_directive;
 mod error;
+mod field_usage;
 mod look_ahead;
 mod model;
+mod operation_builder;
 mod request;
 mod response;
 mod schema;
+mod schema_module;
+#[cfg(feature = "hmac_signing")]
+mod signing;
 mod subscription;
 mod validation;
 
@@ -175,9 +190,15 @@ pub mod context;
 #[cfg_attr(docsrs, doc(cfg(feature = "dataloader")))]
 pub mod dataloader;
 pub mod extensions;
+#[cfg(feature = "ffi")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ffi")))]
+pub mod ffi;
 pub mod guard;
 pub mod http;
+pub mod mock;
+pub mod pubsub;
 pub mod resolver_utils;
+pub mod test;
 pub mod types;
 pub mod validators;
 
@@ -190,6 +211,8 @@ pub use async_stream;
 pub use async_trait;
 #[doc(hidden)]
 pub use context::ContextSelectionSet;
+
+pub use custom_directive::{CustomDirective, DirectiveArgs, DirectiveArgument, NextDirective};
 #[doc(hidden)]
 pub use futures_util;
 #[doc(hidden)]
@@ -201,8 +224,8 @@ pub use subscription::SubscriptionType;
 
 pub use async_graphql_parser as parser;
 pub use async_graphql_value::{
-    from_value, to_value, value, ConstValue as Value, DeserializerError, Name, Number,
-    SerializerError, Variables,
+    from_value, from_value_ref, to_value, value, vars, ConstValue as Value, DeserializerError,
+    Name, Number, SerializerError, ValueSerializer, Variables,
 };
 pub use base::{
     ComplexObject, Description, InputObjectType, InputType, InterfaceType, ObjectType, OutputType,
@@ -212,13 +235,16 @@ pub use error::{
     Error, ErrorExtensionValues, ErrorExtensions, InputValueError, InputValueResult,
     ParseRequestError, PathSegment, ResolverError, Result, ResultExt, ServerError, ServerResult,
 };
+pub use field_usage::FieldUsageSnapshot;
 pub use look_ahead::Lookahead;
-pub use registry::CacheControl;
-pub use request::{BatchRequest, Request};
+pub use operation_builder::{FieldBuilder, OperationBuilder};
+pub use registry::{CacheControl, ConnectionWarning, Metadata, SDLExportOptions};
+pub use request::{BatchRequest, OnError, Request};
 #[doc(no_inline)]
-pub use resolver_utils::{ContainerType, EnumType, ScalarType};
+pub use resolver_utils::{ContainerType, EnumType, NumberPolicy, ScalarType};
 pub use response::{BatchResponse, Response};
-pub use schema::{Schema, SchemaBuilder, SchemaEnv};
+pub use schema::{CheckResult, Schema, SchemaBuilder, SchemaEnv};
+pub use schema_module::SchemaModule;
 pub use validation::{ValidationMode, ValidationResult, VisitorContext};
 
 pub use context::*;
@@ -249,9 +275,11 @@ pub type FieldResult<T> = Result<T>;
 /// | rename_args   | Rename all the arguments according to the given case convention. The possible values are "lowercase", "UPPERCASE", "PascalCase", "camelCase", "snake_case", "SCREAMING_SNAKE_CASE".| string   | Y        |
 /// | cache_control | Object cache control      | [`CacheControl`](struct.CacheControl.html) | Y        |
 /// | extends       | Add fields to an entity that's defined in another service | bool | Y |
+/// | shareable     | Mark all the fields as resolvable by several subgraphs with `@shareable`, see `SchemaBuilder::enable_federation_v2` | bool | Y |
 /// | use_type_description | Specifies that the description of the type is on the type declaration. [`Description`]()(derive.Description.html) | bool | Y |
 /// | visible       | If `false`, it will not be displayed in introspection. *[See also the Book](https://async-graphql.github.io/async-graphql/en/visibility.html).* | bool | Y |
 /// | visible       | Call the specified function. If the return value is `false`, it will not be displayed in introspection. | string | Y |
+/// | meta          | Metadata of the type, which can be read with `Schema::type_metadata`. | key = value list | Y |
 ///
 /// # Field parameters
 ///
@@ -266,9 +294,16 @@ pub type FieldResult<T> = Result<T>;
 /// | external      | Mark a field as owned by another service. This allows service A to use fields from service B while also knowing at runtime the types of that field. | bool | Y |
 /// | provides      | Annotate the expected returned fieldset from a field on a base type that is guaranteed to be selectable by the gateway. | string | Y |
 /// | requires      | Annotate the required input fieldset from a base type for a resolver. It is used to develop a query plan where the required fields may not be needed by the client, but the service may need additional information from other services. | string | Y |
+/// | shareable     | Mark this field as resolvable by several subgraphs with `@shareable`, see `SchemaBuilder::enable_federation_v2` | bool | Y |
+/// | override_from | Take over the resolution of this field from the named subgraph with `@override(from: ...)` | string | Y |
 /// | guard         | Field of guard            | [`Guard`](guard/trait.Guard.html) | Y        |
+/// | guard         | Expression of the guard of the field, which can be combined with `and` and `or` | string | Y |
+/// | public        | Skip the default guard of the schema, see [`SchemaBuilder::default_guard`](struct.SchemaBuilder.html#method.default_guard) | bool | Y |
 /// | visible       | If `false`, it will not be displayed in introspection. *[See also the Book](https://async-graphql.github.io/async-graphql/en/visibility.html).* | bool | Y |
 /// | visible       | Call the specified function. If the return value is `false`, it will not be displayed in introspection. | string | Y |
+/// | meta          | Metadata of the field, which can be read with `Schema::field_metadata`. | key = value list | Y |
+/// | memoize       | Reuse the value of the first invocation for identical invocations (same parent path and arguments) within a request. The field type must implement `Clone`. | bool | Y |
+/// | blocking      | Run the resolver, which must be synchronous, with [`Context::spawn_blocking`](struct.ContextBase.html#method.spawn_blocking). `self` is cloned and the arguments are moved to another thread, so `Self` must implement `Clone + Send + 'static` and the arguments must be owned `'static` types. It takes a `&BlockingContext` instead of a `&Context`. Requires the `unblock` feature. | bool | Y |
 ///
 /// # Field argument parameters
 ///
@@ -286,6 +321,8 @@ pub type FieldResult<T> = Result<T>;
 /// | visible      | If `false`, it will not be displayed in introspection. *[See also the Book](https://async-graphql.github.io/async-graphql/en/visibility.html).* | bool | Y |
 /// | visible      | Call the specified function. If the return value is `false`, it will not be displayed in introspection. | string | Y |
 /// | secret       | Mark this field as a secret, it will not output the actual value in the log. | bool | Y |
+/// | ctx_data     | Hide this argument from the schema and take its value from the context data (`Context::data`). The argument type must be a reference or implement `Clone`. | bool | Y |
+/// | flatten      | Expose each field of an `InputObject` as a separate argument of this field. | bool | Y |
 /// | serial       | Resolve each field sequentially.         | bool        | Y        |
 /// | key          | Is entity key(for Federation)            | bool        | Y        |
 ///
@@ -431,6 +468,8 @@ pub use async_graphql_derive::Object;
 ///
 /// Similar to `Object`, but defined on a structure that automatically generates getters for all fields. For a list of valid field types, see [`Object`](attr.Object.html). All fields are converted to camelCase.
 ///
+/// It can also be used on a tuple struct, where every field that is not skipped must have a `name`, and on a unit struct with the `complex` attribute, whose fields are all defined with [`ComplexObject`](attr.ComplexObject.html).
+///
 /// # Macro parameters
 ///
 /// | Attribute     | description               | Type     | Optional |
@@ -439,8 +478,10 @@ pub use async_graphql_derive::Object;
 /// | rename_fields | Rename all the fields according to the given case convention. The possible values are "lowercase", "UPPERCASE", "PascalCase", "camelCase", "snake_case", "SCREAMING_SNAKE_CASE".| string   | Y        |
 /// | cache_control | Object cache control      | [`CacheControl`](struct.CacheControl.html) | Y        |
 /// | extends       | Add fields to an entity that's defined in another service | bool | Y |
+/// | shareable     | Mark all the fields as resolvable by several subgraphs with `@shareable`, see `SchemaBuilder::enable_federation_v2` | bool | Y |
 /// | visible       | If `false`, it will not be displayed in introspection. *[See also the Book](https://async-graphql.github.io/async-graphql/en/visibility.html).* | bool | Y |
 /// | visible       | Call the specified function. If the return value is `false`, it will not be displayed in introspection. | string | Y |
+/// | meta          | Metadata of the type, which can be read with `Schema::type_metadata`. | key = value list | Y |
 /// | concretes     | Specify how the concrete type of the generic SimpleObject should be implemented. *[See also the Book](https://async-graphql.github.io/async-graphql/en/define_simple_object.html#generic-simpleobjects) | ConcreteType |  Y |
 /// | serial        | Resolve each field sequentially.         | bool        | Y        |
 ///
@@ -448,7 +489,7 @@ pub use async_graphql_derive::Object;
 ///
 /// | Attribute     | description               | Type     | Optional |
 /// |---------------|---------------------------|----------|----------|
-/// | skip          | Skip this field           | bool     | Y        |
+/// | skip          | Skip this field, `PhantomData` fields are always skipped | bool     | Y        |
 /// | name          | Field name                | string   | Y        |
 /// | deprecation   | Field deprecated          | bool     | Y        |
 /// | deprecation   | Field deprecation reason  | string   | Y        |
@@ -458,9 +499,15 @@ pub use async_graphql_derive::Object;
 /// | external      | Mark a field as owned by another service. This allows service A to use fields from service B while also knowing at runtime the types of that field. | bool | Y |
 /// | provides      | Annotate the expected returned fieldset from a field on a base type that is guaranteed to be selectable by the gateway. | string | Y |
 /// | requires      | Annotate the required input fieldset from a base type for a resolver. It is used to develop a query plan where the required fields may not be needed by the client, but the service may need additional information from other services. | string | Y |
+/// | shareable     | Mark this field as resolvable by several subgraphs with `@shareable`, see `SchemaBuilder::enable_federation_v2` | bool | Y |
+/// | override_from | Take over the resolution of this field from the named subgraph with `@override(from: ...)` | string | Y |
 /// | guard         | Field of guard            | [`Guard`](guard/trait.Guard.html) | Y        |
+/// | guard         | Expression of the guard of the field, which can be combined with `and` and `or` | string | Y |
+/// | public        | Skip the default guard of the schema, see [`SchemaBuilder::default_guard`](struct.SchemaBuilder.html#method.default_guard) | bool | Y |
 /// | visible       | If `false`, it will not be displayed in introspection. *[See also the Book](https://async-graphql.github.io/async-graphql/en/visibility.html).* | bool | Y |
 /// | visible       | Call the specified function. If the return value is `false`, it will not be displayed in introspection. | string | Y |
+/// | meta          | Metadata of the field, which can be read with `Schema::field_metadata`. | key = value list | Y |
+/// | complexity    | Custom field complexity, which is a number or an expression of `child_complexity`. | int or string | Y |
 ///
 /// # Derived argument parameters
 ///
@@ -492,6 +539,86 @@ pub use async_graphql_derive::Object;
 /// ```
 pub use async_graphql_derive::SimpleObject;
 
+/// Define a mutation payload object with a `userErrors` field.
+///
+/// This follows the convention of returning the errors caused by the input of a mutation in a
+/// `userErrors: [UserError!]!` field of its payload. It accepts the same parameters as
+/// [`SimpleObject`](derive.SimpleObject.html), and exposes the field marked with
+/// `#[graphql(user_errors)]`, or the field named `user_errors`, as a list of
+/// [`UserError`](struct.UserError.html). The type of that field must be a list of a type that
+/// implements [`UserErrorType`](trait.UserErrorType.html), usually an error enum.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// enum CreateUserError {
+///     NameTaken,
+///     NameTooLong { max: usize },
+/// }
+///
+/// impl UserErrorType for CreateUserError {
+///     fn user_error(&self) -> UserError {
+///         match self {
+///             CreateUserError::NameTaken => UserError::new("The name is already taken.")
+///                 .with_field(["name"])
+///                 .with_code("NAME_TAKEN"),
+///             CreateUserError::NameTooLong { max } => {
+///                 UserError::new(format!("The name must be at most {} characters.", max))
+///                     .with_field(["name"])
+///             }
+///         }
+///     }
+/// }
+///
+/// #[derive(MutationPayload)]
+/// struct CreateUserPayload {
+///     user_id: Option<ID>,
+///     user_errors: Vec<CreateUserError>,
+/// }
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// struct MutationRoot;
+///
+/// #[Object]
+/// impl MutationRoot {
+///     async fn create_user(&self, name: String) -> CreateUserPayload {
+///         CreateUserPayload {
+///             user_id: None,
+///             user_errors: vec![CreateUserError::NameTaken],
+///         }
+///     }
+/// }
+///
+/// tokio::runtime::Runtime::new().unwrap().block_on(async move {
+///     let schema = Schema::new(QueryRoot, MutationRoot, EmptySubscription);
+///     let res = schema
+///         .execute(r#"mutation { createUser(name: "sunli") { userId userErrors { field message code } } }"#)
+///         .await
+///         .into_result()
+///         .unwrap()
+///         .data;
+///     assert_eq!(res, value!({
+///         "createUser": {
+///             "userId": null,
+///             "userErrors": [
+///                 { "field": ["name"], "message": "The name is already taken.", "code": "NAME_TAKEN" },
+///             ],
+///         },
+///     }));
+/// });
+/// ```
+pub use async_graphql_derive::MutationPayload;
+
 /// Define a complex GraphQL object for SimpleObject's complex field resolver.
 ///
 /// *[See also the Book](https://async-graphql.github.io/async-graphql/en/define_simple_object.html).*
@@ -523,10 +650,19 @@ pub use async_graphql_derive::SimpleObject;
 /// | external      | Mark a field as owned by another service. This allows service A to use fields from service B while also knowing at runtime the types of that field. | bool | Y |
 /// | provides      | Annotate the expected returned fieldset from a field on a base type that is guaranteed to be selectable by the gateway. | string | Y |
 /// | requires      | Annotate the required input fieldset from a base type for a resolver. It is used to develop a query plan where the required fields may not be needed by the client, but the service may need additional information from other services. | string | Y |
+/// | shareable     | Mark this field as resolvable by several subgraphs with `@shareable`, see `SchemaBuilder::enable_federation_v2` | bool | Y |
+/// | override_from | Take over the resolution of this field from the named subgraph with `@override(from: ...)` | string | Y |
 /// | guard         | Field of guard            | [`Guard`](guard/trait.Guard.html) | Y        |
+/// | guard         | Expression of the guard of the field, which can be combined with `and` and `or` | string | Y |
+/// | public        | Skip the default guard of the schema, see [`SchemaBuilder::default_guard`](struct.SchemaBuilder.html#method.default_guard) | bool | Y |
 /// | visible       | If `false`, it will not be displayed in introspection. *[See also the Book](https://async-graphql.github.io/async-graphql/en/visibility.html).* | bool | Y |
 /// | visible       | Call the specified function. If the return value is `false`, it will not be displayed in introspection. | string | Y |
+/// | meta          | Metadata of the field, which can be read with `Schema::field_metadata`. | key = value list | Y |
+/// | memoize       | Reuse the value of the first invocation for identical invocations (same parent path and arguments) within a request. The field type must implement `Clone`. | bool | Y |
+/// | blocking      | Run the resolver, which must be synchronous, with [`Context::spawn_blocking`](struct.ContextBase.html#method.spawn_blocking). `self` is cloned and the arguments are moved to another thread, so `Self` must implement `Clone + Send + 'static` and the arguments must be owned `'static` types. It takes a `&BlockingContext` instead of a `&Context`. Requires the `unblock` feature. | bool | Y |
 /// | secret        | Mark this field as a secret, it will not output the actual value in the log. | bool | Y |
+/// | ctx_data      | Hide this argument from the schema and take its value from the context data (`Context::data`). The argument type must be a reference or implement `Clone`. | bool | Y |
+/// | flatten       | Expose each field of an `InputObject` as a separate argument of this field. | bool | Y |
 ///
 /// # Derived argument parameters
 ///
@@ -591,6 +727,7 @@ pub use async_graphql_derive::ComplexObject;
 /// | remote       | Derive a remote enum      | string   | Y        |
 /// | visible       | If `false`, it will not be displayed in introspection. *[See also the Book](https://async-graphql.github.io/async-graphql/en/visibility.html).* | bool | Y |
 /// | visible       | Call the specified function. If the return value is `false`, it will not be displayed in introspection. | string | Y |
+/// | meta          | Metadata of the type, which can be read with `Schema::type_metadata`. | key = value list | Y |
 ///
 /// # Item parameters
 ///
@@ -651,6 +788,8 @@ pub use async_graphql_derive::Enum;
 /// | rename_fields | Rename all the fields according to the given case convention. The possible values are "lowercase", "UPPERCASE", "PascalCase", "camelCase", "snake_case", "SCREAMING_SNAKE_CASE".| string   | Y        |
 /// | visible       | If `false`, it will not be displayed in introspection. *[See also the Book](https://async-graphql.github.io/async-graphql/en/visibility.html).* | bool | Y |
 /// | visible       | Call the specified function. If the return value is `false`, it will not be displayed in introspection. | string | Y |
+/// | max_depth     | Maximum number of nested input objects in a value of this type, see `SchemaBuilder::limit_input_depth`. | usize | Y |
+/// | meta          | Metadata of the type, which can be read with `Schema::type_metadata`. | key = value list | Y |
 ///
 /// # Field parameters
 ///
@@ -662,9 +801,10 @@ pub use async_graphql_derive::Enum;
 /// | default_with | Expression to generate default value     | code string | Y        |
 /// | validator    | Input value validator                    | [`InputValueValidator`](validators/trait.InputValueValidator.html) | Y        |
 /// | flatten      | Similar to serde (flatten)               | boolean     | Y        |
-/// | skip         | Skip this field, use `Default::default` to get a default value for this field. | bool     | Y        |
+/// | skip         | Skip this field, use `Default::default` to get a default value for this field. `PhantomData` fields are always skipped. | bool     | Y        |
 /// | visible      | If `false`, it will not be displayed in introspection. *[See also the Book](https://async-graphql.github.io/async-graphql/en/visibility.html).* | bool | Y |
 /// | visible      | Call the specified function. If the return value is `false`, it will not be displayed in introspection. | string | Y |
+/// | meta          | Metadata of the field, which can be read with `Schema::field_metadata`. | key = value list | Y |
 /// | secret       | Mark this field as a secret, it will not output the actual value in the log. | bool | Y |
 ///
 /// # Examples
@@ -701,6 +841,246 @@ pub use async_graphql_derive::Enum;
 /// ```
 pub use async_graphql_derive::InputObject;
 
+/// Define a GraphQL oneof input object
+///
+/// A oneof input object is an input object whose fields are mutually exclusive: exactly one of
+/// them must be provided, and it must not be `null`. It is defined with an enum whose variants
+/// hold the values of the fields, and is exported with the `@oneOf` directive.
+///
+/// # Macro parameters
+///
+/// | Attribute     | description               | Type     | Optional |
+/// |---------------|---------------------------|----------|----------|
+/// | name          | Object name               | string   | Y        |
+/// | rename_fields | Rename all the fields according to the given case convention. The possible values are "lowercase", "UPPERCASE", "PascalCase", "camelCase", "snake_case", "SCREAMING_SNAKE_CASE".| string   | Y        |
+/// | visible       | If `false`, it will not be displayed in introspection. *[See also the Book](https://async-graphql.github.io/async-graphql/en/visibility.html).* | bool | Y |
+/// | visible       | Call the specified function. If the return value is `false`, it will not be displayed in introspection. | string | Y |
+///
+/// # Field parameters
+///
+/// | Attribute    | description                              | Type        | Optional |
+/// |--------------|------------------------------------------|-------------|----------|
+/// | name         | Field name                               | string      | Y        |
+/// | visible      | If `false`, it will not be displayed in introspection. *[See also the Book](https://async-graphql.github.io/async-graphql/en/visibility.html).* | bool | Y |
+/// | visible      | Call the specified function. If the return value is `false`, it will not be displayed in introspection. | string | Y |
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// #[derive(OneofObject)]
+/// enum UserBy {
+///     Id(ID),
+///     Email(String),
+/// }
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn user(&self, by: UserBy) -> String {
+///         match by {
+///             UserBy::Id(id) => format!("id:{}", id.as_str()),
+///             UserBy::Email(email) => format!("email:{}", email),
+///         }
+///     }
+/// }
+///
+/// tokio::runtime::Runtime::new().unwrap().block_on(async move {
+///     let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+///     let res = schema.execute(r#"{ user(by: { email: "sunli@example.com" }) }"#).await;
+///     assert_eq!(res.into_result().unwrap().data, value!({ "user": "email:sunli@example.com" }));
+///     assert!(schema.execute(r#"{ user(by: { id: "1", email: "sunli@example.com" }) }"#).await.is_err());
+/// });
+/// ```
+pub use async_graphql_derive::OneofObject;
+
+/// Define the root fields to create, read, update and delete an entity
+///
+/// For a struct `X` that is also an output type, this generates:
+///
+/// - `XCreateInput` with all the fields except the id, and `XUpdateInput` whose fields are
+///   optional, with [`MaybeUndefined`](struct.MaybeUndefined.html) for the `Option` fields.
+/// - `XCrudQuery` with the `x(id)` and `xs(after, before, first, last)` fields, the latter is a
+///   connection whose cursors are the offsets of the entities.
+/// - `XCrudMutation` with the `createX(input)`, `updateX(id, input)` and `deleteX(id)` fields.
+///
+/// The resolvers call an [`EntityStore`](crud/trait.EntityStore.html) implemented by the
+/// application, and [`CrudEntity::crud_module`](crud/trait.CrudEntity.html#tymethod.crud_module)
+/// returns a [`SchemaModule`](struct.SchemaModule.html) with all the fields. The id is the field
+/// named `id`, or the field marked with `#[crud(id)]`. As with
+/// [`Filterable`](derive.Filterable.html), the `name`, `rename_fields`, `internal` and `skip`
+/// parameters of `#[graphql(...)]` are taken into account.
+///
+/// # Field parameters
+///
+/// | Attribute   | description                               | Type | Optional |
+/// |-------------|-------------------------------------------|------|----------|
+/// | id          | This field is the id of the entity        | bool | Y        |
+/// | skip        | Skip this field in the input objects, such as a field set by the store | bool | Y |
+///
+/// # Examples
+///
+/// ```rust
+/// use std::sync::{Arc, Mutex};
+/// use async_graphql::*;
+/// use async_graphql::crud::{CrudEntity, EntityStore};
+///
+/// #[derive(SimpleObject, CrudObject, Clone)]
+/// struct Book {
+///     id: i32,
+///     title: String,
+/// }
+///
+/// #[derive(Default)]
+/// struct Books(Mutex<Vec<Book>>);
+///
+/// #[async_trait::async_trait]
+/// impl EntityStore<Book> for Books {
+///     async fn get(&self, _ctx: &Context<'_>, id: i32) -> Result<Option<Book>> {
+///         Ok(self.0.lock().unwrap().iter().find(|book| book.id == id).cloned())
+///     }
+///
+///     async fn list(&self, _ctx: &Context<'_>, offset: usize, limit: usize) -> Result<Vec<Book>> {
+///         Ok(self.0.lock().unwrap().iter().skip(offset).take(limit).cloned().collect())
+///     }
+///
+///     async fn count(&self, _ctx: &Context<'_>) -> Result<usize> {
+///         Ok(self.0.lock().unwrap().len())
+///     }
+///
+///     async fn create(&self, _ctx: &Context<'_>, input: BookCreateInput) -> Result<Book> {
+///         let mut books = self.0.lock().unwrap();
+///         let book = Book { id: books.len() as i32 + 1, title: input.title };
+///         books.push(book.clone());
+///         Ok(book)
+///     }
+///
+///     async fn update(&self, _ctx: &Context<'_>, id: i32, input: BookUpdateInput) -> Result<Option<Book>> {
+///         let mut books = self.0.lock().unwrap();
+///         Ok(books.iter_mut().find(|book| book.id == id).map(|book| {
+///             if let Some(title) = input.title {
+///                 book.title = title;
+///             }
+///             book.clone()
+///         }))
+///     }
+///
+///     async fn delete(&self, _ctx: &Context<'_>, id: i32) -> Result<bool> {
+///         let mut books = self.0.lock().unwrap();
+///         let len = books.len();
+///         books.retain(|book| book.id != id);
+///         Ok(books.len() < len)
+///     }
+/// }
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn version(&self) -> &str {
+///         "1.0"
+///     }
+/// }
+///
+/// tokio::runtime::Runtime::new().unwrap().block_on(async move {
+///     let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///         .module(Book::crud_module(Arc::new(Books::default())))
+///         .finish();
+///     schema.execute(r#"mutation { createBook(input: { title: "Dune" }) { id } }"#).await;
+///     let res = schema.execute("{ book(id: 1) { title } books { edges { node { id } } } }").await;
+///     assert_eq!(
+///         res.into_result().unwrap().data,
+///         value!({ "book": { "title": "Dune" }, "books": { "edges": [{ "node": { "id": 1 } }] } })
+///     );
+/// });
+/// ```
+pub use async_graphql_derive::CrudObject;
+
+/// Define the filter and ordering input objects of a type
+///
+/// For a struct `X`, this generates the input objects `XFilterInput` and `XOrderByInput`, which
+/// resolvers receive as typed arguments and translate to their storage queries.
+///
+/// `XFilterInput` has an optional [`FieldFilter`](struct.FieldFilter.html) for every field, with
+/// the `eq`, `ne`, `in`, `gt`, `lt` and `contains` operators, and the `and`, `or` and `not`
+/// fields to combine the filters. The type of an `Option<T>` field is compared as `T`.
+/// `XOrderByInput` has an optional [`OrderDirection`](enum.OrderDirection.html) for every field,
+/// and a list of them orders by several fields.
+///
+/// The `name`, `rename_fields` and `internal` parameters of `#[graphql(...)]`, and the `name` and
+/// `skip` parameters of its fields, are taken into account, so the generated types follow the
+/// object. The type of every field must implement [`InputType`](trait.InputType.html), the other
+/// fields must be skipped.
+///
+/// # Field parameters
+///
+/// | Attribute   | description                               | Type | Optional |
+/// |-------------|-------------------------------------------|------|----------|
+/// | skip        | Skip this field in the filter and the ordering | bool | Y   |
+/// | skip_filter | Skip this field in the filter             | bool | Y        |
+/// | skip_order  | Skip this field in the ordering           | bool | Y        |
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// #[derive(SimpleObject, Filterable, Clone)]
+/// struct User {
+///     name: String,
+///     age: i32,
+///     #[filterable(skip_order)]
+///     email: Option<String>,
+/// }
+///
+/// struct Query {
+///     users: Vec<User>,
+/// }
+///
+/// #[Object]
+/// impl Query {
+///     async fn users(&self, filter: Option<UserFilterInput>, order_by: Option<Vec<UserOrderByInput>>) -> Vec<User> {
+///         let mut users = self
+///             .users
+///             .iter()
+///             .filter(|user| {
+///                 let filter = filter.clone().unwrap_or_default();
+///                 filter.name.map(|f| f.matches(&user.name)).unwrap_or(true)
+///                     && filter.age.map(|f| f.matches_with(&user.age, |a, b| a == b)).unwrap_or(true)
+///             })
+///             .cloned()
+///             .collect::<Vec<_>>();
+///         for order in order_by.unwrap_or_default().iter().rev() {
+///             match order.age {
+///                 Some(OrderDirection::Asc) => users.sort_by_key(|user| user.age),
+///                 Some(OrderDirection::Desc) => users.sort_by_key(|user| -user.age),
+///                 None => {}
+///             }
+///         }
+///         users
+///     }
+/// }
+///
+/// tokio::runtime::Runtime::new().unwrap().block_on(async move {
+///     let users = vec![
+///         User { name: "alice".to_string(), age: 30, email: None },
+///         User { name: "bob".to_string(), age: 20, email: None },
+///         User { name: "carol".to_string(), age: 40, email: None },
+///     ];
+///     let schema = Schema::new(Query { users }, EmptyMutation, EmptySubscription);
+///     let res = schema
+///         .execute(r#"{ users(filter: { age: { gt: 25 } }, orderBy: [{ age: DESC }]) { name } }"#)
+///         .await
+///         .into_result()
+///         .unwrap()
+///         .data;
+///     assert_eq!(res, value!({ "users": [{ "name": "carol" }, { "name": "alice" }] }));
+/// });
+/// ```
+pub use async_graphql_derive::Filterable;
+
 /// Define a GraphQL interface
 ///
 /// *[See also the Book](https://async-graphql.github.io/async-graphql/en/define_interface.html).*
@@ -716,6 +1096,9 @@ pub use async_graphql_derive::InputObject;
 /// | extends       | Add fields to an entity that's defined in another service | bool | Y |
 /// | visible       | If `false`, it will not be displayed in introspection. *[See also the Book](https://async-graphql.github.io/async-graphql/en/visibility.html).* | bool | Y |
 /// | visible       | Call the specified function. If the return value is `false`, it will not be displayed in introspection. | string | Y |
+/// | meta          | Metadata of the type, which can be read with `Schema::type_metadata`. | key = value list | Y |
+/// | resolve_type  | Call the specified function `fn(&Self) -> Option<T>` (where `T: Into<Cow<'static, str>>`) to decide the `__typename` of a value at runtime. If it returns `None`, the type of the variant is used. The returned name must be one of the possible types. | string | Y |
+/// | node          | Define a [Relay node interface](https://relay.dev/graphql/objectidentification.htm). Adds the `id: ID!` field if it is not declared, and implements [`NodeInterface`], which decodes a global ID with [`Context::decode_global_id`] and loads the object with [`NodeObject::load`]. Merge [`NodeQuery`] into the query root to add the `node(id: ID!)` field. | bool | Y |
 ///
 /// # Field parameters
 ///
@@ -733,6 +1116,7 @@ pub use async_graphql_derive::InputObject;
 /// | requires    | Annotate the required input fieldset from a base type for a resolver. It is used to develop a query plan where the required fields may not be needed by the client, but the service may need additional information from other services. | string | Y |
 /// | visible       | If `false`, it will not be displayed in introspection. *[See also the Book](https://async-graphql.github.io/async-graphql/en/visibility.html).* | bool | Y |
 /// | visible       | Call the specified function. If the return value is `false`, it will not be displayed in introspection. | string | Y |
+/// | meta          | Metadata of the field, which can be read with `Schema::field_metadata`. | key = value list | Y |
 ///
 /// # Field argument parameters
 ///
@@ -748,6 +1132,12 @@ pub use async_graphql_derive::InputObject;
 /// | visible       | Call the specified function. If the return value is `false`, it will not be displayed in introspection. | string | Y |
 /// | secret       | Mark this field as a secret, it will not output the actual value in the log. | bool | Y |
 ///
+/// # Variant parameters
+///
+/// | Attribute    | description                              | Type     | Optional |
+/// |--------------|------------------------------------------|----------|----------|
+/// | dynamic      | The variant holds a [`DynObject`] of a member type added with [`SchemaBuilder::register_possible_type`]. The methods generated for the fields return an error for this variant. | boolean  | Y        |
+///
 /// # Define an interface
 ///
 /// Define TypeA, TypeB, TypeC... Implement the MyInterface
@@ -854,12 +1244,15 @@ pub use async_graphql_derive::Interface;
 /// | name        | Object name               | string   | Y        |
 /// | visible       | If `false`, it will not be displayed in introspection. *[See also the Book](https://async-graphql.github.io/async-graphql/en/visibility.html).* | bool | Y |
 /// | visible       | Call the specified function. If the return value is `false`, it will not be displayed in introspection. | string | Y |
+/// | meta          | Metadata of the type, which can be read with `Schema::type_metadata`. | key = value list | Y |
+/// | resolve_type  | Call the specified function `fn(&Self) -> Option<T>` (where `T: Into<Cow<'static, str>>`) to decide the `__typename` of a value at runtime. If it returns `None`, the type of the variant is used. The returned name must be one of the possible types. | string | Y |
 ///
 /// # Item parameters
 ///
 /// | Attribute    | description                              | Type     | Optional |
 /// |--------------|------------------------------------------|----------|----------|
 /// | flatten      | Similar to serde (flatten)               | boolean  | Y        |
+/// | dynamic      | The variant holds a [`DynObject`] of a member type added with [`SchemaBuilder::register_possible_type`]. | boolean  | Y        |
 /// | visible       | If `false`, it will not be displayed in introspection. *[See also the Book](https://async-graphql.github.io/async-graphql/en/visibility.html).* | bool | Y |
 /// | visible       | Call the specified function. If the return value is `false`, it will not be displayed in introspection. | string | Y |
 ///
@@ -945,9 +1338,12 @@ pub use async_graphql_derive::Union;
 /// | deprecation | Field deprecated          | bool     | Y        |
 /// | deprecation | Field deprecation reason  | string   | Y        |
 /// | guard       | Field of guard            | [`Guard`](guard/trait.Guard.html) | Y        |
+/// | guard         | Expression of the guard of the field, which can be combined with `and` and `or` | string | Y |
+/// | public        | Skip the default guard of the schema, see [`SchemaBuilder::default_guard`](struct.SchemaBuilder.html#method.default_guard) | bool | Y |
 /// | visible       | If `false`, it will not be displayed in introspection. *[See also the Book](https://async-graphql.github.io/async-graphql/en/visibility.html).* | bool | Y |
 /// | visible       | Call the specified function. If the return value is `false`, it will not be displayed in introspection. | string | Y |
 /// | secret       | Mark this field as a secret, it will not output the actual value in the log. | bool | Y |
+/// | topic         | Subscribe to the topic of the [`PubSub`](pubsub/struct.PubSub.html) in the data of the schema, such as `orders:{merchant_id}` where `{merchant_id}` is formatted from the argument. The body of the method must be empty. | string | Y |
 ///
 /// # Field argument parameters
 ///
@@ -978,6 +1374,44 @@ pub use async_graphql_derive::Union;
 ///     }
 /// }
 /// ```
+///
+/// ## Subscribe to a topic
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::pubsub::PubSub;
+/// use futures_util::stream::Stream;
+///
+/// #[derive(SimpleObject, Clone)]
+/// struct Order {
+///     merchant_id: i32,
+///     amount: i32,
+/// }
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// struct SubscriptionRoot;
+///
+/// #[Subscription]
+/// impl SubscriptionRoot {
+///     /// Only receives the orders published to the topic of the merchant.
+///     #[graphql(topic = "orders:{merchant_id}")]
+///     async fn orders(&self, merchant_id: i32) -> impl Stream<Item = Order> {}
+/// }
+///
+/// let pubsub = PubSub::default();
+/// let schema = Schema::build(QueryRoot, EmptyMutation, SubscriptionRoot)
+///     .data(pubsub.clone())
+///     .finish();
+/// pubsub.publish("orders:1", Order { merchant_id: 1, amount: 100 });
+/// ```
 pub use async_graphql_derive::Subscription;
 
 /// Define a Scalar
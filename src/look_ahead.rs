@@ -29,6 +29,9 @@ impl<'a> Lookahead<'a> {
     ///
     /// For example, calling `.field("a")` on `{ a { b } }` will return a lookahead that
     /// represents `{ b }`.
+    ///
+    /// Fields and fragments excluded by `@skip` or `@include` are ignored, the conditions are
+    /// evaluated with the variables of the request.
     pub fn field(&self, name: &str) -> Self {
         let mut fields = Vec::new();
         for field in &self.fields {
@@ -364,4 +367,81 @@ mod tests {
             .await
             .is_ok());
     }
+
+    #[tokio::test]
+    async fn test_look_ahead_skip_include() {
+        #[derive(SimpleObject)]
+        #[graphql(internal)]
+        struct Detail {
+            c: i32,
+        }
+
+        #[derive(SimpleObject)]
+        #[graphql(internal)]
+        struct MyObj {
+            a: i32,
+            detail: Detail,
+        }
+
+        struct Query;
+
+        #[Object(internal)]
+        impl Query {
+            async fn obj(
+                &self,
+                ctx: &Context<'_>,
+                expect_a: bool,
+                expect_c: bool,
+                expect_fields: Vec<String>,
+            ) -> MyObj {
+                let look_ahead = ctx.look_ahead();
+                assert_eq!(look_ahead.field("a").exists(), expect_a);
+                assert_eq!(look_ahead.field("detail").field("c").exists(), expect_c);
+                assert_eq!(
+                    ctx.field()
+                        .selection_set()
+                        .map(|field| field.name())
+                        .collect::<Vec<_>>(),
+                    expect_fields
+                );
+                MyObj {
+                    a: 0,
+                    detail: Detail { c: 0 },
+                }
+            }
+        }
+
+        let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+        let query = r#"query($a: Boolean!, $c: Boolean = false, $expectA: Boolean!, $expectC: Boolean!, $expectFields: [String!]!) {
+            obj(expectA: $expectA, expectC: $expectC, expectFields: $expectFields) {
+                a @include(if: $a)
+                ... on MyObj @skip(if: $c) { x: a }
+                detail { c @include(if: $c) }
+                ... D @include(if: $c)
+            }
+        }
+
+        fragment D on MyObj { detail { c } }"#;
+
+        for variables in [
+            // `a` is only selected by the inline fragment
+            serde_json::json!({
+                "a": false,
+                "expectA": true, "expectC": false, "expectFields": ["a", "detail"],
+            }),
+            serde_json::json!({
+                "a": false, "c": true,
+                "expectA": false, "expectC": true, "expectFields": ["detail", "detail"],
+            }),
+            serde_json::json!({
+                "a": true, "c": true,
+                "expectA": true, "expectC": true, "expectFields": ["a", "detail", "detail"],
+            }),
+        ] {
+            assert!(schema
+                .execute(Request::new(query).variables(Variables::from_json(variables)))
+                .await
+                .is_ok());
+        }
+    }
 }
@@ -0,0 +1,440 @@
+//! Mock data for schemas whose resolvers do not exist yet
+//!
+//! A [`MockSchema`] executes queries against the types of a schema without calling any resolver,
+//! and fills the responses with generated data. This allows the frontend teams to run the real
+//! schema before the resolvers are implemented.
+
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use indexmap::IndexMap;
+
+use crate::parser::types::{
+    Directive, Field, FragmentDefinition, OperationDefinition, OperationType, Selection,
+    SelectionSet,
+};
+use crate::parser::{parse_query, Positioned};
+use crate::registry::{MetaType, MetaTypeName};
+use crate::schema::select_operation;
+use crate::validation::check_rules;
+use crate::{
+    Name, ObjectType, Request, Response, Schema, SchemaEnv, ServerError, ServerResult,
+    SubscriptionType, ValidationMode, Value,
+};
+
+type GeneratorFn = dyn Fn(&MockContext<'_>) -> Value + Send + Sync;
+
+/// The context of a value generated by a [`MockSchema`].
+pub struct MockContext<'a> {
+    /// The name of the type of the value.
+    pub type_name: &'a str,
+
+    /// The name of the object type the field belongs to.
+    pub parent_type: &'a str,
+
+    /// The name of the field.
+    pub field_name: &'a str,
+
+    /// The index of the value in the nearest list containing it, or `0` if there is none.
+    pub index: usize,
+}
+
+/// A schema that resolves queries with generated data.
+///
+/// The values of the scalars and enums are created by the generators, which can be registered for
+/// a type name, or for a field as `Type.field`, which takes precedence. The built-in scalars and
+/// the enums have default generators, and the other scalars are mocked as `"Hello World"`.
+///
+/// Every list has [`MockSchema::list_length`] items, and the values of interfaces and unions are
+/// taken from their possible types in turn. Introspection queries should be executed by the
+/// schema itself.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::mock::MockSchema;
+///
+/// #[derive(SimpleObject)]
+/// struct User {
+///     id: ID,
+///     name: String,
+///     age: i32,
+/// }
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn users(&self) -> Vec<User> {
+///         unimplemented!()
+///     }
+/// }
+///
+/// let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+/// let mock = MockSchema::new(&schema)
+///     .generator("User.name", |ctx| Value::from(format!("user{}", ctx.index)));
+///
+/// assert_eq!(
+///     mock.execute("{ users { id name age } }").data,
+///     value!({
+///         "users": [
+///             { "id": "1", "name": "user0", "age": 42 },
+///             { "id": "2", "name": "user1", "age": 42 },
+///         ]
+///     })
+/// );
+/// ```
+#[derive(Clone)]
+pub struct MockSchema {
+    env: SchemaEnv,
+    validation_mode: ValidationMode,
+    generators: HashMap<String, Arc<GeneratorFn>>,
+    list_length: usize,
+}
+
+impl MockSchema {
+    /// Create a mock schema with the types of `schema`.
+    pub fn new<Query, Mutation, Subscription>(
+        schema: &Schema<Query, Mutation, Subscription>,
+    ) -> Self
+    where
+        Query: ObjectType + 'static,
+        Mutation: ObjectType + 'static,
+        Subscription: SubscriptionType + 'static,
+    {
+        Self {
+            env: schema.env.clone(),
+            validation_mode: schema.validation_mode,
+            generators: Default::default(),
+            list_length: 2,
+        }
+    }
+
+    /// Set the generator of the values of a scalar or enum type, or of a field with the
+    /// `Type.field` name.
+    #[must_use]
+    pub fn generator(
+        mut self,
+        name: impl Into<String>,
+        f: impl Fn(&MockContext<'_>) -> Value + Send + Sync + 'static,
+    ) -> Self {
+        self.generators.insert(name.into(), Arc::new(f));
+        self
+    }
+
+    /// Set the number of items of every list, the default is `2`.
+    #[must_use]
+    pub fn list_length(self, list_length: usize) -> Self {
+        Self {
+            list_length,
+            ..self
+        }
+    }
+
+    /// Execute a GraphQL query with generated data.
+    ///
+    /// Subscriptions produce a single response.
+    pub fn execute(&self, request: impl Into<Request>) -> Response {
+        match self.execute_inner(request.into()) {
+            Ok(data) => Response::new(data),
+            Err(errors) => Response::from_errors(errors),
+        }
+    }
+
+    fn execute_inner(&self, request: Request) -> Result<Value, Vec<ServerError>> {
+        let registry = &self.env.registry;
+        let document = parse_query(&request.query).map_err(|err| vec![err.into()])?;
+        check_rules(
+            registry,
+            &document,
+            Some(&request.variables),
+            self.validation_mode,
+        )?;
+        let (_, operation) =
+            select_operation(document.operations, request.operation_name.as_deref())
+                .map_err(|err| vec![err])?;
+
+        let root_type = match operation.node.ty {
+            OperationType::Query => Some(&registry.query_type),
+            OperationType::Mutation => registry.mutation_type.as_ref(),
+            OperationType::Subscription => registry.subscription_type.as_ref(),
+        }
+        .ok_or_else(|| {
+            vec![ServerError::new(
+                format!("Schema is not configured for {}s.", operation.node.ty),
+                None,
+            )]
+        })?;
+
+        let resolver = MockResolver {
+            mock: self,
+            fragments: &document.fragments,
+            operation: &operation.node,
+            variables: &request.variables,
+            next_id: Cell::new(0),
+        };
+        resolver
+            .resolve_selection_set(root_type, &operation.node.selection_set.node, 0)
+            .map_err(|err| vec![err])
+    }
+}
+
+struct MockResolver<'a> {
+    mock: &'a MockSchema,
+    fragments: &'a HashMap<Name, Positioned<FragmentDefinition>>,
+    operation: &'a OperationDefinition,
+    variables: &'a crate::Variables,
+    next_id: Cell<usize>,
+}
+
+impl<'a> MockResolver<'a> {
+    fn resolve_selection_set(
+        &self,
+        type_name: &str,
+        selection_set: &SelectionSet,
+        index: usize,
+    ) -> ServerResult<Value> {
+        let mut fields = IndexMap::new();
+        self.collect_fields(type_name, selection_set, index, &mut fields)?;
+        Ok(Value::Object(fields))
+    }
+
+    fn collect_fields(
+        &self,
+        type_name: &str,
+        selection_set: &SelectionSet,
+        index: usize,
+        fields: &mut IndexMap<Name, Value>,
+    ) -> ServerResult<()> {
+        for selection in &selection_set.items {
+            match &selection.node {
+                Selection::Field(field) => {
+                    if self.is_skip(&field.node.directives) {
+                        continue;
+                    }
+
+                    let value = match field.node.name.node.as_str() {
+                        "__typename" => Value::String(type_name.to_string()),
+                        "__schema" | "__type" => {
+                            return Err(ServerError::new(
+                                "Introspection is not supported by the mock schema.",
+                                Some(field.pos),
+                            ))
+                        }
+                        field_name => {
+                            let ty = self
+                                .mock
+                                .env
+                                .registry
+                                .types
+                                .get(type_name)
+                                .and_then(|ty| ty.field_by_name(field_name))
+                                .map(|field| field.ty.as_str())
+                                .ok_or_else(|| {
+                                    ServerError::new(
+                                        format!(
+                                            r#"Unknown field "{}" on type "{}"."#,
+                                            field_name, type_name
+                                        ),
+                                        Some(field.pos),
+                                    )
+                                })?;
+                            self.resolve_value(type_name, &field.node, ty, index)?
+                        }
+                    };
+
+                    match fields.get_mut(&field.node.response_key().node) {
+                        Some(existing) => merge(existing, value),
+                        None => {
+                            fields.insert(field.node.response_key().node.clone(), value);
+                        }
+                    }
+                }
+                Selection::FragmentSpread(spread) => {
+                    if self.is_skip(&spread.node.directives) {
+                        continue;
+                    }
+                    if let Some(fragment) = self.fragments.get(&spread.node.fragment_name.node) {
+                        if self.applies(type_name, &fragment.node.type_condition.node.on.node) {
+                            self.collect_fields(
+                                type_name,
+                                &fragment.node.selection_set.node,
+                                index,
+                                fields,
+                            )?;
+                        }
+                    }
+                }
+                Selection::InlineFragment(fragment) => {
+                    if self.is_skip(&fragment.node.directives) {
+                        continue;
+                    }
+                    let applies = fragment
+                        .node
+                        .type_condition
+                        .as_ref()
+                        .map(|condition| self.applies(type_name, &condition.node.on.node))
+                        .unwrap_or(true);
+                    if applies {
+                        self.collect_fields(
+                            type_name,
+                            &fragment.node.selection_set.node,
+                            index,
+                            fields,
+                        )?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn resolve_value(
+        &self,
+        parent_type: &str,
+        field: &Field,
+        ty: &str,
+        index: usize,
+    ) -> ServerResult<Value> {
+        match MetaTypeName::create(ty) {
+            MetaTypeName::NonNull(ty) => self.resolve_value(parent_type, field, ty, index),
+            MetaTypeName::List(ty) => (0..self.mock.list_length)
+                .map(|index| self.resolve_value(parent_type, field, ty, index))
+                .collect::<ServerResult<Vec<_>>>()
+                .map(Value::List),
+            MetaTypeName::Named(type_name) => {
+                let meta_type = match self.mock.env.registry.types.get(type_name) {
+                    Some(meta_type) => meta_type,
+                    None => return Ok(Value::Null),
+                };
+                match meta_type {
+                    MetaType::Object { .. } => {
+                        self.resolve_selection_set(type_name, &field.selection_set.node, index)
+                    }
+                    MetaType::Interface { possible_types, .. }
+                    | MetaType::Union { possible_types, .. } => {
+                        match possible_types.get_index(index % possible_types.len().max(1)) {
+                            Some(concrete_type) => self.resolve_selection_set(
+                                concrete_type,
+                                &field.selection_set.node,
+                                index,
+                            ),
+                            None => Ok(Value::Null),
+                        }
+                    }
+                    _ => Ok(self.generate(
+                        meta_type,
+                        &MockContext {
+                            type_name,
+                            parent_type,
+                            field_name: &field.name.node,
+                            index,
+                        },
+                    )),
+                }
+            }
+        }
+    }
+
+    fn generate(&self, meta_type: &MetaType, ctx: &MockContext<'_>) -> Value {
+        let generator = self
+            .mock
+            .generators
+            .get(&format!("{}.{}", ctx.parent_type, ctx.field_name))
+            .or_else(|| self.mock.generators.get(ctx.type_name));
+        if let Some(generator) = generator {
+            return generator(ctx);
+        }
+
+        match meta_type {
+            MetaType::Enum { enum_values, .. } => enum_values
+                .keys()
+                .nth(ctx.index % enum_values.len().max(1))
+                .map(|value| Value::Enum(Name::new(value)))
+                .unwrap_or(Value::Null),
+            _ => match ctx.type_name {
+                "Int" => Value::from(42),
+                "Float" => Value::from(4.2),
+                "Boolean" => Value::from(true),
+                "ID" => {
+                    let id = self.next_id.get() + 1;
+                    self.next_id.set(id);
+                    Value::String(id.to_string())
+                }
+                _ => Value::from("Hello World"),
+            },
+        }
+    }
+
+    fn applies(&self, type_name: &str, condition: &str) -> bool {
+        type_name == condition
+            || self
+                .mock
+                .env
+                .registry
+                .types
+                .get(condition)
+                .map(|ty| ty.is_possible_type(type_name))
+                .unwrap_or_default()
+    }
+
+    fn is_skip(&self, directives: &[Positioned<Directive>]) -> bool {
+        directives.iter().any(|directive| {
+            let include = match directive.node.name.node.as_str() {
+                "skip" => false,
+                "include" => true,
+                _ => return false,
+            };
+            let condition = directive
+                .node
+                .get_argument("if")
+                .map(|value| {
+                    value
+                        .node
+                        .clone()
+                        .into_const_with(|name| Ok::<_, Infallible>(self.var_value(&name)))
+                        .unwrap()
+                })
+                .unwrap_or(Value::Boolean(include));
+            include != matches!(condition, Value::Boolean(true))
+        })
+    }
+
+    fn var_value(&self, name: &str) -> Value {
+        self.variables
+            .get(name)
+            .cloned()
+            .or_else(|| {
+                self.operation
+                    .variable_definitions
+                    .iter()
+                    .find(|def| def.node.name.node == name)
+                    .and_then(|def| def.node.default_value().cloned())
+            })
+            .unwrap_or(Value::Null)
+    }
+}
+
+fn merge(target: &mut Value, value: Value) {
+    match (target, value) {
+        (Value::Object(target), Value::Object(value)) => {
+            for (name, value) in value {
+                match target.get_mut(&name) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        target.insert(name, value);
+                    }
+                }
+            }
+        }
+        (Value::List(target), Value::List(value)) => {
+            for (existing, value) in target.iter_mut().zip(value) {
+                merge(existing, value);
+            }
+        }
+        _ => {}
+    }
+}
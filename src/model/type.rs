@@ -1,3 +1,5 @@
+use indexmap::IndexSet;
+
 use crate::model::{__EnumValue, __Field, __InputValue, __TypeKind};
 use crate::{registry, Context, Object};
 
@@ -43,6 +45,56 @@ impl<'a> __Type<'a> {
     }
 }
 
+impl<'a> __Type<'a> {
+    /// Collects the interfaces implemented by a type, including the interfaces implemented by
+    /// those interfaces.
+    fn collect_interfaces(&self, name: &str, interfaces: &mut IndexSet<&'a str>) {
+        if let Some(implements) = self.registry.implements.get(name) {
+            for interface in implements {
+                if interfaces.insert(interface.as_str()) {
+                    self.collect_interfaces(interface, interfaces);
+                }
+            }
+        }
+    }
+
+    /// Collects the object types of an interface, replacing the interfaces that implement it with
+    /// their own possible types.
+    fn collect_object_types(
+        &self,
+        possible_types: &'a IndexSet<String>,
+        object_types: &mut IndexSet<&'a str>,
+    ) {
+        for name in possible_types {
+            match self.registry.types.get(name) {
+                Some(registry::MetaType::Interface { possible_types, .. }) => {
+                    self.collect_object_types(possible_types, object_types)
+                }
+                _ => {
+                    object_types.insert(name.as_str());
+                }
+            }
+        }
+    }
+
+    fn visible_types(
+        &self,
+        ctx: &Context<'_>,
+        names: impl IntoIterator<Item = &'a str>,
+    ) -> Vec<__Type<'a>> {
+        names
+            .into_iter()
+            .filter(|name| {
+                self.registry
+                    .types
+                    .get(*name)
+                    .map_or(false, |ty| ty.is_visible(ctx))
+            })
+            .map(|name| __Type::new(self.registry, name))
+            .collect()
+    }
+}
+
 /// The fundamental unit of any GraphQL Schema is the type. There are many kinds of types in GraphQL as represented by the `__TypeKind` enum.
 ///
 /// Depending on the kind of a type, certain fields describe information about that type. Scalar types provide no information beyond a name and description, while Enum types provide their values. Object and Interface types provide the fields they describe. Abstract types, Union and Interface, provide the Object types possible at runtime. List and NonNull types compose other types.
@@ -117,17 +169,14 @@ impl<'a> __Type<'a> {
         }
     }
 
-    async fn interfaces(&self) -> Option<Vec<__Type<'a>>> {
-        if let TypeDetail::Named(registry::MetaType::Object { name, .. }) = &self.detail {
-            Some(
-                self.registry
-                    .implements
-                    .get(name)
-                    .unwrap_or(&Default::default())
-                    .iter()
-                    .map(|ty| __Type::new(self.registry, ty))
-                    .collect(),
-            )
+    async fn interfaces(&self, ctx: &Context<'_>) -> Option<Vec<__Type<'a>>> {
+        if let TypeDetail::Named(
+            registry::MetaType::Object { name, .. } | registry::MetaType::Interface { name, .. },
+        ) = &self.detail
+        {
+            let mut interfaces = IndexSet::new();
+            self.collect_interfaces(name, &mut interfaces);
+            Some(self.visible_types(ctx, interfaces))
         } else {
             None
         }
@@ -137,24 +186,23 @@ impl<'a> __Type<'a> {
         if let TypeDetail::Named(registry::MetaType::Interface { possible_types, .. }) =
             &self.detail
         {
-            Some(
-                possible_types
-                    .iter()
-                    .map(|ty| __Type::new(self.registry, ty))
-                    .collect(),
-            )
+            let mut object_types = IndexSet::new();
+            self.collect_object_types(possible_types, &mut object_types);
+            Some(self.visible_types(ctx, object_types))
         } else if let TypeDetail::Named(registry::MetaType::Union { union_values, .. }) =
             &self.detail
         {
             Some(
-                union_values
-                    .values()
-                    .filter(|value| match &value.visible {
-                        Some(f) => f(ctx),
-                        None => true,
-                    })
-                    .map(|ty| __Type::new(self.registry, &ty.name))
-                    .collect(),
+                self.visible_types(
+                    ctx,
+                    union_values
+                        .values()
+                        .filter(|value| match &value.visible {
+                            Some(f) => f(ctx),
+                            None => true,
+                        })
+                        .map(|value| value.name.as_str()),
+                ),
             )
         } else {
             None
@@ -219,6 +267,14 @@ impl<'a> __Type<'a> {
         }
     }
 
+    async fn is_one_of(&self) -> Option<bool> {
+        if let TypeDetail::Named(registry::MetaType::InputObject { oneof, .. }) = &self.detail {
+            Some(*oneof)
+        } else {
+            None
+        }
+    }
+
     #[graphql(name = "specifiedByURL")]
     async fn specified_by_url(&self) -> Option<&'a str> {
         if let TypeDetail::Named(registry::MetaType::Scalar {
@@ -0,0 +1,248 @@
+use std::any::Any;
+use std::fmt::{self, Display, Formatter, Write};
+
+use crate::parser::types::OperationType;
+use crate::{Data, Name, ObjectType, Request, Response, Schema, SubscriptionType, Value};
+
+/// A field in a selection set built by [`OperationBuilder`].
+///
+/// A `&str` or `String` can be converted into a field without arguments or sub-selections.
+#[derive(Debug, Clone)]
+pub struct FieldBuilder {
+    name: Name,
+    alias: Option<Name>,
+    arguments: Vec<(Name, Value)>,
+    selection_set: Vec<FieldBuilder>,
+}
+
+impl FieldBuilder {
+    /// Create a field with the given name.
+    pub fn new(name: impl AsRef<str>) -> Self {
+        Self {
+            name: Name::new(name),
+            alias: None,
+            arguments: Vec::new(),
+            selection_set: Vec::new(),
+        }
+    }
+
+    /// Set the alias of this field.
+    #[must_use]
+    pub fn alias(mut self, alias: impl AsRef<str>) -> Self {
+        self.alias = Some(Name::new(alias));
+        self
+    }
+
+    /// Add an argument to this field.
+    #[must_use]
+    pub fn argument(mut self, name: impl AsRef<str>, value: impl Into<Value>) -> Self {
+        self.arguments.push((Name::new(name), value.into()));
+        self
+    }
+
+    /// Add all entries of an object value as arguments to this field.
+    ///
+    /// Values that are not objects are ignored.
+    #[must_use]
+    pub fn arguments(mut self, arguments: Value) -> Self {
+        if let Value::Object(map) = arguments {
+            self.arguments.extend(map);
+        }
+        self
+    }
+
+    /// Add fields to the selection set of this field.
+    #[must_use]
+    pub fn select<T: Into<FieldBuilder>>(mut self, fields: impl IntoIterator<Item = T>) -> Self {
+        self.selection_set
+            .extend(fields.into_iter().map(Into::into));
+        self
+    }
+}
+
+impl From<&str> for FieldBuilder {
+    fn from(name: &str) -> Self {
+        FieldBuilder::new(name)
+    }
+}
+
+impl From<String> for FieldBuilder {
+    fn from(name: String) -> Self {
+        FieldBuilder::new(name)
+    }
+}
+
+impl Display for FieldBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(alias) = &self.alias {
+            write!(f, "{}: ", alias)?;
+        }
+        f.write_str(&self.name)?;
+        if !self.arguments.is_empty() {
+            f.write_char('(')?;
+            for (idx, (name, value)) in self.arguments.iter().enumerate() {
+                if idx > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{}: {}", name, value)?;
+            }
+            f.write_char(')')?;
+        }
+        if !self.selection_set.is_empty() {
+            write_selection_set(&self.selection_set, f)?;
+        }
+        Ok(())
+    }
+}
+
+fn write_selection_set(fields: &[FieldBuilder], f: &mut Formatter<'_>) -> fmt::Result {
+    f.write_str(" {")?;
+    for field in fields {
+        write!(f, " {}", field)?;
+    }
+    f.write_str(" }")
+}
+
+/// A builder for executing an operation against a local schema without writing the GraphQL
+/// document by hand.
+///
+/// Created by [`Schema::query`] or [`Schema::mutation`].
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// #[derive(SimpleObject)]
+/// struct User {
+///     id: i32,
+///     name: String,
+/// }
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn user(&self, id: i32) -> User {
+///         User { id, name: format!("user{}", id) }
+///     }
+/// }
+///
+/// tokio::runtime::Runtime::new().unwrap().block_on(async move {
+///     let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+///     let res = schema
+///         .query()
+///         .field("user", value!({ "id": 10 }))
+///         .select(["id", "name"])
+///         .execute()
+///         .await;
+///     assert_eq!(res.data, value!({ "user": { "id": 10, "name": "user10" } }));
+/// });
+/// ```
+pub struct OperationBuilder<'a, Query, Mutation, Subscription> {
+    schema: &'a Schema<Query, Mutation, Subscription>,
+    ty: OperationType,
+    selection_set: Vec<FieldBuilder>,
+    data: Data,
+}
+
+impl<'a, Query, Mutation, Subscription> OperationBuilder<'a, Query, Mutation, Subscription>
+where
+    Query: ObjectType + 'static,
+    Mutation: ObjectType + 'static,
+    Subscription: SubscriptionType + 'static,
+{
+    /// Add a root field with the arguments taken from an object value.
+    ///
+    /// Pass `Value::Null` if the field has no arguments.
+    #[must_use]
+    pub fn field(mut self, name: impl AsRef<str>, arguments: Value) -> Self {
+        self.selection_set
+            .push(FieldBuilder::new(name).arguments(arguments));
+        self
+    }
+
+    /// Add a fully specified root field.
+    #[must_use]
+    pub fn field_with(mut self, field: impl Into<FieldBuilder>) -> Self {
+        self.selection_set.push(field.into());
+        self
+    }
+
+    /// Add fields to the selection set of the most recently added root field.
+    ///
+    /// If no root field has been added, the fields are added to the root selection set.
+    #[must_use]
+    pub fn select<T: Into<FieldBuilder>>(mut self, fields: impl IntoIterator<Item = T>) -> Self {
+        match self.selection_set.last_mut() {
+            Some(field) => field
+                .selection_set
+                .extend(fields.into_iter().map(Into::into)),
+            None => self
+                .selection_set
+                .extend(fields.into_iter().map(Into::into)),
+        }
+        self
+    }
+
+    /// Insert some data for this request.
+    #[must_use]
+    pub fn data<D: Any + Send + Sync>(mut self, data: D) -> Self {
+        self.data.insert(data);
+        self
+    }
+
+    /// Returns the GraphQL document of this operation.
+    pub fn to_query_string(&self) -> String {
+        struct Document<'a>(OperationType, &'a [FieldBuilder]);
+
+        impl<'a> Display for Document<'a> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.0)?;
+                write_selection_set(self.1, f)
+            }
+        }
+
+        Document(self.ty, &self.selection_set).to_string()
+    }
+
+    /// Convert this builder into a request.
+    pub fn into_request(self) -> Request {
+        let mut request = Request::new(self.to_query_string());
+        request.data = self.data;
+        request
+    }
+
+    /// Execute the operation.
+    pub async fn execute(self) -> Response {
+        let schema = self.schema;
+        schema.execute(self.into_request()).await
+    }
+}
+
+impl<Query, Mutation, Subscription> Schema<Query, Mutation, Subscription>
+where
+    Query: ObjectType + 'static,
+    Mutation: ObjectType + 'static,
+    Subscription: SubscriptionType + 'static,
+{
+    /// Start building a query operation to execute against this schema.
+    pub fn query(&self) -> OperationBuilder<'_, Query, Mutation, Subscription> {
+        OperationBuilder {
+            schema: self,
+            ty: OperationType::Query,
+            selection_set: Vec::new(),
+            data: Data::default(),
+        }
+    }
+
+    /// Start building a mutation operation to execute against this schema.
+    pub fn mutation(&self) -> OperationBuilder<'_, Query, Mutation, Subscription> {
+        OperationBuilder {
+            schema: self,
+            ty: OperationType::Mutation,
+            selection_set: Vec::new(),
+            data: Data::default(),
+        }
+    }
+}
@@ -0,0 +1,312 @@
+//! Publish messages to the subscribers of a topic
+//!
+//! A [`PubSub`] routes every message only to the streams subscribed to its topic, so a
+//! subscription resolver does not need to filter all the messages of the application. The topic
+//! of a subscription field can be declared with `#[graphql(topic = "orders:{merchant_id}")]`, see
+//! [`Subscription`](crate::Subscription).
+//!
+//! A [`Broker`] publishes serialized messages through a [`SubscriptionBroker`], so the
+//! subscribers of several instances of an application can be served by a message broker such as
+//! Redis, NATS or Postgres `LISTEN`/`NOTIFY`. The [`SimpleBroker`] is the in-process backend.
+
+use std::any::{Any, TypeId};
+use std::collections::{HashMap, VecDeque};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use futures_util::stream::{BoxStream, Stream, StreamExt};
+use futures_util::task::AtomicWaker;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::{Error, Result};
+
+type TopicKey = (String, TypeId);
+
+#[derive(Default)]
+struct Topics {
+    next_id: usize,
+    subscribers: HashMap<TopicKey, HashMap<usize, Arc<dyn Any + Send + Sync>>>,
+}
+
+struct Queue<T> {
+    messages: Mutex<VecDeque<T>>,
+    waker: AtomicWaker,
+}
+
+/// An in-process publisher of messages to the subscribers of topics.
+///
+/// Cloning a `PubSub` returns a handle to the same topics. The subscribers of a topic only
+/// receive the messages of the same type, so different payloads can share a topic name.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::pubsub::PubSub;
+/// use futures_util::stream::StreamExt;
+///
+/// tokio::runtime::Runtime::new().unwrap().block_on(async move {
+///     let pubsub = PubSub::default();
+///     let mut orders = pubsub.subscribe::<i32>("orders:1");
+///
+///     assert_eq!(pubsub.publish("orders:2", 10), 0);
+///     assert_eq!(pubsub.publish("orders:1", 20), 1);
+///     assert_eq!(orders.next().await, Some(20));
+/// });
+/// ```
+#[derive(Clone, Default)]
+pub struct PubSub {
+    topics: Arc<Mutex<Topics>>,
+}
+
+impl PubSub {
+    /// Publish a message to the subscribers of the topic, and return the number of subscribers
+    /// that received it.
+    pub fn publish<T: Clone + Send + Sync + 'static>(&self, topic: &str, msg: T) -> usize {
+        let topics = self.topics.lock().unwrap();
+        let subscribers = match topics
+            .subscribers
+            .get(&(topic.to_string(), TypeId::of::<T>()))
+        {
+            Some(subscribers) => subscribers,
+            None => return 0,
+        };
+        for queue in subscribers.values() {
+            let queue = queue.downcast_ref::<Queue<T>>().unwrap();
+            queue.messages.lock().unwrap().push_back(msg.clone());
+            queue.waker.wake();
+        }
+        subscribers.len()
+    }
+
+    /// Subscribe to the messages of type `T` published to the topic.
+    ///
+    /// The subscription ends when the stream is dropped.
+    pub fn subscribe<T: Clone + Send + Sync + 'static>(
+        &self,
+        topic: impl Into<String>,
+    ) -> TopicStream<T> {
+        let key = (topic.into(), TypeId::of::<T>());
+        let queue = Arc::new(Queue {
+            messages: Mutex::new(VecDeque::new()),
+            waker: AtomicWaker::new(),
+        });
+        let mut topics = self.topics.lock().unwrap();
+        let id = topics.next_id;
+        topics.next_id += 1;
+        topics
+            .subscribers
+            .entry(key.clone())
+            .or_default()
+            .insert(id, queue.clone());
+        TopicStream {
+            pubsub: self.clone(),
+            key,
+            id,
+            queue,
+        }
+    }
+
+    /// Returns the number of subscribers of the messages of type `T` published to the topic.
+    pub fn subscriber_count<T: 'static>(&self, topic: &str) -> usize {
+        self.topics
+            .lock()
+            .unwrap()
+            .subscribers
+            .get(&(topic.to_string(), TypeId::of::<T>()))
+            .map(HashMap::len)
+            .unwrap_or_default()
+    }
+}
+
+/// A stream of the messages published to a topic, created by [`PubSub::subscribe`].
+pub struct TopicStream<T> {
+    pubsub: PubSub,
+    key: TopicKey,
+    id: usize,
+    queue: Arc<Queue<T>>,
+}
+
+impl<T> Stream for TopicStream<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if let Some(msg) = self.queue.messages.lock().unwrap().pop_front() {
+            return Poll::Ready(Some(msg));
+        }
+        self.queue.waker.register(cx.waker());
+        match self.queue.messages.lock().unwrap().pop_front() {
+            Some(msg) => Poll::Ready(Some(msg)),
+            None => Poll::Pending,
+        }
+    }
+}
+
+impl<T> Drop for TopicStream<T> {
+    fn drop(&mut self) {
+        let mut topics = self.pubsub.topics.lock().unwrap();
+        if let Some(subscribers) = topics.subscribers.get_mut(&self.key) {
+            subscribers.remove(&self.id);
+            if subscribers.is_empty() {
+                topics.subscribers.remove(&self.key);
+            }
+        }
+    }
+}
+
+/// A backend of a [`Broker`], which transports the serialized messages of the topics.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::pubsub::SubscriptionBroker;
+/// use async_graphql::Result;
+/// use futures_util::stream::BoxStream;
+///
+/// struct RedisBroker {
+///     // client: redis::Client,
+/// }
+///
+/// #[async_trait::async_trait]
+/// impl SubscriptionBroker for RedisBroker {
+///     async fn publish(&self, topic: &str, payload: String) -> Result<()> {
+///         // PUBLISH topic payload
+///         todo!()
+///     }
+///
+///     async fn subscribe(&self, topic: &str) -> Result<BoxStream<'static, String>> {
+///         // SUBSCRIBE topic
+///         todo!()
+///     }
+/// }
+/// ```
+#[async_trait::async_trait]
+pub trait SubscriptionBroker: Send + Sync + 'static {
+    /// Publish a serialized message to the topic.
+    async fn publish(&self, topic: &str, payload: String) -> Result<()>;
+
+    /// Subscribe to the serialized messages published to the topic.
+    ///
+    /// The subscription ends when the stream is dropped.
+    async fn subscribe(&self, topic: &str) -> Result<BoxStream<'static, String>>;
+}
+
+/// An in-process [`SubscriptionBroker`], backed by a [`PubSub`].
+#[derive(Clone, Default)]
+pub struct SimpleBroker {
+    pubsub: PubSub,
+}
+
+#[async_trait::async_trait]
+impl SubscriptionBroker for SimpleBroker {
+    async fn publish(&self, topic: &str, payload: String) -> Result<()> {
+        self.pubsub.publish(topic, payload);
+        Ok(())
+    }
+
+    async fn subscribe(&self, topic: &str) -> Result<BoxStream<'static, String>> {
+        Ok(self.pubsub.subscribe::<String>(topic).boxed())
+    }
+}
+
+/// Publish typed messages to the subscribers of topics through a [`SubscriptionBroker`].
+///
+/// The messages are serialized as JSON. A topic can be narrowed by a key, such as the id of a
+/// merchant, with [`Broker::publish_keyed`] and [`Broker::subscribe_keyed`], which append the
+/// serialized key to the name of the topic.
+///
+/// Cloning a `Broker` returns a handle to the same backend. The default broker uses a
+/// [`SimpleBroker`].
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::pubsub::Broker;
+/// use futures_util::stream::StreamExt;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize, Debug, PartialEq)]
+/// struct Order {
+///     merchant_id: i32,
+///     amount: i32,
+/// }
+///
+/// tokio::runtime::Runtime::new().unwrap().block_on(async move {
+///     let broker = Broker::default();
+///     let mut orders = broker.subscribe_keyed::<Order, _>("orders", &1).await.unwrap();
+///
+///     broker.publish_keyed("orders", &2, &Order { merchant_id: 2, amount: 10 }).await.unwrap();
+///     broker.publish_keyed("orders", &1, &Order { merchant_id: 1, amount: 20 }).await.unwrap();
+///     assert_eq!(orders.next().await, Some(Order { merchant_id: 1, amount: 20 }));
+/// });
+/// ```
+#[derive(Clone)]
+pub struct Broker {
+    backend: Arc<dyn SubscriptionBroker>,
+}
+
+impl Default for Broker {
+    fn default() -> Self {
+        Self::new(SimpleBroker::default())
+    }
+}
+
+impl Broker {
+    /// Create a broker with the backend.
+    pub fn new(backend: impl SubscriptionBroker) -> Self {
+        Self {
+            backend: Arc::new(backend),
+        }
+    }
+
+    /// Returns the name of the topic narrowed by the key, such as `orders:1` or
+    /// `orders:[1,"EUR"]`.
+    pub fn keyed_topic<K: Serialize + ?Sized>(topic: &str, key: &K) -> Result<String> {
+        let key = serde_json::to_string(key)
+            .map_err(|err| Error::new(format!("Failed to serialize the topic key: {}", err)))?;
+        Ok(format!("{}:{}", topic, key))
+    }
+
+    /// Publish a message to the subscribers of the topic.
+    pub async fn publish<T: Serialize + ?Sized>(&self, topic: &str, msg: &T) -> Result<()> {
+        let payload = serde_json::to_string(msg)
+            .map_err(|err| Error::new(format!("Failed to serialize the message: {}", err)))?;
+        self.backend.publish(topic, payload).await
+    }
+
+    /// Publish a message to the subscribers of the topic narrowed by the key.
+    pub async fn publish_keyed<T: Serialize + ?Sized, K: Serialize + ?Sized>(
+        &self,
+        topic: &str,
+        key: &K,
+        msg: &T,
+    ) -> Result<()> {
+        self.publish(&Self::keyed_topic(topic, key)?, msg).await
+    }
+
+    /// Subscribe to the messages of type `T` published to the topic.
+    ///
+    /// The messages which cannot be deserialized as `T` are skipped. The subscription ends when
+    /// the stream is dropped.
+    pub async fn subscribe<T: DeserializeOwned + Send + 'static>(
+        &self,
+        topic: &str,
+    ) -> Result<BoxStream<'static, T>> {
+        Ok(self
+            .backend
+            .subscribe(topic)
+            .await?
+            .filter_map(|payload| async move { serde_json::from_str(&payload).ok() })
+            .boxed())
+    }
+
+    /// Subscribe to the messages of type `T` published to the topic narrowed by the key.
+    pub async fn subscribe_keyed<T: DeserializeOwned + Send + 'static, K: Serialize + ?Sized>(
+        &self,
+        topic: &str,
+        key: &K,
+    ) -> Result<BoxStream<'static, T>> {
+        self.subscribe(&Self::keyed_topic(topic, key)?).await
+    }
+}
@@ -0,0 +1,201 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::registry::{MetaField, MetaType, MetaTypeName, Registry};
+
+/// A violation of the [GraphQL Cursor Connections Specification](https://relay.dev/graphql/connections.htm),
+/// found by [`Schema::audit_connections`](crate::Schema::audit_connections).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionWarning {
+    /// The coordinate of the type or the field, such as `Query.users` or `UserConnection`.
+    pub coordinate: String,
+    /// The description of the violation.
+    pub message: String,
+}
+
+impl Display for ConnectionWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.coordinate, self.message)
+    }
+}
+
+/// The arguments used for pagination that are not the ones of the specification.
+const OFFSET_ARGUMENTS: &[&str] = &[
+    "limit", "offset", "page", "pageSize", "perPage", "count", "skip", "take", "cursor",
+];
+
+fn is_connection_name(name: &str) -> bool {
+    name.ends_with("Connection") && name != "Connection"
+}
+
+struct Audit<'a> {
+    registry: &'a Registry,
+    warnings: Vec<ConnectionWarning>,
+}
+
+impl<'a> Audit<'a> {
+    fn warn(&mut self, coordinate: impl Into<String>, message: impl Into<String>) {
+        self.warnings.push(ConnectionWarning {
+            coordinate: coordinate.into(),
+            message: message.into(),
+        });
+    }
+
+    fn field(&self, ty: &str, name: &str) -> Option<&'a MetaField> {
+        self.registry.types.get(ty)?.field_by_name(name)
+    }
+
+    fn check_connection(&mut self, name: &str) {
+        match self.field(name, "pageInfo") {
+            Some(field) => {
+                if !MetaTypeName::create(&field.ty).is_non_null() {
+                    self.warn(
+                        format!("{}.pageInfo", name),
+                        "The field must return a non-null `PageInfo`.",
+                    );
+                }
+                self.check_page_info(MetaTypeName::concrete_typename(&field.ty));
+            }
+            None => self.warn(name, "The connection has no `pageInfo` field."),
+        }
+
+        match self.field(name, "edges") {
+            Some(field) => {
+                if !MetaTypeName::create(&field.ty).is_list() {
+                    self.warn(format!("{}.edges", name), "The field must return a list.");
+                }
+                self.check_edge(MetaTypeName::concrete_typename(&field.ty));
+            }
+            None => self.warn(name, "The connection has no `edges` field."),
+        }
+    }
+
+    fn check_page_info(&mut self, name: &str) {
+        for field_name in ["hasPreviousPage", "hasNextPage"] {
+            match self.field(name, field_name) {
+                Some(field) if field.ty == "Boolean!" => {}
+                Some(_) => self.warn(
+                    format!("{}.{}", name, field_name),
+                    "The field must return `Boolean!`.",
+                ),
+                None => self.warn(name, format!("The type has no `{}` field.", field_name)),
+            }
+        }
+        for field_name in ["startCursor", "endCursor"] {
+            match self.field(name, field_name) {
+                Some(field) if MetaTypeName::create(&field.ty).is_non_null() => self.warn(
+                    format!("{}.{}", name, field_name),
+                    "The field must be nullable, because there is no cursor if there are no results.",
+                ),
+                Some(_) => {}
+                None => self.warn(name, format!("The type has no `{}` field.", field_name)),
+            }
+        }
+    }
+
+    fn check_edge(&mut self, name: &str) {
+        match self.field(name, "node") {
+            Some(field) if MetaTypeName::create(&field.ty).is_list() => self.warn(
+                format!("{}.node", name),
+                "The field must not return a list.",
+            ),
+            Some(_) => {}
+            None => self.warn(name, "The edge has no `node` field."),
+        }
+        match self.field(name, "cursor") {
+            Some(field) => {
+                let ty = MetaTypeName::concrete_typename(&field.ty);
+                let is_string =
+                    matches!(self.registry.types.get(ty), Some(MetaType::Scalar { .. }))
+                        && !matches!(ty, "Int" | "Float" | "Boolean");
+                if !is_string || MetaTypeName::create(&field.ty).is_list() {
+                    self.warn(
+                        format!("{}.cursor", name),
+                        "The field must return a scalar serialized as a string.",
+                    );
+                }
+            }
+            None => self.warn(name, "The edge has no `cursor` field."),
+        }
+    }
+
+    fn check_arguments(&mut self, coordinate: &str, field: &MetaField) {
+        let has_arg = |name: &str| field.args.contains_key(name);
+        let forward = has_arg("first") || has_arg("after");
+        let backward = has_arg("last") || has_arg("before");
+
+        if !forward && !backward {
+            self.warn(
+                coordinate,
+                "The field has neither the `first` and `after` arguments nor the `last` and `before` arguments.",
+            );
+        }
+        for (arg, pair) in [
+            ("first", "after"),
+            ("after", "first"),
+            ("last", "before"),
+            ("before", "last"),
+        ] {
+            if has_arg(arg) && !has_arg(pair) {
+                self.warn(
+                    coordinate,
+                    format!("The `{}` argument is declared without `{}`.", arg, pair),
+                );
+            }
+        }
+        for arg in field.args.values() {
+            let name = arg.name;
+            if matches!(name, "first" | "last" | "after" | "before") {
+                if MetaTypeName::create(&arg.ty).is_non_null() {
+                    self.warn(
+                        coordinate,
+                        format!("The `{}` argument must be nullable.", name),
+                    );
+                }
+                if matches!(name, "first" | "last") && arg.ty.trim_end_matches('!') != "Int" {
+                    self.warn(
+                        coordinate,
+                        format!("The `{}` argument must be an `Int`.", name),
+                    );
+                }
+            } else if OFFSET_ARGUMENTS.contains(&name) {
+                self.warn(
+                    coordinate,
+                    format!(
+                        "The `{}` argument is not a pagination argument of the specification.",
+                        name
+                    ),
+                );
+            }
+        }
+    }
+}
+
+impl Registry {
+    /// Check the connection types, whose name ends with `Connection`, and the fields returning
+    /// them against the GraphQL Cursor Connections Specification.
+    pub(crate) fn audit_connections(&self) -> Vec<ConnectionWarning> {
+        let mut audit = Audit {
+            registry: self,
+            warnings: Vec::new(),
+        };
+
+        for ty in self.types.values() {
+            let name = ty.name();
+            if name.starts_with("__") {
+                continue;
+            }
+            if is_connection_name(name) && matches!(ty, MetaType::Object { .. }) {
+                audit.check_connection(name);
+            }
+            if let Some(fields) = ty.fields() {
+                for field in fields.values() {
+                    if is_connection_name(MetaTypeName::concrete_typename(&field.ty)) {
+                        audit.check_arguments(&format!("{}.{}", name, field.name), field);
+                    }
+                }
+            }
+        }
+
+        audit.warnings
+    }
+}
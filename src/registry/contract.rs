@@ -0,0 +1,174 @@
+use std::collections::HashSet;
+
+use indexmap::map::IndexMap;
+
+use crate::registry::{MetaField, MetaType, MetaTypeName, Registry};
+
+/// The metadata key of the tags of a type or a field.
+const TAGS_KEY: &str = "tags";
+
+fn is_system_type(name: &str) -> bool {
+    name.starts_with("__") || matches!(name, "Boolean" | "Int" | "Float" | "String" | "ID")
+}
+
+impl Registry {
+    /// Returns the tags of a type, or of a field if `field` is `Some`, which are declared as a
+    /// comma-separated list in the `tags` metadata.
+    fn tags(&self, ty: &str, field: Option<&str>) -> Vec<&str> {
+        let metadata = match field {
+            Some(field) => self.field_metadata(ty, field),
+            None => self.type_metadata(ty),
+        };
+        match metadata.and_then(|metadata| metadata.get(TAGS_KEY)) {
+            Some(crate::Value::String(tags)) => tags
+                .split(',')
+                .map(str::trim)
+                .filter(|tag| !tag.is_empty())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Create a copy of the registry which only contains the types and the fields of the contract.
+    ///
+    /// A field is excluded if it or its type is tagged with one of `exclude_tags`. If
+    /// `include_tags` is not empty, a field is only included if it or its type is tagged with one
+    /// of them. The fields whose name starts with `_` are built-in and are always included.
+    /// The types tagged with one of `exclude_tags`, the types without fields, the fields of the
+    /// removed types and the unused types are removed.
+    pub(crate) fn contract(&self, include_tags: &[&str], exclude_tags: &[&str]) -> Registry {
+        let mut registry = self.clone();
+
+        let mut removed_types = HashSet::new();
+        for ty in self.types.values() {
+            let name = ty.name();
+            if is_system_type(name) {
+                continue;
+            }
+            let type_tags = self.tags(name, None);
+            if type_tags.iter().any(|tag| exclude_tags.contains(tag)) {
+                removed_types.insert(name.to_string());
+                continue;
+            }
+
+            let fields = match registry.types.get_mut(name) {
+                Some(MetaType::Object { fields, .. })
+                | Some(MetaType::Interface { fields, .. }) => fields,
+                Some(MetaType::InputObject { input_fields, .. }) => {
+                    input_fields.retain(|field_name, _| {
+                        !self
+                            .tags(name, Some(field_name))
+                            .iter()
+                            .any(|tag| exclude_tags.contains(tag))
+                    });
+                    continue;
+                }
+                _ => continue,
+            };
+            fields.retain(|field_name, _| {
+                if field_name.starts_with('_') {
+                    return true;
+                }
+                let tags = self
+                    .tags(name, Some(field_name))
+                    .into_iter()
+                    .chain(type_tags.iter().copied())
+                    .collect::<Vec<_>>();
+                !tags.iter().any(|tag| exclude_tags.contains(tag))
+                    && (include_tags.is_empty()
+                        || tags.iter().any(|tag| include_tags.contains(tag)))
+            });
+        }
+
+        // Removing a type removes the fields of its type, which can leave other types without
+        // fields.
+        loop {
+            for name in &removed_types {
+                registry.types.remove(name);
+            }
+
+            let types = &registry.types;
+            let is_removed = |ty: &str| !types.contains_key(MetaTypeName::concrete_typename(ty));
+            let is_removed_field = |field: &MetaField| {
+                is_removed(&field.ty) || field.args.values().any(|arg| is_removed(&arg.ty))
+            };
+            let mut removed = Vec::new();
+            for ty in types.values() {
+                let name = ty.name();
+                if name == registry.query_type || is_system_type(name) {
+                    continue;
+                }
+                let is_empty = match ty {
+                    MetaType::Object { fields, .. } | MetaType::Interface { fields, .. } => fields
+                        .iter()
+                        .all(|(name, field)| name.starts_with('_') || is_removed_field(field)),
+                    MetaType::Union { possible_types, .. } => {
+                        possible_types.iter().all(|ty| is_removed(ty))
+                    }
+                    MetaType::InputObject { input_fields, .. } => {
+                        input_fields.values().all(|field| is_removed(&field.ty))
+                    }
+                    _ => false,
+                };
+                if is_empty {
+                    removed.push(name.to_string());
+                }
+            }
+            if removed.is_empty() {
+                break;
+            }
+            removed_types = removed.into_iter().collect();
+        }
+
+        let types = registry.types.keys().cloned().collect::<HashSet<_>>();
+        let is_removed = |ty: &str| !types.contains(MetaTypeName::concrete_typename(ty));
+        let retain_fields = |fields: &mut IndexMap<String, MetaField>| {
+            fields.retain(|_, field| {
+                !is_removed(&field.ty) && !field.args.values().any(|arg| is_removed(&arg.ty))
+            })
+        };
+        for ty in registry.types.values_mut() {
+            match ty {
+                MetaType::Object { fields, .. } => retain_fields(fields),
+                MetaType::Interface {
+                    fields,
+                    possible_types,
+                    ..
+                } => {
+                    retain_fields(fields);
+                    possible_types.retain(|ty| !is_removed(ty));
+                }
+                MetaType::Union {
+                    possible_types,
+                    union_values,
+                    ..
+                } => {
+                    possible_types.retain(|ty| !is_removed(ty));
+                    union_values.retain(|ty, _| !is_removed(ty));
+                }
+                MetaType::InputObject { input_fields, .. } => {
+                    input_fields.retain(|_, field| !is_removed(&field.ty));
+                }
+                _ => {}
+            }
+        }
+        registry.implements.retain(|ty, _| types.contains(ty));
+        for interfaces in registry.implements.values_mut() {
+            interfaces.retain(|ty| types.contains(ty));
+        }
+
+        if let Some(mutation_type) = &registry.mutation_type {
+            if !types.contains(mutation_type) {
+                registry.mutation_type = None;
+            }
+        }
+        if let Some(subscription_type) = &registry.subscription_type {
+            if !types.contains(subscription_type) {
+                registry.subscription_type = None;
+            }
+        }
+
+        registry.remove_unused_types();
+        registry
+    }
+}
@@ -1,10 +1,92 @@
 use std::fmt::Write;
 
-use crate::registry::{MetaField, MetaInputValue, MetaType, Registry};
+use crate::registry::{
+    Deprecation, MetaDirective, MetaField, MetaInputValue, MetaType, Metadata, Registry,
+};
+use crate::Value;
+
+/// The directives defined by the specification or by the executor, which are not exported.
+const BUILTIN_DIRECTIVES: &[&str] = &[
+    "include",
+    "skip",
+    "deprecated",
+    "specifiedBy",
+    "defer",
+    "stream",
+    "ifdef",
+    "oneOf",
+];
+
+/// The options of the SDL exported by [`Schema::sdl_with_options`](crate::Schema::sdl_with_options).
+#[derive(Debug, Copy, Clone, Default)]
+pub struct SDLExportOptions {
+    federation: bool,
+    sorted_fields: bool,
+    sorted_arguments: bool,
+    sorted_enum_items: bool,
+}
+
+impl SDLExportOptions {
+    /// Create the default options.
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Export the Federation SDL, with the federation directives of the fields and without the
+    /// types and fields added by federation.
+    #[inline]
+    #[must_use]
+    pub fn federation(self) -> Self {
+        Self {
+            federation: true,
+            ..self
+        }
+    }
+
+    /// Sort the fields of the objects, interfaces and input objects by name.
+    #[inline]
+    #[must_use]
+    pub fn sorted_fields(self) -> Self {
+        Self {
+            sorted_fields: true,
+            ..self
+        }
+    }
+
+    /// Sort the arguments of the fields and directives by name.
+    #[inline]
+    #[must_use]
+    pub fn sorted_arguments(self) -> Self {
+        Self {
+            sorted_arguments: true,
+            ..self
+        }
+    }
+
+    /// Sort the values of the enums by name.
+    #[inline]
+    #[must_use]
+    pub fn sorted_enum_items(self) -> Self {
+        Self {
+            sorted_enum_items: true,
+            ..self
+        }
+    }
+}
 
 impl Registry {
-    pub fn export_sdl(&self, federation: bool) -> String {
+    pub fn export_sdl(&self, options: SDLExportOptions) -> String {
         let mut sdl = String::new();
+        let federation = options.federation;
+
+        if federation && self.federation_v2 {
+            writeln!(
+                sdl,
+                "extend schema @link(url: \"https://specs.apollo.dev/federation/v2.0\", import: [\"@key\", \"@requires\", \"@provides\", \"@external\", \"@shareable\", \"@override\"])\n"
+            )
+            .ok();
+        }
 
         for ty in self.types.values() {
             if ty.name().starts_with("__") {
@@ -18,7 +100,17 @@ impl Registry {
                 }
             }
 
-            self.export_type(ty, &mut sdl, federation);
+            self.export_type(ty, &mut sdl, &options);
+        }
+
+        let mut directives = self
+            .directives
+            .values()
+            .filter(|directive| !BUILTIN_DIRECTIVES.contains(&directive.name))
+            .collect::<Vec<_>>();
+        directives.sort_by_key(|directive| directive.name);
+        for directive in directives {
+            export_directive(directive, &mut sdl, &options);
         }
 
         if !federation {
@@ -37,37 +129,38 @@ impl Registry {
     }
 
     fn export_fields<'a, I: Iterator<Item = &'a MetaField>>(
+        &self,
         sdl: &mut String,
+        type_name: &str,
         it: I,
-        federation: bool,
+        options: &SDLExportOptions,
     ) {
-        for field in it {
+        let federation = options.federation;
+        // Every subgraph that uses connections defines the same `PageInfo`, `*Connection` and
+        // `*Edge` types.
+        let builtin_shareable = federation
+            && self.federation_v2
+            && matches!(
+                self.types.get(type_name).and_then(MetaType::rust_typename),
+                Some(rust_typename) if rust_typename.starts_with("async_graphql::types::connection::")
+            );
+        let mut fields = it.collect::<Vec<_>>();
+        if options.sorted_fields {
+            fields.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+
+        for field in fields {
             if field.name.starts_with("__")
                 || (federation && matches!(&*field.name, "_service" | "_entities"))
             {
                 continue;
             }
 
-            if field.description.is_some() {
-                writeln!(
-                    sdl,
-                    "\t\"\"\"\n\t{}\n\t\"\"\"",
-                    field.description.unwrap().replace("\n", "\n\t")
-                )
-                .ok();
-            }
-            if !field.args.is_empty() {
-                write!(sdl, "\t{}(", field.name).ok();
-                for (i, arg) in field.args.values().enumerate() {
-                    if i != 0 {
-                        sdl.push_str(", ");
-                    }
-                    sdl.push_str(&export_input_value(arg));
-                }
-                write!(sdl, "): {}", field.ty).ok();
-            } else {
-                write!(sdl, "\t{}: {}", field.name, field.ty).ok();
-            }
+            write_description(sdl, field.description, "\t");
+            write!(sdl, "\t{}", field.name).ok();
+            export_arguments(sdl, field.args.values(), "\t", options);
+            write!(sdl, ": {}", field.ty).ok();
+            write_deprecation(sdl, &field.deprecation);
 
             if federation {
                 if field.external {
@@ -79,16 +172,27 @@ impl Registry {
                 if let Some(provides) = field.provides {
                     write!(sdl, " @provides(fields: \"{}\")", provides).ok();
                 }
+                if field.shareable || builtin_shareable {
+                    write!(sdl, " @shareable").ok();
+                }
+                if let Some(from) = field.override_from {
+                    write!(sdl, " @override(from: \"{}\")", from).ok();
+                }
             }
+            self.write_field_metadata(sdl, type_name, &field.name);
 
             writeln!(sdl).ok();
         }
     }
 
-    fn export_type(&self, ty: &MetaType, sdl: &mut String, federation: bool) {
+    fn export_type(&self, ty: &MetaType, sdl: &mut String, options: &SDLExportOptions) {
+        let federation = options.federation;
         match ty {
             MetaType::Scalar {
-                name, description, ..
+                name,
+                description,
+                specified_by_url,
+                ..
             } => {
                 const SYSTEM_SCALARS: &[&str] = &["Int", "Float", "String", "Boolean", "ID"];
                 const FEDERATION_SCALARS: &[&str] = &["Any"];
@@ -97,10 +201,17 @@ impl Registry {
                     export_scalar = false;
                 }
                 if export_scalar {
-                    if description.is_some() {
-                        writeln!(sdl, "\"\"\"\n{}\n\"\"\"", description.unwrap()).ok();
+                    write_description(sdl, *description, "");
+                    write!(sdl, "scalar {}", name).ok();
+                    if let Some(url) = specified_by_url {
+                        write!(
+                            sdl,
+                            " @specifiedBy(url: {})",
+                            Value::String(url.to_string())
+                        )
+                        .ok();
                     }
-                    writeln!(sdl, "scalar {}", name).ok();
+                    writeln!(sdl).ok();
                 }
             }
             MetaType::Object {
@@ -133,14 +244,13 @@ impl Registry {
                     }
                 }
 
-                if description.is_some() {
-                    writeln!(sdl, "\"\"\"\n{}\n\"\"\"", description.unwrap()).ok();
-                }
+                write_description(sdl, *description, "");
                 if federation && *extends {
                     write!(sdl, "extend ").ok();
                 }
                 write!(sdl, "type {} ", name).ok();
                 self.write_implements(sdl, name);
+                self.write_type_metadata(sdl, name);
 
                 if federation {
                     if let Some(keys) = keys {
@@ -151,7 +261,7 @@ impl Registry {
                 }
 
                 writeln!(sdl, "{{").ok();
-                Self::export_fields(sdl, fields.values(), federation);
+                self.export_fields(sdl, name, fields.values(), options);
                 writeln!(sdl, "}}").ok();
             }
             MetaType::Interface {
@@ -162,9 +272,7 @@ impl Registry {
                 description,
                 ..
             } => {
-                if description.is_some() {
-                    writeln!(sdl, "\"\"\"\n{}\n\"\"\"", description.unwrap()).ok();
-                }
+                write_description(sdl, *description, "");
                 if federation && *extends {
                     write!(sdl, "extend ").ok();
                 }
@@ -177,9 +285,10 @@ impl Registry {
                     }
                 }
                 self.write_implements(sdl, name);
+                self.write_type_metadata(sdl, name);
 
                 writeln!(sdl, "{{").ok();
-                Self::export_fields(sdl, fields.values(), federation);
+                self.export_fields(sdl, name, fields.values(), options);
                 writeln!(sdl, "}}").ok();
             }
             MetaType::Enum {
@@ -188,13 +297,19 @@ impl Registry {
                 description,
                 ..
             } => {
-                if description.is_some() {
-                    writeln!(sdl, "\"\"\"\n{}\n\"\"\"", description.unwrap()).ok();
-                }
+                write_description(sdl, *description, "");
                 write!(sdl, "enum {} ", name).ok();
+                self.write_type_metadata(sdl, name);
                 writeln!(sdl, "{{").ok();
-                for value in enum_values.values() {
-                    writeln!(sdl, "\t{}", value.name).ok();
+                let mut values = enum_values.values().collect::<Vec<_>>();
+                if options.sorted_enum_items {
+                    values.sort_by_key(|value| value.name);
+                }
+                for value in values {
+                    write_description(sdl, value.description, "\t");
+                    write!(sdl, "\t{}", value.name).ok();
+                    write_deprecation(sdl, &value.deprecation);
+                    writeln!(sdl).ok();
                 }
                 writeln!(sdl, "}}").ok();
             }
@@ -202,18 +317,25 @@ impl Registry {
                 name,
                 input_fields,
                 description,
+                oneof,
                 ..
             } => {
-                if description.is_some() {
-                    writeln!(sdl, "\"\"\"\n{}\n\"\"\"", description.unwrap()).ok();
-                }
+                write_description(sdl, *description, "");
                 write!(sdl, "input {} ", name).ok();
+                if *oneof {
+                    write!(sdl, "@oneOf ").ok();
+                }
+                self.write_type_metadata(sdl, name);
                 writeln!(sdl, "{{").ok();
-                for field in input_fields.values() {
-                    if let Some(description) = field.description {
-                        writeln!(sdl, "\t\"\"\"\n\t{}\n\t\"\"\"", description).ok();
-                    }
-                    writeln!(sdl, "\t{}", export_input_value(&field)).ok();
+                let mut fields = input_fields.values().collect::<Vec<_>>();
+                if options.sorted_fields {
+                    fields.sort_by_key(|field| field.name);
+                }
+                for field in fields {
+                    write_description(sdl, field.description, "\t");
+                    write!(sdl, "\t{}", export_input_value(field)).ok();
+                    self.write_field_metadata(sdl, name, field.name);
+                    writeln!(sdl).ok();
                 }
                 writeln!(sdl, "}}").ok();
             }
@@ -223,10 +345,10 @@ impl Registry {
                 description,
                 ..
             } => {
-                if description.is_some() {
-                    writeln!(sdl, "\"\"\"\n{}\n\"\"\"", description.unwrap()).ok();
-                }
-                write!(sdl, "union {} =", name).ok();
+                write_description(sdl, *description, "");
+                write!(sdl, "union {} ", name).ok();
+                self.write_type_metadata(sdl, name);
+                write!(sdl, "=").ok();
                 for ty in possible_types {
                     write!(sdl, " | {}", ty).ok();
                 }
@@ -235,24 +357,133 @@ impl Registry {
         }
     }
 
+    fn write_type_metadata(&self, sdl: &mut String, name: &str) {
+        if let Some(directive) = self.metadata_directive(self.type_metadata(name)) {
+            write!(sdl, "{} ", directive).ok();
+        }
+    }
+
+    fn write_field_metadata(&self, sdl: &mut String, type_name: &str, field_name: &str) {
+        if let Some(directive) = self.metadata_directive(self.field_metadata(type_name, field_name))
+        {
+            write!(sdl, " {}", directive).ok();
+        }
+    }
+
+    fn metadata_directive(&self, metadata: Option<&Metadata>) -> Option<String> {
+        if !self.enable_metadata_directive {
+            return None;
+        }
+        let metadata = metadata.filter(|metadata| !metadata.is_empty())?;
+        Some(format!(
+            "@meta({})",
+            metadata
+                .iter()
+                .map(|(key, value)| format!("{}: {}", key, value))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+    }
+
     fn write_implements(&self, sdl: &mut String, name: &str) {
         if let Some(implements) = self.implements.get(name) {
             if !implements.is_empty() {
-                write!(
-                    sdl,
-                    "implements {} ",
-                    implements
-                        .iter()
-                        .map(AsRef::as_ref)
-                        .collect::<Vec<&str>>()
-                        .join(" & ")
-                )
-                .ok();
+                // The interfaces are sorted, so that the SDL and the schema hash are stable.
+                let mut implements = implements.iter().map(AsRef::as_ref).collect::<Vec<&str>>();
+                implements.sort_unstable();
+                write!(sdl, "implements {} ", implements.join(" & ")).ok();
             }
         }
     }
 }
 
+fn export_directive(directive: &MetaDirective, sdl: &mut String, options: &SDLExportOptions) {
+    write_description(sdl, directive.description, "");
+    write!(sdl, "directive @{}", directive.name).ok();
+    export_arguments(sdl, directive.args.values(), "", options);
+    writeln!(
+        sdl,
+        " on {}",
+        directive
+            .locations
+            .iter()
+            .map(|location| format!("{:?}", location))
+            .collect::<Vec<_>>()
+            .join(" | ")
+    )
+    .ok();
+}
+
+/// Write the arguments of a field or a directive, on separate lines if any of them has a
+/// description.
+fn export_arguments<'a, I: Iterator<Item = &'a MetaInputValue>>(
+    sdl: &mut String,
+    it: I,
+    indent: &str,
+    options: &SDLExportOptions,
+) {
+    let mut args = it.collect::<Vec<_>>();
+    if args.is_empty() {
+        return;
+    }
+    if options.sorted_arguments {
+        args.sort_by_key(|arg| arg.name);
+    }
+
+    if args.iter().any(|arg| arg.description.is_some()) {
+        let arg_indent = format!("{}\t", indent);
+        writeln!(sdl, "(").ok();
+        for arg in args {
+            write_description(sdl, arg.description, &arg_indent);
+            writeln!(sdl, "{}{}", arg_indent, export_input_value(arg)).ok();
+        }
+        write!(sdl, "{})", indent).ok();
+    } else {
+        write!(
+            sdl,
+            "({})",
+            args.into_iter()
+                .map(export_input_value)
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+        .ok();
+    }
+}
+
+fn write_description(sdl: &mut String, description: Option<&str>, indent: &str) {
+    if let Some(description) = description {
+        writeln!(
+            sdl,
+            "{0}\"\"\"\n{0}{1}\n{0}\"\"\"",
+            indent,
+            description
+                .replace("\"\"\"", "\\\"\"\"")
+                .replace('\n', &format!("\n{}", indent))
+        )
+        .ok();
+    }
+}
+
+fn write_deprecation(sdl: &mut String, deprecation: &Deprecation) {
+    match deprecation {
+        Deprecation::NoDeprecated => {}
+        Deprecation::Deprecated { reason: None } => {
+            write!(sdl, " @deprecated").ok();
+        }
+        Deprecation::Deprecated {
+            reason: Some(reason),
+        } => {
+            write!(
+                sdl,
+                " @deprecated(reason: {})",
+                Value::String(reason.to_string())
+            )
+            .ok();
+        }
+    }
+}
+
 fn export_input_value(input_value: &MetaInputValue) -> String {
     if let Some(default_value) = &input_value.default_value {
         format!(
@@ -0,0 +1,70 @@
+use std::iter::FromIterator;
+
+use indexmap::IndexMap;
+
+use crate::Value;
+
+/// Metadata attached to a type or a field.
+///
+/// Metadata is attached with the `meta` attribute of the derive macros, for example
+/// `#[graphql(meta(owner = "payments-team", pii = true))]`, and can be read at runtime with
+/// [`Schema::type_metadata`](crate::Schema::type_metadata) and
+/// [`Schema::field_metadata`](crate::Schema::field_metadata). It is not visible to clients,
+/// unless [`SchemaBuilder::enable_metadata_directive`](crate::SchemaBuilder::enable_metadata_directive)
+/// is called.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// struct Query;
+///
+/// #[Object(meta(owner = "accounts-team"))]
+/// impl Query {
+///     #[graphql(meta(pii = true))]
+///     async fn email(&self) -> &str {
+///         "sunli@example.com"
+///     }
+/// }
+///
+/// let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+/// let metadata = schema.type_metadata("Query").unwrap();
+/// assert_eq!(metadata.get("owner"), Some(&value!("accounts-team")));
+/// let metadata = schema.field_metadata("Query", "email").unwrap();
+/// assert_eq!(metadata.get("pii"), Some(&value!(true)));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Metadata(IndexMap<String, Value>);
+
+impl Metadata {
+    /// Returns the value of the key.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.0.get(key)
+    }
+
+    /// Returns `true` if the metadata contains the key.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.0.contains_key(key)
+    }
+
+    /// Returns an iterator over the keys and values, in the order they were declared.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.0.iter().map(|(key, value)| (key.as_str(), value))
+    }
+
+    /// Returns `true` if the metadata is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<K: Into<String>> FromIterator<(K, Value)> for Metadata {
+    fn from_iter<T: IntoIterator<Item = (K, Value)>>(iter: T) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(key, value)| (key.into(), value))
+                .collect(),
+        )
+    }
+}
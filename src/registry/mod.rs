@@ -1,5 +1,9 @@
 mod cache_control;
+mod connection_audit;
+mod contract;
 mod export_sdl;
+mod metadata;
+mod rename_fields;
 mod stringify_exec_doc;
 
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
@@ -8,13 +12,20 @@ use std::sync::Arc;
 use indexmap::map::IndexMap;
 use indexmap::set::IndexSet;
 
+pub(crate) use rename_fields::RenameFieldFn;
+
 use crate::parser::types::{
     BaseType as ParsedBaseType, Field, Type as ParsedType, VariableDefinition,
 };
 use crate::validators::InputValueValidator;
-use crate::{model, Any, Context, Positioned, ServerResult, Type, Value, VisitorContext};
+use crate::{
+    model, Any, Context, NumberPolicy, Positioned, ServerResult, Type, Value, VisitorContext,
+};
 
 pub use cache_control::CacheControl;
+pub use connection_audit::ConnectionWarning;
+pub use export_sdl::SDLExportOptions;
+pub use metadata::Metadata;
 
 fn strip_brackets(type_name: &str) -> Option<&str> {
     type_name
@@ -162,6 +173,8 @@ pub struct MetaField {
     pub external: bool,
     pub requires: Option<&'static str>,
     pub provides: Option<&'static str>,
+    pub shareable: bool,
+    pub override_from: Option<&'static str>,
     pub visible: Option<MetaVisibleFn>,
     pub compute_complexity: Option<ComplexityType>,
 }
@@ -233,6 +246,7 @@ pub enum MetaType {
         input_fields: IndexMap<String, MetaInputValue>,
         visible: Option<MetaVisibleFn>,
         rust_typename: &'static str,
+        oneof: bool,
     },
 }
 
@@ -354,6 +368,7 @@ impl MetaType {
     }
 }
 
+#[derive(Clone)]
 pub struct MetaDirective {
     pub name: &'static str,
     pub description: Option<&'static str>,
@@ -361,7 +376,7 @@ pub struct MetaDirective {
     pub args: IndexMap<&'static str, MetaInputValue>,
 }
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct Registry {
     pub types: BTreeMap<String, MetaType>,
     pub directives: HashMap<String, MetaDirective>,
@@ -370,11 +385,78 @@ pub struct Registry {
     pub mutation_type: Option<String>,
     pub subscription_type: Option<String>,
     pub disable_introspection: bool,
+    pub introspection_only: bool,
     pub enable_federation: bool,
+    pub federation_v2: bool,
     pub federation_subscription: bool,
+    pub enable_health_check: bool,
+    pub enable_schema_hash_field: bool,
+    pub type_metadata: HashMap<String, Metadata>,
+    pub field_metadata: HashMap<String, HashMap<String, Metadata>>,
+    pub enable_metadata_directive: bool,
+    pub max_input_depth: Option<usize>,
+    pub input_depth_limits: HashMap<String, usize>,
+    pub flattened_input_fields: HashMap<String, HashSet<String>>,
+    pub number_policy: NumberPolicy,
 }
 
 impl Registry {
+    pub fn set_type_metadata(&mut self, ty: &str, metadata: Metadata) {
+        self.type_metadata.insert(ty.to_string(), metadata);
+    }
+
+    pub fn set_field_metadata(&mut self, ty: &str, field: &str, metadata: Metadata) {
+        self.field_metadata
+            .entry(ty.to_string())
+            .or_default()
+            .insert(field.to_string(), metadata);
+    }
+
+    pub fn set_input_depth_limit(&mut self, ty: &str, limit: usize) {
+        self.input_depth_limits.insert(ty.to_string(), limit);
+    }
+
+    /// Records the fields of an input object used as flattened arguments, because it is not
+    /// registered as a type.
+    pub fn add_flattened_input_fields(
+        &mut self,
+        ty: &str,
+        fields: impl IntoIterator<Item = String>,
+    ) {
+        self.flattened_input_fields
+            .entry(ty.to_string())
+            .or_default()
+            .extend(fields);
+    }
+
+    pub fn type_metadata(&self, ty: &str) -> Option<&Metadata> {
+        self.type_metadata.get(ty)
+    }
+
+    pub fn field_metadata(&self, ty: &str, field: &str) -> Option<&Metadata> {
+        self.field_metadata
+            .get(ty)
+            .and_then(|fields| fields.get(field))
+    }
+
+    /// Returns `true` if the field is tagged with `pii = true` metadata.
+    pub(crate) fn is_pii_field(&self, ty: &str, field: &str) -> bool {
+        matches!(
+            self.field_metadata(ty, field)
+                .and_then(|metadata| metadata.get("pii")),
+            Some(Value::Boolean(true))
+        )
+    }
+
+    /// Returns a stable hash of the schema, computed from its SDL.
+    pub(crate) fn schema_hash(&self) -> String {
+        use std::hash::Hasher;
+
+        let mut hasher = fnv::FnvHasher::default();
+        hasher.write(self.export_sdl(SDLExportOptions::new()).as_bytes());
+        format!("{:016x}", hasher.finish())
+    }
+
     pub fn create_type<T: crate::Type + ?Sized, F: FnMut(&mut Registry) -> MetaType>(
         &mut self,
         mut f: F,
@@ -443,6 +525,37 @@ impl Registry {
             });
     }
 
+    pub fn add_possible_type<A: Type, T: Type>(&mut self) {
+        A::create_type_info(self);
+        T::create_type_info(self);
+        let abstract_name = A::type_name();
+        let name = T::type_name().into_owned();
+        match self.types.get_mut(&*abstract_name) {
+            Some(MetaType::Union {
+                possible_types,
+                union_values,
+                ..
+            }) => {
+                possible_types.insert(name.clone());
+                union_values.insert(
+                    name.clone(),
+                    MetaUnionValue {
+                        name,
+                        visible: None,
+                    },
+                );
+            }
+            Some(MetaType::Interface { possible_types, .. }) => {
+                possible_types.insert(name.clone());
+                self.add_implements(&name, &abstract_name);
+            }
+            _ => panic!(
+                "`{}` is not a union or an interface, so `{}` cannot be added to its possible types",
+                abstract_name, name
+            ),
+        }
+    }
+
     pub fn add_keys(&mut self, ty: &str, keys: &str) {
         let all_keys = match self.types.get_mut(ty) {
             Some(MetaType::Object { keys: all_keys, .. }) => all_keys,
@@ -533,6 +646,8 @@ impl Registry {
                             external: false,
                             requires: None,
                             provides: None,
+                            shareable: false,
+                            override_from: None,
                             visible: None,
                             compute_complexity: None,
                         },
@@ -564,6 +679,8 @@ impl Registry {
                     external: false,
                     requires: None,
                     provides: None,
+                    shareable: false,
+                    override_from: None,
                     visible: None,
                     compute_complexity: None,
                 },
@@ -596,6 +713,8 @@ impl Registry {
                     external: false,
                     requires: None,
                     provides: None,
+                    shareable: false,
+                    override_from: None,
                     visible: None,
                     compute_complexity: None,
                 },
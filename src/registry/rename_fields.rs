@@ -0,0 +1,98 @@
+use crate::parser::types::{
+    DocumentOperations, ExecutableDocument, OperationType, Selection, SelectionSet,
+};
+use crate::registry::{MetaType, MetaTypeName, Registry};
+use crate::Name;
+
+pub(crate) type RenameFieldFn = dyn Fn(&str) -> String + Send + Sync;
+
+impl Registry {
+    /// Rename the selected fields that do not exist on their parent type with `f`.
+    ///
+    /// A field is only renamed if the new name exists on the parent type, and the original name is
+    /// kept as the alias so the response is unchanged.
+    pub(crate) fn rename_unknown_fields(&self, doc: &mut ExecutableDocument, f: &RenameFieldFn) {
+        for fragment in doc.fragments.values_mut() {
+            let parent_type = self
+                .types
+                .get(fragment.node.type_condition.node.on.node.as_str());
+            self.rename_unknown_fields_in_selection_set(
+                &mut fragment.node.selection_set.node,
+                parent_type,
+                f,
+            );
+        }
+
+        let operations = match &mut doc.operations {
+            DocumentOperations::Single(operation) => vec![operation],
+            DocumentOperations::Multiple(operations) => operations.values_mut().collect(),
+        };
+        for operation in operations {
+            let root_type = match operation.node.ty {
+                OperationType::Query => Some(&self.query_type),
+                OperationType::Mutation => self.mutation_type.as_ref(),
+                OperationType::Subscription => self.subscription_type.as_ref(),
+            }
+            .and_then(|name| self.types.get(name));
+            self.rename_unknown_fields_in_selection_set(
+                &mut operation.node.selection_set.node,
+                root_type,
+                f,
+            );
+        }
+    }
+
+    fn rename_unknown_fields_in_selection_set(
+        &self,
+        selection_set: &mut SelectionSet,
+        parent_type: Option<&MetaType>,
+        f: &RenameFieldFn,
+    ) {
+        let parent_type = match parent_type {
+            Some(parent_type) => parent_type,
+            None => return,
+        };
+
+        for selection in &mut selection_set.items {
+            match &mut selection.node {
+                Selection::Field(field) => {
+                    let field = &mut field.node;
+                    let mut meta_field = parent_type.field_by_name(&field.name.node);
+                    if meta_field.is_none() && !field.name.node.starts_with("__") {
+                        let new_name = f(&field.name.node);
+                        if let Some(new_meta_field) = parent_type.field_by_name(&new_name) {
+                            if field.alias.is_none() {
+                                field.alias = Some(field.name.clone());
+                            }
+                            field.name.node = Name::new(new_name);
+                            meta_field = Some(new_meta_field);
+                        }
+                    }
+                    let field_type = meta_field.and_then(|meta_field| {
+                        self.types
+                            .get(MetaTypeName::concrete_typename(&meta_field.ty))
+                    });
+                    self.rename_unknown_fields_in_selection_set(
+                        &mut field.selection_set.node,
+                        field_type,
+                        f,
+                    );
+                }
+                Selection::InlineFragment(fragment) => {
+                    let fragment_type = match &fragment.node.type_condition {
+                        Some(type_condition) => {
+                            self.types.get(type_condition.node.on.node.as_str())
+                        }
+                        None => Some(parent_type),
+                    };
+                    self.rename_unknown_fields_in_selection_set(
+                        &mut fragment.node.selection_set.node,
+                        fragment_type,
+                        f,
+                    );
+                }
+                Selection::FragmentSpread(_) => {}
+            }
+        }
+    }
+}
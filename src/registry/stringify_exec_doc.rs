@@ -117,13 +117,20 @@ impl Registry {
                     self.types
                         .get(MetaTypeName::concrete_typename(&input_value.ty))
                 });
-                if let Some(MetaType::InputObject { input_fields, .. }) = parent_type {
+                if let Some(MetaType::InputObject {
+                    name, input_fields, ..
+                }) = parent_type
+                {
                     output.push('{');
                     for (idx, (key, value)) in obj.iter().enumerate() {
                         if idx > 0 {
                             output.push_str(", ");
                         }
                         write!(output, "{}: ", key)?;
+                        if self.is_pii_field(name, key) {
+                            output.push_str("\"<pii>\"");
+                            continue;
+                        }
                         self.stringify_input_value(output, input_fields.get(key.as_str()), value)?;
                     }
                     output.push('}');
@@ -156,6 +163,12 @@ impl Registry {
                     }
                     write!(output, "{}", field.node.name.node)?;
                     if !field.node.arguments.is_empty() {
+                        // The arguments of a PII field, such as a lookup by email, are PII too.
+                        let is_pii = parent_type
+                            .map(|parent_type| {
+                                self.is_pii_field(parent_type.name(), &field.node.name.node)
+                            })
+                            .unwrap_or_default();
                         output.push('(');
                         for (idx, (name, argument)) in field.node.arguments.iter().enumerate() {
                             let meta_input_value = parent_type
@@ -167,6 +180,10 @@ impl Registry {
                                 output.push_str(", ");
                             }
                             write!(output, "{}: ", name)?;
+                            if is_pii {
+                                output.push_str("\"<pii>\"");
+                                continue;
+                            }
                             let value = argument
                                 .node
                                 .clone()
@@ -312,4 +329,51 @@ mod tests {
             r#"query { value(a: 10, b: "<secret>", c: {v1: 1, v2: "<secret>", v3: {v4: 4, v5: "<secret>"}}) }"#
         );
     }
+
+    #[test]
+    fn test_stringify_pii() {
+        #[derive(InputObject)]
+        #[graphql(internal)]
+        struct MyInput {
+            v1: i32,
+            #[graphql(meta(pii = true))]
+            v2: String,
+        }
+
+        struct Query;
+
+        #[Object(internal)]
+        #[allow(unreachable_code, unused_variables)]
+        impl Query {
+            async fn value(&self, a: i32, b: MyInput) -> i32 {
+                todo!()
+            }
+
+            #[graphql(meta(pii = true))]
+            async fn user_by_email(&self, email: String) -> i32 {
+                todo!()
+            }
+        }
+
+        let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+        let registry = schema.registry();
+        let s = registry
+            .stringify_exec_doc(
+                &Default::default(),
+                &parse_query(
+                    r#"
+            {
+                value(a: 10, b: { v1: 1, v2: "a@b.c" })
+                userByEmail(email: "a@b.c")
+            }
+        "#,
+                )
+                .unwrap(),
+            )
+            .unwrap();
+        assert_eq!(
+            s,
+            r#"query { value(a: 10, b: {v1: 1, v2: "<pii>"}) userByEmail(email: "<pii>") }"#
+        );
+    }
 }
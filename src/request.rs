@@ -1,11 +1,30 @@
 use std::any::Any;
 use std::collections::HashMap;
 use std::fmt::{self, Debug, Formatter};
+#[cfg(feature = "timeout")]
+use std::time::Duration;
 
 use serde::{Deserialize, Deserializer, Serialize};
 
+use crate::parser::parse_query;
+use crate::parser::types::{DocumentOperations, OperationType};
 use crate::{Data, ParseRequestError, UploadValue, Value, Variables};
 
+/// How the executor handles the errors of the fields.
+///
+/// The client can choose the behavior with the `onError` key of the request, the default one
+/// is set with [`SchemaBuilder::on_error`](crate::SchemaBuilder::on_error).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OnError {
+    /// Set the field, or its nearest nullable parent if it is non-null, to `null` and continue
+    /// the execution.
+    Propagate,
+    /// Abort the execution on the first error, the response only contains this error and no
+    /// data.
+    Abort,
+}
+
 /// GraphQL request.
 ///
 /// This can be deserialized from a structure of the query string, the operation name and the
@@ -42,6 +61,24 @@ pub struct Request {
     /// Disable introspection queries for this request.
     #[serde(skip)]
     pub disable_introspection: bool,
+
+    /// Only allow introspection queries for this request.
+    #[serde(skip)]
+    pub introspection_only: bool,
+
+    /// Return partial results after this duration.
+    ///
+    /// Overrides [`SchemaBuilder::partial_results_timeout`](crate::SchemaBuilder::partial_results_timeout).
+    #[cfg(feature = "timeout")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "timeout")))]
+    #[serde(skip)]
+    pub partial_results_timeout: Option<Duration>,
+
+    /// How the errors of the fields are handled.
+    ///
+    /// Overrides [`SchemaBuilder::on_error`](crate::SchemaBuilder::on_error).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_error: Option<OnError>,
 }
 
 impl Request {
@@ -55,6 +92,10 @@ impl Request {
             data: Data::default(),
             extensions: Default::default(),
             disable_introspection: false,
+            introspection_only: false,
+            #[cfg(feature = "timeout")]
+            partial_results_timeout: None,
+            on_error: None,
         }
     }
 
@@ -78,11 +119,60 @@ impl Request {
     }
 
     /// Disable introspection queries for this request.
+    ///
+    /// The `__schema` and `__type` fields are rejected with an error.
     pub fn disable_introspection(mut self) -> Self {
         self.disable_introspection = true;
         self
     }
 
+    /// Only allow introspection queries for this request.
+    ///
+    /// The other root fields, mutations and subscriptions are rejected with an error.
+    pub fn introspection_only(mut self) -> Self {
+        self.introspection_only = true;
+        self
+    }
+
+    /// Return partial results if the request has not been executed after the specified duration.
+    ///
+    /// See [`SchemaBuilder::partial_results_timeout`](crate::SchemaBuilder::partial_results_timeout).
+    #[cfg(feature = "timeout")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "timeout")))]
+    pub fn partial_results_timeout(mut self, timeout: Duration) -> Self {
+        self.partial_results_timeout = Some(timeout);
+        self
+    }
+
+    /// Set how the errors of the fields are handled for this request.
+    ///
+    /// See [`SchemaBuilder::on_error`](crate::SchemaBuilder::on_error).
+    pub fn on_error(mut self, on_error: OnError) -> Self {
+        self.on_error = Some(on_error);
+        self
+    }
+
+    /// Returns the type of the operation that will be executed, or `None` if the query can't be
+    /// parsed or doesn't have the operation.
+    ///
+    /// This parses the query without validating it, so that HTTP integrations can route the
+    /// request before executing it, for example to send the subscriptions to the WebSocket
+    /// endpoint or to reject the mutations on a read-only replica.
+    pub fn operation_type(&self) -> Option<OperationType> {
+        let document = parse_query(&self.query).ok()?;
+        match (document.operations, self.operation_name.as_deref()) {
+            (DocumentOperations::Single(operation), None) => Some(operation.node.ty),
+            (DocumentOperations::Multiple(operations), Some(operation_name)) => operations
+                .get(operation_name)
+                .map(|operation| operation.node.ty),
+            (DocumentOperations::Multiple(operations), None) if operations.len() == 1 => operations
+                .values()
+                .next()
+                .map(|operation| operation.node.ty),
+            _ => None,
+        }
+    }
+
     /// Set a variable to an upload value.
     ///
     /// `var_path` is a dot-separated path to the item that begins with `variables`, for example
@@ -190,6 +280,7 @@ impl From<Vec<Request>> for BatchRequest {
 
 #[cfg(test)]
 mod tests {
+    use crate::parser::types::OperationType;
     use crate::*;
 
     #[test]
@@ -249,6 +340,30 @@ mod tests {
         assert!(request.variables.is_empty());
     }
 
+    #[test]
+    fn test_request_operation_type() {
+        assert_eq!(
+            Request::new("{ a }").operation_type(),
+            Some(OperationType::Query)
+        );
+        assert_eq!(
+            Request::new("mutation A { a }").operation_type(),
+            Some(OperationType::Mutation)
+        );
+
+        let query = "query A { a } subscription B { b }";
+        assert_eq!(Request::new(query).operation_type(), None);
+        assert_eq!(
+            Request::new(query).operation_name("B").operation_type(),
+            Some(OperationType::Subscription)
+        );
+        assert_eq!(
+            Request::new(query).operation_name("C").operation_type(),
+            None
+        );
+        assert_eq!(Request::new("{ a").operation_type(), None);
+    }
+
     #[test]
     fn test_batch_request_single() {
         let request: BatchRequest = from_value(value! ({
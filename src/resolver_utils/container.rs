@@ -1,12 +1,19 @@
 use std::future::Future;
 use std::pin::Pin;
 
+#[cfg(feature = "timeout")]
+use futures_util::future::Either;
+use futures_util::future::FutureExt;
 use indexmap::IndexMap;
 
+use crate::custom_directive::resolve_with_directives;
 use crate::extensions::ResolveInfo;
-use crate::parser::types::Selection;
+use crate::parser::types::{Field, Selection};
 use crate::registry::MetaType;
-use crate::{Context, ContextSelectionSet, Name, OutputType, ServerError, ServerResult, Value};
+use crate::{
+    Context, ContextSelectionSet, ErrorExtensionValues, Name, OutputType, Positioned, ServerError,
+    ServerResult, Value,
+};
 
 /// Represents a GraphQL container object.
 ///
@@ -106,32 +113,45 @@ async fn resolve_container_inner<'a, T: ContainerType + ?Sized>(
     root: &'a T,
     parallel: bool,
 ) -> ServerResult<Value> {
-    let mut fields = Fields(Vec::new());
+    let mut fields = Fields::default();
     fields.add_set(ctx, root)?;
-
-    let res = if parallel {
-        futures_util::future::try_join_all(fields.0).await?
-    } else {
-        let mut results = Vec::with_capacity(fields.0.len());
-        for field in fields.0 {
-            results.push(field.await?);
-        }
-        results
-    };
-
-    let mut map = IndexMap::new();
-    for (name, value) in res {
-        insert_value(&mut map, name, value);
-    }
-    Ok(Value::Object(map))
+    fields.resolve(parallel).await
 }
 
 type BoxFieldFuture<'a> = Pin<Box<dyn Future<Output = ServerResult<(Name, Value)>> + 'a + Send>>;
 
+type BoxDeferredFuture<'a> = Pin<Box<dyn Future<Output = ()> + 'a + Send>>;
+
 /// A set of fields on an container that are being selected.
-pub struct Fields<'a>(Vec<BoxFieldFuture<'a>>);
+#[derive(Default)]
+pub struct Fields<'a>(Vec<BoxFieldFuture<'a>>, Vec<BoxDeferredFuture<'a>>);
 
 impl<'a> Fields<'a> {
+    /// Resolve the fields, while the deferred fragments are resolved concurrently into
+    /// incremental payloads.
+    async fn resolve(self, parallel: bool) -> ServerResult<Value> {
+        let Fields(fields, deferred) = self;
+        let fields = async move {
+            if parallel {
+                futures_util::future::try_join_all(fields).await
+            } else {
+                let mut results = Vec::with_capacity(fields.len());
+                for field in fields {
+                    results.push(field.await?);
+                }
+                Ok(results)
+            }
+        };
+        let deferred = futures_util::future::join_all(deferred).map(Ok);
+        let (res, _) = futures_util::future::try_join(fields, deferred).await?;
+
+        let mut map = IndexMap::new();
+        for (name, value) in res {
+            insert_value(&mut map, name, value);
+        }
+        Ok(Value::Object(map))
+    }
+
     /// Add another set of fields to this set of fields using the given container.
     pub fn add_set<T: ContainerType + ?Sized>(
         &mut self,
@@ -157,6 +177,17 @@ impl<'a> Fields<'a> {
                         continue;
                     }
 
+                    if ctx.query_env.disabled_fields.contains(&field.pos) {
+                        let res = field_error::<T>(
+                            ctx,
+                            field,
+                            "FEATURE_DISABLED",
+                            format!(r#"The field "{}" is disabled."#, field.node.name.node),
+                        );
+                        self.0.push(Box::pin(async move { res }));
+                        continue;
+                    }
+
                     if ctx.is_ifdef(&field.node.directives) {
                         if let Some(MetaType::Object { fields, .. }) =
                             ctx.schema_env.registry.types.get(T::type_name().as_ref())
@@ -167,18 +198,24 @@ impl<'a> Fields<'a> {
                         }
                     }
 
-                    self.0.push(Box::pin({
+                    let resolve_fut = {
                         let ctx = ctx.clone();
                         async move {
                             let ctx_field = ctx.with_field(field);
                             let field_name = ctx_field.item.node.response_key().node.clone();
                             let extensions = &ctx.query_env.extensions;
 
-                            if extensions.is_empty() {
-                                Ok((
-                                    field_name,
-                                    root.resolve_field(&ctx_field).await?.unwrap_or_default(),
-                                ))
+                            if let Some(field_usage) = &ctx.schema_env.field_usage {
+                                field_usage.increment(&T::type_name(), &field.node.name.node);
+                            }
+
+                            let resolve_fut = resolve_with_directives(
+                                &ctx_field,
+                                &field.node.directives,
+                                root.resolve_field(&ctx_field),
+                            );
+                            let value = if extensions.is_empty() {
+                                resolve_fut.await?.unwrap_or_default()
                             } else {
                                 let type_name = T::type_name();
                                 let resolve_info = ResolveInfo {
@@ -213,18 +250,28 @@ impl<'a> Fields<'a> {
                                         .map(|alias| alias.node.as_str()),
                                 };
 
-                                let resolve_fut = root.resolve_field(&ctx_field);
                                 futures_util::pin_mut!(resolve_fut);
-                                Ok((
-                                    field_name,
-                                    extensions
-                                        .resolve(resolve_info, &mut resolve_fut)
-                                        .await?
-                                        .unwrap_or_default(),
-                                ))
-                            }
+                                extensions
+                                    .resolve(resolve_info, &mut resolve_fut)
+                                    .await?
+                                    .unwrap_or_default()
+                            };
+
+                            #[cfg(feature = "strict_output")]
+                            super::strict_output::check_field_output(
+                                &ctx_field,
+                                &T::type_name(),
+                                &value,
+                            );
+
+                            ctx_field.add_response_size(&field_name, &value)?;
+                            Ok((field_name, value))
                         }
-                    }));
+                    };
+                    #[cfg(feature = "timeout")]
+                    let resolve_fut =
+                        resolve_with_deadline::<T, _>(ctx.clone(), field, resolve_fut);
+                    self.0.push(Box::pin(resolve_fut));
                 }
                 selection => {
                     let (type_condition, selection_set) = match selection {
@@ -268,11 +315,32 @@ impl<'a> Fields<'a> {
                                 .get(&*introspection_type_name)
                                 .map_or(false, |interfaces| interfaces.contains(condition))
                     });
+                    // Otherwise the fragment applies to an interface type.
+                    let applies_interface = !applies_concrete_object
+                        && type_condition.map_or(true, |condition| T::type_name() == condition);
+                    if !applies_concrete_object && !applies_interface {
+                        continue;
+                    }
+
+                    let defer = ctx.incremental_directive(selection.directives(), "defer")?;
+                    let mut deferred_fields = Fields::default();
+                    let fields = if defer.is_some() {
+                        &mut deferred_fields
+                    } else {
+                        &mut *self
+                    };
                     if applies_concrete_object {
-                        root.collect_all_fields(&ctx.with_selection_set(selection_set), self)?;
-                    } else if type_condition.map_or(true, |condition| T::type_name() == condition) {
-                        // The fragment applies to an interface type.
-                        self.add_set(&ctx.with_selection_set(selection_set), root)?;
+                        root.collect_all_fields(&ctx.with_selection_set(selection_set), fields)?;
+                    } else {
+                        fields.add_set(&ctx.with_selection_set(selection_set), root)?;
+                    }
+
+                    if let Some(defer) = defer {
+                        let ctx = ctx.clone();
+                        self.1.push(Box::pin(async move {
+                            let res = deferred_fields.resolve(true).await;
+                            ctx.add_incremental_payload(&defer, res);
+                        }));
                     }
                 }
             }
@@ -280,3 +348,72 @@ impl<'a> Fields<'a> {
         Ok(())
     }
 }
+
+/// Resolves a field, or reports a `TIMEOUT` error for it if the deadline of the query expires
+/// first.
+#[cfg(feature = "timeout")]
+async fn resolve_with_deadline<'a, T: ContainerType + ?Sized, Fut>(
+    ctx: ContextSelectionSet<'a>,
+    field: &'a Positioned<Field>,
+    fut: Fut,
+) -> ServerResult<(Name, Value)>
+where
+    Fut: Future<Output = ServerResult<(Name, Value)>>,
+{
+    let deadline = match &ctx.query_env.deadline {
+        Some(deadline) => deadline.clone(),
+        None => return fut.await,
+    };
+
+    futures_util::pin_mut!(fut);
+    match futures_util::future::select(fut, deadline).await {
+        Either::Left((res, _)) => res,
+        Either::Right(_) => {
+            // The fields of the subtree may have timed out before this field, but they are not
+            // in the response any more.
+            let path = ctx.with_field(field).path();
+            let mut timeout = ErrorExtensionValues::default();
+            timeout.set("code", "TIMEOUT");
+            ctx.query_env.errors.lock().unwrap().retain(|err| {
+                !(err.path.len() > path.len()
+                    && err.path.starts_with(&path)
+                    && err.extensions.as_ref() == Some(&timeout))
+            });
+            field_error::<T>(&ctx, field, "TIMEOUT", "Execution timed out.")
+        }
+    }
+}
+
+/// Reports an error with the code for a field which is not resolved.
+///
+/// A nullable field is set to `null`, while the error of a non-null field is propagated to its
+/// parent.
+fn field_error<T: ContainerType + ?Sized>(
+    ctx: &ContextSelectionSet<'_>,
+    field: &Positioned<Field>,
+    code: &str,
+    message: impl Into<String>,
+) -> ServerResult<(Name, Value)> {
+    let ctx_field = ctx.with_field(field);
+    let mut extensions = ErrorExtensionValues::default();
+    extensions.set("code", code);
+    let err = ctx_field.set_error_path(ServerError {
+        extensions: Some(extensions),
+        ..ServerError::new(message, Some(field.pos))
+    });
+
+    let nullable = matches!(
+        ctx.schema_env
+            .registry
+            .types
+            .get(T::type_name().as_ref())
+            .and_then(|ty| ty.field_by_name(field.node.name.node.as_str())),
+        Some(meta_field) if !meta_field.ty.ends_with('!')
+    );
+    if nullable {
+        ctx.add_nullable_error(err)?;
+        Ok((field.node.response_key().node.clone(), Value::Null))
+    } else {
+        Err(err)
+    }
+}
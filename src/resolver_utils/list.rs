@@ -1,3 +1,8 @@
+use std::future::Future;
+
+use futures_util::stream::{Stream, StreamExt};
+use futures_util::FutureExt;
+
 use crate::extensions::ResolveInfo;
 use crate::parser::types::Field;
 use crate::{ContextSelectionSet, OutputType, Positioned, ServerResult, Type, Value};
@@ -9,52 +14,115 @@ pub async fn resolve_list<'a, T: OutputType + 'a>(
     iter: impl IntoIterator<Item = T>,
     len: Option<usize>,
 ) -> ServerResult<Value> {
-    let extensions = &ctx.query_env.extensions;
-    if !extensions.is_empty() {
-        let mut futures = len.map(Vec::with_capacity).unwrap_or_default();
-        for (idx, item) in iter.into_iter().enumerate() {
-            futures.push({
-                let ctx = ctx.clone();
-                async move {
-                    let ctx_idx = ctx.with_index(idx);
-                    let extensions = &ctx.query_env.extensions;
-
-                    let resolve_info = ResolveInfo {
-                        path_node: ctx_idx.path_node.as_ref().unwrap(),
-                        parent_type: &Vec::<T>::type_name(),
-                        return_type: &T::qualified_type_name(),
-                        name: field.node.name.node.as_str(),
-                        alias: field.node.alias.as_ref().map(|alias| alias.node.as_str()),
-                    };
-                    let resolve_fut = async {
-                        OutputType::resolve(&item, &ctx_idx, field)
-                            .await
-                            .map(Option::Some)
-                            .map_err(|err| ctx_idx.set_error_path(err))
-                    };
-                    futures_util::pin_mut!(resolve_fut);
-                    extensions
-                        .resolve(resolve_info, &mut resolve_fut)
-                        .await
-                        .map(|value| value.expect("You definitely encountered a bug!"))
-                }
-            });
-        }
-        Ok(Value::List(
-            futures_util::future::try_join_all(futures).await?,
-        ))
-    } else {
-        let mut futures = len.map(Vec::with_capacity).unwrap_or_default();
-        for (idx, item) in iter.into_iter().enumerate() {
-            let ctx_idx = ctx.with_index(idx);
-            futures.push(async move {
-                OutputType::resolve(&item, &ctx_idx, field)
-                    .await
-                    .map_err(|err| ctx_idx.set_error_path(err))
-            });
+    let mut futures = len.map(Vec::with_capacity).unwrap_or_default();
+    for (idx, item) in iter.into_iter().enumerate() {
+        futures.push(resolve_item(ctx, field, idx, item));
+    }
+    resolve_items(ctx, field, futures).await
+}
+
+/// Resolve an list from a stream, by executing at most `concurrency` items concurrently.
+///
+/// The items are resolved as they are produced by the stream, so the stream doesn't need to be
+/// collected first. The items after the initial count of the `@stream` directive are resolved
+/// into incremental payloads.
+pub async fn resolve_list_stream<'a, T: OutputType + 'a>(
+    ctx: &ContextSelectionSet<'a>,
+    field: &Positioned<Field>,
+    stream: impl Stream<Item = T>,
+    concurrency: usize,
+) -> ServerResult<Value> {
+    let directive = ctx.incremental_directive(&field.node.directives, "stream")?;
+    let results = stream
+        .enumerate()
+        .map(|(idx, item)| resolve_item(ctx, field, idx, item).map(move |res| (idx, res)))
+        .buffered(concurrency.max(1));
+    futures_util::pin_mut!(results);
+
+    let mut items = Vec::new();
+    while let Some((idx, res)) = results.next().await {
+        ctx.add_list_elements(1)?;
+        match &directive {
+            Some(directive) if idx >= directive.initial_count => {
+                ctx.with_index(idx).add_incremental_payload(directive, res);
+            }
+            _ => items.push(res?),
         }
-        Ok(Value::List(
-            futures_util::future::try_join_all(futures).await?,
-        ))
     }
+    Ok(Value::List(items))
+}
+
+async fn resolve_item<'a, T: OutputType + 'a>(
+    ctx: &ContextSelectionSet<'a>,
+    field: &Positioned<Field>,
+    idx: usize,
+    item: T,
+) -> ServerResult<Value> {
+    let ctx_idx = ctx.with_index(idx);
+    let extensions = &ctx.query_env.extensions;
+    if extensions.is_empty() {
+        return OutputType::resolve(&item, &ctx_idx, field)
+            .await
+            .map_err(|err| ctx_idx.set_error_path(err));
+    }
+
+    let resolve_info = ResolveInfo {
+        path_node: ctx_idx.path_node.as_ref().unwrap(),
+        parent_type: &Vec::<T>::type_name(),
+        return_type: &T::qualified_type_name(),
+        name: field.node.name.node.as_str(),
+        alias: field.node.alias.as_ref().map(|alias| alias.node.as_str()),
+    };
+    let resolve_fut = async {
+        OutputType::resolve(&item, &ctx_idx, field)
+            .await
+            .map(Option::Some)
+            .map_err(|err| ctx_idx.set_error_path(err))
+    };
+    futures_util::pin_mut!(resolve_fut);
+    extensions
+        .resolve(resolve_info, &mut resolve_fut)
+        .await
+        .map(|value| value.expect("You definitely encountered a bug!"))
+}
+
+/// Resolve the items of a list, while the items after the initial count of the `@stream`
+/// directive are resolved concurrently into incremental payloads.
+async fn resolve_items<'a, F>(
+    ctx: &ContextSelectionSet<'a>,
+    field: &Positioned<Field>,
+    mut futures: Vec<F>,
+) -> ServerResult<Value>
+where
+    F: Future<Output = ServerResult<Value>>,
+{
+    ctx.add_list_elements(futures.len())?;
+
+    let stream = match ctx.incremental_directive(&field.node.directives, "stream")? {
+        Some(stream) => stream,
+        None => {
+            return Ok(Value::List(
+                futures_util::future::try_join_all(futures).await?,
+            ))
+        }
+    };
+
+    let initial_count = stream.initial_count.min(futures.len());
+    let streamed = futures
+        .split_off(initial_count)
+        .into_iter()
+        .enumerate()
+        .map(|(idx, fut)| {
+            let stream = &stream;
+            async move {
+                let res = fut.await;
+                ctx.with_index(initial_count + idx)
+                    .add_incremental_payload(stream, res);
+            }
+        });
+    let streamed = futures_util::future::join_all(streamed).map(Ok);
+    let (items, _) =
+        futures_util::future::try_join(futures_util::future::try_join_all(futures), streamed)
+            .await?;
+    Ok(Value::List(items))
 }
@@ -5,6 +5,8 @@ mod container;
 mod r#enum;
 mod list;
 mod scalar;
+#[cfg(feature = "strict_output")]
+mod strict_output;
 
 pub use container::*;
 pub use list::*;
@@ -1,4 +1,63 @@
-use crate::{InputValueResult, Value};
+use crate::{InputValueResult, Number, Value};
+
+/// How 64-bit integers and decimals are serialized in responses.
+///
+/// Many JSON parsers, such as the one of JavaScript, parse all numbers as doubles, which can only
+/// represent integers up to 2^53 exactly. This policy is set with
+/// [`SchemaBuilder::number_policy`](crate::SchemaBuilder::number_policy), and is applied to the
+/// `i64`, `u64`, `isize`, `usize`, `NonZeroI64`, `NonZeroU64` and `Decimal` scalars.
+///
+/// The GraphQL specification requires the values of the `Int` type to be numbers, so the strings
+/// returned for these integers by the `String` and `SafeNumber` policies are off-spec, and clients
+/// must be prepared for them. So that the returned values can be sent back, the arguments of
+/// these integer types also accept numeric strings with these policies, and `Decimal` arguments
+/// accept numbers.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum NumberPolicy {
+    /// Integers are serialized as numbers, and decimals as strings. This is the default.
+    Native,
+
+    /// Integers and decimals are serialized as numbers.
+    ///
+    /// Decimals are converted to doubles, which may lose precision.
+    Number,
+
+    /// Integers and decimals are serialized as strings.
+    String,
+
+    /// Integers and decimals are serialized as numbers if they can be exactly represented by
+    /// doubles, otherwise as strings.
+    SafeNumber,
+}
+
+impl Default for NumberPolicy {
+    fn default() -> Self {
+        NumberPolicy::Native
+    }
+}
+
+/// The largest integer `n` such that `n` and `n + 1` can be exactly represented by a double.
+const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+impl NumberPolicy {
+    pub(crate) fn signed_to_value(self, n: i64) -> Value {
+        match self {
+            NumberPolicy::String => Value::String(n.to_string()),
+            NumberPolicy::SafeNumber if n.unsigned_abs() > MAX_SAFE_INTEGER => {
+                Value::String(n.to_string())
+            }
+            _ => Value::Number(Number::from(n)),
+        }
+    }
+
+    pub(crate) fn unsigned_to_value(self, n: u64) -> Value {
+        match self {
+            NumberPolicy::String => Value::String(n.to_string()),
+            NumberPolicy::SafeNumber if n > MAX_SAFE_INTEGER => Value::String(n.to_string()),
+            _ => Value::Number(Number::from(n)),
+        }
+    }
+}
 
 /// A GraphQL scalar.
 ///
@@ -40,6 +99,13 @@ pub trait ScalarType: Sized + Send {
 
     /// Convert the scalar to `Value`.
     fn to_value(&self) -> Value;
+
+    /// Convert the scalar to the `Value` of a response, with the number policy of the schema.
+    ///
+    /// The default implementation calls [`ScalarType::to_value`], and ignores the policy.
+    fn to_output_value(&self, _policy: NumberPolicy) -> Value {
+        self.to_value()
+    }
 }
 
 /// Define a scalar
@@ -180,10 +246,13 @@ macro_rules! scalar_internal {
         impl $crate::OutputType for $ty {
             async fn resolve(
                 &self,
-                _: &$crate::ContextSelectionSet<'_>,
+                ctx: &$crate::ContextSelectionSet<'_>,
                 _field: &$crate::Positioned<$crate::parser::types::Field>,
             ) -> $crate::ServerResult<$crate::Value> {
-                ::std::result::Result::Ok($crate::ScalarType::to_value(self))
+                ::std::result::Result::Ok($crate::ScalarType::to_output_value(
+                    self,
+                    ctx.schema_env.registry.number_policy,
+                ))
             }
         }
     };
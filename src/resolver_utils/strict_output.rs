@@ -0,0 +1,84 @@
+use crate::registry::{MetaType, MetaTypeName, Registry};
+use crate::{Context, NumberPolicy, Value};
+
+/// Check that the value of a resolved field conforms to the declared type of the field, and
+/// panic with a description of the problem if it does not.
+pub(crate) fn check_field_output(ctx: &Context<'_>, parent_type: &str, value: &Value) {
+    let registry = &ctx.schema_env.registry;
+    let ty = match registry
+        .types
+        .get(parent_type)
+        .and_then(|ty| ty.field_by_name(ctx.item.node.name.node.as_str()))
+    {
+        Some(field) => &field.ty,
+        None => return,
+    };
+
+    if let Some(reason) = check_output_value(registry, ty, value) {
+        panic!(
+            r#"Field "{}" of type "{}" resolved to an invalid value {}: {}"#,
+            ctx.path_node
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+            ty,
+            value,
+            reason
+        );
+    }
+}
+
+fn check_output_value(registry: &Registry, type_name: &str, value: &Value) -> Option<String> {
+    match MetaTypeName::create(type_name) {
+        MetaTypeName::NonNull(type_name) => match value {
+            Value::Null => Some(format!(r#"expected non-null type "{}!""#, type_name)),
+            _ => check_output_value(registry, type_name, value),
+        },
+        MetaTypeName::List(type_name) => match value {
+            Value::List(items) => items.iter().enumerate().find_map(|(idx, item)| {
+                check_output_value(registry, type_name, item)
+                    .map(|reason| format!("at index {}, {}", idx, reason))
+            }),
+            Value::Null => None,
+            _ => Some(format!(r#"expected list type "[{}]""#, type_name)),
+        },
+        MetaTypeName::Named(type_name) => {
+            if let Value::Null = value {
+                return None;
+            }
+
+            let valid = match registry.types.get(type_name)? {
+                MetaType::Scalar { is_valid, .. } => match type_name {
+                    "Int" => match value {
+                        Value::Number(n) => n.is_i64() || n.is_u64(),
+                        // Large integers are serialized as strings by some number policies.
+                        Value::String(s) => {
+                            registry.number_policy != NumberPolicy::Native
+                                && s.parse::<i128>().is_ok()
+                        }
+                        _ => false,
+                    },
+                    "Float" => matches!(value, Value::Number(_)),
+                    "String" => matches!(value, Value::String(_) | Value::Binary(_)),
+                    "Boolean" => matches!(value, Value::Boolean(_)),
+                    "ID" => matches!(value, Value::String(_) | Value::Number(_)),
+                    _ => is_valid(value),
+                },
+                MetaType::Enum { enum_values, .. } => match value {
+                    Value::Enum(name) => enum_values.contains_key(name.as_str()),
+                    Value::String(name) => enum_values.contains_key(name.as_str()),
+                    _ => false,
+                },
+                MetaType::Object { .. } | MetaType::Interface { .. } | MetaType::Union { .. } => {
+                    matches!(value, Value::Object(_))
+                }
+                MetaType::InputObject { .. } => true,
+            };
+            if valid {
+                None
+            } else {
+                Some(format!(r#"expected type "{}""#, type_name))
+            }
+        }
+    }
+}
@@ -1,9 +1,12 @@
 use std::collections::BTreeMap;
 
 use http::header::HeaderMap;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
-use crate::{CacheControl, Result, ServerError, Value};
+use crate::{
+    from_value_ref, CacheControl, DeserializerError, PathSegment, Result, ServerError, Value,
+};
 
 /// Query response
 #[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
@@ -24,6 +27,18 @@ pub struct Response {
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub errors: Vec<ServerError>,
 
+    /// The path of the data of an incremental payload
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub path: Vec<PathSegment>,
+
+    /// The label of the `@defer` or `@stream` directive of an incremental payload
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub label: Option<String>,
+
+    /// Whether more payloads follow this one when the response is delivered incrementally
+    #[serde(rename = "hasNext", skip_serializing_if = "Option::is_none", default)]
+    pub has_next: Option<bool>,
+
     /// HTTP headers
     #[serde(skip)]
     pub http_headers: HeaderMap<String>,
@@ -95,6 +110,30 @@ impl Response {
             Ok(self)
         }
     }
+
+    /// Deserialize the data of the response into a type that implements `serde::Deserialize`.
+    ///
+    /// This avoids a round trip through a JSON string when a typed view of the result is needed,
+    /// for example in tests or when calling a schema from Rust code.
+    pub fn deserialize_data<T: DeserializeOwned>(&self) -> Result<T, DeserializerError> {
+        from_value_ref(&self.data)
+    }
+
+    /// Encode the response as CBOR.
+    #[cfg(feature = "cbor")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbor")))]
+    pub fn to_cbor(&self) -> serde_cbor::Result<Vec<u8>> {
+        serde_cbor::to_vec(self)
+    }
+
+    /// Encode the response as MessagePack.
+    ///
+    /// Objects are encoded as maps, so the response has the same shape as the JSON response.
+    #[cfg(feature = "msgpack")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "msgpack")))]
+    pub fn to_msgpack(&self) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+        rmp_serde::to_vec_named(self)
+    }
 }
 
 /// Response for batchable queries
@@ -148,6 +187,22 @@ impl BatchResponse {
         };
         it
     }
+
+    /// Encode the response as CBOR.
+    #[cfg(feature = "cbor")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbor")))]
+    pub fn to_cbor(&self) -> serde_cbor::Result<Vec<u8>> {
+        serde_cbor::to_vec(self)
+    }
+
+    /// Encode the response as MessagePack.
+    ///
+    /// Objects are encoded as maps, so the response has the same shape as the JSON response.
+    #[cfg(feature = "msgpack")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "msgpack")))]
+    pub fn to_msgpack(&self) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+        rmp_serde::to_vec_named(self)
+    }
 }
 
 impl From<Response> for BatchResponse {
@@ -166,6 +221,32 @@ impl From<Vec<Response>> for BatchResponse {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_deserialize_data() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        #[serde(rename_all = "camelCase")]
+        struct User {
+            id: i32,
+            display_name: String,
+            tags: Vec<String>,
+        }
+
+        let resp = Response::new(crate::value!({
+            "id": 10,
+            "displayName": "sunli",
+            "tags": ["a", "b"],
+        }));
+        assert_eq!(
+            resp.deserialize_data::<User>().unwrap(),
+            User {
+                id: 10,
+                display_name: "sunli".to_string(),
+                tags: vec!["a".to_string(), "b".to_string()],
+            }
+        );
+        assert!(resp.deserialize_data::<Vec<i32>>().is_err());
+    }
+
     #[test]
     fn test_batch_response_single() {
         let resp = BatchResponse::Single(Response::new(Value::Boolean(true)));
@@ -183,4 +264,26 @@ mod tests {
             r#"[{"data":true},{"data":"1"}]"#
         );
     }
+
+    #[cfg(feature = "cbor")]
+    #[test]
+    fn test_response_to_cbor() {
+        let resp = Response::new(crate::value!({ "a": 1, "b": [true, "x"] }));
+        assert_eq!(
+            serde_cbor::from_slice::<Response>(&resp.to_cbor().unwrap()).unwrap(),
+            resp
+        );
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn test_response_to_msgpack() {
+        let resp = Response::from_errors(vec![ServerError::new("error", None)]);
+        let bytes = resp.to_msgpack().unwrap();
+        assert_eq!(rmp_serde::from_slice::<Response>(&bytes).unwrap(), resp);
+
+        // The fields are encoded by name.
+        let value: serde_json::Value = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(value["errors"][0]["message"], "error");
+    }
 }
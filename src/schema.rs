@@ -1,23 +1,47 @@
-use std::any::Any;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
 use std::ops::Deref;
 use std::sync::Arc;
+#[cfg(feature = "timeout")]
+use std::time::Duration;
+use std::time::Instant;
 
+#[cfg(feature = "timeout")]
+use futures_timer::Delay;
+#[cfg(feature = "timeout")]
+use futures_util::future::FutureExt;
 use futures_util::stream::{self, Stream, StreamExt};
 use indexmap::map::IndexMap;
+use serde::{Deserialize, Serialize};
 
 use crate::context::{Data, QueryEnvInner};
+use crate::custom_directive::RegisteredDirective;
 use crate::extensions::{ExtensionFactory, Extensions};
+use crate::field_usage::FieldUsage;
+use crate::guard::Guard;
 use crate::model::__DirectiveLocation;
 use crate::parser::parse_query;
-use crate::parser::types::{DocumentOperations, OperationType};
-use crate::registry::{MetaDirective, MetaInputValue, Registry};
+use crate::parser::types::{
+    DocumentOperations, Field, FragmentDefinition, OperationDefinition, OperationType, Selection,
+    SelectionSet,
+};
+use crate::registry::{
+    ConnectionWarning, MetaDirective, MetaInputValue, Registry, RenameFieldFn, SDLExportOptions,
+};
 use crate::resolver_utils::{resolve_container, resolve_container_serial};
+use crate::schema_module::MutationRoot;
+#[cfg(feature = "hmac_signing")]
+use crate::signing::Signer;
 use crate::subscription::collect_subscription_streams;
-use crate::types::QueryRoot;
-use crate::validation::{check_rules, ValidationMode};
+use crate::types::{
+    create_health_types, create_schema_hash_field, remove_optional_fields, QueryRoot,
+};
+use crate::validation::{check_rules, ValidationMode, ValidationResult};
 use crate::{
-    BatchRequest, BatchResponse, CacheControl, ContextBase, ObjectType, QueryEnv, Request,
-    Response, ServerError, SubscriptionType, Type, ID,
+    Base64GlobalIdCodec, BatchRequest, BatchResponse, CacheControl, ContextBase, CustomDirective,
+    DynObject, ErrorExtensionValues, FieldUsageSnapshot, GlobalIdCodec, Metadata, NumberPolicy,
+    Name, ObjectType, OnError, ParseRequestError, PathSegment, Positioned, QueryEnv, Request,
+    Response, SchemaModule, ServerError, SubscriptionType, Type, Value, ID,
 };
 
 /// Schema builder
@@ -25,12 +49,27 @@ pub struct SchemaBuilder<Query, Mutation, Subscription> {
     validation_mode: ValidationMode,
     query: QueryRoot<Query>,
     mutation: Mutation,
+    mutation_modules: Vec<DynObject>,
     subscription: Subscription,
     registry: Registry,
     data: Data,
+    operation_data: HashMap<OperationType, Data>,
     complexity: Option<usize>,
     depth: Option<usize>,
     extensions: Vec<Box<dyn ExtensionFactory>>,
+    rename_unknown_fields: Option<Box<RenameFieldFn>>,
+    schema_hash_extension: bool,
+    global_id_codec: Box<dyn GlobalIdCodec>,
+    #[cfg(feature = "hmac_signing")]
+    signer: Option<Signer>,
+    #[cfg(feature = "timeout")]
+    partial_results_timeout: Option<Duration>,
+    on_error: OnError,
+    response_size: Option<usize>,
+    list_elements: Option<usize>,
+    enable_field_usage: bool,
+    default_guard: Option<Box<dyn Guard + Send + Sync>>,
+    custom_directives: HashMap<&'static str, RegisteredDirective>,
 }
 
 impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription> {
@@ -42,12 +81,54 @@ impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription>
         self
     }
 
+    /// Add a module, which contributes root fields, types, extensions and data to the schema.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the module defines a root field that is already defined.
+    pub fn module(mut self, module: SchemaModule) -> Self {
+        for registration in module.registrations {
+            registration(&mut self.registry, &module.name);
+        }
+        self.query.modules.extend(module.query);
+        self.mutation_modules.extend(module.mutation);
+        self.extensions.extend(module.extensions);
+        self.data.merge(module.data);
+        self
+    }
+
+    /// Add an object type to the possible types of a union or an interface.
+    ///
+    /// This allows a member type to be defined in another crate than the union or the interface.
+    /// Values of the member type are returned through a [`DynObject`](crate::DynObject) variant
+    /// of the union or the interface.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `A` is not a union or an interface.
+    pub fn register_possible_type<A: Type, T: ObjectType>(mut self) -> Self {
+        self.registry.add_possible_type::<A, T>();
+        self
+    }
+
     /// Disable introspection queries.
+    ///
+    /// The `__schema` and `__type` fields are not defined in the schema, and are rejected with an
+    /// error.
     pub fn disable_introspection(mut self) -> Self {
         self.registry.disable_introspection = true;
         self
     }
 
+    /// Only allow introspection queries, which is useful for a schema that is only queried by
+    /// schema registries and code generators.
+    ///
+    /// The other root fields, mutations and subscriptions are rejected with an error.
+    pub fn introspection_only(mut self) -> Self {
+        self.registry.introspection_only = true;
+        self
+    }
+
     /// Set the maximum complexity a query can have. By default, there is no limit.
     pub fn limit_complexity(mut self, complexity: usize) -> Self {
         self.complexity = Some(complexity);
@@ -60,6 +141,39 @@ impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription>
         self
     }
 
+    /// Set the maximum size of a response, measured as the length of its JSON encoding. By
+    /// default, there is no limit.
+    ///
+    /// The size of the fields is counted while they are resolved, and the execution is aborted as
+    /// soon as the limit is exceeded. The complete response is checked again after the execution,
+    /// to also count the errors and the objects returned by scalars. A larger response is
+    /// replaced with a `RESPONSE_TOO_LARGE` error. The limit is not applied to the events of
+    /// subscriptions.
+    pub fn limit_response_size(mut self, size: usize) -> Self {
+        self.response_size = Some(size);
+        self
+    }
+
+    /// Set the maximum total number of the elements of the lists resolved by a query. By
+    /// default, there is no limit.
+    ///
+    /// The execution is aborted with a `RESPONSE_TOO_LARGE` error as soon as the limit is
+    /// exceeded. The limit is not applied to subscriptions.
+    pub fn limit_list_elements(mut self, count: usize) -> Self {
+        self.list_elements = Some(count);
+        self
+    }
+
+    /// Set the maximum number of nested input objects in an argument. By default, there is no
+    /// limit.
+    ///
+    /// The depth of the values of an input object type can also be limited with the `max_depth`
+    /// attribute of [`InputObject`](derive.InputObject.html).
+    pub fn limit_input_depth(mut self, depth: usize) -> Self {
+        self.registry.max_input_depth = Some(depth);
+        self
+    }
+
     /// Add an extension to the schema.
     ///
     /// # Examples
@@ -91,6 +205,53 @@ impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription>
         self
     }
 
+    /// Add a global data that can only be accessed in the operations of the type `ty`, which
+    /// takes precedence over the data added by [`SchemaBuilder::data`].
+    ///
+    /// This allows the resolvers to use the same type for different resources depending on the
+    /// operation, for example a connection pool of a read replica for the queries, and of the
+    /// primary database for the mutations.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_graphql::*;
+    /// use async_graphql::parser::types::OperationType;
+    ///
+    /// struct Pool(&'static str);
+    ///
+    /// struct Query;
+    ///
+    /// #[Object]
+    /// impl Query {
+    ///     async fn pool(&self, ctx: &Context<'_>) -> &str {
+    ///         ctx.data_unchecked::<Pool>().0
+    ///     }
+    /// }
+    ///
+    /// struct Mutation;
+    ///
+    /// #[Object]
+    /// impl Mutation {
+    ///     async fn pool(&self, ctx: &Context<'_>) -> &str {
+    ///         ctx.data_unchecked::<Pool>().0
+    ///     }
+    /// }
+    ///
+    /// tokio::runtime::Runtime::new().unwrap().block_on(async move {
+    ///     let schema = Schema::build(Query, Mutation, EmptySubscription)
+    ///         .data(Pool("primary"))
+    ///         .operation_data(OperationType::Query, Pool("replica"))
+    ///         .finish();
+    ///     assert_eq!(schema.execute("{ pool }").await.data, value!({ "pool": "replica" }));
+    ///     assert_eq!(schema.execute("mutation { pool }").await.data, value!({ "pool": "primary" }));
+    /// });
+    /// ```
+    pub fn operation_data<D: Any + Send + Sync>(mut self, ty: OperationType, data: D) -> Self {
+        self.operation_data.entry(ty).or_default().insert(data);
+        self
+    }
+
     /// Set the validation mode, default is `ValidationMode::Strict`.
     pub fn validation_mode(mut self, validation_mode: ValidationMode) -> Self {
         self.validation_mode = validation_mode;
@@ -103,6 +264,17 @@ impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription>
         self
     }
 
+    /// Enable federation, and make the Federation SDL compliant with Apollo Federation 2.
+    ///
+    /// The SDL starts with `extend schema @link(...)`, which imports the federation directives,
+    /// so the subgraph can be composed by Apollo Gateway and Router. The `@shareable` and
+    /// `@override` directives of the fields are only defined in Federation 2.
+    pub fn enable_federation_v2(mut self) -> Self {
+        self.registry.enable_federation = true;
+        self.registry.federation_v2 = true;
+        self
+    }
+
     /// Make the Federation SDL include subscriptions.
     ///
     /// Note: Not included by default, in order to be compatible with Apollo Server.
@@ -111,6 +283,259 @@ impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription>
         self
     }
 
+    /// Resolve the fields of incoming queries that are not defined on their parent type under
+    /// another name.
+    ///
+    /// `f` is called with the name of each selected field that does not exist on its parent type.
+    /// If the returned name is a field of that type, it is resolved instead, and the original
+    /// name is kept as the response key. This allows a schema to accept queries written for
+    /// another naming convention, for example during a migration from `camelCase` to
+    /// `snake_case` field names.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_graphql::*;
+    ///
+    /// #[derive(SimpleObject)]
+    /// #[graphql(rename_fields = "snake_case")]
+    /// struct Query {
+    ///     user_name: String,
+    /// }
+    ///
+    /// fn to_snake_case(name: &str) -> String {
+    ///     let mut s = String::new();
+    ///     for c in name.chars() {
+    ///         if c.is_ascii_uppercase() {
+    ///             s.push('_');
+    ///         }
+    ///         s.push(c.to_ascii_lowercase());
+    ///     }
+    ///     s
+    /// }
+    ///
+    /// tokio::runtime::Runtime::new().unwrap().block_on(async move {
+    ///     let schema = Schema::build(Query { user_name: "sunli".to_string() }, EmptyMutation, EmptySubscription)
+    ///         .rename_unknown_fields(to_snake_case)
+    ///         .finish();
+    ///     let res = schema.execute("{ user_name userName }").await.into_result().unwrap().data;
+    ///     assert_eq!(res, value!({ "user_name": "sunli", "userName": "sunli" }));
+    /// });
+    /// ```
+    pub fn rename_unknown_fields(
+        mut self,
+        f: impl Fn(&str) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.rename_unknown_fields = Some(Box::new(f));
+        self
+    }
+
+    /// Add the `_health` and `_readiness` fields to the query root.
+    ///
+    /// Both fields report the schema hash, the uptime of the schema and the status of the
+    /// components reported by extensions with [`Extension::health`](crate::extensions::Extension::health).
+    /// `_health.healthy` is always `true` and can be used as a liveness check, while
+    /// `_readiness.healthy` is only `true` if all components are healthy.
+    pub fn enable_health_check(mut self) -> Self {
+        self.registry.enable_health_check = true;
+        self
+    }
+
+    /// Export the [`Metadata`] of types and fields as `@meta` directives in the SDL, for example
+    /// `email: String! @meta(pii: true)`.
+    pub fn enable_metadata_directive(mut self) -> Self {
+        self.registry.enable_metadata_directive = true;
+        self
+    }
+
+    /// Add the `_schemaHash` field to the query root, which returns [`Schema::schema_hash`].
+    pub fn enable_schema_hash_field(mut self) -> Self {
+        self.registry.enable_schema_hash_field = true;
+        self
+    }
+
+    /// Add the [`Schema::schema_hash`] to the `schemaHash` extension of every response.
+    ///
+    /// Clients can use it to detect that the schema of the server has changed, for example to
+    /// invalidate their persisted operations.
+    pub fn enable_schema_hash_extension(mut self) -> Self {
+        self.schema_hash_extension = true;
+        self
+    }
+
+    /// Set the codec used by [`Context::encode_global_id`](crate::Context::encode_global_id) and
+    /// [`Context::decode_global_id`](crate::Context::decode_global_id).
+    ///
+    /// The default codec is [`Base64GlobalIdCodec`](crate::Base64GlobalIdCodec).
+    pub fn global_id_codec(mut self, codec: impl GlobalIdCodec) -> Self {
+        self.global_id_codec = Box::new(codec);
+        self
+    }
+
+    /// Set how 64-bit integers and decimals are serialized in responses.
+    ///
+    /// The default is [`NumberPolicy::Native`].
+    pub fn number_policy(mut self, policy: NumberPolicy) -> Self {
+        self.registry.number_policy = policy;
+        self
+    }
+
+    /// Set the guard of all the fields that have neither a `guard` nor the `public` attribute.
+    ///
+    /// This makes a schema secure by default: a field is only accessible to the callers allowed
+    /// by this guard, unless it is marked as `#[graphql(public)]` or has its own guard. The
+    /// fields of the types defined by this crate, such as `PageInfo`, are not guarded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_graphql::*;
+    /// use async_graphql::guard::Guard;
+    ///
+    /// struct CurrentUser(String);
+    ///
+    /// struct LoggedIn;
+    ///
+    /// #[async_trait::async_trait]
+    /// impl Guard for LoggedIn {
+    ///     async fn check(&self, ctx: &Context<'_>) -> Result<()> {
+    ///         ctx.data::<CurrentUser>().map(|_| ())
+    ///     }
+    /// }
+    ///
+    /// struct Query;
+    ///
+    /// #[Object]
+    /// impl Query {
+    ///     async fn balance(&self) -> i32 {
+    ///         100
+    ///     }
+    ///
+    ///     #[graphql(public)]
+    ///     async fn version(&self) -> &str {
+    ///         "1.0"
+    ///     }
+    /// }
+    ///
+    /// tokio::runtime::Runtime::new().unwrap().block_on(async move {
+    ///     let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+    ///         .default_guard(LoggedIn)
+    ///         .finish();
+    ///     assert!(schema.execute("{ balance }").await.is_err());
+    ///     assert!(schema.execute("{ version }").await.is_ok());
+    ///     assert!(schema
+    ///         .execute(Request::new("{ balance }").data(CurrentUser("sunli".to_string())))
+    ///         .await
+    ///         .is_ok());
+    /// });
+    /// ```
+    pub fn default_guard(mut self, guard: impl Guard + Send + Sync + 'static) -> Self {
+        self.default_guard = Some(Box::new(guard));
+        self
+    }
+
+    /// Add a custom directive, which can be applied to the fields of a query.
+    ///
+    /// See [`CustomDirective`] for an example.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a directive with the same name is already defined.
+    pub fn directive(mut self, directive: impl CustomDirective) -> Self {
+        let name = directive.name();
+        assert!(
+            !self.registry.directives.contains_key(name),
+            "Directive `@{}` is already defined.",
+            name
+        );
+
+        let mut args = IndexMap::new();
+        let mut defaults = Vec::new();
+        for arg in directive.arguments() {
+            args.insert(
+                arg.name,
+                MetaInputValue {
+                    name: arg.name,
+                    description: arg.description,
+                    ty: (arg.ty)(&mut self.registry),
+                    default_value: arg.default_value.as_ref().map(ToString::to_string),
+                    validator: None,
+                    visible: None,
+                    is_secret: false,
+                },
+            );
+            if let Some(default_value) = arg.default_value {
+                defaults.push((arg.name, default_value));
+            }
+        }
+        self.registry.add_directive(MetaDirective {
+            name,
+            description: directive.description(),
+            locations: vec![__DirectiveLocation::FIELD],
+            args,
+        });
+        self.custom_directives.insert(
+            name,
+            RegisteredDirective {
+                directive: Arc::new(directive),
+                defaults,
+            },
+        );
+        self
+    }
+
+    /// Sign the cursors of connections and the global IDs encoded with
+    /// [`Context::encode_global_id`](crate::Context::encode_global_id) with HMAC-SHA256, so
+    /// that clients cannot forge them to probe adjacent records.
+    ///
+    /// The cursors are verified by [`connection::query`](crate::connection::query). Cursors and
+    /// global IDs with a missing or invalid signature are rejected with a `BAD_USER_INPUT`
+    /// error.
+    #[cfg(feature = "hmac_signing")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hmac_signing")))]
+    pub fn hmac_key(mut self, key: impl AsRef<[u8]>) -> Self {
+        self.signer = Some(Signer::new(key.as_ref()));
+        self
+    }
+
+    /// Return partial results for queries and mutations that have not been executed after the
+    /// specified duration.
+    ///
+    /// When the timeout expires, the fields that are still being resolved are set to `null` and
+    /// a `TIMEOUT` error is reported for each of them, and the response is returned immediately.
+    /// As with any other error, if such a field is non-null, its nearest nullable parent is set
+    /// to `null` instead.
+    ///
+    /// The timeout can be overridden per request with
+    /// [`Request::partial_results_timeout`](crate::Request::partial_results_timeout).
+    #[cfg(feature = "timeout")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "timeout")))]
+    pub fn partial_results_timeout(mut self, timeout: Duration) -> Self {
+        self.partial_results_timeout = Some(timeout);
+        self
+    }
+
+    /// Set how the errors of the fields are handled, by default [`OnError::Propagate`].
+    ///
+    /// With [`OnError::Abort`] the execution stops on the first error, and the response only
+    /// contains this error and no data.
+    ///
+    /// The behavior can be overridden per request with
+    /// [`Request::on_error`](crate::Request::on_error).
+    pub fn on_error(mut self, on_error: OnError) -> Self {
+        self.on_error = on_error;
+        self
+    }
+
+    /// Count how many times each field of the schema is resolved.
+    ///
+    /// The counts can be read with [`Schema::field_usage_snapshot`], for example to find the
+    /// fields that are never queried.
+    pub fn enable_field_usage(mut self) -> Self {
+        self.enable_field_usage = true;
+        self
+    }
+
     /// Override the name of the specified type.
     pub fn override_description<T: Type>(mut self, desc: &'static str) -> Self {
         self.registry.set_description::<T>(desc);
@@ -124,37 +549,102 @@ impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription>
             self.registry.create_federation_types();
         }
 
-        Schema(Arc::new(SchemaInner {
-            validation_mode: self.validation_mode,
-            query: self.query,
-            mutation: self.mutation,
-            subscription: self.subscription,
-            complexity: self.complexity,
-            depth: self.depth,
-            extensions: self.extensions,
-            env: SchemaEnv(Arc::new(SchemaEnvInner {
-                registry: self.registry,
-                data: self.data,
-            })),
-        }))
+        // computed before adding the optional built-in fields, so enabling them does not change it
+        let schema_hash = self.registry.schema_hash();
+
+        if self.registry.enable_health_check {
+            create_health_types(&mut self.registry);
+        }
+
+        if self.registry.enable_schema_hash_field {
+            create_schema_hash_field(&mut self.registry);
+        }
+
+        let field_usage = if self.enable_field_usage {
+            Some(FieldUsage::new(&self.registry))
+        } else {
+            None
+        };
+
+        Schema {
+            inner: Arc::new(SchemaInner {
+                validation_mode: self.validation_mode,
+                query: self.query,
+                mutation: self.mutation,
+                mutation_modules: self.mutation_modules,
+                subscription: self.subscription,
+                complexity: self.complexity,
+                depth: self.depth,
+                extensions: self.extensions,
+                rename_unknown_fields: self.rename_unknown_fields,
+                schema_hash_extension: self.schema_hash_extension,
+                #[cfg(feature = "timeout")]
+                partial_results_timeout: self.partial_results_timeout,
+                on_error: self.on_error,
+                response_size: self.response_size,
+                list_elements: self.list_elements,
+            }),
+            env: SchemaEnv {
+                inner: Arc::new(SchemaEnvInner {
+                    data: self.data,
+                    operation_data: self.operation_data,
+                    created_at: Instant::now(),
+                    global_id_codec: self.global_id_codec,
+                    #[cfg(feature = "hmac_signing")]
+                    signer: self.signer,
+                    field_usage,
+                    default_guard: self.default_guard,
+                    custom_directives: self.custom_directives,
+                }),
+                registry: Arc::new(self.registry),
+                schema_hash: schema_hash.into(),
+            },
+        }
     }
 }
 
 #[doc(hidden)]
 pub struct SchemaEnvInner {
-    pub registry: Registry,
     pub data: Data,
+    pub(crate) operation_data: HashMap<OperationType, Data>,
+    pub created_at: Instant,
+    pub global_id_codec: Box<dyn GlobalIdCodec>,
+    #[cfg(feature = "hmac_signing")]
+    pub(crate) signer: Option<Signer>,
+    pub(crate) field_usage: Option<FieldUsage>,
+    pub(crate) default_guard: Option<Box<dyn Guard + Send + Sync>>,
+    pub(crate) custom_directives: HashMap<&'static str, RegisteredDirective>,
+}
+
+impl SchemaEnvInner {
+    /// Returns the global data for the operations of the type `ty`.
+    pub(crate) fn data_for(
+        &self,
+        ty: Option<OperationType>,
+        type_id: &TypeId,
+    ) -> Option<&(dyn Any + Send + Sync)> {
+        ty.and_then(|ty| self.operation_data.get(&ty))
+            .and_then(|data| data.get(type_id))
+            .or_else(|| self.data.get(type_id))
+            .map(AsRef::as_ref)
+    }
 }
 
+/// The registry is not part of [`SchemaEnvInner`], so that the contracts of a schema can share
+/// everything else.
 #[doc(hidden)]
 #[derive(Clone)]
-pub struct SchemaEnv(Arc<SchemaEnvInner>);
+pub struct SchemaEnv {
+    inner: Arc<SchemaEnvInner>,
+    pub registry: Arc<Registry>,
+    pub schema_hash: Arc<str>,
+}
 
 impl Deref for SchemaEnv {
     type Target = SchemaEnvInner;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.inner
     }
 }
 
@@ -163,21 +653,34 @@ pub struct SchemaInner<Query, Mutation, Subscription> {
     pub(crate) validation_mode: ValidationMode,
     pub(crate) query: QueryRoot<Query>,
     pub(crate) mutation: Mutation,
+    pub(crate) mutation_modules: Vec<DynObject>,
     pub(crate) subscription: Subscription,
     pub(crate) complexity: Option<usize>,
     pub(crate) depth: Option<usize>,
     pub(crate) extensions: Vec<Box<dyn ExtensionFactory>>,
-    pub(crate) env: SchemaEnv,
+    pub(crate) rename_unknown_fields: Option<Box<RenameFieldFn>>,
+    pub(crate) schema_hash_extension: bool,
+    #[cfg(feature = "timeout")]
+    pub(crate) partial_results_timeout: Option<Duration>,
+    pub(crate) on_error: OnError,
+    pub(crate) response_size: Option<usize>,
+    pub(crate) list_elements: Option<usize>,
 }
 
 /// GraphQL schema.
 ///
 /// Cloning a schema is cheap, so it can be easily shared.
-pub struct Schema<Query, Mutation, Subscription>(Arc<SchemaInner<Query, Mutation, Subscription>>);
+pub struct Schema<Query, Mutation, Subscription> {
+    inner: Arc<SchemaInner<Query, Mutation, Subscription>>,
+    pub(crate) env: SchemaEnv,
+}
 
 impl<Query, Mutation, Subscription> Clone for Schema<Query, Mutation, Subscription> {
     fn clone(&self) -> Self {
-        Schema(self.0.clone())
+        Schema {
+            inner: self.inner.clone(),
+            env: self.env.clone(),
+        }
     }
 }
 
@@ -200,7 +703,7 @@ impl<Query, Mutation, Subscription> Deref for Schema<Query, Mutation, Subscripti
     type Target = SchemaInner<Query, Mutation, Subscription>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.inner
     }
 }
 
@@ -222,14 +725,32 @@ where
     ) -> SchemaBuilder<Query, Mutation, Subscription> {
         SchemaBuilder {
             validation_mode: ValidationMode::Strict,
-            query: QueryRoot { inner: query },
+            query: QueryRoot {
+                inner: query,
+                modules: Vec::new(),
+            },
+            mutation_modules: Vec::new(),
             mutation,
             subscription,
             registry: Self::create_registry(),
             data: Default::default(),
+            operation_data: Default::default(),
             complexity: None,
             depth: None,
             extensions: Default::default(),
+            rename_unknown_fields: None,
+            schema_hash_extension: false,
+            global_id_codec: Box::new(Base64GlobalIdCodec),
+            #[cfg(feature = "hmac_signing")]
+            signer: None,
+            #[cfg(feature = "timeout")]
+            partial_results_timeout: None,
+            on_error: OnError::Propagate,
+            response_size: None,
+            list_elements: None,
+            enable_field_usage: false,
+            default_guard: None,
+            custom_directives: Default::default(),
         }
     }
 
@@ -250,8 +771,19 @@ where
                 Some(Subscription::type_name().to_string())
             },
             disable_introspection: false,
+            introspection_only: false,
             enable_federation: false,
+            federation_v2: false,
             federation_subscription: false,
+            enable_health_check: false,
+            enable_schema_hash_field: false,
+            type_metadata: Default::default(),
+            field_metadata: Default::default(),
+            enable_metadata_directive: false,
+            max_input_depth: None,
+            input_depth_limits: Default::default(),
+            flattened_input_fields: Default::default(),
+            number_policy: NumberPolicy::Native,
         };
 
         registry.add_directive(MetaDirective {
@@ -300,6 +832,74 @@ where
             }
         });
 
+        registry.add_directive(MetaDirective {
+            name: "defer",
+            description: Some("Directs the executor to deliver this fragment after the rest of the response, when the response is delivered incrementally."),
+            locations: vec![
+                __DirectiveLocation::FRAGMENT_SPREAD,
+                __DirectiveLocation::INLINE_FRAGMENT
+            ],
+            args: {
+                let mut args = IndexMap::new();
+                args.insert("if", MetaInputValue {
+                    name: "if",
+                    description: Some("Deferred when true."),
+                    ty: "Boolean!".to_string(),
+                    default_value: Some("true".to_string()),
+                    validator: None,
+                    visible: None,
+                    is_secret: false,
+                });
+                args.insert("label", MetaInputValue {
+                    name: "label",
+                    description: Some("Identifies the payload of this fragment."),
+                    ty: "String".to_string(),
+                    default_value: None,
+                    validator: None,
+                    visible: None,
+                    is_secret: false,
+                });
+                args
+            }
+        });
+
+        registry.add_directive(MetaDirective {
+            name: "stream",
+            description: Some("Directs the executor to deliver the items of this list field after the rest of the response, when the response is delivered incrementally."),
+            locations: vec![__DirectiveLocation::FIELD],
+            args: {
+                let mut args = IndexMap::new();
+                args.insert("if", MetaInputValue {
+                    name: "if",
+                    description: Some("Streamed when true."),
+                    ty: "Boolean!".to_string(),
+                    default_value: Some("true".to_string()),
+                    validator: None,
+                    visible: None,
+                    is_secret: false,
+                });
+                args.insert("label", MetaInputValue {
+                    name: "label",
+                    description: Some("Identifies the payloads of this field."),
+                    ty: "String".to_string(),
+                    default_value: None,
+                    validator: None,
+                    visible: None,
+                    is_secret: false,
+                });
+                args.insert("initialCount", MetaInputValue {
+                    name: "initialCount",
+                    description: Some("The number of items in the initial response."),
+                    ty: "Int!".to_string(),
+                    default_value: Some("0".to_string()),
+                    validator: None,
+                    visible: None,
+                    is_secret: false,
+                });
+                args
+            }
+        });
+
         registry.add_directive(MetaDirective {
             name: "ifdef",
             description: Some("Directs the executor to query only when the field exists."),
@@ -307,6 +907,13 @@ where
             args: Default::default(),
         });
 
+        registry.add_directive(MetaDirective {
+            name: "oneOf",
+            description: Some("Indicates that exactly one field of an input object must be provided and not null."),
+            locations: vec![__DirectiveLocation::INPUT_OBJECT],
+            args: Default::default(),
+        });
+
         // register scalars
         bool::create_type_info(&mut registry);
         i32::create_type_info(&mut registry);
@@ -341,14 +948,164 @@ where
         &self.env.registry
     }
 
+    /// Returns a snapshot of the field usage counters.
+    ///
+    /// The snapshot is empty unless [`SchemaBuilder::enable_field_usage`] was called.
+    pub fn field_usage_snapshot(&self) -> FieldUsageSnapshot {
+        self.env
+            .field_usage
+            .as_ref()
+            .map(FieldUsage::snapshot)
+            .unwrap_or_default()
+    }
+
+    /// Returns the metadata of a type.
+    pub fn type_metadata(&self, ty: &str) -> Option<&Metadata> {
+        self.env.registry.type_metadata(ty)
+    }
+
+    /// Returns the metadata of a field, or of an input field of an input object.
+    pub fn field_metadata(&self, ty: &str, field: &str) -> Option<&Metadata> {
+        self.env.registry.field_metadata(ty, field)
+    }
+
+    /// Returns a variant of this schema which only contains the types and the fields of a
+    /// contract, such as the public API of the schema.
+    ///
+    /// The tags of a type or a field are declared as a comma-separated list in the `tags`
+    /// metadata, for example `#[graphql(meta(tags = "public, partner"))]`. A field is excluded if
+    /// it or its type is tagged with one of `exclude_tags`. If `include_tags` is not empty, a
+    /// field is only included if it or its type is tagged with one of them. The types left without
+    /// fields, and the fields that return them, are removed too.
+    ///
+    /// The contract shares the resolvers, the data and the extensions of this schema, so it can be
+    /// used to export its SDL or to execute the queries of the clients of the contract.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_graphql::*;
+    ///
+    /// struct Query;
+    ///
+    /// #[Object(meta(tags = "public"))]
+    /// impl Query {
+    ///     async fn value(&self) -> i32 {
+    ///         10
+    ///     }
+    ///
+    ///     #[graphql(meta(tags = "internal"))]
+    ///     async fn secret(&self) -> i32 {
+    ///         20
+    ///     }
+    /// }
+    ///
+    /// tokio::runtime::Runtime::new().unwrap().block_on(async move {
+    ///     let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    ///     let public = schema.contract(&["public"], &["internal"]);
+    ///     assert!(!public.sdl().contains("secret"));
+    ///     assert!(public.execute("{ value }").await.is_ok());
+    ///     assert!(public.execute("{ secret }").await.is_err());
+    /// });
+    /// ```
+    #[must_use]
+    pub fn contract(&self, include_tags: &[&str], exclude_tags: &[&str]) -> Self {
+        let registry = self.env.registry.contract(include_tags, exclude_tags);
+        // like in `SchemaBuilder::finish`, the optional built-in fields are not hashed
+        let mut hashed_registry = registry.clone();
+        remove_optional_fields(&mut hashed_registry);
+        Schema {
+            inner: self.inner.clone(),
+            env: SchemaEnv {
+                inner: self.env.inner.clone(),
+                schema_hash: hashed_registry.schema_hash().into(),
+                registry: Arc::new(registry),
+            },
+        }
+    }
+
     /// Returns SDL(Schema Definition Language) of this schema.
     pub fn sdl(&self) -> String {
-        self.0.env.registry.export_sdl(false)
+        self.env.registry.export_sdl(SDLExportOptions::new())
+    }
+
+    /// Returns SDL(Schema Definition Language) of this schema with the options.
+    ///
+    /// The SDL can be fed to schema registries and code generators without running an
+    /// introspection query.
+    pub fn sdl_with_options(&self, options: SDLExportOptions) -> String {
+        self.env.registry.export_sdl(options)
     }
 
     /// Returns Federation SDL(Schema Definition Language) of this schema.
     pub fn federation_sdl(&self) -> String {
-        self.0.env.registry.export_sdl(true)
+        self.env
+            .registry
+            .export_sdl(SDLExportOptions::new().federation())
+    }
+
+    /// Returns a stable hash of this schema.
+    ///
+    /// The hash is computed from the SDL of the schema when it is built, so it only changes when
+    /// the schema changes. Fields added by [`SchemaBuilder::enable_health_check`] and
+    /// [`SchemaBuilder::enable_schema_hash_field`] are not included.
+    pub fn schema_hash(&self) -> &str {
+        &self.env.schema_hash
+    }
+
+    fn add_schema_hash_extension(&self, resp: Response) -> Response {
+        if self.schema_hash_extension {
+            resp.extension(
+                "schemaHash",
+                Value::String(self.env.schema_hash.to_string()),
+            )
+        } else {
+            resp
+        }
+    }
+
+    /// Check the connections of this schema against the
+    /// [GraphQL Cursor Connections Specification](https://relay.dev/graphql/connections.htm), and
+    /// returns the violations.
+    ///
+    /// The connection types are the object types whose name ends with `Connection`. The audit
+    /// checks their `pageInfo` and `edges` fields, their edge and page info types, and the
+    /// pagination arguments of the fields returning them. It is not run when the schema is built,
+    /// it can be called at startup or in a test to keep the pagination conventions consistent.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_graphql::*;
+    /// use async_graphql::connection::*;
+    ///
+    /// struct Query;
+    ///
+    /// #[Object]
+    /// impl Query {
+    ///     async fn numbers(
+    ///         &self,
+    ///         after: Option<String>,
+    ///         first: Option<i32>,
+    ///     ) -> Connection<usize, i32> {
+    ///         Connection::new(false, false)
+    ///     }
+    ///
+    ///     async fn users(&self, limit: i32) -> Connection<usize, i32> {
+    ///         Connection::new(false, false)
+    ///     }
+    /// }
+    ///
+    /// let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    /// let warnings = schema.audit_connections();
+    /// assert!(warnings.iter().all(|warning| warning.coordinate == "Query.users"));
+    /// assert_eq!(
+    ///     warnings[1].to_string(),
+    ///     "Query.users: The `limit` argument is not a pagination argument of the specification.",
+    /// );
+    /// ```
+    pub fn audit_connections(&self) -> Vec<ConnectionWarning> {
+        self.env.registry.audit_connections()
     }
 
     /// Get all names in this schema
@@ -357,7 +1114,7 @@ where
     /// is usually used to compress type names, field names, directive names, and parameter names. This function gets all the names,
     /// so you can create this dictionary.
     pub fn names(&self) -> Vec<String> {
-        self.0.env.registry.names()
+        self.env.registry.names()
     }
 
     fn create_extensions(&self, session_data: Arc<Data>) -> Extensions {
@@ -368,18 +1125,160 @@ where
         )
     }
 
+    fn check_limits(&self, validation_result: &ValidationResult) -> Result<(), Vec<ServerError>> {
+        if let Some(limit_complexity) = self.complexity {
+            if validation_result.complexity > limit_complexity {
+                return Err(vec![ServerError::new("Query is too complex.", None)]);
+            }
+        }
+
+        if let Some(limit_depth) = self.depth {
+            if validation_result.depth > limit_depth {
+                return Err(vec![ServerError::new("Query is nested too deep.", None)]);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_introspection(
+        &self,
+        request: &Request,
+        operation: &Positioned<OperationDefinition>,
+        fragments: &HashMap<Name, Positioned<FragmentDefinition>>,
+    ) -> Result<(), Vec<ServerError>> {
+        let disable_introspection =
+            self.env.registry.disable_introspection || request.disable_introspection;
+        let introspection_only =
+            self.env.registry.introspection_only || request.introspection_only;
+        if !disable_introspection && !introspection_only {
+            return Ok(());
+        }
+
+        if introspection_only && operation.node.ty != OperationType::Query {
+            return Err(vec![ServerError::new(
+                "Only introspection queries are allowed.",
+                Some(operation.pos),
+            )]);
+        }
+
+        let mut fields = Vec::new();
+        collect_root_fields(&operation.node.selection_set, fragments, &mut fields);
+
+        let errors = fields
+            .into_iter()
+            .filter_map(|field| {
+                let is_introspection =
+                    matches!(field.node.name.node.as_str(), "__schema" | "__type");
+                let message = if is_introspection && disable_introspection {
+                    "Introspection is disabled."
+                } else if !is_introspection
+                    && introspection_only
+                    && field.node.name.node != "__typename"
+                {
+                    "Only introspection queries are allowed."
+                } else {
+                    return None;
+                };
+                let mut err = ServerError::new(message, Some(field.pos));
+                err.path = vec![PathSegment::Field(
+                    field.node.response_key().node.to_string(),
+                )];
+                Some(err)
+            })
+            .collect::<Vec<_>>();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Parse and validate a request without executing it.
+    ///
+    /// This runs the same checks as executing the request, including the complexity and depth
+    /// limits, the selection of the operation and the introspection settings, and reports the
+    /// depth and complexity of the query. Extensions are not called, so a request that relies on an extension to provide its
+    /// query, such as an automatic persisted query, must contain the full query.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_graphql::*;
+    ///
+    /// struct Query;
+    ///
+    /// #[Object]
+    /// impl Query {
+    ///     async fn value(&self) -> i32 {
+    ///         10
+    ///     }
+    /// }
+    ///
+    /// let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    ///
+    /// let res = schema.check("{ value }");
+    /// assert!(res.valid);
+    /// assert_eq!(res.depth, 1);
+    /// assert_eq!(res.complexity, 1);
+    ///
+    /// assert!(!schema.check("{ unknown }").valid);
+    /// ```
+    pub fn check(&self, request: impl Into<Request>) -> CheckResult {
+        let request = request.into();
+        let mut document = match parse_query(&request.query) {
+            Ok(document) => document,
+            Err(err) => return CheckResult::from_errors(vec![err.into()]),
+        };
+
+        if let Some(f) = &self.rename_unknown_fields {
+            self.env
+                .registry
+                .rename_unknown_fields(&mut document, f.as_ref());
+        }
+
+        let validation_result = match check_rules(
+            &self.env.registry,
+            &document,
+            Some(&request.variables),
+            self.validation_mode,
+        ) {
+            Ok(validation_result) => validation_result,
+            Err(errors) => return CheckResult::from_errors(errors),
+        };
+
+        let errors = match self.check_limits(&validation_result).and_then(|_| {
+            let (_, operation) =
+                select_operation(document.operations, request.operation_name.as_deref())
+                    .map_err(|err| vec![err])?;
+            self.check_introspection(&request, &operation, &document.fragments)
+        }) {
+            Ok(_) => Vec::new(),
+            Err(errors) => errors,
+        };
+
+        CheckResult {
+            valid: errors.is_empty(),
+            depth: validation_result.depth,
+            complexity: validation_result.complexity,
+            errors,
+        }
+    }
+
     async fn prepare_request(
         &self,
         mut extensions: Extensions,
         request: Request,
         session_data: Arc<Data>,
+        incremental_delivery: bool,
     ) -> Result<(QueryEnv, CacheControl), Vec<ServerError>> {
         let mut request = request;
         let query_data = Arc::new(std::mem::take(&mut request.data));
         extensions.attach_query_data(query_data.clone());
 
         let request = extensions.prepare_request(request).await?;
-        let document = {
+        let mut document = {
             let query = &request.query;
             let fut_parse = async { parse_query(&query).map_err(Into::<ServerError>::into) };
             futures_util::pin_mut!(fut_parse);
@@ -388,6 +1287,12 @@ where
                 .await?
         };
 
+        if let Some(f) = &self.rename_unknown_fields {
+            self.env
+                .registry
+                .rename_unknown_fields(&mut document, f.as_ref());
+        }
+
         // check rules
         let validation_result = {
             let validation_fut = async {
@@ -402,60 +1307,58 @@ where
             extensions.validation(&mut validation_fut).await?
         };
 
-        // check limit
-        if let Some(limit_complexity) = self.complexity {
-            if validation_result.complexity > limit_complexity {
-                return Err(vec![ServerError::new("Query is too complex.", None)]);
-            }
-        }
+        self.check_limits(&validation_result)?;
 
-        if let Some(limit_depth) = self.depth {
-            if validation_result.depth > limit_depth {
-                return Err(vec![ServerError::new("Query is nested too deep.", None)]);
-            }
+        if !extensions.is_empty() {
+            extensions.attach_document(Arc::new(document.clone()));
         }
+        let (operation_name, mut operation) =
+            select_operation(document.operations, request.operation_name.as_deref())
+                .map_err(|err| vec![err])?;
+        extensions.attach_operation(operation.node.ty, operation_name.clone());
+        self.check_introspection(&request, &operation, &document.fragments)?;
+        let mut fragments = document.fragments;
+        let disabled_fields = extensions.rewrite_operation(&mut operation, &mut fragments);
 
-        let operation = if let Some(operation_name) = &request.operation_name {
-            match document.operations {
-                DocumentOperations::Single(_) => None,
-                DocumentOperations::Multiple(mut operations) => operations
-                    .remove(operation_name.as_str())
-                    .map(|operation| (Some(operation_name.clone()), operation)),
-            }
-            .ok_or_else(|| {
-                ServerError::new(
-                    format!(r#"Unknown operation named "{}""#, operation_name),
-                    None,
-                )
-            })
-        } else {
-            match document.operations {
-                DocumentOperations::Single(operation) => Ok((None, operation)),
-                DocumentOperations::Multiple(map) if map.len() == 1 => {
-                    let (operation_name, operation) = map.into_iter().next().unwrap();
-                    Ok((Some(operation_name.to_string()), operation))
-                }
-                DocumentOperations::Multiple(_) => Err(ServerError::new(
-                    "Operation name required in request.",
-                    None,
-                )),
+        #[cfg(feature = "timeout")]
+        let deadline = match request
+            .partial_results_timeout
+            .or(self.partial_results_timeout)
+        {
+            Some(timeout) if operation.node.ty != OperationType::Subscription => {
+                Some(Delay::new(timeout).shared())
             }
+            _ => None,
         };
 
-        let (operation_name, operation) = operation.map_err(|err| vec![err])?;
-
+        let (max_list_elements, max_response_size) = match operation.node.ty {
+            OperationType::Subscription => (None, None),
+            _ => (self.list_elements, self.response_size),
+        };
         let env = QueryEnvInner {
             extensions,
             variables: request.variables,
             operation_name,
             operation,
-            fragments: document.fragments,
+            fragments,
             uploads: request.uploads,
             session_data,
             ctx_data: query_data,
             http_headers: Default::default(),
             disable_introspection: request.disable_introspection,
+            on_error: request.on_error.unwrap_or(self.on_error),
+            max_list_elements,
+            list_elements: Default::default(),
+            max_response_size,
+            response_size: Default::default(),
+            response_too_large: Default::default(),
             errors: Default::default(),
+            memoize_cache: Default::default(),
+            #[cfg(feature = "timeout")]
+            deadline,
+            incremental_delivery,
+            incremental_payloads: Default::default(),
+            disabled_fields,
         };
         Ok((QueryEnv::new(env), validation_result.cache_control))
     }
@@ -471,7 +1374,19 @@ where
 
         let res = match &env.operation.node.ty {
             OperationType::Query => resolve_container(&ctx, &self.query).await,
-            OperationType::Mutation => resolve_container_serial(&ctx, &self.mutation).await,
+            OperationType::Mutation => {
+                let root = MutationRoot {
+                    inner: &self.mutation,
+                    type_name: self
+                        .env
+                        .registry
+                        .mutation_type
+                        .as_deref()
+                        .unwrap_or_default(),
+                    modules: &self.mutation_modules,
+                };
+                resolve_container_serial(&ctx, &root).await
+            }
             OperationType::Subscription => Err(ServerError::new(
                 "Subscriptions are not supported on this transport.",
                 None,
@@ -486,6 +1401,21 @@ where
 
         resp.errors
             .extend(std::mem::take(&mut *env.errors.lock().unwrap()));
+
+        if let Some(err) = env.response_too_large.lock().unwrap().take() {
+            resp.data = Value::Null;
+            resp.errors = vec![err];
+        } else if let Some(max_size) = env.max_response_size {
+            // The values which are not resolved as fields, such as the errors and the objects
+            // returned by scalars, are only counted here.
+            if json_size(&resp) > max_size {
+                resp.data = Value::Null;
+                resp.errors = vec![response_too_large_error(format!(
+                    "The response is larger than {} bytes.",
+                    max_size
+                ))];
+            }
+        }
         resp
     }
 
@@ -497,7 +1427,7 @@ where
             let extensions = extensions.clone();
             async move {
                 match self
-                    .prepare_request(extensions, request, Default::default())
+                    .prepare_request(extensions, request, Default::default(), false)
                     .await
                 {
                     Ok((env, cache_control)) => {
@@ -516,7 +1446,8 @@ where
             }
         };
         futures_util::pin_mut!(request_fut);
-        extensions.request(&mut request_fut).await
+        let resp = extensions.request(&mut request_fut).await;
+        self.add_schema_hash_extension(resp)
     }
 
     /// Execute a GraphQL batch query.
@@ -532,6 +1463,52 @@ where
         }
     }
 
+    /// Execute a GraphQL request, or a batch of requests, in the JSON format, and returns the
+    /// response in the JSON format.
+    ///
+    /// If the request cannot be deserialized, the response contains an error describing why.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_graphql::*;
+    /// use serde_json::json;
+    ///
+    /// struct Query;
+    ///
+    /// #[Object]
+    /// impl Query {
+    ///     async fn add(&self, a: i32, b: i32) -> i32 {
+    ///         a + b
+    ///     }
+    /// }
+    ///
+    /// tokio::runtime::Runtime::new().unwrap().block_on(async move {
+    ///     let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    ///     let request = json!({
+    ///         "query": "query($a: Int!) { add(a: $a, b: 2) }",
+    ///         "variables": { "a": 1 },
+    ///     });
+    ///     assert_eq!(
+    ///         schema.execute_json(&request).await,
+    ///         json!({ "data": { "add": 3 } })
+    ///     );
+    /// });
+    /// ```
+    pub async fn execute_json(&self, request: &serde_json::Value) -> serde_json::Value {
+        let resp = match BatchRequest::deserialize(request) {
+            Ok(batch_request) => self.execute_batch(batch_request).await,
+            Err(err) => BatchResponse::Single(Response::from_errors(vec![ServerError::new(
+                ParseRequestError::InvalidRequest(Box::new(err)).to_string(),
+                None,
+            )])),
+        };
+        serde_json::to_value(resp).unwrap_or_else(|err| {
+            let message = format!("Failed to serialize the response: {}", err);
+            serde_json::json!({ "errors": [{ "message": message }] })
+        })
+    }
+
     /// Execute a GraphQL subscription with session data.
     #[doc(hidden)]
     pub fn execute_stream_with_session_data(
@@ -546,7 +1523,7 @@ where
         let stream = futures_util::stream::StreamExt::boxed({
             let extensions = extensions.clone();
             async_stream::stream! {
-                let (env, cache_control) = match schema.prepare_request(extensions, request, session_data).await {
+                let (env, cache_control) = match schema.prepare_request(extensions, request, session_data, true).await {
                     Ok(res) => res,
                     Err(errors) => {
                         yield Response::from_errors(errors);
@@ -555,7 +1532,27 @@ where
                 };
 
                 if env.operation.node.ty != OperationType::Subscription {
-                    yield schema.execute_once(env).await.cache_control(cache_control);
+                    let fut = async {
+                        schema
+                            .execute_once(env.clone())
+                            .await
+                            .cache_control(cache_control)
+                    };
+                    futures_util::pin_mut!(fut);
+                    let resp = env.extensions
+                        .execute(env.operation_name.as_deref(), &mut fut)
+                        .await;
+
+                    let payloads = std::mem::take(&mut *env.incremental_payloads.lock().unwrap());
+                    if payloads.is_empty() {
+                        yield resp;
+                        return;
+                    }
+                    yield Response { has_next: Some(true), ..resp };
+                    let count = payloads.len();
+                    for (idx, payload) in payloads.into_iter().enumerate() {
+                        yield Response { has_next: Some(idx + 1 < count), ..payload };
+                    }
                     return;
                 }
 
@@ -576,10 +1573,27 @@ where
                 }
             }
         });
-        extensions.subscribe(stream)
+        let stream = extensions.subscribe(stream);
+        if self.schema_hash_extension {
+            let schema = self.clone();
+            stream
+                .map(move |resp| schema.add_schema_hash_extension(resp))
+                .boxed()
+        } else {
+            stream
+        }
     }
 
     /// Execute a GraphQL subscription.
+    ///
+    /// Queries and mutations are also accepted, in which case the stream yields exactly one
+    /// response, the same as the one returned by [`Schema::execute`]. This allows stream-oriented
+    /// transports to use a single code path for all operation types.
+    ///
+    /// Queries and mutations with `@defer` or `@stream` directives are delivered incrementally:
+    /// the deferred fragments and the streamed list items are left out of the first response, and
+    /// are sent in the following responses with their `path`. Every response has a `hasNext`
+    /// field, which is `false` for the last one. [`Schema::execute`] ignores these directives.
     pub fn execute_stream(
         &self,
         request: impl Into<Request>,
@@ -587,3 +1601,109 @@ where
         self.execute_stream_with_session_data(request.into(), Default::default())
     }
 }
+
+/// The result of [`Schema::check`].
+#[derive(Debug, Default)]
+pub struct CheckResult {
+    /// Whether the request can be executed.
+    pub valid: bool,
+
+    /// The depth of the query, or `0` if it could not be parsed or validated.
+    pub depth: usize,
+
+    /// The complexity of the query, or `0` if it could not be parsed or validated.
+    pub complexity: usize,
+
+    /// The errors that prevent the request from being executed.
+    pub errors: Vec<ServerError>,
+}
+
+impl CheckResult {
+    fn from_errors(errors: Vec<ServerError>) -> Self {
+        Self {
+            errors,
+            ..Default::default()
+        }
+    }
+}
+
+fn collect_root_fields<'a>(
+    selection_set: &'a Positioned<SelectionSet>,
+    fragments: &'a HashMap<Name, Positioned<FragmentDefinition>>,
+    fields: &mut Vec<&'a Positioned<Field>>,
+) {
+    for selection in &selection_set.node.items {
+        match &selection.node {
+            Selection::Field(field) => fields.push(field),
+            Selection::FragmentSpread(spread) => {
+                if let Some(fragment) = fragments.get(&spread.node.fragment_name.node) {
+                    collect_root_fields(&fragment.node.selection_set, fragments, fields);
+                }
+            }
+            Selection::InlineFragment(fragment) => {
+                collect_root_fields(&fragment.node.selection_set, fragments, fields);
+            }
+        }
+    }
+}
+
+pub(crate) fn select_operation(
+    operations: DocumentOperations,
+    operation_name: Option<&str>,
+) -> Result<(Option<String>, Positioned<OperationDefinition>), ServerError> {
+    if let Some(operation_name) = operation_name {
+        match operations {
+            DocumentOperations::Single(_) => None,
+            DocumentOperations::Multiple(mut operations) => operations
+                .remove(operation_name)
+                .map(|operation| (Some(operation_name.to_string()), operation)),
+        }
+        .ok_or_else(|| {
+            ServerError::new(
+                format!(r#"Unknown operation named "{}""#, operation_name),
+                None,
+            )
+        })
+    } else {
+        match operations {
+            DocumentOperations::Single(operation) => Ok((None, operation)),
+            DocumentOperations::Multiple(map) if map.len() == 1 => {
+                let (operation_name, operation) = map.into_iter().next().unwrap();
+                Ok((Some(operation_name.to_string()), operation))
+            }
+            DocumentOperations::Multiple(_) => Err(ServerError::new(
+                "Operation name required in request.",
+                None,
+            )),
+        }
+    }
+}
+
+pub(crate) fn response_too_large_error(message: impl Into<String>) -> ServerError {
+    let mut extensions = ErrorExtensionValues::default();
+    extensions.set("code", "RESPONSE_TOO_LARGE");
+    ServerError {
+        extensions: Some(extensions),
+        ..ServerError::new(message, None)
+    }
+}
+
+/// Returns the length of the JSON encoding of a value, without buffering it.
+pub(crate) fn json_size(value: &impl Serialize) -> usize {
+    struct Counter(usize);
+
+    impl std::io::Write for Counter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0 += buf.len();
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut counter = Counter(0);
+    let _ = serde_json::to_writer(&mut counter, value);
+    counter.0
+}
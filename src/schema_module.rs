@@ -0,0 +1,276 @@
+use std::any::Any;
+use std::borrow::Cow;
+
+use crate::context::Data;
+use crate::extensions::ExtensionFactory;
+use crate::parser::types::Field;
+use crate::registry::{MetaField, MetaType, MetaTypeName, Registry};
+use crate::resolver_utils::{resolve_container, ContainerType};
+use crate::{
+    Context, ContextSelectionSet, DynObject, ObjectType, OutputType, Positioned, ServerResult,
+    Type, Value,
+};
+
+type Registration = Box<dyn FnOnce(&mut Registry, &str) + Send + Sync>;
+
+/// A part of a schema that is defined in its own crate.
+///
+/// A module bundles root fields, types that are not directly referenced, extensions and data, and
+/// is added to a schema with [`SchemaBuilder::module`](crate::SchemaBuilder::module). This allows
+/// a large application to assemble its schema from feature crates, instead of one query object
+/// that lists all of the root fields.
+///
+/// The fields of the objects added with [`SchemaModule::query`] and [`SchemaModule::mutation`]
+/// become fields of the query and the mutation root types. Subscription fields are not supported.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// // Defined in the `users` crate.
+/// struct UsersQuery;
+///
+/// #[Object]
+/// impl UsersQuery {
+///     async fn user_count(&self, ctx: &Context<'_>) -> i32 {
+///         *ctx.data_unchecked::<i32>()
+///     }
+/// }
+///
+/// fn users_module() -> SchemaModule {
+///     SchemaModule::new("users").query(UsersQuery).data(10i32)
+/// }
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn version(&self) -> &str {
+///         "1.0"
+///     }
+/// }
+///
+/// tokio::runtime::Runtime::new().unwrap().block_on(async move {
+///     let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///         .module(users_module())
+///         .finish();
+///     let res = schema.execute("{ version userCount }").await.into_result().unwrap().data;
+///     assert_eq!(res, value!({ "version": "1.0", "userCount": 10 }));
+/// });
+/// ```
+pub struct SchemaModule {
+    pub(crate) name: String,
+    pub(crate) query: Vec<DynObject>,
+    pub(crate) mutation: Vec<DynObject>,
+    pub(crate) registrations: Vec<Registration>,
+    pub(crate) extensions: Vec<Box<dyn ExtensionFactory>>,
+    pub(crate) data: Data,
+}
+
+impl SchemaModule {
+    /// Create an empty module with the specified name, which is used in error messages.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            query: Vec::new(),
+            mutation: Vec::new(),
+            registrations: Vec::new(),
+            extensions: Vec::new(),
+            data: Default::default(),
+        }
+    }
+
+    /// Add the fields of an object to the query root type.
+    ///
+    /// # Panics
+    ///
+    /// The schema builder panics if a field is already defined in the query root type.
+    #[must_use]
+    pub fn query<T: ObjectType + Send + Sync + 'static>(mut self, obj: T) -> Self {
+        self.query.push(DynObject::new(obj));
+        self.registrations.push(Box::new(|registry, module| {
+            let root = registry.query_type.clone();
+            merge_root_fields::<T>(registry, &root, module);
+        }));
+        self
+    }
+
+    /// Add the fields of an object to the mutation root type.
+    ///
+    /// If the schema has no mutation root type, a `Mutation` type is created.
+    ///
+    /// # Panics
+    ///
+    /// The schema builder panics if a field is already defined in the mutation root type.
+    #[must_use]
+    pub fn mutation<T: ObjectType + Send + Sync + 'static>(mut self, obj: T) -> Self {
+        self.mutation.push(DynObject::new(obj));
+        self.registrations.push(Box::new(|registry, module| {
+            let root = match &registry.mutation_type {
+                Some(root) => root.clone(),
+                None => {
+                    registry.types.insert(
+                        "Mutation".to_string(),
+                        MetaType::Object {
+                            name: "Mutation".to_string(),
+                            description: None,
+                            fields: Default::default(),
+                            cache_control: Default::default(),
+                            extends: false,
+                            keys: None,
+                            visible: None,
+                            is_subscription: false,
+                            rust_typename: std::any::type_name::<SchemaModule>(),
+                        },
+                    );
+                    registry.mutation_type = Some("Mutation".to_string());
+                    "Mutation".to_string()
+                }
+            };
+            merge_root_fields::<T>(registry, &root, module);
+        }));
+        self
+    }
+
+    /// Register a type that is not directly referenced by the fields of the module.
+    #[must_use]
+    pub fn register_type<T: Type>(mut self) -> Self {
+        self.registrations.push(Box::new(|registry, _| {
+            T::create_type_info(registry);
+        }));
+        self
+    }
+
+    /// Add an object type to the possible types of a union or an interface.
+    ///
+    /// See [`SchemaBuilder::register_possible_type`](crate::SchemaBuilder::register_possible_type).
+    #[must_use]
+    pub fn register_possible_type<A: Type, T: ObjectType>(mut self) -> Self {
+        self.registrations.push(Box::new(|registry, _| {
+            registry.add_possible_type::<A, T>();
+        }));
+        self
+    }
+
+    /// Add an extension to the schema.
+    #[must_use]
+    pub fn extension(mut self, extension: impl ExtensionFactory) -> Self {
+        self.extensions.push(Box::new(extension));
+        self
+    }
+
+    /// Add a global data that can be accessed in the `Schema`. You access it with
+    /// `Context::data`.
+    #[must_use]
+    pub fn data<D: Any + Send + Sync>(mut self, data: D) -> Self {
+        self.data.insert(data);
+        self
+    }
+}
+
+fn merge_root_fields<T: Type>(registry: &mut Registry, root: &str, module: &str) {
+    let type_name = T::type_name();
+    let registered = registry.types.contains_key(&*type_name);
+    let fields = match registry.create_dummy_type::<T>() {
+        MetaType::Object { fields, .. } => fields,
+        _ => unreachable!(),
+    };
+
+    if let Some(MetaType::Object {
+        fields: root_fields,
+        ..
+    }) = registry.types.get_mut(root)
+    {
+        for (name, field) in fields {
+            if root_fields.contains_key(&name) {
+                panic!(
+                    "The module `{}` defines the field `{}.{}`, which is already defined.",
+                    module, root, name
+                );
+            }
+            root_fields.insert(name, field);
+        }
+    }
+
+    // The object is still a type of the schema if it was registered by other types, or if the
+    // merged fields return it.
+    if !registered && !is_referenced(registry, &type_name) {
+        registry.types.remove(&*type_name);
+    }
+}
+
+fn is_referenced(registry: &Registry, type_name: &str) -> bool {
+    let returns_type = |field: &MetaField| MetaTypeName::concrete_typename(&field.ty) == type_name;
+    registry
+        .types
+        .values()
+        .filter(|ty| ty.name() != type_name)
+        .any(|ty| match ty {
+            MetaType::Object { fields, .. } => fields.values().any(returns_type),
+            MetaType::Interface {
+                fields,
+                possible_types,
+                ..
+            } => possible_types.contains(type_name) || fields.values().any(returns_type),
+            MetaType::Union { possible_types, .. } => possible_types.contains(type_name),
+            _ => false,
+        })
+}
+
+/// The mutation root, which resolves the fields of the modules after the fields of the mutation
+/// object.
+pub(crate) struct MutationRoot<'a, T> {
+    pub(crate) inner: &'a T,
+    pub(crate) type_name: &'a str,
+    pub(crate) modules: &'a [DynObject],
+}
+
+impl<'a, T: Type> Type for MutationRoot<'a, T> {
+    fn type_name() -> Cow<'static, str> {
+        T::type_name()
+    }
+
+    fn introspection_type_name(&self) -> Cow<'static, str> {
+        Cow::Owned(self.type_name.to_string())
+    }
+
+    fn create_type_info(registry: &mut Registry) -> String {
+        T::create_type_info(registry)
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a, T: ObjectType> ContainerType for MutationRoot<'a, T> {
+    async fn resolve_field(&self, ctx: &Context<'_>) -> ServerResult<Option<Value>> {
+        if let Some(value) = self.inner.resolve_field(ctx).await? {
+            return Ok(Some(value));
+        }
+        resolve_module_field(self.modules, ctx).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a, T: ObjectType> OutputType for MutationRoot<'a, T> {
+    async fn resolve(
+        &self,
+        ctx: &ContextSelectionSet<'_>,
+        _field: &Positioned<Field>,
+    ) -> ServerResult<Value> {
+        resolve_container(ctx, self).await
+    }
+}
+
+impl<'a, T: ObjectType> ObjectType for MutationRoot<'a, T> {}
+
+pub(crate) async fn resolve_module_field(
+    modules: &[DynObject],
+    ctx: &Context<'_>,
+) -> ServerResult<Option<Value>> {
+    for module in modules {
+        if let Some(value) = module.resolve_field(ctx).await? {
+            return Ok(Some(value));
+        }
+    }
+    Ok(None)
+}
@@ -0,0 +1,56 @@
+use hmac::{Hmac, Mac, NewMac};
+use sha2::Sha256;
+
+/// Signs cursors and global IDs with HMAC-SHA256, so that clients cannot forge them.
+///
+/// A signed value is `{value}.{signature}`, where the signature is base64 encoded.
+pub(crate) struct Signer {
+    key: Vec<u8>,
+}
+
+impl Signer {
+    pub(crate) fn new(key: &[u8]) -> Self {
+        Self { key: key.to_vec() }
+    }
+
+    fn mac(&self, value: &str) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_varkey(&self.key).expect("HMAC accepts any key length");
+        mac.update(value.as_bytes());
+        mac
+    }
+
+    pub(crate) fn sign(&self, value: &str) -> String {
+        let signature = self.mac(value).finalize().into_bytes();
+        format!(
+            "{}.{}",
+            value,
+            base64::encode_config(signature, base64::URL_SAFE_NO_PAD)
+        )
+    }
+
+    /// Returns the value without the signature, or `None` if the signature is missing or
+    /// invalid.
+    pub(crate) fn verify<'a>(&self, signed: &'a str) -> Option<&'a str> {
+        let (value, signature) = signed.rsplit_once('.')?;
+        let signature = base64::decode_config(signature, base64::URL_SAFE_NO_PAD).ok()?;
+        self.mac(value).verify(&signature).ok()?;
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let signer = Signer::new(b"secret");
+        let signed = signer.sign("VXNlcjox");
+        assert!(signed.starts_with("VXNlcjox."));
+        assert_eq!(signer.verify(&signed), Some("VXNlcjox"));
+
+        assert_eq!(signer.verify("VXNlcjox"), None);
+        assert_eq!(signer.verify(&signed.replace("VXNlcjox", "VXNlcjoy")), None);
+        assert_eq!(Signer::new(b"other").verify(&signed), None);
+    }
+}
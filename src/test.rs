@@ -0,0 +1,268 @@
+//! Utilities for testing schemas
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::Poll;
+
+use futures_util::stream::{self, BoxStream, StreamExt};
+use futures_util::task::AtomicWaker;
+use serde::Deserialize;
+
+use crate::http::{WebSocket, WebSocketProtocols, WsMessage};
+use crate::{Data, ObjectType, Request, Result, Schema, SubscriptionType};
+
+/// An event received by a [`WsClient`].
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WsEvent {
+    /// The server accepted the connection.
+    ConnectionAck,
+
+    /// The server rejected the connection.
+    ConnectionError {
+        /// The error.
+        payload: serde_json::Value,
+    },
+
+    /// A response of an operation, sent as `data` or `next` depending on the protocol.
+    #[serde(alias = "data")]
+    Next {
+        /// The id of the operation.
+        id: String,
+        /// The response.
+        payload: serde_json::Value,
+    },
+
+    /// An operation is completed.
+    Complete {
+        /// The id of the operation.
+        id: String,
+    },
+
+    /// The answer to a ping.
+    Pong,
+
+    /// The server closed the connection.
+    #[serde(skip)]
+    Close {
+        /// The close code.
+        code: u16,
+        /// The reason.
+        reason: String,
+    },
+}
+
+#[derive(Default)]
+struct Outbox {
+    messages: Mutex<VecDeque<String>>,
+    waker: AtomicWaker,
+    closed: AtomicBool,
+}
+
+/// A WebSocket client for testing subscriptions.
+///
+/// The client is connected to the [`WebSocket`] of a schema in the same process, and speaks the
+/// protocol it is created with, so that the same test can run with both protocols. The messages
+/// are sent without waiting, and the events are received with [`WsClient::next_event`], which
+/// waits forever if the server does not send anything.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::http::WebSocketProtocols;
+/// use async_graphql::test::{WsClient, WsEvent};
+/// use futures_util::stream::{self, Stream};
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// struct Subscription;
+///
+/// #[Subscription]
+/// impl Subscription {
+///     async fn values(&self) -> impl Stream<Item = i32> {
+///         stream::iter(vec![1, 2])
+///     }
+/// }
+///
+/// tokio::runtime::Runtime::new().unwrap().block_on(async move {
+///     let schema = Schema::new(Query, EmptyMutation, Subscription);
+///     for protocol in [WebSocketProtocols::SubscriptionsTransportWS, WebSocketProtocols::GraphQLWS] {
+///         let mut client = WsClient::new(schema.clone(), protocol);
+///         client.init(None);
+///         assert_eq!(client.next_event().await, Some(WsEvent::ConnectionAck));
+///
+///         client.subscribe("1", "subscription { values }");
+///         assert_eq!(
+///             client.next_payloads("1").await,
+///             vec![
+///                 serde_json::json!({ "data": { "values": 1 } }),
+///                 serde_json::json!({ "data": { "values": 2 } }),
+///             ]
+///         );
+///     }
+/// });
+/// ```
+pub struct WsClient {
+    protocol: WebSocketProtocols,
+    outbox: Arc<Outbox>,
+    stream: BoxStream<'static, WsMessage>,
+}
+
+impl WsClient {
+    /// Connect a client to a schema.
+    pub fn new<Query, Mutation, Subscription>(
+        schema: Schema<Query, Mutation, Subscription>,
+        protocol: WebSocketProtocols,
+    ) -> Self
+    where
+        Query: ObjectType + 'static,
+        Mutation: ObjectType + 'static,
+        Subscription: SubscriptionType + 'static,
+    {
+        Self::with_data(schema, protocol, |_| {
+            futures_util::future::ready(Ok(Default::default()))
+        })
+    }
+
+    /// Connect a client to a schema, whose connection data is created by `data_initializer`
+    /// from the payload of the `connection_init` message, as with [`WebSocket::with_data`].
+    pub fn with_data<Query, Mutation, Subscription, F, R>(
+        schema: Schema<Query, Mutation, Subscription>,
+        protocol: WebSocketProtocols,
+        data_initializer: F,
+    ) -> Self
+    where
+        Query: ObjectType + 'static,
+        Mutation: ObjectType + 'static,
+        Subscription: SubscriptionType + 'static,
+        F: FnOnce(serde_json::Value) -> R + Send + 'static,
+        R: Future<Output = Result<Data>> + Send + 'static,
+    {
+        let outbox = Arc::new(Outbox::default());
+        let messages = stream::poll_fn({
+            let outbox = outbox.clone();
+            move |cx| {
+                outbox.waker.register(cx.waker());
+                if let Some(message) = outbox.messages.lock().unwrap().pop_front() {
+                    Poll::Ready(Some(message))
+                } else if outbox.closed.load(Ordering::SeqCst) {
+                    Poll::Ready(None)
+                } else {
+                    Poll::Pending
+                }
+            }
+        });
+
+        Self {
+            protocol,
+            outbox,
+            stream: WebSocket::with_data(schema, messages, data_initializer, protocol).boxed(),
+        }
+    }
+
+    /// Returns the protocol of the client.
+    pub fn protocol(&self) -> WebSocketProtocols {
+        self.protocol
+    }
+
+    /// Send a message to the server.
+    pub fn send(&self, message: serde_json::Value) {
+        self.outbox
+            .messages
+            .lock()
+            .unwrap()
+            .push_back(message.to_string());
+        self.outbox.waker.wake();
+    }
+
+    /// Send the `connection_init` message.
+    pub fn init(&self, payload: Option<serde_json::Value>) {
+        let mut message = serde_json::json!({ "type": "connection_init" });
+        if let Some(payload) = payload {
+            message["payload"] = payload;
+        }
+        self.send(message);
+    }
+
+    /// Start an operation with the `id`.
+    pub fn subscribe(&self, id: &str, request: impl Into<Request>) {
+        let request = request.into();
+        let ty = match self.protocol {
+            WebSocketProtocols::SubscriptionsTransportWS => "start",
+            WebSocketProtocols::GraphQLWS => "subscribe",
+        };
+        self.send(serde_json::json!({
+            "type": ty,
+            "id": id,
+            "payload": {
+                "query": request.query,
+                "operationName": request.operation_name,
+                "variables": request.variables,
+            },
+        }));
+    }
+
+    /// Stop the operation with the `id`.
+    pub fn stop(&self, id: &str) {
+        let ty = match self.protocol {
+            WebSocketProtocols::SubscriptionsTransportWS => "stop",
+            WebSocketProtocols::GraphQLWS => "complete",
+        };
+        self.send(serde_json::json!({ "type": ty, "id": id }));
+    }
+
+    /// Send a ping message.
+    pub fn ping(&self) {
+        self.send(serde_json::json!({ "type": "ping" }));
+    }
+
+    /// Close the connection, after the messages that were already sent.
+    pub fn close(&self) {
+        self.outbox.closed.store(true, Ordering::SeqCst);
+        self.outbox.waker.wake();
+    }
+
+    /// Receive the next event, or `None` if the connection is closed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the server sends a message that is not an event.
+    pub async fn next_event(&mut self) -> Option<WsEvent> {
+        match self.stream.next().await? {
+            WsMessage::Text(text) => Some(
+                serde_json::from_str(&text)
+                    .unwrap_or_else(|err| panic!("invalid message {}: {}", text, err)),
+            ),
+            WsMessage::Close(code, reason) => Some(WsEvent::Close { code, reason }),
+        }
+    }
+
+    /// Receive the payloads of the operation with the `id` until it is completed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if another event is received before the operation is completed.
+    pub async fn next_payloads(&mut self, id: &str) -> Vec<serde_json::Value> {
+        let mut payloads = Vec::new();
+        loop {
+            match self.next_event().await {
+                Some(WsEvent::Next {
+                    id: event_id,
+                    payload,
+                }) if event_id == id => payloads.push(payload),
+                Some(WsEvent::Complete { id: event_id }) if event_id == id => return payloads,
+                event => panic!("unexpected event {:?}", event),
+            }
+        }
+    }
+}
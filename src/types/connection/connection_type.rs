@@ -159,6 +159,8 @@ where
                             external: false,
                             requires: None,
                             provides: None,
+                            shareable: false,
+                            override_from: None,
                             visible: None,
                             compute_complexity: None,
                         },
@@ -178,6 +180,8 @@ where
                             external: false,
                             requires: None,
                             provides: None,
+                            shareable: false,
+                            override_from: None,
                             visible: None,
                             compute_complexity: None,
                         },
@@ -210,8 +214,14 @@ where
             let page_info = PageInfo {
                 has_previous_page: self.has_previous_page,
                 has_next_page: self.has_next_page,
-                start_cursor: self.edges.first().map(|edge| edge.cursor.encode_cursor()),
-                end_cursor: self.edges.last().map(|edge| edge.cursor.encode_cursor()),
+                start_cursor: self
+                    .edges
+                    .first()
+                    .map(|edge| ctx.sign(edge.cursor.encode_cursor())),
+                end_cursor: self
+                    .edges
+                    .last()
+                    .map(|edge| ctx.sign(edge.cursor.encode_cursor())),
             };
             let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
             return OutputType::resolve(&page_info, &ctx_obj, ctx.item)
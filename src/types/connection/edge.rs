@@ -78,6 +78,8 @@ where
                             external: false,
                             requires: None,
                             provides: None,
+                            shareable: false,
+                            override_from: None,
                             visible: None,
                             compute_complexity: None,
                         },
@@ -95,6 +97,8 @@ where
                             external: false,
                             requires: None,
                             provides: None,
+                            shareable: false,
+                            override_from: None,
                             visible: None,
                             compute_complexity: None,
                         },
@@ -128,7 +132,7 @@ where
                 .await
                 .map(Some);
         } else if ctx.item.node.name.node == "cursor" {
-            return Ok(Some(Value::String(self.cursor.encode_cursor())));
+            return Ok(Some(Value::String(ctx.sign(self.cursor.encode_cursor()))));
         }
 
         self.additional_fields.resolve_field(ctx).await
@@ -8,7 +8,7 @@ mod page_info;
 use std::fmt::Display;
 use std::future::Future;
 
-use crate::{Result, SimpleObject};
+use crate::{Context, Result, SimpleObject};
 pub use connection_type::Connection;
 pub use cursor::CursorType;
 pub use edge::Edge;
@@ -21,6 +21,9 @@ pub struct EmptyFields;
 
 /// Parses the parameters and executes the query.
 ///
+/// If the schema has an HMAC key, the `after` and `before` cursors must have been signed with it.
+/// See [`SchemaBuilder::hmac_key`](crate::SchemaBuilder::hmac_key).
+///
 /// # Examples
 ///
 /// ```rust
@@ -39,12 +42,13 @@ pub struct EmptyFields;
 /// #[Object]
 /// impl QueryRoot {
 ///     async fn numbers(&self,
+///         ctx: &Context<'_>,
 ///         after: Option<String>,
 ///         before: Option<String>,
 ///         first: Option<i32>,
 ///         last: Option<i32>
 ///     ) -> Result<Connection<usize, i32, EmptyFields, Diff>> {
-///         query(after, before, first, last, |after, before, first, last| async move {
+///         query(ctx, after, before, first, last, |after, before, first, last| async move {
 ///             let mut start = after.map(|after| after + 1).unwrap_or(0);
 ///             let mut end = before.unwrap_or(10000);
 ///             if let Some(first) = first {
@@ -90,6 +94,7 @@ pub struct EmptyFields;
 /// });
 /// ```
 pub async fn query<Cursor, Node, ConnectionFields, EdgeFields, F, R>(
+    ctx: &Context<'_>,
     after: Option<String>,
     before: Option<String>,
     first: Option<i32>,
@@ -123,12 +128,12 @@ where
     };
 
     let before = match before {
-        Some(before) => Some(Cursor::decode_cursor(&before)?),
+        Some(before) => Some(Cursor::decode_cursor(ctx.verify(&before)?)?),
         None => None,
     };
 
     let after = match after {
-        Some(after) => Some(Cursor::decode_cursor(&after)?),
+        Some(after) => Some(Cursor::decode_cursor(ctx.verify(&after)?)?),
         None => None,
     };
 
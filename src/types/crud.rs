@@ -0,0 +1,100 @@
+//! Root fields to create, read, update and delete simple entities
+//!
+//! The [`CrudObject`](crate::CrudObject) derive generates the input objects of an entity, and the
+//! `XCrudQuery` and `XCrudMutation` objects whose resolvers call an [`EntityStore`]. This is meant
+//! to bootstrap admin APIs, the fields can be replaced by hand-written resolvers later.
+
+use std::sync::Arc;
+
+use crate::connection::{query, Connection, Edge};
+use crate::{Context, InputObjectType, InputType, OutputType, Result, SchemaModule};
+
+/// An entity generated by [`CrudObject`](crate::CrudObject).
+pub trait CrudEntity: OutputType + Send + Sync + Sized + 'static {
+    /// The type of the `id` field.
+    type Id: InputType + Send + Sync;
+
+    /// The input object to create an entity, with all the fields except `id`.
+    type CreateInput: InputObjectType + Send + Sync;
+
+    /// The input object to update an entity, whose fields are set if they are present.
+    type UpdateInput: InputObjectType + Send + Sync;
+
+    /// Create a module with the root fields of the entity, whose resolvers call the store.
+    fn crud_module(store: Arc<dyn EntityStore<Self>>) -> SchemaModule;
+}
+
+/// The storage of the entities of type `T`, which is implemented by the application.
+///
+/// The context of the request is passed to every method, so the store can access the data of
+/// the request, such as the current user.
+#[async_trait::async_trait]
+pub trait EntityStore<T: CrudEntity>: Send + Sync {
+    /// Returns the entity with the `id`, or `None` if it does not exist.
+    async fn get(&self, ctx: &Context<'_>, id: T::Id) -> Result<Option<T>>;
+
+    /// Returns at most `limit` entities, starting at `offset` in the order of the store.
+    async fn list(&self, ctx: &Context<'_>, offset: usize, limit: usize) -> Result<Vec<T>>;
+
+    /// Returns the number of entities.
+    async fn count(&self, ctx: &Context<'_>) -> Result<usize>;
+
+    /// Create an entity and return it.
+    async fn create(&self, ctx: &Context<'_>, input: T::CreateInput) -> Result<T>;
+
+    /// Update the entity with the `id` and return it, or `None` if it does not exist.
+    async fn update(
+        &self,
+        ctx: &Context<'_>,
+        id: T::Id,
+        input: T::UpdateInput,
+    ) -> Result<Option<T>>;
+
+    /// Delete the entity with the `id`, and return `false` if it does not exist.
+    async fn delete(&self, ctx: &Context<'_>, id: T::Id) -> Result<bool>;
+}
+
+/// Resolve a page of the entities of a store as a connection, whose cursors are the offsets of
+/// the entities.
+pub async fn list_connection<T: CrudEntity>(
+    store: &dyn EntityStore<T>,
+    ctx: &Context<'_>,
+    after: Option<String>,
+    before: Option<String>,
+    first: Option<i32>,
+    last: Option<i32>,
+) -> Result<Connection<usize, T>> {
+    query(
+        ctx,
+        after,
+        before,
+        first,
+        last,
+        |after, before, first, last| async move {
+            let count = store.count(ctx).await?;
+            let mut start = after.map(|after| after + 1).unwrap_or(0);
+            let mut end = before.unwrap_or(count).min(count);
+            if let Some(first) = first {
+                end = (start + first).min(end);
+            }
+            if let Some(last) = last {
+                start = end.saturating_sub(last).max(start);
+            }
+            let items = if start < end {
+                store.list(ctx, start, end - start).await?
+            } else {
+                Vec::new()
+            };
+
+            let mut connection = Connection::new(start > 0, end < count);
+            connection.append(
+                items
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, item)| Edge::new(start + index, item)),
+            );
+            Ok(connection)
+        },
+    )
+    .await
+}
@@ -0,0 +1,123 @@
+use std::borrow::Cow;
+
+use crate::resolver_utils::Fields;
+use crate::{Context, ContextSelectionSet, ObjectType, ServerResult, Value};
+
+#[async_trait::async_trait]
+trait ErasedObject: Send + Sync {
+    fn introspection_type_name(&self) -> Cow<'static, str>;
+
+    async fn resolve_field(&self, ctx: &Context<'_>) -> ServerResult<Option<Value>>;
+
+    fn collect_all_fields<'a>(
+        &'a self,
+        ctx: &ContextSelectionSet<'a>,
+        fields: &mut Fields<'a>,
+    ) -> ServerResult<()>;
+}
+
+#[async_trait::async_trait]
+impl<T: ObjectType + Send + Sync> ErasedObject for T {
+    fn introspection_type_name(&self) -> Cow<'static, str> {
+        T::introspection_type_name(self)
+    }
+
+    async fn resolve_field(&self, ctx: &Context<'_>) -> ServerResult<Option<Value>> {
+        T::resolve_field(self, ctx).await
+    }
+
+    fn collect_all_fields<'a>(
+        &'a self,
+        ctx: &ContextSelectionSet<'a>,
+        fields: &mut Fields<'a>,
+    ) -> ServerResult<()> {
+        T::collect_all_fields(self, ctx, fields)
+    }
+}
+
+/// A type-erased GraphQL object.
+///
+/// A union or an interface can have a variant of this type marked with `#[graphql(dynamic)]`,
+/// which holds objects of the member types that are added with
+/// [`SchemaBuilder::register_possible_type`](crate::SchemaBuilder::register_possible_type).
+/// This allows the member types to be defined in other crates, without one enum listing all of
+/// them.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// struct Circle;
+///
+/// #[Object]
+/// impl Circle {
+///     async fn radius(&self) -> f32 { 1.0 }
+/// }
+///
+/// // Defined in a plugin crate.
+/// struct Square;
+///
+/// #[Object]
+/// impl Square {
+///     async fn side(&self) -> f32 { 2.0 }
+/// }
+///
+/// #[derive(Union)]
+/// enum Shape {
+///     Circle(Circle),
+///     #[graphql(dynamic)]
+///     Dynamic(DynObject),
+/// }
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn shapes(&self) -> Vec<Shape> {
+///         vec![Circle.into(), DynObject::new(Square).into()]
+///     }
+/// }
+///
+/// tokio::runtime::Runtime::new().unwrap().block_on(async move {
+///     let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///         .register_possible_type::<Shape, Square>()
+///         .finish();
+///     let res = schema
+///         .execute("{ shapes { ... on Circle { radius } ... on Square { side } } }")
+///         .await
+///         .into_result()
+///         .unwrap()
+///         .data;
+///     assert_eq!(res, value!({ "shapes": [{ "radius": 1.0 }, { "side": 2.0 }] }));
+/// });
+/// ```
+pub struct DynObject(Box<dyn ErasedObject>);
+
+impl DynObject {
+    /// Create a type-erased object.
+    pub fn new<T: ObjectType + Send + Sync + 'static>(obj: T) -> Self {
+        Self(Box::new(obj))
+    }
+
+    /// Returns the GraphQL type name of the object.
+    pub fn introspection_type_name(&self) -> Cow<'static, str> {
+        self.0.introspection_type_name()
+    }
+
+    /// Resolves a field of the object.
+    ///
+    /// If the field was not found returns None.
+    pub async fn resolve_field(&self, ctx: &Context<'_>) -> ServerResult<Option<Value>> {
+        self.0.resolve_field(ctx).await
+    }
+
+    /// Collect all the fields of the object that are queried in the selection set.
+    pub fn collect_all_fields<'a>(
+        &'a self,
+        ctx: &ContextSelectionSet<'a>,
+        fields: &mut Fields<'a>,
+    ) -> ServerResult<()> {
+        self.0.collect_all_fields(ctx, fields)
+    }
+}
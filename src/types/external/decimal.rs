@@ -1,14 +1,17 @@
 use std::str::FromStr;
 
+use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
 
-use crate::{InputValueError, InputValueResult, Scalar, ScalarType, Value};
+use crate::{InputValueError, InputValueResult, Number, NumberPolicy, Scalar, ScalarType, Value};
 
 #[Scalar(internal, name = "Decimal")]
 impl ScalarType for Decimal {
     fn parse(value: Value) -> InputValueResult<Self> {
         match &value {
             Value::String(s) => Ok(Decimal::from_str(s)?),
+            // Accepted with the number policies that return the value as a number.
+            Value::Number(n) => Ok(Decimal::from_str(&n.to_string())?),
             _ => Err(InputValueError::expected_type(value)),
         }
     }
@@ -16,4 +19,19 @@ impl ScalarType for Decimal {
     fn to_value(&self) -> Value {
         Value::String(self.to_string())
     }
+
+    fn to_output_value(&self, policy: NumberPolicy) -> Value {
+        let number = match policy {
+            NumberPolicy::Native | NumberPolicy::String => None,
+            NumberPolicy::Number => self.to_f64().and_then(Number::from_f64),
+            NumberPolicy::SafeNumber => self
+                .to_f64()
+                .filter(|f| Decimal::from_str(&f.to_string()).ok().as_ref() == Some(self))
+                .and_then(Number::from_f64),
+        };
+        match number {
+            Some(number) => Value::Number(number),
+            None => self.to_value(),
+        }
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{InputValueError, InputValueResult, Number, Scalar, ScalarType, Value};
+use crate::{InputValueError, InputValueResult, Number, NumberPolicy, Scalar, ScalarType, Value};
 
 /// The `Int` scalar type represents non-fractional whole numeric values.
 #[Scalar(internal, name = "Int")]
@@ -111,6 +111,8 @@ impl ScalarType for i64 {
                 }
                 Ok(n as Self)
             }
+            // Accepted with the number policies that return the value as a string.
+            Value::String(s) => Ok(s.parse()?),
             _ => Err(InputValueError::expected_type(value)),
         }
     }
@@ -122,6 +124,10 @@ impl ScalarType for i64 {
     fn to_value(&self) -> Value {
         Value::Number(Number::from(*self as i64))
     }
+
+    fn to_output_value(&self, policy: NumberPolicy) -> Value {
+        policy.signed_to_value(*self)
+    }
 }
 
 /// The `Int` scalar type represents non-fractional whole numeric values.
@@ -235,6 +241,8 @@ impl ScalarType for u64 {
                 }
                 Ok(n as Self)
             }
+            // Accepted with the number policies that return the value as a string.
+            Value::String(s) => Ok(s.parse()?),
             _ => Err(InputValueError::expected_type(value)),
         }
     }
@@ -246,6 +254,10 @@ impl ScalarType for u64 {
     fn to_value(&self) -> Value {
         Value::Number(Number::from(*self as u64))
     }
+
+    fn to_output_value(&self, policy: NumberPolicy) -> Value {
+        policy.unsigned_to_value(*self)
+    }
 }
 
 /// The `Int` scalar type represents non-fractional whole numeric values.
@@ -266,6 +278,8 @@ impl ScalarType for usize {
                 }
                 Ok(n as Self)
             }
+            // Accepted with the number policies that return the value as a string.
+            Value::String(s) => Ok(s.parse()?),
             _ => Err(InputValueError::expected_type(value)),
         }
     }
@@ -277,6 +291,10 @@ impl ScalarType for usize {
     fn to_value(&self) -> Value {
         Value::Number(Number::from(*self as u64))
     }
+
+    fn to_output_value(&self, policy: NumberPolicy) -> Value {
+        policy.unsigned_to_value(*self as u64)
+    }
 }
 
 /// The `Int` scalar type represents non-fractional whole numeric values.
@@ -297,6 +315,8 @@ impl ScalarType for isize {
                 }
                 Ok(n as Self)
             }
+            // Accepted with the number policies that return the value as a string.
+            Value::String(s) => Ok(s.parse()?),
             _ => Err(InputValueError::expected_type(value)),
         }
     }
@@ -308,4 +328,8 @@ impl ScalarType for isize {
     fn to_value(&self) -> Value {
         Value::Number(Number::from(*self as i64))
     }
+
+    fn to_output_value(&self, policy: NumberPolicy) -> Value {
+        policy.signed_to_value(*self as i64)
+    }
 }
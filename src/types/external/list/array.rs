@@ -24,25 +24,25 @@ impl<T: Type, const N: usize> Type for [T; N] {
 
 impl<T: InputType, const N: usize> InputType for [T; N] {
     fn parse(value: Option<Value>) -> InputValueResult<Self> {
-        if let Some(Value::List(values)) = value {
-            let items: Vec<T> = values
+        let items: Vec<T> = match value.unwrap_or_default() {
+            Value::List(values) => values
                 .into_iter()
                 .map(|value| InputType::parse(Some(value)))
                 .collect::<Result<_, _>>()
-                .map_err(InputValueError::propagate)?;
-            let len = items.len();
-            items.try_into().map_err(|_| {
-                InputValueError::custom(format!(
-                    "Expected input type \"[{}; {}]\", found [{}; {}].",
-                    T::type_name(),
-                    N,
-                    T::type_name(),
-                    len
-                ))
-            })
-        } else {
-            Err(InputValueError::expected_type(value.unwrap_or_default()))
-        }
+                .map_err(InputValueError::propagate)?,
+            Value::Null => return Err(InputValueError::expected_type(Value::Null)),
+            value => vec![InputType::parse(Some(value)).map_err(InputValueError::propagate)?],
+        };
+        let len = items.len();
+        items.try_into().map_err(|_| {
+            InputValueError::custom(format!(
+                "Expected input type \"[{}; {}]\", found [{}; {}].",
+                T::type_name(),
+                N,
+                T::type_name(),
+                len
+            ))
+        })
     }
 
     fn to_value(&self) -> Value {
@@ -2,7 +2,7 @@ use std::num::{
     NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8,
 };
 
-use crate::{InputValueError, InputValueResult, Number, Scalar, ScalarType, Value};
+use crate::{InputValueError, InputValueResult, Number, NumberPolicy, Scalar, ScalarType, Value};
 
 /// The `Int` scalar type represents non-fractional whole numeric values.
 #[Scalar(internal, name = "Int")]
@@ -115,6 +115,8 @@ impl ScalarType for NonZeroI64 {
                 }
                 Ok(NonZeroI64::new(n as i64).unwrap())
             }
+            // Accepted with the number policies that return the value as a string.
+            Value::String(s) => Ok(s.parse()?),
             _ => Err(InputValueError::expected_type(value)),
         }
     }
@@ -126,6 +128,10 @@ impl ScalarType for NonZeroI64 {
     fn to_value(&self) -> Value {
         Value::Number(Number::from(self.get() as i64))
     }
+
+    fn to_output_value(&self, policy: NumberPolicy) -> Value {
+        policy.signed_to_value(self.get())
+    }
 }
 
 /// The `Int` scalar type represents non-fractional whole numeric values.
@@ -239,6 +245,8 @@ impl ScalarType for NonZeroU64 {
                 }
                 Ok(NonZeroU64::new(n as u64).unwrap())
             }
+            // Accepted with the number policies that return the value as a string.
+            Value::String(s) => Ok(s.parse()?),
             _ => Err(InputValueError::expected_type(value)),
         }
     }
@@ -250,4 +258,8 @@ impl ScalarType for NonZeroU64 {
     fn to_value(&self) -> Value {
         Value::Number(Number::from(self.get() as u64))
     }
+
+    fn to_output_value(&self, policy: NumberPolicy) -> Value {
+        policy.unsigned_to_value(self.get())
+    }
 }
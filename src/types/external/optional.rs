@@ -50,7 +50,7 @@ impl<T: OutputType + Sync> OutputType for Option<T> {
             match OutputType::resolve(inner, ctx, field).await {
                 Ok(value) => Ok(value),
                 Err(err) => {
-                    ctx.add_error(err);
+                    ctx.add_nullable_error(err)?;
                     Ok(Value::Null)
                 }
             }
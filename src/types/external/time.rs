@@ -0,0 +1,292 @@
+use time::format_description::well_known::Rfc3339;
+use time::format_description::FormatItem;
+use time::macros::format_description;
+use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
+
+use crate::{InputValueError, InputValueResult, ScalarType, Value};
+
+const DATE_FORMAT: &[FormatItem<'_>] = format_description!("[year]-[month]-[day]");
+const TIME_FORMAT: &[FormatItem<'_>] = format_description!("[hour]:[minute]:[second]");
+const TIME_FORMAT_WITH_SUBSECOND: &[FormatItem<'_>] =
+    format_description!("[hour]:[minute]:[second].[subsecond]");
+const PARSE_TIME_FORMAT: &[FormatItem<'_>] =
+    format_description!("[hour]:[minute]:[second][optional [.[subsecond]]]");
+
+fn time_format(time: Time) -> &'static [FormatItem<'static>] {
+    if time.nanosecond() == 0 {
+        TIME_FORMAT
+    } else {
+        TIME_FORMAT_WITH_SUBSECOND
+    }
+}
+
+fn format_offset_date_time(value: &OffsetDateTime) -> Result<String, time::error::Format> {
+    value.format(&Rfc3339)
+}
+
+fn format_primitive_date_time(value: &PrimitiveDateTime) -> Result<String, time::error::Format> {
+    Ok(format!(
+        "{}T{}",
+        format_date(&value.date())?,
+        format_time(&value.time())?
+    ))
+}
+
+fn format_date(value: &Date) -> Result<String, time::error::Format> {
+    value.format(DATE_FORMAT)
+}
+
+fn format_time(value: &Time) -> Result<String, time::error::Format> {
+    value.format(time_format(*value))
+}
+
+/// Implements the `Type`, `InputType` and `OutputType` traits for a scalar of the `time` crate.
+///
+/// Unlike `#[Scalar]`, a value that can't be formatted is reported as a field error when it is
+/// resolved, instead of being silently replaced.
+macro_rules! time_scalar {
+    ($ty:ty, $name:literal, $desc:literal, $specified_by_url:expr, $format:ident) => {
+        impl crate::Type for $ty {
+            fn type_name() -> std::borrow::Cow<'static, str> {
+                std::borrow::Cow::Borrowed($name)
+            }
+
+            fn create_type_info(registry: &mut crate::registry::Registry) -> String {
+                registry.create_type::<$ty, _>(|_| crate::registry::MetaType::Scalar {
+                    name: $name.to_string(),
+                    description: Some($desc),
+                    is_valid: |value| <$ty as ScalarType>::is_valid(value),
+                    visible: None,
+                    specified_by_url: $specified_by_url,
+                })
+            }
+        }
+
+        impl crate::InputType for $ty {
+            fn parse(value: Option<Value>) -> InputValueResult<Self> {
+                <$ty as ScalarType>::parse(value.unwrap_or_default())
+            }
+
+            fn to_value(&self) -> Value {
+                <$ty as ScalarType>::to_value(self)
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl crate::OutputType for $ty {
+            async fn resolve(
+                &self,
+                _ctx: &crate::ContextSelectionSet<'_>,
+                field: &crate::Positioned<crate::parser::types::Field>,
+            ) -> crate::ServerResult<Value> {
+                $format(self).map(Value::String).map_err(|err| {
+                    crate::ServerError::new(
+                        format!("Failed to format the `{}` scalar: {}", $name, err),
+                        Some(field.pos),
+                    )
+                })
+            }
+        }
+    };
+}
+
+time_scalar!(
+    OffsetDateTime,
+    "DateTime",
+    "Implement the OffsetDateTime scalar\n\nThe input/output is a string in RFC3339 format. \
+     This is the same `DateTime` scalar as the one of the `chrono` feature, so both features can \
+     be enabled together.",
+    Some("https://datatracker.ietf.org/doc/html/rfc3339"),
+    format_offset_date_time
+);
+
+impl ScalarType for OffsetDateTime {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => Ok(OffsetDateTime::parse(s, &Rfc3339)?),
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        format_offset_date_time(self)
+            .map(Value::String)
+            .unwrap_or(Value::Null)
+    }
+}
+
+time_scalar!(
+    PrimitiveDateTime,
+    "PrimitiveDateTime",
+    "ISO 8601 combined date and time without timezone.\nAllows for the nanosecond precision.\n\n\
+     # Examples\n\n* `2015-07-01T08:59:59`\n* `2015-07-01T08:59:59.123`",
+    None,
+    format_primitive_date_time
+);
+
+impl ScalarType for PrimitiveDateTime {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => {
+                let (date, time) = s
+                    .split_once('T')
+                    .ok_or_else(|| InputValueError::custom("Expected a date and a time."))?;
+                Ok(PrimitiveDateTime::new(
+                    Date::parse(date, DATE_FORMAT)?,
+                    Time::parse(time, PARSE_TIME_FORMAT)?,
+                ))
+            }
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        format_primitive_date_time(self)
+            .map(Value::String)
+            .unwrap_or(Value::Null)
+    }
+}
+
+time_scalar!(
+    Date,
+    "Date",
+    "ISO 8601 calendar date without timezone.\nFormat: YYYY-MM-DD\n\n# Examples\n\n\
+     * `1994-11-13`\n* `2000-02-24`",
+    None,
+    format_date
+);
+
+impl ScalarType for Date {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => Ok(Date::parse(s, DATE_FORMAT)?),
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        format_date(self).map(Value::String).unwrap_or(Value::Null)
+    }
+}
+
+time_scalar!(
+    Time,
+    "Time",
+    "ISO 8601 time without timezone.\nAllows for the nanosecond precision.\n\
+     Format: HH:MM:SS[.fraction]\n\n# Examples\n\n* `08:59:59`\n* `08:59:59.123`",
+    None,
+    format_time
+);
+
+impl ScalarType for Time {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => Ok(Time::parse(s, PARSE_TIME_FORMAT)?),
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        format_time(self).map(Value::String).unwrap_or(Value::Null)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::{date, datetime, time};
+    use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
+
+    use crate::{ScalarType, Value};
+
+    #[test]
+    fn test_time_scalars() {
+        let value = <OffsetDateTime as ScalarType>::parse(Value::String(
+            "2021-11-03T08:59:59.5+09:00".into(),
+        ));
+        assert_eq!(value.unwrap(), datetime!(2021-11-03 08:59:59.5 +9));
+        assert_eq!(
+            datetime!(2021-11-03 08:59:59 UTC).to_value(),
+            Value::String("2021-11-03T08:59:59Z".into())
+        );
+
+        let value =
+            <PrimitiveDateTime as ScalarType>::parse(Value::String("2021-11-03T08:59:59".into()));
+        assert_eq!(value.unwrap(), datetime!(2021-11-03 08:59:59));
+        assert_eq!(
+            datetime!(2021-11-03 08:59:59.123).to_value(),
+            Value::String("2021-11-03T08:59:59.123".into())
+        );
+        assert!(
+            <PrimitiveDateTime as ScalarType>::parse(Value::String("2021-11-03".into())).is_err()
+        );
+
+        let value = <Date as ScalarType>::parse(Value::String("2021-11-03".into()));
+        assert_eq!(value.unwrap(), date!(2021 - 11 - 03));
+        assert_eq!(
+            date!(2021 - 11 - 03).to_value(),
+            Value::String("2021-11-03".into())
+        );
+        assert!(<Date as ScalarType>::parse(Value::String("2021-13-03".into())).is_err());
+
+        let value = <Time as ScalarType>::parse(Value::String("08:59:59.123".into()));
+        assert_eq!(value.unwrap(), time!(08:59:59.123));
+        assert_eq!(time!(08:59:59).to_value(), Value::String("08:59:59".into()));
+        assert!(<Time as ScalarType>::parse(Value::Number(1.into())).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_offset_date_time_out_of_rfc3339_range() {
+        use crate::*;
+
+        struct Query;
+
+        #[Object(internal)]
+        impl Query {
+            async fn value(&self) -> OffsetDateTime {
+                Date::from_calendar_date(-1, time::Month::January, 1)
+                    .unwrap()
+                    .midnight()
+                    .assume_utc()
+            }
+        }
+
+        let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+        let resp = schema.execute("{ value }").await;
+        assert_eq!(resp.data, Value::Null);
+        assert_eq!(resp.errors.len(), 1);
+        assert!(resp.errors[0]
+            .message
+            .starts_with("Failed to format the `DateTime` scalar:"));
+        assert_eq!(
+            resp.errors[0].locations,
+            vec![Pos {
+                line: 1,
+                column: 3
+            }]
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_time_and_chrono_scalars() {
+        use crate::*;
+
+        struct Query;
+
+        #[Object(internal)]
+        impl Query {
+            async fn offset(&self) -> OffsetDateTime {
+                time::macros::datetime!(2021-11-03 08:59:59 UTC)
+            }
+
+            async fn utc(&self) -> chrono::DateTime<chrono::Utc> {
+                "2021-11-03T08:59:59Z".parse().unwrap()
+            }
+        }
+
+        let sdl = Schema::new(Query, EmptyMutation, EmptySubscription).sdl();
+        assert!(sdl.contains("offset: DateTime!"));
+        assert!(sdl.contains("utc: DateTime!"));
+        assert_eq!(sdl.matches("scalar DateTime").count(), 1);
+    }
+}
@@ -11,6 +11,9 @@ use crate::{InputValueError, InputValueResult, Scalar, ScalarType, Value};
 /// within GraphQL. UUIDs are used to assign unique identifiers to entities without requiring a central
 /// allocating authority.
 ///
+/// Both the hyphenated and the simple forms are accepted as input, and the hyphenated form is
+/// used as output.
+///
 /// # References
 ///
 /// * [Wikipedia: Universally Unique Identifier](http://en.wikipedia.org/wiki/Universally_unique_identifier)
@@ -19,6 +22,9 @@ impl ScalarType for Uuid {
     fn parse(value: Value) -> InputValueResult<Self> {
         match value {
             Value::String(s) => Ok(Uuid::parse_str(&s)?),
+            Value::Binary(_) => Err(InputValueError::custom(
+                "Expected a UUID string, such as \"67e55044-10b1-426f-9247-bb680e5fe0c8\", found bytes.",
+            )),
             _ => Err(InputValueError::expected_type(value)),
         }
     }
@@ -27,3 +33,36 @@ impl ScalarType for Uuid {
         Value::String(self.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::{ScalarType, Value};
+
+    #[test]
+    fn test_uuid_scalar() {
+        let uuid = Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+        for s in [
+            "67e55044-10b1-426f-9247-bb680e5fe0c8",
+            "67e5504410b1426f9247bb680e5fe0c8",
+        ] {
+            assert_eq!(
+                <Uuid as ScalarType>::parse(Value::String(s.to_string())).unwrap(),
+                uuid
+            );
+        }
+        assert_eq!(
+            uuid.to_value(),
+            Value::String("67e55044-10b1-426f-9247-bb680e5fe0c8".to_string())
+        );
+
+        let err = <Uuid as ScalarType>::parse(Value::Binary(uuid.as_bytes().to_vec().into()))
+            .unwrap_err()
+            .into_server_error(Default::default());
+        assert_eq!(
+            err.message,
+            r#"Failed to parse "UUID": Expected a UUID string, such as "67e55044-10b1-426f-9247-bb680e5fe0c8", found bytes."#
+        );
+    }
+}
@@ -0,0 +1,159 @@
+use std::borrow::Cow;
+
+use indexmap::IndexMap;
+
+use crate::registry::{MetaInputValue, MetaType, Registry};
+use crate::{Enum, InputType, InputValueError, InputValueResult, Name, Type, Value};
+
+/// The operators of a field in the filter generated by
+/// [`Filterable`](derive.Filterable.html).
+///
+/// The GraphQL name of the type is the name of `T` followed by `Filter`, such as `IntFilter` or
+/// `StringFilter`. The operators that are set must all hold for a value to match.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldFilter<T> {
+    /// The value is equal to.
+    pub eq: Option<T>,
+
+    /// The value is not equal to.
+    pub ne: Option<T>,
+
+    /// The value is one of.
+    pub r#in: Option<Vec<T>>,
+
+    /// The value is greater than.
+    pub gt: Option<T>,
+
+    /// The value is less than.
+    pub lt: Option<T>,
+
+    /// The value contains, such as a substring of a string.
+    pub contains: Option<T>,
+}
+
+impl<T> Default for FieldFilter<T> {
+    fn default() -> Self {
+        Self {
+            eq: None,
+            ne: None,
+            r#in: None,
+            gt: None,
+            lt: None,
+            contains: None,
+        }
+    }
+}
+
+impl<T: PartialOrd> FieldFilter<T> {
+    /// Returns `true` if the value matches the comparison operators, with `contains` tested by
+    /// the function.
+    pub fn matches_with(&self, value: &T, contains: impl Fn(&T, &T) -> bool) -> bool {
+        self.eq.as_ref().map(|eq| value == eq).unwrap_or(true)
+            && self.ne.as_ref().map(|ne| value != ne).unwrap_or(true)
+            && self
+                .r#in
+                .as_ref()
+                .map(|values| values.contains(value))
+                .unwrap_or(true)
+            && self.gt.as_ref().map(|gt| value > gt).unwrap_or(true)
+            && self.lt.as_ref().map(|lt| value < lt).unwrap_or(true)
+            && self
+                .contains
+                .as_ref()
+                .map(|part| contains(value, part))
+                .unwrap_or(true)
+    }
+}
+
+impl FieldFilter<String> {
+    /// Returns `true` if the string matches the operators.
+    pub fn matches(&self, value: &str) -> bool {
+        self.matches_with(&value.to_string(), |value, part| {
+            value.contains(part.as_str())
+        })
+    }
+}
+
+impl<T: InputType> Type for FieldFilter<T> {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Owned(format!("{}Filter", T::type_name()))
+    }
+
+    fn create_type_info(registry: &mut Registry) -> String {
+        registry.create_type::<Self, _>(|registry| {
+            let mut input_fields = IndexMap::new();
+            for (name, ty) in [
+                ("eq", Option::<T>::create_type_info(registry)),
+                ("ne", Option::<T>::create_type_info(registry)),
+                ("in", Option::<Vec<T>>::create_type_info(registry)),
+                ("gt", Option::<T>::create_type_info(registry)),
+                ("lt", Option::<T>::create_type_info(registry)),
+                ("contains", Option::<T>::create_type_info(registry)),
+            ] {
+                input_fields.insert(
+                    name.to_string(),
+                    MetaInputValue {
+                        name,
+                        description: None,
+                        ty,
+                        default_value: None,
+                        validator: None,
+                        visible: None,
+                        is_secret: false,
+                    },
+                );
+            }
+            MetaType::InputObject {
+                name: Self::type_name().to_string(),
+                description: None,
+                input_fields,
+                visible: None,
+                rust_typename: std::any::type_name::<Self>(),
+                oneof: false,
+            }
+        })
+    }
+}
+
+impl<T: InputType> InputType for FieldFilter<T> {
+    fn parse(value: Option<Value>) -> InputValueResult<Self> {
+        match value {
+            Some(Value::Object(obj)) => {
+                let field = |name: &str| obj.get(name).cloned();
+                Ok(Self {
+                    eq: InputType::parse(field("eq")).map_err(InputValueError::propagate)?,
+                    ne: InputType::parse(field("ne")).map_err(InputValueError::propagate)?,
+                    r#in: InputType::parse(field("in")).map_err(InputValueError::propagate)?,
+                    gt: InputType::parse(field("gt")).map_err(InputValueError::propagate)?,
+                    lt: InputType::parse(field("lt")).map_err(InputValueError::propagate)?,
+                    contains: InputType::parse(field("contains"))
+                        .map_err(InputValueError::propagate)?,
+                })
+            }
+            value => Err(InputValueError::expected_type(value.unwrap_or_default())),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        let mut map = IndexMap::new();
+        map.insert(Name::new("eq"), self.eq.to_value());
+        map.insert(Name::new("ne"), self.ne.to_value());
+        map.insert(Name::new("in"), self.r#in.to_value());
+        map.insert(Name::new("gt"), self.gt.to_value());
+        map.insert(Name::new("lt"), self.lt.to_value());
+        map.insert(Name::new("contains"), self.contains.to_value());
+        Value::Object(map)
+    }
+}
+
+/// The direction of a field in the ordering generated by
+/// [`Filterable`](derive.Filterable.html).
+#[derive(Enum, Debug, Copy, Clone, Eq, PartialEq)]
+#[graphql(internal)]
+pub enum OrderDirection {
+    /// Ascending order.
+    Asc,
+
+    /// Descending order.
+    Desc,
+}
@@ -0,0 +1,215 @@
+use std::fmt::Display;
+use std::marker::PhantomData;
+
+use crate::{Context, Error, ErrorExtensions, Object, OutputType, Result, Type, ID};
+
+/// Encodes and decodes the global IDs used for
+/// [Relay global object identification](https://relay.dev/graphql/objectidentification.htm).
+///
+/// The default codec is [`Base64GlobalIdCodec`]. A custom codec can be set with
+/// [`SchemaBuilder::global_id_codec`](crate::SchemaBuilder::global_id_codec), for example to
+/// encrypt the IDs so that clients cannot guess them.
+pub trait GlobalIdCodec: Send + Sync + 'static {
+    /// Encode the type name and the id of an object into a global ID.
+    fn encode(&self, type_name: &str, id: &str) -> String;
+
+    /// Decode a global ID into the type name and the id of an object.
+    ///
+    /// Returns `None` if the global ID is invalid.
+    fn decode(&self, global_id: &str) -> Option<(String, String)>;
+}
+
+/// The default global ID codec, which encodes `TypeName:id` with base64.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Base64GlobalIdCodec;
+
+impl GlobalIdCodec for Base64GlobalIdCodec {
+    fn encode(&self, type_name: &str, id: &str) -> String {
+        base64::encode(format!("{}:{}", type_name, id))
+    }
+
+    fn decode(&self, global_id: &str) -> Option<(String, String)> {
+        let s = String::from_utf8(base64::decode(global_id).ok()?).ok()?;
+        let (type_name, id) = s.split_once(':')?;
+        Some((type_name.to_string(), id.to_string()))
+    }
+}
+
+/// A decoded global ID.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// let id = GlobalId::encode("User", 10);
+/// assert_eq!(id, ID::from("VXNlcjoxMA=="));
+///
+/// let global_id = GlobalId::decode(&id).unwrap();
+/// assert_eq!(global_id.type_name(), "User");
+/// assert_eq!(global_id.id(), "10");
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct GlobalId {
+    type_name: String,
+    id: String,
+}
+
+impl GlobalId {
+    /// Create a global ID from the type name and the id of an object.
+    pub fn new(type_name: impl Into<String>, id: impl Display) -> Self {
+        Self {
+            type_name: type_name.into(),
+            id: id.to_string(),
+        }
+    }
+
+    /// Encode a global ID with [`Base64GlobalIdCodec`].
+    ///
+    /// Use [`Context::encode_global_id`] to encode it with the codec of the schema.
+    pub fn encode(type_name: &str, id: impl Display) -> ID {
+        Self::new(type_name, id).encode_with(&Base64GlobalIdCodec)
+    }
+
+    /// Decode a global ID with [`Base64GlobalIdCodec`].
+    ///
+    /// Use [`Context::decode_global_id`] to decode it with the codec of the schema.
+    pub fn decode(id: &str) -> Result<Self> {
+        Self::decode_with(&Base64GlobalIdCodec, id)
+    }
+
+    /// Encode this global ID with the specified codec.
+    pub fn encode_with(&self, codec: &dyn GlobalIdCodec) -> ID {
+        ID(codec.encode(&self.type_name, &self.id))
+    }
+
+    /// Decode a global ID with the specified codec.
+    pub fn decode_with(codec: &dyn GlobalIdCodec, id: &str) -> Result<Self> {
+        codec
+            .decode(id)
+            .map(|(type_name, id)| Self { type_name, id })
+            .ok_or_else(|| {
+                Error::new("Invalid global ID.").extend_with(|_, e| e.set("code", "BAD_USER_INPUT"))
+            })
+    }
+
+    /// Returns the type name of the object.
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    /// Returns the id of the object.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+/// An object that can be fetched with its global ID.
+///
+/// This is used by the [`NodeInterface`] implementations of the interfaces with the `node`
+/// attribute.
+#[async_trait::async_trait]
+pub trait NodeObject: Type + Send + Sync + Sized {
+    /// Load the object with the specified id, which is the id part of its global ID.
+    async fn load(ctx: &Context<'_>, id: &str) -> Result<Option<Self>>;
+}
+
+/// A Relay node interface, whose members can be fetched with their global IDs.
+///
+/// This is implemented by the [`Interface`](derive.Interface.html) macro for interfaces with the
+/// `node` attribute.
+#[async_trait::async_trait]
+pub trait NodeInterface: OutputType + Sized {
+    /// Load the object identified by a global ID, which is decoded with
+    /// [`Context::decode_global_id`].
+    ///
+    /// Returns `None` if the type of the global ID is not a member of this interface.
+    async fn load_node(ctx: &Context<'_>, id: &str) -> Result<Option<Self>>;
+}
+
+/// An object with the `node(id: ID!)` field of a Relay node interface.
+///
+/// Merge it into the query root with the [`MergedObject`](derive.MergedObject.html) macro.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// #[derive(SimpleObject)]
+/// #[graphql(complex)]
+/// struct User {
+///     #[graphql(skip)]
+///     raw_id: i32,
+///     name: String,
+/// }
+///
+/// #[ComplexObject]
+/// impl User {
+///     async fn id(&self, ctx: &Context<'_>) -> ID {
+///         ctx.encode_global_id("User", self.raw_id)
+///     }
+/// }
+///
+/// #[async_trait::async_trait]
+/// impl NodeObject for User {
+///     async fn load(_ctx: &Context<'_>, id: &str) -> Result<Option<Self>> {
+///         Ok(Some(User {
+///             raw_id: id.parse()?,
+///             name: format!("user{}", id),
+///         }))
+///     }
+/// }
+///
+/// #[derive(Interface)]
+/// #[graphql(node)]
+/// enum Node {
+///     User(User),
+/// }
+///
+/// #[derive(Default)]
+/// struct UserQuery;
+///
+/// #[Object]
+/// impl UserQuery {
+///     async fn me(&self) -> User {
+///         User {
+///             raw_id: 1,
+///             name: "user1".to_string(),
+///         }
+///     }
+/// }
+///
+/// #[derive(MergedObject, Default)]
+/// struct Query(UserQuery, NodeQuery<Node>);
+///
+/// tokio::runtime::Runtime::new().unwrap().block_on(async move {
+///     let schema = Schema::new(Query::default(), EmptyMutation, EmptySubscription);
+///     let id = GlobalId::encode("User", 2);
+///     let query = format!(r#"{{ node(id: "{}") {{ id ... on User {{ name }} }} }}"#, id.as_str());
+///     assert_eq!(
+///         schema.execute(query).await.into_result().unwrap().data,
+///         value!({
+///             "node": {
+///                 "id": id,
+///                 "name": "user2",
+///             }
+///         })
+///     );
+/// });
+/// ```
+pub struct NodeQuery<T>(PhantomData<fn() -> T>);
+
+impl<T> Default for NodeQuery<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[Object(internal)]
+impl<T: NodeInterface> NodeQuery<T> {
+    /// Fetches an object given its ID.
+    async fn node(&self, ctx: &Context<'_>, id: ID) -> Result<Option<T>> {
+        T::load_node(ctx, &id).await
+    }
+}
@@ -111,7 +111,7 @@ impl ScalarType for serde_json::Value {
     }
 
     fn to_value(&self) -> Value {
-        Value::from_json(self.clone()).unwrap_or_default()
+        to_value(self).unwrap_or_default()
     }
 }
 
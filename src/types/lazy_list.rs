@@ -0,0 +1,141 @@
+use std::borrow::Cow;
+use std::fmt::{self, Debug, Formatter};
+use std::iter::{Enumerate, FusedIterator};
+use std::marker::PhantomData;
+
+use crate::{registry, Error, InputType, InputValueError, InputValueResult, Result, Type, Value};
+
+/// A list argument whose items are parsed when they are iterated.
+///
+/// The argument is exposed as a `[T]` list, like `Vec<T>`, but the items are only converted to
+/// `T` one at a time while the resolver iterates over them. A resolver consuming a huge list,
+/// for example a bulk import, doesn't hold all the parsed items at once, and can stop on the
+/// first invalid item.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// struct Mutation;
+///
+/// #[Object]
+/// impl Mutation {
+///     async fn import(&self, values: LazyList<i32>) -> Result<i32> {
+///         let mut sum = 0;
+///         for value in values {
+///             sum += value?;
+///         }
+///         Ok(sum)
+///     }
+/// }
+///
+/// tokio::runtime::Runtime::new().unwrap().block_on(async move {
+///     let schema = Schema::new(Query, Mutation, EmptySubscription);
+///     let res = schema.execute("mutation { import(values: [1, 2, 3]) }").await;
+///     assert_eq!(res.into_result().unwrap().data, value!({ "import": 6 }));
+/// });
+/// ```
+pub struct LazyList<T> {
+    values: Vec<Value>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> LazyList<T> {
+    /// Returns the number of items of the list.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the list has no items.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl<T> Debug for LazyList<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("LazyList").field(&self.values).finish()
+    }
+}
+
+impl<T: InputType> Type for LazyList<T> {
+    fn type_name() -> Cow<'static, str> {
+        Vec::<T>::type_name()
+    }
+
+    fn qualified_type_name() -> String {
+        Vec::<T>::qualified_type_name()
+    }
+
+    fn create_type_info(registry: &mut registry::Registry) -> String {
+        Vec::<T>::create_type_info(registry)
+    }
+}
+
+impl<T: InputType> InputType for LazyList<T> {
+    fn parse(value: Option<Value>) -> InputValueResult<Self> {
+        let values = match value.unwrap_or_default() {
+            Value::List(values) => values,
+            value => vec![value],
+        };
+        Ok(Self {
+            values,
+            _marker: PhantomData,
+        })
+    }
+
+    fn to_value(&self) -> Value {
+        Value::List(self.values.clone())
+    }
+}
+
+impl<T: InputType> IntoIterator for LazyList<T> {
+    type Item = Result<T>;
+    type IntoIter = LazyListIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        LazyListIter {
+            values: self.values.into_iter().enumerate(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// An iterator over the parsed items of a [`LazyList`].
+pub struct LazyListIter<T> {
+    values: Enumerate<std::vec::IntoIter<Value>>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: InputType> Iterator for LazyListIter<T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (idx, value) = self.values.next()?;
+        Some(T::parse(Some(value)).map_err(|err: InputValueError<T>| {
+            Error::new(format!(
+                "{} (occurred while parsing the item {} of the list)",
+                err.into_message(),
+                idx
+            ))
+        }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.values.size_hint()
+    }
+}
+
+impl<T: InputType> ExactSizeIterator for LazyListIter<T> {}
+
+impl<T: InputType> FusedIterator for LazyListIter<T> {}
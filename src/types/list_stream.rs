@@ -0,0 +1,108 @@
+use std::borrow::Cow;
+use std::sync::Mutex;
+
+use futures_util::stream::Stream;
+
+use crate::parser::types::Field;
+use crate::resolver_utils::resolve_list_stream;
+use crate::{
+    registry, ContextSelectionSet, OutputType, Positioned, ServerError, ServerResult, Type, Value,
+};
+
+/// A list resolved from a stream.
+///
+/// The items are resolved as they are produced by the stream, so for example the rows of a
+/// database cursor don't need to be collected into a `Vec` first. The items after the initial
+/// count of the `@stream` directive are delivered incrementally.
+///
+/// A resolver of [`Object`](crate::Object) can also return `impl Stream<Item = T>`, which is
+/// converted to a `ListStream` and exposed as a `[T]` field.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use futures_util::stream::{self, Stream};
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn values(&self) -> impl Stream<Item = i32> {
+///         stream::iter(0..3)
+///     }
+///
+///     async fn names(&self) -> ListStream<stream::Iter<std::vec::IntoIter<String>>> {
+///         ListStream::new(stream::iter(vec!["a".to_string(), "b".to_string()]))
+///     }
+/// }
+///
+/// tokio::runtime::Runtime::new().unwrap().block_on(async move {
+///     let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+///     let res = schema.execute("{ values names }").await.into_result().unwrap().data;
+///     assert_eq!(res, value!({ "values": [0, 1, 2], "names": ["a", "b"] }));
+/// });
+/// ```
+pub struct ListStream<S> {
+    stream: Mutex<Option<S>>,
+    concurrency: usize,
+}
+
+impl<S> ListStream<S> {
+    /// Create a list from a stream.
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream: Mutex::new(Some(stream)),
+            concurrency: 64,
+        }
+    }
+
+    /// Set the maximum number of items resolved concurrently, the default is 64.
+    #[must_use]
+    pub fn concurrency(self, concurrency: usize) -> Self {
+        Self {
+            concurrency,
+            ..self
+        }
+    }
+}
+
+impl<S, T> Type for ListStream<S>
+where
+    S: Stream<Item = T> + Send,
+    T: Type,
+{
+    fn type_name() -> Cow<'static, str> {
+        Vec::<T>::type_name()
+    }
+
+    fn qualified_type_name() -> String {
+        Vec::<T>::qualified_type_name()
+    }
+
+    fn create_type_info(registry: &mut registry::Registry) -> String {
+        Vec::<T>::create_type_info(registry)
+    }
+}
+
+#[async_trait::async_trait]
+impl<S, T> OutputType for ListStream<S>
+where
+    S: Stream<Item = T> + Send,
+    T: OutputType,
+{
+    async fn resolve(
+        &self,
+        ctx: &ContextSelectionSet<'_>,
+        field: &Positioned<Field>,
+    ) -> ServerResult<Value> {
+        let stream = self.stream.lock().unwrap().take();
+        match stream {
+            Some(stream) => resolve_list_stream(ctx, field, stream, self.concurrency).await,
+            None => Err(ServerError::new(
+                "The stream of the list has already been consumed.",
+                Some(field.pos),
+            )),
+        }
+    }
+}
@@ -1,30 +1,47 @@
 //! Useful GraphQL types.
 
 pub mod connection;
+pub mod crud;
 
 mod any;
+mod dyn_object;
 mod empty_mutation;
 mod empty_subscription;
+mod filter;
+mod global_id;
 mod id;
 mod json;
+mod lazy_list;
+mod list_stream;
 mod maybe_undefined;
 mod merged_object;
 mod query_root;
 #[cfg(feature = "string_number")]
 mod string_number;
 mod upload;
+mod user_error;
 
 mod external;
 
 pub use any::Any;
+pub use dyn_object::DynObject;
 pub use empty_mutation::EmptyMutation;
 pub use empty_subscription::EmptySubscription;
+pub use filter::{FieldFilter, OrderDirection};
+pub use global_id::{
+    Base64GlobalIdCodec, GlobalId, GlobalIdCodec, NodeInterface, NodeObject, NodeQuery,
+};
 pub use id::ID;
 pub use json::{Json, OutputJson};
+pub use lazy_list::{LazyList, LazyListIter};
+pub use list_stream::ListStream;
 pub use maybe_undefined::MaybeUndefined;
 pub use merged_object::{MergedObject, MergedObjectTail};
 #[cfg(feature = "string_number")]
 pub use string_number::StringNumber;
-pub use upload::{Upload, UploadValue};
+pub use upload::{Upload, UploadContent, UploadValue};
+pub use user_error::{UserError, UserErrorType};
 
-pub(crate) use query_root::QueryRoot;
+pub(crate) use query_root::{
+    create_health_types, create_schema_hash_field, remove_optional_fields, QueryRoot,
+};
@@ -2,12 +2,15 @@ use std::borrow::Cow;
 
 use indexmap::map::IndexMap;
 
+use crate::extensions::ComponentStatus;
 use crate::model::{__Schema, __Type};
 use crate::parser::types::Field;
+use crate::registry::SDLExportOptions;
 use crate::resolver_utils::{resolve_container, ContainerType};
+use crate::schema_module::resolve_module_field;
 use crate::{
-    registry, Any, Context, ContextSelectionSet, ObjectType, OutputType, Positioned, ServerError,
-    ServerResult, SimpleObject, Type, Value,
+    registry, Any, Context, ContextSelectionSet, DynObject, ObjectType, OutputType, Positioned,
+    ServerError, ServerResult, SimpleObject, Type, Value,
 };
 
 /// Federation service
@@ -17,8 +20,100 @@ struct Service {
     sdl: Option<String>,
 }
 
+/// Health check result
+#[derive(SimpleObject)]
+#[graphql(internal, name = "_Health")]
+struct Health {
+    /// Whether the service is healthy.
+    healthy: bool,
+    /// The hash of the schema.
+    schema_hash: String,
+    /// The number of seconds since the schema was created.
+    uptime: u64,
+    /// The status of the components reported by extensions.
+    components: Vec<ComponentStatus>,
+}
+
+pub(crate) fn create_health_types(registry: &mut registry::Registry) {
+    let health_type = Health::create_type_info(registry);
+    if let Some(registry::MetaType::Object { fields, .. }) =
+        registry.types.get_mut(&registry.query_type)
+    {
+        for (name, description) in [
+            ("_health", "Check whether the service is alive."),
+            (
+                "_readiness",
+                "Check whether the service and all of its components are ready.",
+            ),
+        ] {
+            fields.insert(
+                name.to_string(),
+                registry::MetaField {
+                    name: name.to_string(),
+                    description: Some(description),
+                    args: Default::default(),
+                    ty: format!("{}!", health_type),
+                    deprecation: Default::default(),
+                    cache_control: Default::default(),
+                    external: false,
+                    requires: None,
+                    provides: None,
+                    shareable: false,
+                    override_from: None,
+                    visible: None,
+                    compute_complexity: None,
+                },
+            );
+        }
+    }
+}
+
+pub(crate) fn create_schema_hash_field(registry: &mut registry::Registry) {
+    if let Some(registry::MetaType::Object { fields, .. }) =
+        registry.types.get_mut(&registry.query_type)
+    {
+        fields.insert(
+            "_schemaHash".to_string(),
+            registry::MetaField {
+                name: "_schemaHash".to_string(),
+                description: Some("The hash of the schema of this server."),
+                args: Default::default(),
+                ty: "String!".to_string(),
+                deprecation: Default::default(),
+                cache_control: Default::default(),
+                external: false,
+                requires: None,
+                provides: None,
+                shareable: false,
+                override_from: None,
+                visible: None,
+                compute_complexity: None,
+            },
+        );
+    }
+}
+
+/// Removes the fields added by [`create_health_types`] and [`create_schema_hash_field`], and the
+/// types only used by them, so that the schema hash can be computed without them.
+pub(crate) fn remove_optional_fields(registry: &mut registry::Registry) {
+    let mut removed = Vec::new();
+    if registry.enable_health_check {
+        removed.extend(["_health", "_readiness"]);
+    }
+    if registry.enable_schema_hash_field {
+        removed.push("_schemaHash");
+    }
+    if let Some(registry::MetaType::Object { fields, .. }) =
+        registry.types.get_mut(&registry.query_type)
+    {
+        fields.retain(|name, _| !removed.contains(&name.as_str()));
+    }
+    registry.remove_unused_types();
+}
+
 pub(crate) struct QueryRoot<T> {
     pub(crate) inner: T,
+    pub(crate) modules: Vec<DynObject>,
 }
 
 impl<T: Type> Type for QueryRoot<T> {
@@ -46,6 +141,8 @@ impl<T: Type> Type for QueryRoot<T> {
                         external: false,
                         requires: None,
                         provides: None,
+                        shareable: false,
+                        override_from: None,
                         visible: None,
                         compute_complexity: None,
                     },
@@ -78,6 +175,8 @@ impl<T: Type> Type for QueryRoot<T> {
                         external: false,
                         requires: None,
                         provides: None,
+                        shareable: false,
+                        override_from: None,
                         visible: None,
                         compute_complexity: None,
                     },
@@ -138,7 +237,11 @@ impl<T: ObjectType> ContainerType for QueryRoot<T> {
                 let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
                 return OutputType::resolve(
                     &Service {
-                        sdl: Some(ctx.schema_env.registry.export_sdl(true)),
+                        sdl: Some(
+                            ctx.schema_env
+                                .registry
+                                .export_sdl(SDLExportOptions::new().federation()),
+                        ),
                     },
                     &ctx_obj,
                     ctx.item,
@@ -148,7 +251,37 @@ impl<T: ObjectType> ContainerType for QueryRoot<T> {
             }
         }
 
-        self.inner.resolve_field(ctx).await
+        if ctx.schema_env.registry.enable_schema_hash_field
+            && ctx.item.node.name.node == "_schemaHash"
+        {
+            return Ok(Some(Value::String(ctx.schema_env.schema_hash.to_string())));
+        }
+
+        if ctx.schema_env.registry.enable_health_check {
+            let name = &ctx.item.node.name.node;
+            if name == "_health" || name == "_readiness" {
+                let components = ctx.query_env.extensions.health().await;
+                let healthy = name == "_health" || components.iter().all(|c| c.healthy);
+                let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
+                return OutputType::resolve(
+                    &Health {
+                        healthy,
+                        schema_hash: ctx.schema_env.schema_hash.to_string(),
+                        uptime: ctx.schema_env.created_at.elapsed().as_secs(),
+                        components,
+                    },
+                    &ctx_obj,
+                    ctx.item,
+                )
+                .await
+                .map(Some);
+            }
+        }
+
+        if let Some(value) = self.inner.resolve_field(ctx).await? {
+            return Ok(Some(value));
+        }
+        resolve_module_field(&self.modules, ctx).await
     }
 }
 
@@ -1,12 +1,92 @@
 use std::borrow::Cow;
 use std::fs::File;
-use std::io::Read;
+use std::io::{self, Cursor, Read, Seek, SeekFrom};
 
+use bytes::Bytes;
 #[cfg(feature = "unblock")]
 use futures_util::io::AsyncRead;
 
 use crate::{registry, Context, InputType, InputValueError, InputValueResult, Type, Value};
 
+enum UploadContentInner {
+    Memory(Cursor<Bytes>),
+    File(File),
+}
+
+/// The content of an uploaded file.
+///
+/// Small files are kept in memory, and the files larger than
+/// [`MultipartOptions::spool_threshold`](crate::http::MultipartOptions::spool_threshold) are
+/// spooled to a temporary file.
+pub struct UploadContent(UploadContentInner);
+
+impl UploadContent {
+    /// Attempt to clone the content, the clone reads the content from the current position.
+    ///
+    /// # Errors
+    ///
+    /// Fails if cloning the inner `File` fails.
+    pub fn try_clone(&self) -> io::Result<Self> {
+        Ok(Self(match &self.0 {
+            UploadContentInner::Memory(cursor) => {
+                let mut new_cursor = Cursor::new(cursor.get_ref().clone());
+                new_cursor.set_position(cursor.position());
+                UploadContentInner::Memory(new_cursor)
+            }
+            UploadContentInner::File(file) => UploadContentInner::File(file.try_clone()?),
+        }))
+    }
+
+    /// Returns `true` if the content is kept in memory.
+    pub fn is_in_memory(&self) -> bool {
+        matches!(self.0, UploadContentInner::Memory(_))
+    }
+
+    /// Returns the size of the content, in bytes.
+    pub fn size(&self) -> io::Result<u64> {
+        match &self.0 {
+            UploadContentInner::Memory(cursor) => Ok(cursor.get_ref().len() as u64),
+            UploadContentInner::File(file) => file.metadata().map(|meta| meta.len()),
+        }
+    }
+}
+
+impl From<File> for UploadContent {
+    fn from(file: File) -> Self {
+        Self(UploadContentInner::File(file))
+    }
+}
+
+impl From<Bytes> for UploadContent {
+    fn from(data: Bytes) -> Self {
+        Self(UploadContentInner::Memory(Cursor::new(data)))
+    }
+}
+
+impl From<Vec<u8>> for UploadContent {
+    fn from(data: Vec<u8>) -> Self {
+        Bytes::from(data).into()
+    }
+}
+
+impl Read for UploadContent {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match &mut self.0 {
+            UploadContentInner::Memory(cursor) => cursor.read(buf),
+            UploadContentInner::File(file) => file.read(buf),
+        }
+    }
+}
+
+impl Seek for UploadContent {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        match &mut self.0 {
+            UploadContentInner::Memory(cursor) => cursor.seek(pos),
+            UploadContentInner::File(file) => file.seek(pos),
+        }
+    }
+}
+
 /// A file upload value.
 pub struct UploadValue {
     /// The name of the file.
@@ -14,7 +94,7 @@ pub struct UploadValue {
     /// The content type of the file.
     pub content_type: Option<String>,
     /// The file data.
-    pub content: File,
+    pub content: UploadContent,
 }
 
 impl UploadValue {
@@ -42,13 +122,23 @@ impl UploadValue {
     #[cfg(feature = "unblock")]
     #[cfg_attr(docsrs, doc(cfg(feature = "unblock")))]
     /// Convert to a `AsyncRead`.
+    ///
+    /// The content kept in memory is read directly, and a temporary file is read on a thread
+    /// pool.
     pub fn into_async_read(self) -> impl AsyncRead + Sync + Send + 'static {
-        blocking::Unblock::new(self.content)
+        use futures_util::future::Either;
+
+        match self.content.0 {
+            UploadContentInner::Memory(cursor) => {
+                Either::Left(futures_util::io::AllowStdIo::new(cursor))
+            }
+            UploadContentInner::File(file) => Either::Right(blocking::Unblock::new(file)),
+        }
     }
 
     /// Returns the size of the file, in bytes.
     pub fn size(&self) -> std::io::Result<u64> {
-        self.content.metadata().map(|meta| meta.len())
+        self.content.size()
     }
 }
 
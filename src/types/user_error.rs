@@ -0,0 +1,66 @@
+use crate::SimpleObject;
+
+/// An error caused by the input of a mutation, such as a validation failure.
+///
+/// User errors are returned in the `userErrors` field of a mutation payload instead of the
+/// `errors` of the response, so that clients can display them next to the offending input.
+/// See [`MutationPayload`](derive.MutationPayload.html).
+#[derive(SimpleObject, Debug, Clone, Eq, PartialEq)]
+#[graphql(internal)]
+pub struct UserError {
+    /// The path to the input field that caused the error.
+    pub field: Option<Vec<String>>,
+
+    /// The error message.
+    pub message: String,
+
+    /// A machine-readable error code.
+    pub code: Option<String>,
+}
+
+impl UserError {
+    /// Create a user error with the message.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            field: None,
+            message: message.into(),
+            code: None,
+        }
+    }
+
+    /// Set the path to the input field that caused the error.
+    #[must_use]
+    pub fn with_field<I, T>(self, field: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        Self {
+            field: Some(field.into_iter().map(Into::into).collect()),
+            ..self
+        }
+    }
+
+    /// Set the error code.
+    #[must_use]
+    pub fn with_code(self, code: impl Into<String>) -> Self {
+        Self {
+            code: Some(code.into()),
+            ..self
+        }
+    }
+}
+
+/// A typed error that can be returned in the `userErrors` field of a mutation payload.
+///
+/// This is usually implemented for an error enum of a mutation.
+pub trait UserErrorType {
+    /// Convert this error into a [`UserError`].
+    fn user_error(&self) -> UserError;
+}
+
+impl UserErrorType for UserError {
+    fn user_error(&self) -> UserError {
+        self.clone()
+    }
+}
@@ -8,6 +8,7 @@ mod utils;
 mod visitor;
 mod visitors;
 
+use crate::extensions::DeprecatedUsage;
 use crate::parser::types::ExecutableDocument;
 use crate::registry::Registry;
 use crate::{CacheControl, ServerError, Variables};
@@ -77,7 +78,8 @@ pub fn check_rules(
                     cache_control: &mut cache_control,
                 })
                 .with(visitors::ComplexityCalculate::new(&mut complexity))
-                .with(visitors::DepthCalculate::new(&mut depth));
+                .with(visitors::DepthCalculate::new(&mut depth))
+                .with(visitors::InputDepthCheck::default());
             visit(&mut visitor, &mut ctx, doc);
         }
         ValidationMode::Fast => {
@@ -88,7 +90,8 @@ pub fn check_rules(
                     cache_control: &mut cache_control,
                 })
                 .with(visitors::ComplexityCalculate::new(&mut complexity))
-                .with(visitors::DepthCalculate::new(&mut depth));
+                .with(visitors::DepthCalculate::new(&mut depth))
+                .with(visitors::InputDepthCheck::default());
             visit(&mut visitor, &mut ctx, doc);
         }
     }
@@ -103,3 +106,18 @@ pub fn check_rules(
         depth,
     })
 }
+
+/// Collect the deprecated fields and enum values used in the document.
+pub(crate) fn collect_deprecated_usages(
+    registry: &Registry,
+    doc: &ExecutableDocument,
+    variables: Option<&Variables>,
+) -> Vec<DeprecatedUsage> {
+    let mut ctx = VisitorContext::new(registry, doc, variables);
+    let mut usages = Vec::new();
+    let mut visitor = visitors::DeprecationCollect {
+        usages: &mut usages,
+    };
+    visit(&mut visitor, &mut ctx, doc);
+    usages
+}
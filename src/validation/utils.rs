@@ -4,7 +4,7 @@ use async_graphql_value::{ConstValue, Value};
 
 use crate::context::QueryPathNode;
 use crate::error::Error;
-use crate::{registry, QueryPathSegment};
+use crate::{registry, NumberPolicy, QueryPathSegment};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Scope<'a> {
@@ -72,7 +72,12 @@ pub fn is_valid_input_value(
 
             match registry.types.get(type_name).unwrap() {
                 registry::MetaType::Scalar { is_valid, .. } => {
-                    if is_valid(&value) {
+                    // Large integers are returned as strings by some number policies, so they
+                    // can also be sent back as strings.
+                    let is_policy_string = type_name == "Int"
+                        && registry.number_policy != NumberPolicy::Native
+                        && matches!(value, ConstValue::String(s) if s.parse::<i128>().is_ok());
+                    if is_valid(&value) || is_policy_string {
                         None
                     } else {
                         Some(
@@ -125,9 +130,37 @@ pub fn is_valid_input_value(
                 registry::MetaType::InputObject {
                     input_fields,
                     name: object_name,
+                    oneof,
                     ..
                 } => match value {
                     ConstValue::Object(values) => {
+                        if *oneof {
+                            if values.len() != 1 {
+                                return Some(
+                                    valid_error(
+                                        &path_node,
+                                        format!(
+                                            "oneof input object \"{}\" requires exactly one field",
+                                            object_name
+                                        ),
+                                    )
+                                    .into(),
+                                );
+                            }
+                            if let Some((name, ConstValue::Null)) = values.iter().next() {
+                                return Some(
+                                    valid_error(
+                                        &path_node,
+                                        format!(
+                                            "field \"{}\" of oneof input object \"{}\" must not be null",
+                                            name, object_name
+                                        ),
+                                    )
+                                    .into(),
+                                );
+                            }
+                        }
+
                         let mut input_names =
                             values.keys().map(AsRef::as_ref).collect::<HashSet<_>>();
 
@@ -61,18 +61,16 @@ impl<'ctx, 'a> Visitor<'ctx> for ComplexityCalculate<'ctx, 'a> {
                             *self.complexity_stack.last_mut().unwrap() += n;
                         }
                         ComplexityType::Fn(f) => {
-                            if MetaTypeName::create(&meta_field.ty).is_list() {
-                                match f(
-                                    ctx,
-                                    self.variable_definition.unwrap(),
-                                    &field.node,
-                                    children_complex,
-                                ) {
-                                    Ok(n) => {
-                                        *self.complexity_stack.last_mut().unwrap() += n;
-                                    }
-                                    Err(err) => ctx.report_error(vec![field.pos], err.to_string()),
+                            match f(
+                                ctx,
+                                self.variable_definition.unwrap(),
+                                &field.node,
+                                children_complex,
+                            ) {
+                                Ok(n) => {
+                                    *self.complexity_stack.last_mut().unwrap() += n;
                                 }
+                                Err(err) => ctx.report_error(vec![field.pos], err.to_string()),
                             }
                         }
                     }
@@ -91,7 +89,7 @@ mod tests {
     use super::*;
     use crate::parser::parse_query;
     use crate::validation::{visit, VisitorContext};
-    use crate::{EmptyMutation, Object, Schema, Subscription};
+    use crate::{EmptyMutation, Object, Schema, SimpleObject, Subscription};
     use futures_util::stream::BoxStream;
 
     struct Query;
@@ -99,6 +97,15 @@ mod tests {
     #[derive(Copy, Clone)]
     struct MyObj;
 
+    #[derive(SimpleObject)]
+    #[graphql(internal)]
+    struct MySimpleObj {
+        #[graphql(complexity = 5)]
+        a: i32,
+        #[graphql(complexity = "2 * child_complexity")]
+        obj: MyObj,
+    }
+
     #[Object(internal)]
     #[allow(unreachable_code)]
     impl MyObj {
@@ -136,6 +143,10 @@ mod tests {
         async fn d(&self) -> MyObj {
             todo!()
         }
+
+        async fn simple(&self) -> MySimpleObj {
+            todo!()
+        }
     }
 
     struct Subscription;
@@ -288,6 +299,17 @@ mod tests {
         }"#,
             20,
         );
+
+        check_complex(
+            r#"
+        {
+            simple { # 1
+                a # 5
+                obj { a b } # 4
+            }
+        }"#,
+            10,
+        );
     }
 
     #[test]
@@ -0,0 +1,118 @@
+use async_graphql_value::{ConstValue, Value};
+
+use crate::extensions::{DeprecatedUsage, DeprecatedUsageKind};
+use crate::parser::types::Field;
+use crate::registry::{MetaType, MetaTypeName};
+use crate::validation::visitor::{Visitor, VisitorContext};
+use crate::{Pos, Positioned};
+
+pub struct DeprecationCollect<'a> {
+    pub usages: &'a mut Vec<DeprecatedUsage>,
+}
+
+impl<'a> DeprecationCollect<'a> {
+    fn check_enum_value(&mut self, ctx: &VisitorContext<'_>, pos: Pos, ty: &str, value: &str) {
+        if let Some(MetaType::Enum {
+            name, enum_values, ..
+        }) = ctx.registry.types.get(ty)
+        {
+            if let Some(enum_value) = enum_values.get(value) {
+                if enum_value.deprecation.is_deprecated() {
+                    self.usages.push(DeprecatedUsage {
+                        kind: DeprecatedUsageKind::EnumValue,
+                        type_name: name.clone(),
+                        name: value.to_string(),
+                        reason: enum_value.deprecation.reason().map(ToString::to_string),
+                        pos,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Checks the value of a variable, which is not visited by the visitor.
+    fn check_const_value(
+        &mut self,
+        ctx: &VisitorContext<'_>,
+        pos: Pos,
+        ty: MetaTypeName<'_>,
+        value: &ConstValue,
+    ) {
+        match (ty.unwrap_non_null(), value) {
+            (MetaTypeName::List(elem_ty), ConstValue::List(values)) => {
+                for value in values {
+                    self.check_const_value(ctx, pos, MetaTypeName::create(elem_ty), value);
+                }
+            }
+            (MetaTypeName::List(elem_ty), value) => {
+                self.check_const_value(ctx, pos, MetaTypeName::create(elem_ty), value)
+            }
+            (MetaTypeName::Named(ty), ConstValue::Enum(value)) => {
+                self.check_enum_value(ctx, pos, ty, value)
+            }
+            (MetaTypeName::Named(ty), ConstValue::String(value)) => {
+                self.check_enum_value(ctx, pos, ty, value)
+            }
+            (MetaTypeName::Named(ty), ConstValue::Object(values)) => {
+                if let Some(MetaType::InputObject { input_fields, .. }) = ctx.registry.types.get(ty)
+                {
+                    for (name, value) in values {
+                        if let Some(field) = input_fields.get(name.as_str()) {
+                            self.check_const_value(
+                                ctx,
+                                pos,
+                                MetaTypeName::create(&field.ty),
+                                value,
+                            );
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl<'ctx, 'a> Visitor<'ctx> for DeprecationCollect<'a> {
+    fn enter_field(&mut self, ctx: &mut VisitorContext<'ctx>, field: &'ctx Positioned<Field>) {
+        let parent_type = match ctx.parent_type() {
+            Some(parent_type) => parent_type,
+            None => return,
+        };
+        if let Some(registry_field) = parent_type.field_by_name(&field.node.name.node) {
+            if registry_field.deprecation.is_deprecated() {
+                self.usages.push(DeprecatedUsage {
+                    kind: DeprecatedUsageKind::Field,
+                    type_name: parent_type.name().to_string(),
+                    name: registry_field.name.clone(),
+                    reason: registry_field.deprecation.reason().map(ToString::to_string),
+                    pos: field.pos,
+                });
+            }
+        }
+    }
+
+    fn enter_input_value(
+        &mut self,
+        ctx: &mut VisitorContext<'ctx>,
+        pos: Pos,
+        expected_type: &Option<MetaTypeName<'ctx>>,
+        value: &'ctx Value,
+    ) {
+        let expected_type = match expected_type {
+            Some(expected_type) => *expected_type,
+            None => return,
+        };
+        match (expected_type.unwrap_non_null(), value) {
+            (MetaTypeName::Named(ty), Value::Enum(value)) => {
+                self.check_enum_value(ctx, pos, ty, value)
+            }
+            (_, Value::Variable(name)) => {
+                if let Some(value) = ctx.variables.and_then(|variables| variables.get(name)) {
+                    self.check_const_value(ctx, pos, expected_type, value);
+                }
+            }
+            _ => {}
+        }
+    }
+}
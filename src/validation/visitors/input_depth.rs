@@ -0,0 +1,233 @@
+use indexmap::IndexMap;
+
+use crate::parser::types::{Directive, Field, OperationDefinition, VariableDefinition};
+use crate::registry::{MetaInputValue, MetaType, MetaTypeName, Registry};
+use crate::validation::visitor::{VisitMode, Visitor, VisitorContext};
+use crate::{Name, Positioned};
+use async_graphql_value::{ConstValue, Value};
+
+/// Checks the depth of the input objects in the arguments, against the limit of the schema and
+/// the limits of the input object types.
+#[derive(Default)]
+pub struct InputDepthCheck<'a> {
+    variable_definitions: Option<&'a [Positioned<VariableDefinition>]>,
+    current_args: Option<&'a IndexMap<&'static str, MetaInputValue>>,
+}
+
+impl<'a> Visitor<'a> for InputDepthCheck<'a> {
+    fn mode(&self) -> VisitMode {
+        VisitMode::Inline
+    }
+
+    fn enter_operation_definition(
+        &mut self,
+        _ctx: &mut VisitorContext<'a>,
+        _name: Option<&'a Name>,
+        operation_definition: &'a Positioned<OperationDefinition>,
+    ) {
+        self.variable_definitions = Some(&operation_definition.node.variable_definitions);
+    }
+
+    fn enter_directive(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        directive: &'a Positioned<Directive>,
+    ) {
+        self.current_args = ctx
+            .registry
+            .directives
+            .get(directive.node.name.node.as_str())
+            .map(|d| &d.args);
+    }
+
+    fn exit_directive(
+        &mut self,
+        _ctx: &mut VisitorContext<'a>,
+        _directive: &'a Positioned<Directive>,
+    ) {
+        self.current_args = None;
+    }
+
+    fn enter_argument(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        name: &'a Positioned<Name>,
+        value: &'a Positioned<Value>,
+    ) {
+        let arg = match self
+            .current_args
+            .and_then(|args| args.get(name.node.as_str()))
+        {
+            Some(arg) => arg,
+            None => return,
+        };
+        let value = value.node.clone().into_const_with(|var_name| {
+            ctx.variables
+                .and_then(|variables| variables.get(&var_name))
+                .or_else(|| {
+                    self.variable_definitions
+                        .unwrap_or_default()
+                        .iter()
+                        .find(|def| def.node.name.node == var_name)
+                        .and_then(|def| def.node.default_value())
+                })
+                .cloned()
+                .ok_or(())
+        });
+        let value = match value {
+            Ok(value) => value,
+            Err(()) => return,
+        };
+
+        let mut exceeded = None;
+        let depth = input_depth(ctx.registry, &arg.ty, &value, &mut exceeded);
+        if let Some((ty, limit)) = exceeded {
+            ctx.report_error(
+                vec![name.pos],
+                format!(
+                    r#"Input value of type "{}" is nested too deep, the maximum depth is {}."#,
+                    ty, limit
+                ),
+            );
+        } else if let Some(limit) = ctx.registry.max_input_depth {
+            if depth > limit {
+                ctx.report_error(
+                    vec![name.pos],
+                    format!(
+                        "Input value is nested too deep, the maximum depth is {}.",
+                        limit
+                    ),
+                );
+            }
+        }
+    }
+
+    fn enter_field(&mut self, ctx: &mut VisitorContext<'a>, field: &'a Positioned<Field>) {
+        self.current_args = ctx
+            .parent_type()
+            .and_then(|p| p.field_by_name(&field.node.name.node))
+            .map(|f| &f.args);
+    }
+
+    fn exit_field(&mut self, _ctx: &mut VisitorContext<'a>, _field: &'a Positioned<Field>) {
+        self.current_args = None;
+    }
+}
+
+/// Returns the number of nested input objects in the value, and sets `exceeded` to the first
+/// input object type whose depth limit is exceeded.
+fn input_depth<'r>(
+    registry: &'r Registry,
+    ty: &str,
+    value: &ConstValue,
+    exceeded: &mut Option<(&'r str, usize)>,
+) -> usize {
+    match MetaTypeName::create(ty) {
+        MetaTypeName::NonNull(ty) => input_depth(registry, ty, value, exceeded),
+        MetaTypeName::List(ty) => match value {
+            ConstValue::List(items) => items
+                .iter()
+                .map(|item| input_depth(registry, ty, item, exceeded))
+                .max()
+                .unwrap_or_default(),
+            value => input_depth(registry, ty, value, exceeded),
+        },
+        MetaTypeName::Named(ty) => match (registry.types.get(ty), value) {
+            (
+                Some(MetaType::InputObject {
+                    name, input_fields, ..
+                }),
+                ConstValue::Object(fields),
+            ) => {
+                let depth = 1 + fields
+                    .iter()
+                    .filter_map(|(field_name, value)| {
+                        input_fields
+                            .get(field_name.as_str())
+                            .map(|field| input_depth(registry, &field.ty, value, exceeded))
+                    })
+                    .max()
+                    .unwrap_or_default();
+                if let Some(limit) = registry.input_depth_limits.get(name) {
+                    if depth > *limit && exceeded.is_none() {
+                        *exceeded = Some((name, *limit));
+                    }
+                }
+                depth
+            }
+            _ => 0,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[derive(InputObject)]
+    #[graphql(internal, max_depth = 3)]
+    struct Filter {
+        value: Option<i32>,
+        and: Option<Vec<Filter>>,
+    }
+
+    #[derive(InputObject)]
+    #[graphql(internal)]
+    struct Wrapper {
+        filter: Filter,
+    }
+
+    struct Query;
+
+    #[Object(internal)]
+    impl Query {
+        async fn filter(&self, _filter: Filter) -> bool {
+            true
+        }
+
+        async fn wrapper(&self, _wrapper: Wrapper) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn test_input_depth() {
+        let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+        assert!(schema
+            .execute("{ filter(filter: { and: [{ and: [{ value: 1 }] }] }) }")
+            .await
+            .is_ok());
+        assert_eq!(
+            schema
+                .execute("{ filter(filter: { and: [{ and: [{ and: [{ value: 1 }] }] }] }) }")
+                .await
+                .into_result()
+                .unwrap_err()[0]
+                .message,
+            r#"Input value of type "Filter" is nested too deep, the maximum depth is 3."#
+        );
+
+        let query = "query($filter: Filter!) { wrapper(wrapper: { filter: $filter }) }";
+        let filter = serde_json::json!({ "and": [{ "and": [{ "value": 1 }] }] });
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .limit_input_depth(3)
+            .finish();
+        assert_eq!(
+            schema
+                .execute(Request::new(query).variables(Variables::from_json(
+                    serde_json::json!({ "filter": filter })
+                )))
+                .await
+                .into_result()
+                .unwrap_err()[0]
+                .message,
+            "Input value is nested too deep, the maximum depth is 3."
+        );
+        assert!(schema
+            .execute(Request::new(query).variables(Variables::from_json(
+                serde_json::json!({ "filter": { "value": 1 } })
+            )))
+            .await
+            .is_ok());
+    }
+}
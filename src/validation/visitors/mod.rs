@@ -1,7 +1,11 @@
 mod cache_control;
 mod complexity;
+mod deprecation;
 mod depth;
+mod input_depth;
 
 pub use cache_control::CacheControlCalculate;
 pub use complexity::ComplexityCalculate;
+pub use deprecation::DeprecationCollect;
 pub use depth::DepthCalculate;
+pub use input_depth::InputDepthCheck;
@@ -19,12 +19,14 @@ pub async fn test_connection_additional_fields() {
     impl QueryRoot {
         async fn numbers(
             &self,
+            ctx: &Context<'_>,
             after: Option<String>,
             before: Option<String>,
             first: Option<i32>,
             last: Option<i32>,
         ) -> Result<Connection<usize, i32, ConnectionFields, Diff>> {
             connection::query(
+                ctx,
                 after,
                 before,
                 first,
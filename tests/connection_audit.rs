@@ -0,0 +1,113 @@
+use async_graphql::connection::*;
+use async_graphql::*;
+
+#[derive(SimpleObject)]
+struct BadPageInfo {
+    has_next_page: Option<bool>,
+    start_cursor: String,
+}
+
+#[derive(SimpleObject)]
+struct BadEdge {
+    node: Vec<i32>,
+}
+
+#[derive(SimpleObject)]
+struct BadConnection {
+    page_info: Option<BadPageInfo>,
+    edges: BadEdge,
+}
+
+#[derive(SimpleObject)]
+struct ItemEdge {
+    node: i32,
+    cursor: i32,
+}
+
+#[derive(SimpleObject)]
+struct ItemConnection {
+    page_info: PageInfo,
+    edges: Vec<ItemEdge>,
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn numbers(
+        &self,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Connection<usize, i32> {
+        let _ = (after, before, first, last);
+        Connection::new(false, false)
+    }
+
+    async fn bad(&self, offset: i32, first: String) -> BadConnection {
+        let _ = (offset, first);
+        BadConnection {
+            page_info: None,
+            edges: BadEdge { node: vec![] },
+        }
+    }
+
+    async fn items(&self, last: i32) -> Option<ItemConnection> {
+        let _ = last;
+        None
+    }
+}
+
+fn warning(coordinate: &str, message: &str) -> ConnectionWarning {
+    ConnectionWarning {
+        coordinate: coordinate.to_string(),
+        message: message.to_string(),
+    }
+}
+
+#[test]
+pub fn test_audit_connections() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema.audit_connections(),
+        vec![
+            warning(
+                "BadConnection.pageInfo",
+                "The field must return a non-null `PageInfo`."
+            ),
+            warning("BadPageInfo", "The type has no `hasPreviousPage` field."),
+            warning(
+                "BadPageInfo.hasNextPage",
+                "The field must return `Boolean!`."
+            ),
+            warning(
+                "BadPageInfo.startCursor",
+                "The field must be nullable, because there is no cursor if there are no results."
+            ),
+            warning("BadPageInfo", "The type has no `endCursor` field."),
+            warning("BadConnection.edges", "The field must return a list."),
+            warning("BadEdge.node", "The field must not return a list."),
+            warning("BadEdge", "The edge has no `cursor` field."),
+            warning(
+                "ItemEdge.cursor",
+                "The field must return a scalar serialized as a string."
+            ),
+            warning(
+                "Query.bad",
+                "The `first` argument is declared without `after`."
+            ),
+            warning(
+                "Query.bad",
+                "The `offset` argument is not a pagination argument of the specification."
+            ),
+            warning("Query.bad", "The `first` argument must be nullable."),
+            warning("Query.bad", "The `first` argument must be an `Int`."),
+            warning(
+                "Query.items",
+                "The `last` argument is declared without `before`."
+            ),
+            warning("Query.items", "The `last` argument must be nullable."),
+        ]
+    );
+}
@@ -0,0 +1,159 @@
+use async_graphql::*;
+
+#[derive(SimpleObject)]
+#[graphql(meta(tags = "public"))]
+struct User {
+    id: i32,
+    name: String,
+    #[graphql(meta(tags = "internal"))]
+    email: String,
+}
+
+#[derive(SimpleObject)]
+#[graphql(meta(tags = "internal"))]
+struct AuditLog {
+    message: String,
+}
+
+#[derive(SimpleObject)]
+#[graphql(meta(tags = "partner"))]
+struct Invoice {
+    amount: i32,
+}
+
+#[derive(InputObject)]
+struct UserFilter {
+    name: Option<String>,
+    #[graphql(meta(tags = "internal"))]
+    email: Option<String>,
+}
+
+struct Query;
+
+#[Object(meta(tags = "public"))]
+impl Query {
+    async fn users(&self, filter: Option<UserFilter>) -> Vec<User> {
+        let _ = filter;
+        vec![User {
+            id: 1,
+            name: "Alice".to_string(),
+            email: "alice@example.com".to_string(),
+        }]
+    }
+
+    async fn audit_logs(&self) -> Vec<AuditLog> {
+        vec![]
+    }
+
+    #[graphql(meta(tags = "partner"))]
+    async fn invoices(&self) -> Vec<Invoice> {
+        vec![Invoice { amount: 100 }]
+    }
+}
+
+struct Mutation;
+
+#[Object(meta(tags = "internal"))]
+impl Mutation {
+    async fn reset(&self) -> bool {
+        true
+    }
+}
+
+fn schema() -> Schema<Query, Mutation, EmptySubscription> {
+    Schema::new(Query, Mutation, EmptySubscription)
+}
+
+#[tokio::test]
+pub async fn test_contract_exclude() {
+    let schema = schema();
+    let public = schema.contract(&[], &["internal"]);
+    let sdl = public.sdl();
+
+    assert!(sdl.contains("type User"));
+    assert!(!sdl.contains("email"));
+    // The types tagged with an excluded tag and the fields returning them are removed.
+    assert!(!sdl.contains("AuditLog"));
+    assert!(!sdl.contains("auditLogs"));
+    // The mutation root without fields is removed.
+    assert!(!sdl.contains("Mutation"));
+    assert!(!sdl.contains("reset"));
+    // The fields without tags are kept.
+    assert!(sdl.contains("invoices"));
+
+    assert_eq!(
+        public
+            .execute("{ users { id name } invoices { amount } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "users": [{ "id": 1, "name": "Alice" }], "invoices": [{ "amount": 100 }] })
+    );
+    assert!(public.execute("{ users { email } }").await.is_err());
+    assert!(public.execute("{ auditLogs { message } }").await.is_err());
+    assert!(public
+        .execute(r#"{ users(filter: { email: "a" }) { id } }"#)
+        .await
+        .is_err());
+    assert!(public.execute("mutation { reset }").await.is_err());
+
+    // The original schema is not changed.
+    assert!(schema.sdl().contains("email"));
+    assert!(schema.execute("mutation { reset }").await.is_ok());
+    assert_ne!(schema.schema_hash(), public.schema_hash());
+}
+
+#[tokio::test]
+pub async fn test_contract_include() {
+    let partner = schema().contract(&["partner"], &[]);
+    let sdl = partner.sdl();
+
+    // Only the fields tagged with an included tag, or whose type is, are kept.
+    assert!(sdl.contains("invoices"));
+    assert!(sdl.contains("type Invoice"));
+    assert!(!sdl.contains("users"));
+    assert!(!sdl.contains("type User"));
+    assert!(!sdl.contains("UserFilter"));
+
+    let public_or_partner = schema().contract(&["public", "partner"], &["internal"]);
+    let sdl = public_or_partner.sdl();
+    assert!(sdl.contains("users(filter: UserFilter): [User!]!"));
+    assert!(sdl.contains("invoices"));
+    assert!(!sdl.contains("email"));
+}
+
+#[tokio::test]
+pub async fn test_contract_introspection() {
+    let public = schema().contract(&[], &["internal"]);
+    let resp = public
+        .execute(r#"{ __type(name: "User") { fields { name } } }"#)
+        .await
+        .into_result()
+        .unwrap();
+    assert_eq!(
+        resp.data,
+        value!({ "__type": { "fields": [{ "name": "id" }, { "name": "name" }] } })
+    );
+    assert_eq!(
+        public
+            .execute(r#"{ __schema { mutationType { name } } }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "__schema": { "mutationType": null } })
+    );
+}
+
+#[tokio::test]
+pub async fn test_contract_schema_hash_without_optional_fields() {
+    let public = schema().contract(&[], &["internal"]);
+    let with_optional_fields = Schema::build(Query, Mutation, EmptySubscription)
+        .enable_health_check()
+        .enable_schema_hash_field()
+        .finish()
+        .contract(&[], &["internal"]);
+    assert!(with_optional_fields.sdl().contains("_schemaHash"));
+    assert_eq!(with_optional_fields.schema_hash(), public.schema_hash());
+}
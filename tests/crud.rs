@@ -0,0 +1,210 @@
+use std::sync::{Arc, Mutex};
+
+use async_graphql::crud::{CrudEntity, EntityStore};
+use async_graphql::*;
+
+#[derive(SimpleObject, CrudObject, Clone)]
+struct User {
+    #[crud(id)]
+    key: ID,
+    name: String,
+    nickname: Option<String>,
+    #[crud(skip)]
+    version: i32,
+}
+
+#[derive(Default)]
+struct Users(Mutex<Vec<User>>);
+
+#[async_trait::async_trait]
+impl EntityStore<User> for Users {
+    async fn get(&self, _ctx: &Context<'_>, key: ID) -> Result<Option<User>> {
+        Ok(self
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|user| user.key == key)
+            .cloned())
+    }
+
+    async fn list(&self, _ctx: &Context<'_>, offset: usize, limit: usize) -> Result<Vec<User>> {
+        Ok(self
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .skip(offset)
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    async fn count(&self, _ctx: &Context<'_>) -> Result<usize> {
+        Ok(self.0.lock().unwrap().len())
+    }
+
+    async fn create(&self, _ctx: &Context<'_>, input: UserCreateInput) -> Result<User> {
+        let mut users = self.0.lock().unwrap();
+        let user = User {
+            key: ID::from(input.name.to_lowercase()),
+            name: input.name,
+            nickname: input.nickname,
+            version: 1,
+        };
+        users.push(user.clone());
+        Ok(user)
+    }
+
+    async fn update(
+        &self,
+        _ctx: &Context<'_>,
+        key: ID,
+        input: UserUpdateInput,
+    ) -> Result<Option<User>> {
+        let mut users = self.0.lock().unwrap();
+        Ok(users.iter_mut().find(|user| user.key == key).map(|user| {
+            if let Some(name) = input.name {
+                user.name = name;
+            }
+            match input.nickname {
+                MaybeUndefined::Value(nickname) => user.nickname = Some(nickname),
+                MaybeUndefined::Null => user.nickname = None,
+                MaybeUndefined::Undefined => {}
+            }
+            user.version += 1;
+            user.clone()
+        }))
+    }
+
+    async fn delete(&self, _ctx: &Context<'_>, key: ID) -> Result<bool> {
+        let mut users = self.0.lock().unwrap();
+        let len = users.len();
+        users.retain(|user| user.key != key);
+        Ok(users.len() < len)
+    }
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn version(&self) -> &str {
+        "1.0"
+    }
+}
+
+#[tokio::test]
+pub async fn test_crud_object() {
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .module(User::crud_module(Arc::new(Users::default())))
+        .finish();
+
+    for name in ["Alice", "Bob", "Carol"] {
+        let res = schema
+            .execute(format!(
+                r#"mutation {{ createUser(input: {{ name: "{}", nickname: "{}" }}) {{ key version }} }}"#,
+                name,
+                &name[..1]
+            ))
+            .await;
+        assert_eq!(
+            res.into_result().unwrap().data,
+            value!({ "createUser": { "key": name.to_lowercase(), "version": 1 } })
+        );
+    }
+
+    assert_eq!(
+        schema
+            .execute(
+                r#"{ user(key: "bob") { name nickname } missing: user(key: "dave") { name } }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "user": { "name": "Bob", "nickname": "B" }, "missing": null })
+    );
+
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    users(first: 2, after: "0") {
+                        edges { cursor node { key } }
+                        pageInfo { hasPreviousPage hasNextPage }
+                    }
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "users": {
+                "edges": [
+                    { "cursor": "1", "node": { "key": "bob" } },
+                    { "cursor": "2", "node": { "key": "carol" } },
+                ],
+                "pageInfo": { "hasPreviousPage": true, "hasNextPage": false },
+            }
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute(
+                r#"mutation {
+                    updateUser(key: "bob", input: { name: "Robert", nickname: null }) { name nickname version }
+                    deleteUser(key: "alice")
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "updateUser": { "name": "Robert", "nickname": null, "version": 2 },
+            "deleteUser": true,
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute(r#"mutation { deleteUser(key: "alice") }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "deleteUser": false })
+    );
+    assert_eq!(
+        schema
+            .execute("{ users(last: 1) { edges { node { name } } } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "users": { "edges": [{ "node": { "name": "Carol" } }] } })
+    );
+}
+
+#[tokio::test]
+pub async fn test_crud_object_sdl() {
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .module(User::crud_module(Arc::new(Users::default())))
+        .finish();
+    let sdl = schema.sdl();
+    assert!(sdl.contains(
+        r#"input UserCreateInput {
+	name: String!
+	nickname: String
+}"#
+    ));
+    assert!(sdl.contains(
+        r#"input UserUpdateInput {
+	name: String
+	nickname: String
+}"#
+    ));
+}
@@ -0,0 +1,148 @@
+use async_graphql::*;
+
+struct Database {
+    prefix: String,
+}
+
+#[derive(Clone)]
+struct Version(i32);
+
+#[tokio::test]
+pub async fn test_ctx_data_args() {
+    #[derive(SimpleObject)]
+    #[graphql(complex)]
+    struct MyObj {
+        a: i32,
+    }
+
+    #[ComplexObject]
+    impl MyObj {
+        async fn b(&self, #[graphql(ctx_data)] db: &Database, n: i32) -> String {
+            format!("{}{}", db.prefix, self.a + n)
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn name(&self, #[graphql(ctx_data)] db: &Database, id: i32) -> String {
+            format!("{}{}", db.prefix, id)
+        }
+
+        async fn version(&self, ctx: &Context<'_>, #[graphql(ctx_data)] version: Version) -> i32 {
+            assert!(ctx.data_opt::<Version>().is_some());
+            version.0
+        }
+
+        async fn obj(&self) -> MyObj {
+            MyObj { a: 10 }
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(Database {
+            prefix: "user".to_string(),
+        })
+        .data(Version(2))
+        .finish();
+    assert_eq!(
+        schema
+            .execute("{ name(id: 1) version obj { b(n: 5) } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "name": "user1",
+            "version": 2,
+            "obj": { "b": "user15" },
+        })
+    );
+
+    let sdl = schema.sdl();
+    assert!(sdl.contains("name(id: Int!): String!"));
+    assert!(sdl.contains("version: Int!"));
+    assert!(sdl.contains("b(n: Int!): String!"));
+
+    assert_eq!(
+        schema
+            .execute("{ name(id: 1, db: 1) }")
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        r#"Unknown argument "db" on field "name" of type "Query"."#
+    );
+}
+
+#[tokio::test]
+pub async fn test_ctx_data_args_missing() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn name(&self, #[graphql(ctx_data)] db: &Database) -> String {
+            db.prefix.clone()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let errors = schema.execute("{ name }").await.into_result().unwrap_err();
+    assert_eq!(
+        errors[0].message,
+        format!(
+            "Data `{}` does not exist.",
+            std::any::type_name::<Database>()
+        )
+    );
+    assert_eq!(errors[0].path, vec![PathSegment::Field("name".to_string())]);
+}
+
+#[tokio::test]
+pub async fn test_ctx_data_args_entity() {
+    #[derive(SimpleObject)]
+    struct MyObj {
+        id: i32,
+        name: String,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(entity)]
+        async fn find_obj(&self, #[graphql(ctx_data)] db: &Database, id: i32) -> MyObj {
+            MyObj {
+                id,
+                name: format!("{}{}", db.prefix, id),
+            }
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(Database {
+            prefix: "user".to_string(),
+        })
+        .finish();
+    assert!(schema
+        .federation_sdl()
+        .contains(r#"type MyObj @key(fields: "id")"#));
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    _entities(representations: [{__typename: "MyObj", id: 1}]) {
+                        ... on MyObj { id name }
+                    }
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "_entities": [{ "id": 1, "name": "user1" }],
+        })
+    );
+}
@@ -0,0 +1,218 @@
+use async_graphql::*;
+
+struct Lowercase;
+
+#[async_trait::async_trait]
+impl CustomDirective for Lowercase {
+    fn name(&self) -> &'static str {
+        "lowercase"
+    }
+
+    async fn resolve_field(
+        &self,
+        ctx: &Context<'_>,
+        _args: &DirectiveArgs,
+        next: NextDirective<'_>,
+    ) -> ServerResult<Option<Value>> {
+        Ok(match next.run(ctx).await? {
+            Some(Value::String(s)) => Some(Value::String(s.to_lowercase())),
+            value => value,
+        })
+    }
+}
+
+struct Suffix;
+
+#[async_trait::async_trait]
+impl CustomDirective for Suffix {
+    fn name(&self) -> &'static str {
+        "suffix"
+    }
+
+    fn arguments(&self) -> Vec<DirectiveArgument> {
+        vec![DirectiveArgument::new::<String>("value").default_value("!".to_string())]
+    }
+
+    async fn resolve_field(
+        &self,
+        ctx: &Context<'_>,
+        args: &DirectiveArgs,
+        next: NextDirective<'_>,
+    ) -> ServerResult<Option<Value>> {
+        let suffix = args.get::<String>("value")?;
+        Ok(match next.run(ctx).await? {
+            Some(Value::String(s)) => Some(Value::String(s + &suffix)),
+            value => value,
+        })
+    }
+}
+
+struct Role(&'static str);
+
+struct Auth;
+
+#[async_trait::async_trait]
+impl CustomDirective for Auth {
+    fn name(&self) -> &'static str {
+        "auth"
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("Requires the role to resolve the field.")
+    }
+
+    fn arguments(&self) -> Vec<DirectiveArgument> {
+        vec![DirectiveArgument::new::<String>("role").description("The required role.")]
+    }
+
+    async fn resolve_field(
+        &self,
+        ctx: &Context<'_>,
+        args: &DirectiveArgs,
+        next: NextDirective<'_>,
+    ) -> ServerResult<Option<Value>> {
+        let role = args.get::<String>("role")?;
+        if ctx.data_opt::<Role>().map(|r| r.0) != Some(role.as_str()) {
+            return Err(ServerError::new("Forbidden", Some(ctx.item.pos)));
+        }
+        next.run(ctx).await
+    }
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self) -> &str {
+        "Hello"
+    }
+
+    async fn number(&self) -> i32 {
+        10
+    }
+}
+
+fn schema() -> Schema<Query, EmptyMutation, EmptySubscription> {
+    Schema::build(Query, EmptyMutation, EmptySubscription)
+        .directive(Lowercase)
+        .directive(Suffix)
+        .directive(Auth)
+        .finish()
+}
+
+#[tokio::test]
+pub async fn test_custom_directive_transform() {
+    let schema = schema();
+    assert_eq!(
+        schema
+            .execute("{ a: value @lowercase b: value number @lowercase }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "a": "hello", "b": "Hello", "number": 10 })
+    );
+
+    // The directives are called in order, the first one receives the value of the second one.
+    assert_eq!(
+        schema
+            .execute(r#"{ a: value @lowercase @suffix(value: "-A") b: value @suffix @lowercase }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "a": "hello-a", "b": "hello!" })
+    );
+
+    assert_eq!(
+        schema
+            .execute(
+                Request::new(r#"query($s: String!) { value @suffix(value: $s) }"#)
+                    .variables(Variables::from_value(value!({ "s": "?" }))),
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "value": "Hello?" })
+    );
+}
+
+#[tokio::test]
+pub async fn test_custom_directive_short_circuit() {
+    let schema = schema();
+    let query = r#"{ value @auth(role: "ADMIN") }"#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap_err(),
+        vec![ServerError {
+            message: "Forbidden".to_string(),
+            source: None,
+            locations: vec![Pos { line: 1, column: 3 }],
+            path: vec![PathSegment::Field("value".to_owned())],
+            extensions: None,
+        }]
+    );
+    assert_eq!(
+        schema
+            .execute(Request::new(query).data(Role("ADMIN")))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "value": "Hello" })
+    );
+}
+
+#[tokio::test]
+pub async fn test_custom_directive_validation() {
+    let schema = schema();
+    assert!(schema.execute("{ value @auth }").await.is_err());
+    assert!(schema
+        .execute(r#"{ value @lowercase(unknown: 1) }"#)
+        .await
+        .is_err());
+    assert!(schema.execute("{ value @unknown }").await.is_err());
+}
+
+#[test]
+pub fn test_custom_directive_sdl() {
+    let sdl = schema().sdl();
+    assert!(sdl.contains(
+        r#""""
+Requires the role to resolve the field.
+"""
+directive @auth(
+	"""
+	The required role.
+	"""
+	role: String!
+) on FIELD
+directive @lowercase on FIELD
+directive @suffix(value: String! = "!") on FIELD
+"#
+    ));
+}
+
+#[test]
+#[should_panic(expected = "Directive `@skip` is already defined.")]
+pub fn test_custom_directive_duplicate() {
+    struct Skip;
+
+    #[async_trait::async_trait]
+    impl CustomDirective for Skip {
+        fn name(&self) -> &'static str {
+            "skip"
+        }
+
+        async fn resolve_field(
+            &self,
+            ctx: &Context<'_>,
+            _args: &DirectiveArgs,
+            next: NextDirective<'_>,
+        ) -> ServerResult<Option<Value>> {
+            next.run(ctx).await
+        }
+    }
+
+    Schema::build(Query, EmptyMutation, EmptySubscription).directive(Skip);
+}
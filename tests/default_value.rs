@@ -107,3 +107,135 @@ pub async fn test_default_value_inputobject() {
         })
     );
 }
+
+#[tokio::test]
+pub async fn test_default_value_null_and_absent() {
+    #[derive(InputObject)]
+    struct MyInput {
+        #[graphql(default = 3)]
+        value: Option<i32>,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn arg(
+            &self,
+            ctx: &Context<'_>,
+            #[graphql(default = 10)] input: Option<i32>,
+        ) -> String {
+            format!("{:?} {}", input, ctx.args_provided("input"))
+        }
+
+        async fn input(&self, input: MyInput) -> Option<i32> {
+            input.value
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let query = r#"query($a: Int, $b: Int = 5) {
+        arg1: arg
+        arg2: arg(input: null)
+        arg3: arg(input: 1)
+        arg4: arg(input: $a)
+        arg5: arg(input: $b)
+        input1: input(input: {})
+        input2: input(input: { value: null })
+        input3: input(input: { value: $a })
+        input4: input(input: { value: $b })
+    }"#;
+
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({
+            "arg1": "Some(10) false",
+            "arg2": "None true",
+            "arg3": "Some(1) true",
+            "arg4": "Some(10) false",
+            "arg5": "Some(5) true",
+            "input1": 3,
+            "input2": null,
+            "input3": 3,
+            "input4": 5,
+        })
+    );
+
+    // A variable explicitly set to `null` is not absent.
+    let request = Request::new(query).variables(Variables::from_value(value!({ "a": null })));
+    let data = schema.execute(request).await.into_result().unwrap().data;
+    assert_eq!(
+        data,
+        value!({
+            "arg1": "Some(10) false",
+            "arg2": "None true",
+            "arg3": "Some(1) true",
+            "arg4": "None true",
+            "arg5": "Some(5) true",
+            "input1": 3,
+            "input2": null,
+            "input3": null,
+            "input4": 5,
+        })
+    );
+}
+
+#[tokio::test]
+pub async fn test_default_value_sdl_literals() {
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    enum Color {
+        Red,
+        Green,
+    }
+
+    #[derive(InputObject)]
+    struct Point {
+        x: i32,
+        color: Color,
+        name: String,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(
+            &self,
+            #[graphql(default = "a \"b\"\n\u{1f}")] s: String,
+            #[graphql(default_with = "vec![Color::Red, Color::Green]")] colors: Vec<Color>,
+            #[graphql(
+                default_with = "Point { x: 1, color: Color::Green, name: \"n\".to_string() }"
+            )]
+            point: Point,
+            #[graphql(default = 1.5)] f: f32,
+        ) -> i32 {
+            let _ = (s, colors, point, f);
+            1
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert!(schema.sdl().contains(
+        r#"value(s: String! = "a \"b\"\n\u001F", colors: [Color!]! = [RED, GREEN], point: Point! = {x: 1, color: GREEN, name: "n"}, f: Float! = 1.5): Int!"#
+    ));
+    assert_eq!(
+        schema
+            .execute(r#"{ __type(name: "Query") { fields { args { name defaultValue } } } }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "__type": {
+                "fields": [{
+                    "args": [
+                        { "name": "s", "defaultValue": r#""a \"b\"\n\u001F""# },
+                        { "name": "colors", "defaultValue": "[RED, GREEN]" },
+                        { "name": "point", "defaultValue": r#"{x: 1, color: GREEN, name: "n"}"# },
+                        { "name": "f", "defaultValue": "1.5" },
+                    ]
+                }]
+            }
+        })
+    );
+}
@@ -0,0 +1,53 @@
+use async_graphql::*;
+use serde_json::json;
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn add(&self, a: i32, b: i32) -> i32 {
+        a + b
+    }
+}
+
+#[tokio::test]
+pub async fn test_execute_json() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    assert_eq!(
+        schema
+            .execute_json(&json!({
+                "query": "query Add($a: Int!) { add(a: $a, b: 2) }",
+                "operationName": "Add",
+                "variables": { "a": 1 },
+            }))
+            .await,
+        json!({ "data": { "add": 3 } })
+    );
+
+    assert_eq!(
+        schema
+            .execute_json(&json!([
+                { "query": "{ add(a: 1, b: 2) }" },
+                { "query": "{ add(a: 1) }" },
+            ]))
+            .await,
+        json!([
+            { "data": { "add": 3 } },
+            {
+                "data": null,
+                "errors": [{
+                    "message": r#"Field "add" argument "b" of type "Query" is required but not provided"#,
+                    "locations": [{ "line": 1, "column": 3 }],
+                }],
+            },
+        ])
+    );
+
+    let resp = schema.execute_json(&json!(1)).await;
+    assert_eq!(resp["data"], json!(null));
+    assert!(resp["errors"][0]["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("Invalid request: "));
+}
@@ -0,0 +1,134 @@
+use async_graphql::*;
+
+/// A color.
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+enum Color {
+    /// Red color.
+    Red,
+    #[graphql(deprecation = "Use `Red`.")]
+    Green,
+    Blue,
+}
+
+struct Query;
+
+/// The query root.
+#[Object]
+impl Query {
+    /// Returns the value.
+    async fn value(
+        &self,
+        #[graphql(desc = "The value to return.", default = 10)] n: i32,
+        flag: Option<bool>,
+    ) -> i32 {
+        if flag.unwrap_or_default() {
+            n
+        } else {
+            0
+        }
+    }
+
+    async fn color(&self, b: i32, a: i32) -> Color {
+        let _ = (a, b);
+        Color::Red
+    }
+
+    #[graphql(deprecation)]
+    async fn old(&self) -> bool {
+        true
+    }
+
+    #[graphql(deprecation = "Use \"value\".")]
+    async fn older(&self) -> bool {
+        true
+    }
+}
+
+#[test]
+fn test_export_sdl() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema.sdl(),
+        r#""""
+A color.
+"""
+enum Color {
+	"""
+	Red color.
+	"""
+	RED
+	GREEN @deprecated(reason: "Use `Red`.")
+	BLUE
+}
+"""
+The query root.
+"""
+type Query {
+	"""
+	Returns the value.
+	"""
+	value(
+		"""
+		The value to return.
+		"""
+		n: Int! = 10
+		flag: Boolean
+	): Int!
+	color(b: Int!, a: Int!): Color!
+	old: Boolean! @deprecated
+	older: Boolean! @deprecated(reason: "Use \"value\".")
+}
+schema {
+	query: Query
+}
+"#
+    );
+}
+
+#[test]
+fn test_export_sdl_sorted() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let sdl = schema.sdl_with_options(
+        SDLExportOptions::new()
+            .sorted_fields()
+            .sorted_arguments()
+            .sorted_enum_items(),
+    );
+    assert!(sdl.contains(
+        r#"enum Color {
+	BLUE
+	GREEN @deprecated(reason: "Use `Red`.")
+	"""
+	Red color.
+	"""
+	RED
+}"#
+    ));
+    assert!(sdl.contains(
+        r#"type Query {
+	color(a: Int!, b: Int!): Color!
+	old: Boolean! @deprecated
+"#
+    ));
+    assert!(sdl.contains(
+        r#"	value(
+		flag: Boolean
+		"""
+		The value to return.
+		"""
+		n: Int! = 10
+	): Int!
+}"#
+    ));
+}
+
+#[test]
+fn test_export_sdl_federation() {
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .enable_federation()
+        .finish();
+    let sdl = schema.sdl_with_options(SDLExportOptions::new().federation());
+    assert_eq!(sdl, schema.federation_sdl());
+    assert!(!sdl.contains("_service"));
+    assert!(!sdl.contains("schema {"));
+}
@@ -329,3 +329,279 @@ pub async fn test_extension_call_order() {
         );
     }
 }
+
+#[tokio::test]
+pub async fn test_extension_operation() {
+    struct MyExtensionImpl {
+        operations: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Extension for MyExtensionImpl {
+        async fn request(&self, ctx: &ExtensionContext<'_>, next: NextRequest<'_>) -> Response {
+            assert_eq!(ctx.operation_type(), None);
+            let resp = next.run(ctx).await;
+            self.operations.lock().await.push(format!(
+                "request {:?} {:?}",
+                ctx.operation_type(),
+                ctx.operation_name()
+            ));
+            resp
+        }
+
+        async fn execute(
+            &self,
+            ctx: &ExtensionContext<'_>,
+            operation_name: Option<&str>,
+            next: NextExecute<'_>,
+        ) -> Response {
+            assert_eq!(ctx.operation_name(), operation_name);
+            self.operations.lock().await.push(format!(
+                "{} {}",
+                ctx.operation_type().unwrap(),
+                operation_name.unwrap_or_default()
+            ));
+            next.run(ctx, operation_name).await
+        }
+    }
+
+    struct MyExtension {
+        operations: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl ExtensionFactory for MyExtension {
+        fn create(&self) -> Arc<dyn Extension> {
+            Arc::new(MyExtensionImpl {
+                operations: self.operations.clone(),
+            })
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct Mutation;
+
+    #[Object]
+    impl Mutation {
+        async fn action(&self) -> bool {
+            true
+        }
+    }
+
+    let operations: Arc<Mutex<Vec<String>>> = Default::default();
+    let schema = Schema::build(Query, Mutation, EmptySubscription)
+        .extension(MyExtension {
+            operations: operations.clone(),
+        })
+        .finish();
+
+    schema.execute("query GetValue { value }").await;
+    schema.execute("mutation { action }").await;
+    schema.execute("{ unknown }").await;
+
+    assert_eq!(
+        &*operations.lock().await,
+        &[
+            "query GetValue",
+            "request Some(Query) Some(\"GetValue\")",
+            "mutation ",
+            "request Some(Mutation) None",
+            "request None None",
+        ]
+    );
+}
+
+#[tokio::test]
+pub async fn test_extension_document() {
+    struct MyExtensionImpl {
+        fields: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Extension for MyExtensionImpl {
+        async fn validation(
+            &self,
+            ctx: &ExtensionContext<'_>,
+            next: NextValidation<'_>,
+        ) -> Result<ValidationResult, Vec<ServerError>> {
+            assert!(ctx.document().is_none());
+            assert!(ctx.operation().is_none());
+            next.run(ctx).await
+        }
+
+        async fn execute(
+            &self,
+            ctx: &ExtensionContext<'_>,
+            operation_name: Option<&str>,
+            next: NextExecute<'_>,
+        ) -> Response {
+            let mut fields = self.fields.lock().await;
+            for selection in &ctx.operation().unwrap().node.selection_set.node.items {
+                if let parser::types::Selection::Field(field) = &selection.node {
+                    fields.push(format!("Query.{}", field.node.name.node));
+                }
+            }
+            let mut fragments = ctx.fragments().unwrap().keys().collect::<Vec<_>>();
+            fragments.sort();
+            for name in fragments {
+                fields.push(format!("fragment {}", name));
+            }
+            assert_eq!(ctx.document().unwrap().operations.iter().count(), 2);
+            next.run(ctx, operation_name).await
+        }
+    }
+
+    struct MyExtension {
+        fields: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl ExtensionFactory for MyExtension {
+        fn create(&self) -> Arc<dyn Extension> {
+            Arc::new(MyExtensionImpl {
+                fields: self.fields.clone(),
+            })
+        }
+    }
+
+    #[derive(SimpleObject)]
+    struct Obj {
+        a: i32,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+
+        async fn obj(&self) -> Obj {
+            Obj { a: 1 }
+        }
+    }
+
+    let fields: Arc<Mutex<Vec<String>>> = Default::default();
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .extension(MyExtension {
+            fields: fields.clone(),
+        })
+        .finish();
+
+    let resp = schema
+        .execute(
+            Request::new(
+                r#"
+                query A { value }
+                query B { value obj { ...ObjFields } }
+                fragment ObjFields on Obj { a }
+                "#,
+            )
+            .operation_name("B"),
+        )
+        .await;
+    assert!(resp.errors.is_empty(), "{:?}", resp.errors);
+    assert_eq!(
+        &*fields.lock().await,
+        &["Query.value", "Query.obj", "fragment ObjFields"]
+    );
+}
+
+#[tokio::test]
+pub async fn test_extension_execute_transaction() {
+    #[derive(Default, Clone)]
+    struct Log(Arc<Mutex<Vec<String>>>);
+
+    struct TransactionImpl;
+
+    #[async_trait::async_trait]
+    impl Extension for TransactionImpl {
+        async fn execute(
+            &self,
+            ctx: &ExtensionContext<'_>,
+            operation_name: Option<&str>,
+            next: NextExecute<'_>,
+        ) -> Response {
+            if ctx.operation_type() != Some(parser::types::OperationType::Mutation) {
+                return next.run(ctx, operation_name).await;
+            }
+
+            let log = ctx.data_unchecked::<Log>();
+            log.0.lock().await.push("begin".to_string());
+            let resp = next.run(ctx, operation_name).await;
+            log.0
+                .lock()
+                .await
+                .push(if resp.is_ok() { "commit" } else { "rollback" }.to_string());
+            resp
+        }
+    }
+
+    struct Transaction;
+
+    impl ExtensionFactory for Transaction {
+        fn create(&self) -> Arc<dyn Extension> {
+            Arc::new(TransactionImpl)
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct Mutation;
+
+    #[Object]
+    impl Mutation {
+        async fn insert(&self, ctx: &Context<'_>, value: i32) -> Result<i32> {
+            if value < 0 {
+                return Err("invalid value".into());
+            }
+            ctx.data_unchecked::<Log>()
+                .0
+                .lock()
+                .await
+                .push(format!("insert {}", value));
+            Ok(value)
+        }
+    }
+
+    let log = Log::default();
+    let schema = Schema::build(Query, Mutation, EmptySubscription)
+        .data(log.clone())
+        .extension(Transaction)
+        .finish();
+
+    assert!(schema.execute("{ value }").await.is_ok());
+    assert!(log.0.lock().await.is_empty());
+
+    assert!(schema
+        .execute("mutation { a: insert(value: 1) b: insert(value: 2) }")
+        .await
+        .is_ok());
+    assert_eq!(
+        std::mem::take(&mut *log.0.lock().await),
+        vec!["begin", "insert 1", "insert 2", "commit"]
+    );
+
+    assert!(schema
+        .execute("mutation { a: insert(value: 1) b: insert(value: -1) }")
+        .await
+        .is_err());
+    assert_eq!(
+        std::mem::take(&mut *log.0.lock().await),
+        vec!["begin", "insert 1", "rollback"]
+    );
+}
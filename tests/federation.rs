@@ -252,3 +252,128 @@ pub async fn test_find_entity_with_context() {
         }]
     );
 }
+
+#[tokio::test]
+pub async fn test_federation_v2() {
+    #[derive(SimpleObject)]
+    #[graphql(shareable)]
+    struct Position {
+        x: i32,
+        y: i32,
+    }
+
+    struct Product {
+        upc: String,
+    }
+
+    #[Object]
+    impl Product {
+        async fn upc(&self) -> &str {
+            &self.upc
+        }
+
+        #[graphql(override_from = "inventory")]
+        async fn in_stock(&self) -> bool {
+            true
+        }
+
+        #[graphql(shareable)]
+        async fn position(&self) -> Position {
+            Position { x: 1, y: 2 }
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(entity)]
+        async fn find_product_by_upc(&self, upc: String) -> Product {
+            Product { upc }
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .enable_federation_v2()
+        .finish();
+    let sdl = schema.federation_sdl();
+    assert!(sdl.starts_with(
+        r#"extend schema @link(url: "https://specs.apollo.dev/federation/v2.0", import: ["@key", "@requires", "@provides", "@external", "@shareable", "@override"])"#
+    ));
+    assert!(sdl.contains(
+        r#"type Position {
+	x: Int! @shareable
+	y: Int! @shareable
+}"#
+    ));
+    assert!(sdl.contains(
+        r#"type Product @key(fields: "upc") {
+	upc: String!
+	inStock: Boolean! @override(from: "inventory")
+	position: Position! @shareable
+}"#
+    ));
+
+    assert_eq!(
+        schema
+            .execute("{ _service { sdl } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "_service": { "sdl": sdl } })
+    );
+    assert!(!Schema::new(Query, EmptyMutation, EmptySubscription)
+        .federation_sdl()
+        .contains("@link"));
+}
+
+#[tokio::test]
+pub async fn test_federation_v2_connection() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(entity)]
+        async fn find_position_by_x(&self, x: i32) -> Option<i32> {
+            Some(x)
+        }
+
+        async fn values(&self) -> connection::Connection<usize, i32> {
+            let mut connection = connection::Connection::new(false, false);
+            connection.append([connection::Edge::new(0, 10)]);
+            connection
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .enable_federation_v2()
+        .finish();
+    let sdl = schema.federation_sdl();
+    assert!(sdl.contains(
+        r#"type PageInfo {
+	"""
+	When paginating backwards, are there more items?
+	"""
+	hasPreviousPage: Boolean! @shareable"#
+    ));
+    assert!(sdl.contains(
+        r#"type IntConnection {
+	"""
+	Information to aid in pagination.
+	"""
+	pageInfo: PageInfo! @shareable"#
+    ));
+    assert!(sdl.contains(
+        r#"type IntEdge {
+	"""
+	The item at the end of the edge
+	"""
+	node: Int! @shareable"#
+    ));
+    assert!(!sdl.contains("values: IntConnection! @shareable"));
+
+    assert!(!Schema::new(Query, EmptyMutation, EmptySubscription)
+        .federation_sdl()
+        .contains("@shareable"));
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "ffi")]
+
+use std::ffi::{CStr, CString};
+
+use async_graphql::ffi::*;
+use async_graphql::*;
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn add(&self, a: i32, b: i32) -> i32 {
+        a + b
+    }
+
+    async fn div(&self, a: i32, b: i32) -> i32 {
+        a / b
+    }
+}
+
+fn execute(schema: *const SchemaHandle, request: &str) -> serde_json::Value {
+    let request = CString::new(request).unwrap();
+    unsafe {
+        let resp = async_graphql_execute(schema, request.as_ptr());
+        let value = serde_json::from_str(CStr::from_ptr(resp).to_str().unwrap()).unwrap();
+        async_graphql_free_string(resp);
+        value
+    }
+}
+
+#[test]
+pub fn test_ffi() {
+    let schema = SchemaHandle::new(Schema::new(Query, EmptyMutation, EmptySubscription)).into_raw();
+
+    assert_eq!(
+        execute(
+            schema,
+            r#"{ "query": "query($a: Int!) { add(a: $a, b: 2) }", "variables": { "a": 1 } }"#
+        ),
+        serde_json::json!({ "data": { "add": 3 } })
+    );
+
+    let resp = execute(schema, "{");
+    assert!(resp["errors"][0]["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("Invalid request: "));
+
+    assert_eq!(
+        execute(schema, r#"{ "query": "{ div(a: 1, b: 0) }" }"#),
+        serde_json::json!({
+            "errors": [{ "message": "Internal error: the execution panicked." }]
+        })
+    );
+
+    unsafe {
+        assert!(async_graphql_execute(schema, std::ptr::null()).is_null());
+        async_graphql_free_schema(schema);
+        async_graphql_free_schema(std::ptr::null_mut());
+        async_graphql_free_string(std::ptr::null_mut());
+    }
+}
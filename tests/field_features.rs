@@ -160,3 +160,94 @@ pub async fn test_field_features() {
         }]
     );
 }
+
+#[tokio::test]
+pub async fn test_field_features_cfg_attr() {
+    #[derive(SimpleObject)]
+    struct MyObj {
+        #[cfg_attr(feature = "bson", graphql(name = "valueBson"))]
+        value: i32,
+        #[cfg_attr(feature = "abc", graphql(skip))]
+        value_abc: i32,
+    }
+
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    enum MyEnum {
+        A,
+        #[cfg_attr(feature = "bson", graphql(name = "BSON"))]
+        B,
+        #[cfg(feature = "abc")]
+        C,
+    }
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        #[cfg_attr(feature = "bson", graphql(name = "valueBson"))]
+        #[cfg_attr(feature = "abc", graphql(deprecation = "abc"), allow(unused))]
+        async fn value(&self, #[cfg_attr(feature = "bson", graphql(default = 20))] n: i32) -> i32 {
+            n
+        }
+
+        #[cfg_attr(feature = "abc", graphql(skip))]
+        async fn value_abc(&self) -> i32 {
+            10
+        }
+
+        async fn obj(&self) -> MyObj {
+            MyObj {
+                value: 10,
+                value_abc: 20,
+            }
+        }
+
+        async fn e(&self) -> MyEnum {
+            MyEnum::B
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ valueBson valueAbc obj { valueBson valueAbc } e }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "valueBson": 20,
+            "valueAbc": 10,
+            "obj": { "valueBson": 10, "valueAbc": 20 },
+            "e": "BSON",
+        })
+    );
+    assert!(schema.execute("{ value }").await.is_err());
+
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    query: __type(name: "QueryRoot") { fields { name isDeprecated } }
+                    enum: __type(name: "MyEnum") { enumValues { name } }
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "query": {
+                "fields": [
+                    { "name": "valueBson", "isDeprecated": false },
+                    { "name": "valueAbc", "isDeprecated": false },
+                    { "name": "obj", "isDeprecated": false },
+                    { "name": "e", "isDeprecated": false },
+                ],
+            },
+            "enum": {
+                "enumValues": [{ "name": "A" }, { "name": "BSON" }],
+            },
+        })
+    );
+}
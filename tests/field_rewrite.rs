@@ -0,0 +1,158 @@
+use std::sync::Arc;
+
+use async_graphql::extensions::{
+    Extension, ExtensionContext, ExtensionFactory, FieldKillSwitch, FieldRewrite,
+};
+use async_graphql::parser::types::Field;
+use async_graphql::*;
+
+#[derive(SimpleObject)]
+struct Obj {
+    a: i32,
+    b: Option<i32>,
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self) -> i32 {
+        10
+    }
+
+    async fn old_value(&self) -> i32 {
+        20
+    }
+
+    async fn search(&self) -> Option<i32> {
+        Some(30)
+    }
+
+    async fn obj(&self) -> Option<Obj> {
+        Some(Obj { a: 1, b: Some(2) })
+    }
+}
+
+fn disabled_error(name: &str, path: Vec<PathSegment>, pos: Pos) -> ServerError {
+    let mut extensions = ErrorExtensionValues::default();
+    extensions.set("code", "FEATURE_DISABLED");
+    ServerError {
+        message: format!(r#"The field "{}" is disabled."#, name),
+        source: None,
+        locations: vec![pos],
+        path,
+        extensions: Some(extensions),
+    }
+}
+
+#[tokio::test]
+pub async fn test_kill_switch() {
+    let kill_switch = FieldKillSwitch::new();
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .extension(kill_switch.clone())
+        .finish();
+
+    kill_switch.disable("Query.search");
+    let resp = schema.execute("{ value search }").await;
+    assert_eq!(resp.data, value!({ "value": 10, "search": null }));
+    assert_eq!(
+        resp.errors,
+        vec![disabled_error(
+            "search",
+            vec![PathSegment::Field("search".to_owned())],
+            Pos { line: 1, column: 9 }
+        )]
+    );
+
+    kill_switch.enable("Query.search");
+    let resp = schema.execute("{ value search }").await;
+    assert_eq!(
+        resp.into_result().unwrap().data,
+        value!({ "value": 10, "search": 30 })
+    );
+}
+
+#[tokio::test]
+pub async fn test_kill_switch_nested() {
+    let kill_switch = FieldKillSwitch::new();
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .extension(kill_switch.clone())
+        .finish();
+    kill_switch.disable("Obj.a");
+
+    // The error of a non-null field is propagated to its nullable parent.
+    let resp = schema.execute("{ obj { a b } }").await;
+    assert_eq!(resp.data, value!({ "obj": null }));
+    assert_eq!(
+        resp.errors,
+        vec![disabled_error(
+            "a",
+            vec![
+                PathSegment::Field("obj".to_owned()),
+                PathSegment::Field("a".to_owned())
+            ],
+            Pos { line: 1, column: 9 }
+        )]
+    );
+
+    // The fields of fragments are disabled too.
+    kill_switch.enable("Obj.a");
+    kill_switch.disable("Obj.b");
+    let resp = schema
+        .execute("{ obj { ...ObjFields ... on Obj { c: b } } } fragment ObjFields on Obj { a b }")
+        .await;
+    assert_eq!(
+        resp.data,
+        value!({ "obj": { "a": 1, "b": null, "c": null } })
+    );
+    assert_eq!(resp.errors.len(), 2);
+}
+
+#[tokio::test]
+pub async fn test_rewrite_field_replace() {
+    struct RenameExtension;
+
+    impl ExtensionFactory for RenameExtension {
+        fn create(&self) -> Arc<dyn Extension> {
+            Arc::new(RenameExtension)
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Extension for RenameExtension {
+        fn rewrite_field(
+            &self,
+            _ctx: &ExtensionContext<'_>,
+            parent_type: &str,
+            field: &Positioned<Field>,
+        ) -> FieldRewrite {
+            if parent_type == "Query" && field.node.name.node == "oldValue" {
+                let mut new_field = field.clone();
+                new_field.node.alias = Some(
+                    field
+                        .node
+                        .alias
+                        .clone()
+                        .unwrap_or_else(|| field.node.name.clone()),
+                );
+                new_field.node.name.node = Name::new("value");
+                FieldRewrite::Replace(new_field)
+            } else {
+                FieldRewrite::Keep
+            }
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .extension(RenameExtension)
+        .finish();
+    assert_eq!(
+        schema
+            .execute("{ oldValue a: oldValue search }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "oldValue": 10, "a": 10, "search": 30 })
+    );
+}
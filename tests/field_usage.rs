@@ -0,0 +1,81 @@
+use async_graphql::*;
+
+#[tokio::test]
+pub async fn test_field_usage() {
+    #[derive(Interface)]
+    #[graphql(field(name = "name", type = "&String"))]
+    enum Node {
+        User(User),
+    }
+
+    #[derive(SimpleObject)]
+    struct User {
+        name: String,
+        email: String,
+    }
+
+    fn user() -> User {
+        User {
+            name: "sunli".to_string(),
+            email: "sunli@example.com".to_string(),
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn user(&self) -> User {
+            user()
+        }
+
+        async fn node(&self) -> Node {
+            user().into()
+        }
+
+        async fn users(&self) -> Vec<User> {
+            vec![user(), user()]
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    schema
+        .execute("{ user { name } }")
+        .await
+        .into_result()
+        .unwrap();
+    assert_eq!(schema.field_usage_snapshot(), FieldUsageSnapshot::default());
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .enable_field_usage()
+        .finish();
+    let snapshot = schema.field_usage_snapshot();
+    assert_eq!(snapshot.count("User.name"), 0);
+    assert_eq!(
+        snapshot.unused_fields().collect::<Vec<_>>(),
+        vec![
+            "Node.name",
+            "Query.node",
+            "Query.user",
+            "Query.users",
+            "User.email",
+            "User.name",
+        ]
+    );
+
+    schema
+        .execute("{ user { name } users { name __typename } node { name } }")
+        .await
+        .into_result()
+        .unwrap();
+    let snapshot = schema.field_usage_snapshot();
+    assert_eq!(snapshot.count("Query.user"), 1);
+    assert_eq!(snapshot.count("Query.users"), 1);
+    assert_eq!(snapshot.count("Query.node"), 1);
+    assert_eq!(snapshot.count("User.name"), 3);
+    assert_eq!(snapshot.count("Node.name"), 4);
+    assert_eq!(
+        snapshot.unused_fields().collect::<Vec<_>>(),
+        vec!["User.email"]
+    );
+}
@@ -0,0 +1,143 @@
+use async_graphql::*;
+
+#[tokio::test]
+pub async fn test_filterable() {
+    #[derive(SimpleObject, Filterable)]
+    #[graphql(name = "Person")]
+    struct User {
+        #[graphql(name = "fullName")]
+        name: String,
+        age: Option<i32>,
+        #[filterable(skip_order)]
+        email: String,
+        #[filterable(skip_filter)]
+        created_at: i64,
+        #[filterable(skip)]
+        tags: Vec<String>,
+        #[graphql(skip)]
+        #[allow(dead_code)]
+        password: String,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn user(&self) -> Option<User> {
+            None
+        }
+
+        async fn filter(&self, filter: UserFilterInput, order_by: Vec<UserOrderByInput>) -> String {
+            format!("{:?} {:?}", filter, order_by)
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let sdl = schema.sdl();
+    assert!(sdl.contains(
+        r#"input PersonFilterInput {
+	fullName: StringFilter
+	age: IntFilter
+	email: StringFilter
+	"""
+	All the filters match.
+	"""
+	and: [PersonFilterInput!]
+	"""
+	Any of the filters matches.
+	"""
+	or: [PersonFilterInput!]
+	"""
+	The filter does not match.
+	"""
+	not: PersonFilterInput
+}"#
+    ));
+    assert!(sdl.contains(
+        r#"input PersonOrderByInput {
+	fullName: OrderDirection
+	age: OrderDirection
+	createdAt: OrderDirection
+}"#
+    ));
+    assert!(sdl.contains(
+        r#"input IntFilter {
+	eq: Int
+	ne: Int
+	in: [Int!]
+	gt: Int
+	lt: Int
+	contains: Int
+}"#
+    ));
+
+    let query = r#"{
+        filter(
+            filter: { fullName: { contains: "a" }, or: [{ age: { in: [1, 2] } }, { not: { email: { eq: "b" } } }] }
+            orderBy: [{ age: DESC }, { createdAt: ASC }]
+        )
+    }"#;
+    let data = schema.execute(query).await.into_result().unwrap().data;
+    let filter = UserFilterInput {
+        name: Some(FieldFilter {
+            contains: Some("a".to_string()),
+            ..Default::default()
+        }),
+        or: Some(vec![
+            UserFilterInput {
+                age: Some(FieldFilter {
+                    r#in: Some(vec![1, 2]),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            UserFilterInput {
+                not: Some(Box::new(UserFilterInput {
+                    email: Some(FieldFilter {
+                        eq: Some("b".to_string()),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                })),
+                ..Default::default()
+            },
+        ]),
+        ..Default::default()
+    };
+    let order_by = vec![
+        UserOrderByInput {
+            age: Some(OrderDirection::Desc),
+            ..Default::default()
+        },
+        UserOrderByInput {
+            created_at: Some(OrderDirection::Asc),
+            ..Default::default()
+        },
+    ];
+    assert_eq!(
+        data,
+        value!({ "filter": format!("{:?} {:?}", filter, order_by) })
+    );
+}
+
+#[test]
+pub fn test_field_filter_matches() {
+    let filter = FieldFilter {
+        gt: Some(1),
+        lt: Some(5),
+        ne: Some(3),
+        ..Default::default()
+    };
+    assert!(filter.matches_with(&2, |_, _| true));
+    assert!(!filter.matches_with(&3, |_, _| true));
+    assert!(!filter.matches_with(&5, |_, _| true));
+
+    let filter = FieldFilter {
+        contains: Some("ell".to_string()),
+        r#in: Some(vec!["hello".to_string(), "yellow".to_string()]),
+        ..Default::default()
+    };
+    assert!(filter.matches("hello"));
+    assert!(!filter.matches("bell"));
+    assert!(!filter.matches("world"));
+}
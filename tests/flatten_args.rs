@@ -0,0 +1,148 @@
+use async_graphql::*;
+
+#[derive(InputObject)]
+struct SearchArgs {
+    keyword: String,
+    #[graphql(default = 10)]
+    limit: i32,
+    #[graphql(default)]
+    offset: i32,
+}
+
+#[tokio::test]
+pub async fn test_flatten_args() {
+    #[derive(SimpleObject)]
+    #[graphql(complex)]
+    struct MyObj {
+        a: i32,
+    }
+
+    #[ComplexObject]
+    impl MyObj {
+        async fn search(&self, #[graphql(flatten)] args: SearchArgs) -> String {
+            format!("{}:{}:{}:{}", self.a, args.keyword, args.limit, args.offset)
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn search(
+            &self,
+            #[graphql(flatten)] args: SearchArgs,
+            #[graphql(default)] desc: bool,
+        ) -> String {
+            format!("{}:{}:{}:{}", args.keyword, args.limit, args.offset, desc)
+        }
+
+        async fn obj(&self) -> MyObj {
+            MyObj { a: 1 }
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let sdl = schema.sdl();
+    assert!(sdl.contains(
+        "search(keyword: String!, limit: Int! = 10, offset: Int! = 0, desc: Boolean! = false): String!"
+    ));
+    assert!(sdl.contains("search(keyword: String!, limit: Int! = 10, offset: Int! = 0): String!"));
+    assert!(!sdl.contains("input SearchArgs"));
+
+    let query = r#"query($limit: Int!) {
+        a: search(keyword: "abc")
+        b: search(keyword: "abc", limit: $limit, offset: 5, desc: true)
+        obj { search(keyword: "def", offset: 1) }
+    }"#;
+    assert_eq!(
+        schema
+            .execute(
+                Request::new(query)
+                    .variables(Variables::from_json(serde_json::json!({ "limit": 20 })))
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "a": "abc:10:0:false",
+            "b": "abc:20:5:true",
+            "obj": { "search": "1:def:10:1" },
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute("{ search(limit: 5) }")
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        r#"Field "search" argument "keyword" of type "Query" is required but not provided"#
+    );
+}
+
+#[tokio::test]
+pub async fn test_flatten_args_only_parse_input_fields() {
+    // Rejects the fields which are not in `SearchArgs`.
+    struct StrictSearchArgs(SearchArgs);
+
+    impl Type for StrictSearchArgs {
+        fn type_name() -> std::borrow::Cow<'static, str> {
+            SearchArgs::type_name()
+        }
+
+        fn create_type_info(registry: &mut registry::Registry) -> String {
+            SearchArgs::create_type_info(registry)
+        }
+    }
+
+    impl InputType for StrictSearchArgs {
+        fn parse(value: Option<Value>) -> InputValueResult<Self> {
+            if let Some(Value::Object(obj)) = &value {
+                if let Some(name) = obj
+                    .keys()
+                    .find(|name| !["keyword", "limit", "offset"].contains(&name.as_str()))
+                {
+                    return Err(InputValueError::custom(format!("Unknown field {}", name)));
+                }
+            }
+            SearchArgs::parse(value)
+                .map(StrictSearchArgs)
+                .map_err(InputValueError::propagate)
+        }
+
+        fn to_value(&self) -> Value {
+            self.0.to_value()
+        }
+    }
+
+    impl InputObjectType for StrictSearchArgs {}
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn search(
+            &self,
+            #[graphql(flatten)] args: StrictSearchArgs,
+            #[graphql(default)] desc: bool,
+        ) -> String {
+            format!(
+                "{}:{}:{}:{}",
+                args.0.keyword, args.0.limit, args.0.offset, desc
+            )
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(r#"{ search(keyword: "abc", desc: true) }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "search": "abc:10:0:true" })
+    );
+}
@@ -348,3 +348,48 @@ pub async fn test_concrete_object() {
         })
     );
 }
+
+#[tokio::test]
+pub async fn test_phantom_data_fields() {
+    use std::marker::PhantomData;
+
+    #[derive(SimpleObject)]
+    #[graphql(concrete(name = "IntId", params(i32)))]
+    struct Id<T: Send + Sync + 'static> {
+        value: String,
+        _marker: PhantomData<T>,
+    }
+
+    #[derive(InputObject)]
+    #[graphql(concrete(name = "IntIdInput", params(i32)))]
+    struct IdInput<T: Send + Sync + 'static> {
+        value: String,
+        _marker: PhantomData<T>,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn id(&self, input: IdInput<i32>) -> Id<i32> {
+            Id {
+                value: input.value,
+                _marker: PhantomData,
+            }
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(r#"{ id(input: { value: "abc" }) { value } }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "id": { "value": "abc" } })
+    );
+
+    let sdl = schema.sdl();
+    assert!(!sdl.contains("marker"));
+}
@@ -0,0 +1,198 @@
+use async_graphql::*;
+
+#[tokio::test]
+pub async fn test_global_id() {
+    let id = GlobalId::encode("User", 10);
+    let global_id = GlobalId::decode(&id).unwrap();
+    assert_eq!(global_id, GlobalId::new("User", 10));
+    assert_eq!(global_id.type_name(), "User");
+    assert_eq!(global_id.id(), "10");
+    assert_eq!(
+        GlobalId::decode("abc").unwrap_err().message,
+        "Invalid global ID."
+    );
+}
+
+#[tokio::test]
+pub async fn test_node_interface() {
+    #[derive(SimpleObject)]
+    #[graphql(complex)]
+    struct User {
+        #[graphql(skip)]
+        raw_id: i32,
+        name: String,
+    }
+
+    #[ComplexObject]
+    impl User {
+        async fn id(&self, ctx: &Context<'_>) -> ID {
+            ctx.encode_global_id("User", self.raw_id)
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl NodeObject for User {
+        async fn load(_ctx: &Context<'_>, id: &str) -> Result<Option<Self>> {
+            Ok(match id.parse::<i32>()? {
+                1 => Some(User {
+                    raw_id: 1,
+                    name: "sunli".to_string(),
+                }),
+                _ => None,
+            })
+        }
+    }
+
+    #[derive(SimpleObject)]
+    #[graphql(complex)]
+    struct Post {
+        #[graphql(skip)]
+        raw_id: i32,
+        title: String,
+    }
+
+    #[ComplexObject]
+    impl Post {
+        async fn id(&self, ctx: &Context<'_>) -> ID {
+            ctx.encode_global_id("Post", self.raw_id)
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl NodeObject for Post {
+        async fn load(_ctx: &Context<'_>, id: &str) -> Result<Option<Self>> {
+            Ok(Some(Post {
+                raw_id: id.parse()?,
+                title: format!("post{}", id),
+            }))
+        }
+    }
+
+    #[derive(Interface)]
+    #[graphql(node)]
+    enum Node {
+        User(User),
+        Post(Post),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn node(&self, ctx: &Context<'_>, id: ID) -> Result<Option<Node>> {
+            Node::load_node(ctx, &id).await
+        }
+    }
+
+    // Reverses the ids instead of encoding them with base64.
+    struct ReverseCodec;
+
+    impl GlobalIdCodec for ReverseCodec {
+        fn encode(&self, type_name: &str, id: &str) -> String {
+            format!("{}:{}", type_name, id).chars().rev().collect()
+        }
+
+        fn decode(&self, global_id: &str) -> Option<(String, String)> {
+            let s = global_id.chars().rev().collect::<String>();
+            let (type_name, id) = s.split_once(':')?;
+            Some((type_name.to_string(), id.to_string()))
+        }
+    }
+
+    let query = r#"query($id: ID!) {
+        node(id: $id) {
+            __typename
+            id
+            ... on User { name }
+            ... on Post { title }
+        }
+    }"#;
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let execute = |id: ID| {
+        let schema = schema.clone();
+        async move {
+            schema
+                .execute(Request::new(query).variables(Variables::from_value(value!({ "id": id }))))
+                .await
+        }
+    };
+
+    let user_id = GlobalId::encode("User", 1);
+    assert_eq!(
+        execute(user_id.clone()).await.into_result().unwrap().data,
+        value!({
+            "node": { "__typename": "User", "id": user_id, "name": "sunli" }
+        })
+    );
+
+    let post_id = GlobalId::encode("Post", 2);
+    assert_eq!(
+        execute(post_id.clone()).await.into_result().unwrap().data,
+        value!({
+            "node": { "__typename": "Post", "id": post_id, "title": "post2" }
+        })
+    );
+
+    assert_eq!(
+        execute(GlobalId::encode("User", 2))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "node": null })
+    );
+    assert_eq!(
+        execute(GlobalId::encode("Comment", 1))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "node": null })
+    );
+    assert!(execute("abc".into()).await.is_err());
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .global_id_codec(ReverseCodec)
+        .finish();
+    assert_eq!(
+        schema
+            .execute(r#"{ node(id: "1:resU") { id ... on User { name } } }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "node": { "id": "1:resU", "name": "sunli" }
+        })
+    );
+
+    #[derive(Default)]
+    struct PostQuery;
+
+    #[Object]
+    impl PostQuery {
+        async fn post_count(&self) -> i32 {
+            1
+        }
+    }
+
+    #[derive(MergedObject, Default)]
+    struct MergedQuery(PostQuery, NodeQuery<Node>);
+
+    let schema = Schema::new(MergedQuery::default(), EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(
+                Request::new(query).variables(Variables::from_value(value!({ "id": post_id })))
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "node": { "__typename": "Post", "id": post_id, "title": "post2" }
+        })
+    );
+    assert!(schema.sdl().contains("node(id: ID!): Node"));
+}
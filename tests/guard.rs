@@ -556,3 +556,261 @@ pub async fn test_guard_use_params() {
         }]
     );
 }
+
+#[tokio::test]
+pub async fn test_default_guard() {
+    #[derive(SimpleObject)]
+    struct MyObj {
+        value: i32,
+        #[graphql(public)]
+        public_value: i32,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn obj(&self) -> MyObj {
+            MyObj {
+                value: 1,
+                public_value: 2,
+            }
+        }
+
+        #[graphql(public)]
+        async fn public_obj(&self) -> MyObj {
+            MyObj {
+                value: 1,
+                public_value: 2,
+            }
+        }
+
+        #[graphql(guard(RoleGuard(role = "Role::Admin")))]
+        async fn admin(&self) -> i32 {
+            3
+        }
+    }
+
+    struct Subscription;
+
+    #[Subscription]
+    impl Subscription {
+        async fn values(&self) -> impl Stream<Item = i32> {
+            futures_util::stream::iter(vec![1, 2, 3])
+        }
+
+        #[graphql(public)]
+        async fn public_values(&self) -> impl Stream<Item = i32> {
+            futures_util::stream::iter(vec![1, 2, 3])
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, Subscription)
+        .default_guard(UserGuard {
+            username: "test".to_string(),
+        })
+        .finish();
+
+    let query = "{ publicObj { publicValue } }";
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({ "publicObj": { "publicValue": 2 } })
+    );
+
+    let query = "{ publicObj { value } }";
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap_err(),
+        vec![ServerError {
+            message: "Forbidden".to_string(),
+            source: None,
+            locations: vec![Pos {
+                line: 1,
+                column: 15
+            }],
+            path: vec![
+                PathSegment::Field("publicObj".to_owned()),
+                PathSegment::Field("value".to_owned())
+            ],
+            extensions: None,
+        }]
+    );
+
+    let query = "{ obj { publicValue } }";
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap_err(),
+        vec![ServerError {
+            message: "Forbidden".to_string(),
+            source: None,
+            locations: vec![Pos { line: 1, column: 3 }],
+            path: vec![PathSegment::Field("obj".to_owned())],
+            extensions: None,
+        }]
+    );
+
+    let query = "{ obj { value publicValue } }";
+    assert_eq!(
+        schema
+            .execute(Request::new(query).data(Username("test".to_string())))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "obj": { "value": 1, "publicValue": 2 } })
+    );
+
+    // The explicit guard replaces the default guard.
+    let query = "{ admin }";
+    assert_eq!(
+        schema
+            .execute(Request::new(query).data(Role::Admin))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "admin": 3 })
+    );
+    assert!(schema
+        .execute(Request::new(query).data(Username("test".to_string())))
+        .await
+        .into_result()
+        .is_err());
+
+    // The fields of the introspection types are not guarded.
+    assert!(schema
+        .execute("{ __typename __schema { queryType { name } } }")
+        .await
+        .into_result()
+        .is_ok());
+
+    assert_eq!(
+        schema
+            .execute_stream("subscription { publicValues }")
+            .map(|resp| resp.into_result().unwrap().data)
+            .collect::<Vec<_>>()
+            .await,
+        vec![
+            value!({ "publicValues": 1 }),
+            value!({ "publicValues": 2 }),
+            value!({ "publicValues": 3 }),
+        ]
+    );
+
+    assert!(schema
+        .execute_stream("subscription { values }")
+        .next()
+        .await
+        .unwrap()
+        .is_err());
+    assert_eq!(
+        schema
+            .execute_stream(
+                Request::new("subscription { values }").data(Username("test".to_string()))
+            )
+            .map(|resp| resp.into_result().unwrap().data)
+            .collect::<Vec<_>>()
+            .await,
+        vec![
+            value!({ "values": 1 }),
+            value!({ "values": 2 }),
+            value!({ "values": 3 }),
+        ]
+    );
+}
+
+#[tokio::test]
+pub async fn test_guard_expression() {
+    #[derive(SimpleObject)]
+    struct MyObj {
+        #[graphql(guard = "RoleGuard { role: Role::Admin }")]
+        value: i32,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn obj(&self) -> MyObj {
+            MyObj { value: 1 }
+        }
+
+        #[graphql(
+            guard = r#"RoleGuard { role: Role::Admin }.or(UserGuard { username: "test".to_string() })"#
+        )]
+        async fn value(&self) -> i32 {
+            2
+        }
+    }
+
+    struct Subscription;
+
+    #[Subscription]
+    impl Subscription {
+        #[graphql(guard = r#"RoleGuard { role: Role::Admin }.and(AgeGuard { age: 10 })"#)]
+        async fn values(&self) -> impl Stream<Item = i32> {
+            futures_util::stream::iter(vec![1, 2, 3])
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, Subscription);
+
+    let query = "{ obj { value } }";
+    assert_eq!(
+        schema
+            .execute(Request::new(query).data(Role::Admin))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "obj": { "value": 1 } })
+    );
+    assert!(schema
+        .execute(Request::new(query).data(Role::Guest))
+        .await
+        .into_result()
+        .is_err());
+
+    let query = "{ value }";
+    assert_eq!(
+        schema
+            .execute(Request::new(query).data(Username("test".to_string())))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "value": 2 })
+    );
+    assert_eq!(
+        schema
+            .execute(Request::new(query).data(Role::Guest))
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message: "Forbidden".to_string(),
+            source: None,
+            locations: vec![Pos { line: 1, column: 3 }],
+            path: vec![PathSegment::Field("value".to_owned())],
+            extensions: None,
+        }]
+    );
+
+    let query = "subscription { values }";
+    assert_eq!(
+        schema
+            .execute_stream(Request::new(query).data(Role::Admin).data(Age(10)))
+            .map(|resp| resp.into_result().unwrap().data)
+            .collect::<Vec<_>>()
+            .await,
+        vec![
+            value!({ "values": 1 }),
+            value!({ "values": 2 }),
+            value!({ "values": 3 }),
+        ]
+    );
+    assert!(schema
+        .execute_stream(Request::new(query).data(Role::Admin))
+        .next()
+        .await
+        .unwrap()
+        .is_err());
+}
@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use async_graphql::extensions::{ComponentStatus, Extension, ExtensionContext, ExtensionFactory};
+use async_graphql::*;
+
+#[tokio::test]
+pub async fn test_health_check() {
+    struct DatabaseImpl;
+
+    #[async_trait::async_trait]
+    impl Extension for DatabaseImpl {
+        async fn health(&self, _ctx: &ExtensionContext<'_>) -> Option<ComponentStatus> {
+            Some(ComponentStatus::new_unhealthy(
+                "database",
+                "connection refused",
+            ))
+        }
+    }
+
+    struct Database;
+
+    impl ExtensionFactory for Database {
+        fn create(&self) -> Arc<dyn Extension> {
+            Arc::new(DatabaseImpl)
+        }
+    }
+
+    struct CacheImpl;
+
+    #[async_trait::async_trait]
+    impl Extension for CacheImpl {
+        async fn health(&self, _ctx: &ExtensionContext<'_>) -> Option<ComponentStatus> {
+            Some(ComponentStatus::new_healthy("cache"))
+        }
+    }
+
+    struct Cache;
+
+    impl ExtensionFactory for Cache {
+        fn create(&self) -> Arc<dyn Extension> {
+            Arc::new(CacheImpl)
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .extension(Database)
+        .extension(Cache)
+        .enable_health_check()
+        .finish();
+
+    let data = schema
+        .execute(
+            r#"{
+                _health { healthy uptime components { name healthy message } }
+                _readiness { healthy schemaHash }
+            }"#,
+        )
+        .await
+        .into_result()
+        .unwrap()
+        .data;
+    let schema_hash = match &data {
+        Value::Object(obj) => match &obj["_readiness"] {
+            Value::Object(obj) => obj["schemaHash"].clone(),
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    };
+    assert!(matches!(&schema_hash, Value::String(s) if s.len() == 16));
+    assert_eq!(
+        data,
+        value!({
+            "_health": {
+                "healthy": true,
+                "uptime": 0,
+                "components": [
+                    { "name": "database", "healthy": false, "message": "connection refused" },
+                    { "name": "cache", "healthy": true, "message": null },
+                ],
+            },
+            "_readiness": {
+                "healthy": false,
+                "schemaHash": schema_hash,
+            }
+        })
+    );
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema.execute("{ _health { healthy } }").await.errors[0].message,
+        r#"Unknown field "_health" on type "Query"."#
+    );
+}
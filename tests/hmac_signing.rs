@@ -0,0 +1,138 @@
+#![cfg(feature = "hmac_signing")]
+
+use async_graphql::connection::*;
+use async_graphql::*;
+
+#[tokio::test]
+pub async fn test_signed_cursors() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn numbers(
+            &self,
+            ctx: &Context<'_>,
+            after: Option<String>,
+            first: Option<i32>,
+        ) -> Result<Connection<usize, i32>> {
+            query(
+                ctx,
+                after,
+                None,
+                first,
+                None,
+                |after, _before, first, _last| async move {
+                    let start = after.map(|after| after + 1).unwrap_or(0);
+                    let end = start + first.unwrap_or(10);
+                    let mut connection = Connection::new(start > 0, true);
+                    connection.append((start..end).map(|n| Edge::new(n, n as i32)));
+                    Ok(connection)
+                },
+            )
+            .await
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .hmac_key("secret")
+        .finish();
+
+    let res = schema
+        .execute("{ numbers(first: 2) { edges { cursor } pageInfo { endCursor } } }")
+        .await
+        .into_result()
+        .unwrap()
+        .data
+        .into_json()
+        .unwrap();
+    let end_cursor = res["numbers"]["pageInfo"]["endCursor"].as_str().unwrap();
+    assert_eq!(res["numbers"]["edges"][1]["cursor"], end_cursor);
+    assert!(end_cursor.starts_with("1."));
+
+    let query = r#"query($after: String) { numbers(after: $after, first: 1) { edges { node } } }"#;
+    let execute = |after: &str| {
+        let request =
+            Request::new(query).variables(Variables::from_value(value!({ "after": after })));
+        let schema = schema.clone();
+        async move { schema.execute(request).await }
+    };
+
+    assert_eq!(
+        execute(end_cursor).await.into_result().unwrap().data,
+        value!({ "numbers": { "edges": [{ "node": 2 }] } })
+    );
+
+    // Forged cursors are rejected.
+    for cursor in ["5", &end_cursor.replacen('1', "5", 1)] {
+        let errors = execute(cursor).await.errors;
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].message, "Invalid signature.");
+        assert_eq!(
+            serde_json::to_value(&errors[0]).unwrap()["extensions"]["code"],
+            "BAD_USER_INPUT"
+        );
+    }
+}
+
+#[tokio::test]
+pub async fn test_signed_global_ids() {
+    #[derive(SimpleObject)]
+    struct User {
+        id: ID,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn user(&self, ctx: &Context<'_>, id: Option<ID>) -> Result<User> {
+            let raw_id = match id {
+                Some(id) => ctx.decode_global_id(&id)?.id().parse()?,
+                None => 1,
+            };
+            Ok(User {
+                id: ctx.encode_global_id("User", raw_id + 1),
+            })
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .hmac_key("secret")
+        .finish();
+
+    let id = schema
+        .execute("{ user { id } }")
+        .await
+        .into_result()
+        .unwrap()
+        .data
+        .into_json()
+        .unwrap()["user"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    assert!(id.starts_with(&format!("{}.", GlobalId::encode("User", 2).0)));
+
+    let query = r#"query($id: ID) { user(id: $id) { id } }"#;
+    let res = schema
+        .execute(Request::new(query).variables(Variables::from_value(value!({ "id": id }))))
+        .await
+        .into_result()
+        .unwrap();
+    let next_id = res.data.into_json().unwrap()["user"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    assert!(next_id.starts_with(&format!("{}.", GlobalId::encode("User", 3).0)));
+
+    let forged = GlobalId::encode("User", 100);
+    let errors = schema
+        .execute(Request::new(query).variables(Variables::from_value(value!({ "id": forged }))))
+        .await
+        .errors;
+    assert_eq!(errors[0].message, "Invalid signature.");
+    assert_eq!(
+        serde_json::to_value(&errors[0]).unwrap()["extensions"]["code"],
+        "BAD_USER_INPUT"
+    );
+}
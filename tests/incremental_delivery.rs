@@ -0,0 +1,192 @@
+use async_graphql::*;
+use futures_util::stream::StreamExt;
+
+#[derive(SimpleObject)]
+struct User {
+    id: i32,
+    name: String,
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self) -> i32 {
+        10
+    }
+
+    async fn user(&self) -> User {
+        User {
+            id: 1,
+            name: "sunli".to_string(),
+        }
+    }
+
+    async fn numbers(&self) -> Vec<i32> {
+        vec![1, 2, 3]
+    }
+
+    async fn fail(&self) -> Result<i32> {
+        Err("Failed".into())
+    }
+}
+
+async fn execute_stream(query: &str) -> Vec<serde_json::Value> {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    schema
+        .execute_stream(query)
+        .map(|resp| serde_json::to_value(&resp).unwrap())
+        .collect()
+        .await
+}
+
+#[tokio::test]
+pub async fn test_defer() {
+    assert_eq!(
+        execute_stream(
+            r#"{
+                value
+                user {
+                    id
+                    ... @defer(label: "name") { name }
+                }
+            }"#
+        )
+        .await,
+        vec![
+            serde_json::json!({
+                "data": { "value": 10, "user": { "id": 1 } },
+                "hasNext": true,
+            }),
+            serde_json::json!({
+                "data": { "name": "sunli" },
+                "path": ["user"],
+                "label": "name",
+                "hasNext": false,
+            }),
+        ]
+    );
+}
+
+#[tokio::test]
+pub async fn test_defer_fragment_spread() {
+    assert_eq!(
+        execute_stream(
+            r#"{
+                value
+                ... UserFragment @defer
+            }
+
+            fragment UserFragment on Query {
+                user { name }
+            }"#
+        )
+        .await,
+        vec![
+            serde_json::json!({
+                "data": { "value": 10 },
+                "hasNext": true,
+            }),
+            serde_json::json!({
+                "data": { "user": { "name": "sunli" } },
+                "hasNext": false,
+            }),
+        ]
+    );
+}
+
+#[tokio::test]
+pub async fn test_defer_disabled() {
+    assert_eq!(
+        execute_stream("{ value ... @defer(if: false) { user { id } } }").await,
+        vec![serde_json::json!({
+            "data": { "value": 10, "user": { "id": 1 } },
+        })]
+    );
+
+    // The directives are ignored when the response is not delivered incrementally.
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ value ... @defer { user { id } } numbers @stream }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "value": 10, "user": { "id": 1 }, "numbers": [1, 2, 3] })
+    );
+}
+
+#[tokio::test]
+pub async fn test_defer_error() {
+    assert_eq!(
+        execute_stream("{ value ... @defer { fail } }").await,
+        vec![
+            serde_json::json!({
+                "data": { "value": 10 },
+                "hasNext": true,
+            }),
+            serde_json::json!({
+                "data": null,
+                "errors": [{
+                    "message": "Failed",
+                    "locations": [{ "line": 1, "column": 22 }],
+                    "path": ["fail"],
+                }],
+                "hasNext": false,
+            }),
+        ]
+    );
+}
+
+#[tokio::test]
+pub async fn test_stream() {
+    let mut payloads = execute_stream(r#"{ numbers @stream(initialCount: 1, label: "numbers") }"#)
+        .await
+        .into_iter();
+    assert_eq!(
+        payloads.next().unwrap(),
+        serde_json::json!({
+            "data": { "numbers": [1] },
+            "hasNext": true,
+        })
+    );
+
+    let mut items = payloads.collect::<Vec<_>>();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[1]["hasNext"], serde_json::json!(false));
+    for item in &mut items {
+        item.as_object_mut().unwrap().remove("hasNext");
+    }
+    items.sort_by_key(|item| item["path"][1].as_u64());
+    assert_eq!(
+        items,
+        vec![
+            serde_json::json!({
+                "data": 2,
+                "path": ["numbers", 1],
+                "label": "numbers",
+            }),
+            serde_json::json!({
+                "data": 3,
+                "path": ["numbers", 2],
+                "label": "numbers",
+            }),
+        ]
+    );
+}
+
+#[tokio::test]
+pub async fn test_stream_invalid_initial_count() {
+    let payloads = execute_stream("{ numbers @stream(initialCount: -1) }").await;
+    assert_eq!(
+        payloads,
+        vec![serde_json::json!({
+            "data": null,
+            "errors": [{
+                "message": "initialCount must be a positive integer.",
+                "locations": [{ "line": 1, "column": 33 }],
+            }],
+        })]
+    );
+}
@@ -473,3 +473,147 @@ pub async fn test_issue_330() {
         })
     );
 }
+
+#[tokio::test]
+pub async fn test_interface_resolve_type() {
+    #[derive(SimpleObject)]
+    struct Remote {
+        kind: String,
+        id: i32,
+    }
+
+    #[derive(SimpleObject)]
+    struct User {
+        kind: String,
+        id: i32,
+    }
+
+    #[derive(Interface)]
+    #[graphql(field(name = "id", type = "&i32"), resolve_type = "Node::resolve_type")]
+    enum Node {
+        Remote(Remote),
+        User(User),
+    }
+
+    impl Node {
+        fn resolve_type(&self) -> Option<String> {
+            match self {
+                Node::Remote(remote) => Some(remote.kind.clone()),
+                _ => None,
+            }
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn nodes(&self) -> Vec<Node> {
+            vec![
+                Remote {
+                    kind: "User".to_string(),
+                    id: 1,
+                }
+                .into(),
+                Remote {
+                    kind: "Remote".to_string(),
+                    id: 2,
+                }
+                .into(),
+            ]
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ nodes { __typename id ... on User { kind } } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "nodes": [
+                { "__typename": "User", "id": 1, "kind": "User" },
+                { "__typename": "Remote", "id": 2 },
+            ]
+        })
+    );
+}
+
+#[tokio::test]
+pub async fn test_interface_dynamic_members() {
+    struct User;
+
+    #[Object]
+    impl User {
+        async fn id(&self) -> i32 {
+            1
+        }
+
+        async fn name(&self) -> &str {
+            "sunli"
+        }
+    }
+
+    #[derive(Interface)]
+    #[graphql(field(name = "id", type = "i32"))]
+    enum Node {
+        User(User),
+        #[graphql(dynamic)]
+        Dynamic(DynObject),
+    }
+
+    // Defined in another crate.
+    struct Post;
+
+    #[Object]
+    impl Post {
+        async fn id(&self) -> i32 {
+            2
+        }
+
+        async fn title(&self) -> &str {
+            "hello"
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn nodes(&self) -> Vec<Node> {
+            vec![User.into(), DynObject::new(Post).into()]
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .register_possible_type::<Node, Post>()
+        .finish();
+    assert_eq!(
+        schema
+            .execute("{ nodes { __typename id ... on User { name } ... on Post { title } } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "nodes": [
+                { "__typename": "User", "id": 1, "name": "sunli" },
+                { "__typename": "Post", "id": 2, "title": "hello" },
+            ]
+        })
+    );
+    assert_eq!(
+        schema
+            .execute(r#"{ __type(name: "Node") { possibleTypes { name } } }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "__type": { "possibleTypes": [{ "name": "User" }, { "name": "Post" }] }
+        })
+    );
+    assert!(schema.sdl().contains("type Post implements Node"));
+}
@@ -1228,7 +1228,17 @@ pub async fn test_disable_introspection() {
         value: i32,
     }
 
-    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+    let disabled_error = |field: &str| {
+        vec![ServerError {
+            message: "Introspection is disabled.".to_string(),
+            source: None,
+            locations: vec![Pos { line: 1, column: 3 }],
+            path: vec![PathSegment::Field(field.to_string())],
+            extensions: None,
+        }]
+    };
+
+    let schema = Schema::build(Query { value: 10 }, EmptyMutation, EmptySubscription)
         .disable_introspection()
         .finish();
     assert_eq!(
@@ -1236,22 +1246,28 @@ pub async fn test_disable_introspection() {
             .execute("{ __type(name: \"Query\") { name } }")
             .await
             .into_result()
-            .unwrap()
-            .data,
-        value!({ "__type": null })
+            .unwrap_err(),
+        disabled_error("__type")
     );
-
     assert_eq!(
         schema
-            .execute(Request::new("{ __type(name: \"Query\") { name } }").disable_introspection())
+            .execute("{ __schema { queryType { name } } }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        disabled_error("__schema")
+    );
+    assert_eq!(
+        schema
+            .execute("{ value __typename }")
             .await
             .into_result()
             .unwrap()
             .data,
-        value!({ "__type": null })
+        value!({ "value": 10, "__typename": "Query" })
     );
 
-    let schema = Schema::build(Query, EmptyMutation, EmptySubscription).finish();
+    let schema = Schema::build(Query { value: 10 }, EmptyMutation, EmptySubscription).finish();
     assert_eq!(
         schema
             .execute("{ __type(name: \"Query\") { name } }")
@@ -1267,8 +1283,210 @@ pub async fn test_disable_introspection() {
             .execute(Request::new("{ __type(name: \"Query\") { name } }").disable_introspection())
             .await
             .into_result()
+            .unwrap_err(),
+        disabled_error("__type")
+    );
+}
+
+#[tokio::test]
+pub async fn test_introspection_only() {
+    #[derive(SimpleObject)]
+    struct Query {
+        value: i32,
+    }
+
+    struct Mutation;
+
+    #[Object]
+    impl Mutation {
+        async fn action(&self) -> bool {
+            true
+        }
+    }
+
+    let only_error = |column, path: Vec<PathSegment>| {
+        vec![ServerError {
+            message: "Only introspection queries are allowed.".to_string(),
+            source: None,
+            locations: vec![Pos { line: 1, column }],
+            path,
+            extensions: None,
+        }]
+    };
+
+    let schema = Schema::build(Query { value: 10 }, Mutation, EmptySubscription)
+        .introspection_only()
+        .finish();
+    assert_eq!(
+        schema
+            .execute("{ __type(name: \"Query\") { name } __typename }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "__type": { "name": "Query" }, "__typename": "Query" })
+    );
+    assert_eq!(
+        schema.execute("{ value }").await.into_result().unwrap_err(),
+        only_error(3, vec![PathSegment::Field("value".to_string())])
+    );
+    assert_eq!(
+        schema
+            .execute("mutation { action }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        only_error(1, Vec::new())
+    );
+
+    let res = schema.check("{ value }");
+    assert!(!res.valid);
+    assert_eq!(
+        res.errors,
+        only_error(3, vec![PathSegment::Field("value".to_string())])
+    );
+    assert!(schema.check("{ __type(name: \"Query\") { name } }").valid);
+
+    let schema = Schema::build(Query { value: 10 }, Mutation, EmptySubscription).finish();
+    assert_eq!(
+        schema
+            .execute(Request::new("{ value }").introspection_only())
+            .await
+            .into_result()
+            .unwrap_err(),
+        only_error(3, vec![PathSegment::Field("value".to_string())])
+    );
+    assert_eq!(
+        schema
+            .execute(Request::new("{ value }"))
+            .await
+            .into_result()
             .unwrap()
             .data,
-        value!({ "__type": null })
+        value!({ "value": 10 })
+    );
+}
+
+#[tokio::test]
+pub async fn test_introspection_type_details() {
+    #[derive(InputObject)]
+    struct MyInput {
+        a: i32,
+        b: Option<Vec<String>>,
+    }
+
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    enum MyEnum {
+        A,
+        #[graphql(deprecation = "use A")]
+        B,
+    }
+
+    #[derive(SimpleObject)]
+    struct Company {
+        id: ID,
+    }
+
+    #[derive(SimpleObject)]
+    #[graphql(visible = false)]
+    struct Hidden {
+        id: ID,
+    }
+
+    #[derive(Interface)]
+    #[graphql(field(name = "id", type = "&ID"))]
+    enum Entity {
+        Company(Company),
+        Hidden(Hidden),
+    }
+
+    #[derive(Interface)]
+    #[graphql(field(name = "id", type = "&ID"))]
+    enum Node {
+        Entity(Entity),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn node(&self, _input: MyInput, _e: MyEnum) -> Node {
+            Entity::Company(Company { id: "1".into() }).into()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let query = r#"{
+        input: __type(name: "MyInput") {
+            kind
+            inputFields {
+                name
+                type { kind name ofType { kind name ofType { kind name ofType { kind name } } } }
+            }
+        }
+        enum: __type(name: "MyEnum") {
+            enumValues { name }
+            all: enumValues(includeDeprecated: true) { name isDeprecated deprecationReason }
+        }
+        node: __type(name: "Node") { kind possibleTypes { name } interfaces { name } }
+        entity: __type(name: "Entity") { kind possibleTypes { name } interfaces { name } }
+        company: __type(name: "Company") { kind possibleTypes { name } interfaces { name } }
+        hidden: __type(name: "Hidden") { name }
+        int: __type(name: "Int") { kind specifiedByURL }
+    }"#;
+
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({
+            "input": {
+                "kind": "INPUT_OBJECT",
+                "inputFields": [
+                    {
+                        "name": "a",
+                        "type": {
+                            "kind": "NON_NULL",
+                            "name": null,
+                            "ofType": { "kind": "SCALAR", "name": "Int", "ofType": null },
+                        },
+                    },
+                    {
+                        "name": "b",
+                        "type": {
+                            "kind": "LIST",
+                            "name": null,
+                            "ofType": {
+                                "kind": "NON_NULL",
+                                "name": null,
+                                "ofType": { "kind": "SCALAR", "name": "String", "ofType": null },
+                            },
+                        },
+                    },
+                ],
+            },
+            "enum": {
+                "enumValues": [{ "name": "A" }],
+                "all": [
+                    { "name": "A", "isDeprecated": false, "deprecationReason": null },
+                    { "name": "B", "isDeprecated": true, "deprecationReason": "use A" },
+                ],
+            },
+            "node": {
+                "kind": "INTERFACE",
+                "possibleTypes": [{ "name": "Company" }],
+                "interfaces": [],
+            },
+            "entity": {
+                "kind": "INTERFACE",
+                "possibleTypes": [{ "name": "Company" }],
+                "interfaces": [{ "name": "Node" }],
+            },
+            "company": {
+                "kind": "OBJECT",
+                "possibleTypes": null,
+                "interfaces": [{ "name": "Entity" }, { "name": "Node" }],
+            },
+            "hidden": null,
+            "int": { "kind": "SCALAR", "specifiedByURL": null },
+        })
     );
 }
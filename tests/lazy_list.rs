@@ -0,0 +1,89 @@
+use async_graphql::*;
+
+#[derive(InputObject)]
+struct Item {
+    id: i32,
+    name: String,
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self) -> i32 {
+        10
+    }
+}
+
+struct Mutation;
+
+#[Object]
+impl Mutation {
+    async fn import(&self, items: LazyList<Item>) -> Result<Vec<String>> {
+        let mut names = Vec::with_capacity(items.len());
+        for item in items {
+            let item = item?;
+            names.push(format!("{}:{}", item.id, item.name));
+        }
+        Ok(names)
+    }
+
+    async fn count(&self, values: LazyList<i32>) -> usize {
+        values.len()
+    }
+}
+
+#[tokio::test]
+pub async fn test_lazy_list() {
+    let schema = Schema::new(Query, Mutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(
+                Request::new("mutation($items: [Item!]!) { import(items: $items) }").variables(
+                    Variables::from_json(serde_json::json!({
+                        "items": [{ "id": 1, "name": "a" }, { "id": 2, "name": "b" }]
+                    }))
+                )
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "import": ["1:a", "2:b"] })
+    );
+
+    // A single value is coerced to a list.
+    assert_eq!(
+        schema
+            .execute("mutation { count(values: 5) }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "count": 1 })
+    );
+}
+
+#[test]
+pub fn test_lazy_list_sdl() {
+    let sdl = Schema::new(Query, Mutation, EmptySubscription).sdl();
+    assert!(sdl.contains("import(items: [Item!]!): [String!]!"));
+    assert!(sdl.contains("count(values: [Int!]!): Int!"));
+}
+
+#[test]
+pub fn test_lazy_list_iter() {
+    let list = <LazyList<i32> as InputType>::parse(Some(value!([1, "2", 3]))).unwrap();
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.to_value(), value!([1, "2", 3]));
+
+    let mut iter = list.into_iter();
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.next().unwrap().unwrap(), 1);
+    assert_eq!(
+        iter.next().unwrap().unwrap_err().message,
+        r#"Expected input type "Int", found "2". (occurred while parsing the item 1 of the list)"#
+    );
+    assert_eq!(iter.next().unwrap().unwrap(), 3);
+    assert!(iter.next().is_none());
+}
@@ -0,0 +1,96 @@
+use std::collections::HashSet;
+
+use async_graphql::*;
+
+#[tokio::test]
+pub async fn test_list_coercion() {
+    #[derive(InputObject)]
+    struct MyInput {
+        values: Vec<i32>,
+        set: HashSet<String>,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn test_arg(&self, input: Vec<i32>) -> Vec<i32> {
+            input
+        }
+
+        async fn test_arg2(&self, input: Option<Vec<i32>>) -> Option<Vec<i32>> {
+            input
+        }
+
+        async fn test_nested(&self, input: Vec<Vec<i32>>) -> Vec<Vec<i32>> {
+            input
+        }
+
+        async fn test_array(&self, input: [i32; 1]) -> [i32; 1] {
+            input
+        }
+
+        async fn test_input(&self, input: MyInput) -> Vec<String> {
+            let mut set = input.set.into_iter().collect::<Vec<_>>();
+            set.sort();
+            input
+                .values
+                .into_iter()
+                .map(|value| value.to_string())
+                .chain(set)
+                .collect()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    let query = r#"{
+        testArg(input: 5)
+        testArg2(input: 5)
+        testArg21: testArg2(input: null)
+        testNested(input: 5)
+        testNested2: testNested(input: [1, 2])
+        testArray(input: 5)
+        testInput(input: { values: 1, set: "a" })
+    }"#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({
+            "testArg": [5],
+            "testArg2": [5],
+            "testArg21": null,
+            "testNested": [[5]],
+            "testNested2": [[1], [2]],
+            "testArray": [5],
+            "testInput": ["1", "a"],
+        })
+    );
+
+    let query = r#"query($a: [Int!]!, $b: [Int!], $c: MyInput!) {
+        testArg(input: $a)
+        testArg2(input: $b)
+        testInput(input: $c)
+    }"#;
+    let request = Request::new(query).variables(Variables::from_value(value!({
+        "a": 5,
+        "b": 6,
+        "c": { "values": 7, "set": "b" },
+    })));
+    assert_eq!(
+        schema.execute(request).await.into_result().unwrap().data,
+        value!({
+            "testArg": [5],
+            "testArg2": [6],
+            "testInput": ["7", "b"],
+        })
+    );
+
+    // A `null` item is not coerced into a list of non-null items.
+    assert!(schema.execute("{ testArg(input: null) }").await.is_err());
+
+    // The coerced list must still have the expected length.
+    assert!(schema
+        .execute("{ testArray2: testArray(input: [1, 2]) }")
+        .await
+        .is_err());
+}
@@ -0,0 +1,143 @@
+use async_graphql::*;
+use futures_util::stream::{self, Stream, StreamExt};
+
+#[derive(SimpleObject)]
+struct User {
+    id: i32,
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn numbers(&self) -> impl Stream<Item = i32> {
+        stream::iter(vec![1, 2, 3])
+    }
+
+    async fn users(&self, count: i32) -> Result<impl Stream<Item = User>> {
+        if count < 0 {
+            return Err("Invalid count".into());
+        }
+        Ok(stream::iter(0..count).map(|id| User { id }))
+    }
+
+    async fn evens(&self, limit: i32) -> impl Stream<Item = i32> {
+        if limit <= 0 {
+            return stream::iter(Vec::new());
+        }
+        stream::iter((0..limit).filter(|n| n % 2 == 0).collect::<Vec<_>>())
+    }
+
+    async fn parsed(&self, values: Vec<String>) -> Result<impl Stream<Item = i32>> {
+        if values.is_empty() {
+            return Ok(stream::iter(vec![0]));
+        }
+        let mut numbers = Vec::new();
+        for value in values {
+            numbers.push(value.parse::<i32>()?);
+        }
+        Ok(stream::iter(numbers))
+    }
+
+    async fn names(&self) -> ListStream<stream::Iter<std::vec::IntoIter<String>>> {
+        ListStream::new(stream::iter(vec!["a".to_string(), "b".to_string()])).concurrency(1)
+    }
+}
+
+#[tokio::test]
+pub async fn test_list_stream() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ numbers users(count: 2) { id } names }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "numbers": [1, 2, 3],
+            "users": [{ "id": 0 }, { "id": 1 }],
+            "names": ["a", "b"],
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute("{ users(count: -1) { id } }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message: "Invalid count".to_string(),
+            source: None,
+            locations: vec![Pos { line: 1, column: 3 }],
+            path: vec![PathSegment::Field("users".to_owned())],
+            extensions: None,
+        }]
+    );
+}
+
+#[test]
+pub fn test_list_stream_sdl() {
+    let sdl = Schema::new(Query, EmptyMutation, EmptySubscription).sdl();
+    assert!(sdl.contains("numbers: [Int!]!"));
+    assert!(sdl.contains("users(count: Int!): [User!]!"));
+    assert!(sdl.contains("names: [String!]!"));
+}
+
+#[tokio::test]
+pub async fn test_list_stream_incremental() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let payloads = schema
+        .execute_stream("{ numbers @stream(initialCount: 2) }")
+        .map(|resp| serde_json::to_value(&resp).unwrap())
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(
+        payloads,
+        vec![
+            serde_json::json!({
+                "data": { "numbers": [1, 2] },
+                "hasNext": true,
+            }),
+            serde_json::json!({
+                "data": 3,
+                "path": ["numbers", 2],
+                "hasNext": false,
+            }),
+        ]
+    );
+}
+
+#[tokio::test]
+pub async fn test_list_stream_limit_list_elements() {
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_list_elements(3)
+        .finish();
+    assert!(schema.execute("{ numbers }").await.is_ok());
+    assert!(schema.execute("{ users(count: 4) { id } }").await.is_err());
+}
+
+#[tokio::test]
+pub async fn test_list_stream_early_return() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(r#"{ a: evens(limit: 0) b: evens(limit: 5) c: parsed(values: []) d: parsed(values: ["1", "2"]) }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "a": [],
+            "b": [0, 2, 4],
+            "c": [0],
+            "d": [1, 2],
+        })
+    );
+    assert!(schema
+        .execute(r#"{ parsed(values: ["a"]) }"#)
+        .await
+        .into_result()
+        .is_err());
+}
@@ -0,0 +1,107 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_graphql::*;
+
+#[tokio::test]
+pub async fn test_memoize() {
+    #[derive(Default, Clone)]
+    struct Counter(Arc<AtomicUsize>);
+
+    #[derive(SimpleObject)]
+    #[graphql(complex)]
+    struct Item {
+        id: i32,
+    }
+
+    #[ComplexObject]
+    impl Item {
+        #[graphql(memoize)]
+        async fn score(&self, ctx: &Context<'_>) -> i32 {
+            ctx.data_unchecked::<Counter>()
+                .0
+                .fetch_add(1, Ordering::SeqCst);
+            self.id * 10
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(memoize)]
+        async fn expensive(&self, ctx: &Context<'_>, x: i32, y: Option<i32>) -> String {
+            ctx.data_unchecked::<Counter>()
+                .0
+                .fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            format!("{}-{:?}", x, y)
+        }
+
+        async fn not_memoized(&self, ctx: &Context<'_>) -> i32 {
+            ctx.data_unchecked::<Counter>()
+                .0
+                .fetch_add(1, Ordering::SeqCst);
+            1
+        }
+
+        async fn items(&self) -> Vec<Item> {
+            vec![Item { id: 1 }, Item { id: 2 }]
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    let counter = Counter::default();
+    let query = r#"query($x: Int!) {
+        a: expensive(x: 1, y: 2)
+        b: expensive(y: 2, x: $x)
+        ... on Query { c: expensive(x: 1, y: 2) }
+        d: expensive(x: 2)
+    }"#;
+    let resp = schema
+        .execute(
+            Request::new(query)
+                .variables(Variables::from_json(serde_json::json!({ "x": 1 })))
+                .data(counter.clone()),
+        )
+        .await;
+    assert_eq!(
+        resp.into_result().unwrap().data,
+        value!({
+            "a": "1-Some(2)",
+            "b": "1-Some(2)",
+            "c": "1-Some(2)",
+            "d": "2-None",
+        })
+    );
+    assert_eq!(counter.0.load(Ordering::SeqCst), 2);
+
+    // the cache only lives for one request
+    let counter = Counter::default();
+    schema
+        .execute(Request::new("{ expensive(x: 1) }").data(counter.clone()))
+        .await;
+    assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+
+    let counter = Counter::default();
+    let resp = schema
+        .execute(
+            Request::new("{ a: notMemoized b: notMemoized items { a: score b: score } }")
+                .data(counter.clone()),
+        )
+        .await;
+    assert_eq!(
+        resp.into_result().unwrap().data,
+        value!({
+            "a": 1,
+            "b": 1,
+            "items": [
+                { "a": 10, "b": 10 },
+                { "a": 20, "b": 20 },
+            ],
+        })
+    );
+    assert_eq!(counter.0.load(Ordering::SeqCst), 4);
+}
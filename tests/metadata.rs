@@ -0,0 +1,184 @@
+use std::sync::{Arc, Mutex};
+
+use async_graphql::extensions::{Extension, ExtensionContext, ExtensionFactory, NextExecute};
+use async_graphql::*;
+
+#[tokio::test]
+pub async fn test_metadata() {
+    #[derive(SimpleObject)]
+    #[graphql(meta(owner = "accounts-team", version = 2))]
+    struct User {
+        #[graphql(meta(pii = true))]
+        email: String,
+        name: String,
+    }
+
+    #[derive(InputObject)]
+    #[graphql(meta(owner = "accounts-team"))]
+    struct UserInput {
+        #[graphql(meta(pii = true))]
+        email: String,
+    }
+
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    #[graphql(meta(owner = "billing-team"))]
+    enum Plan {
+        Free,
+        Pro,
+    }
+
+    struct Query;
+
+    #[Object(meta(owner = "platform-team"))]
+    impl Query {
+        #[graphql(meta(owner = "accounts-team", cost = 1.5))]
+        async fn user(&self, input: UserInput) -> User {
+            User {
+                email: input.email,
+                name: "sunli".to_string(),
+            }
+        }
+
+        async fn plan(&self) -> Plan {
+            Plan::Pro
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    let metadata = schema.type_metadata("User").unwrap();
+    assert_eq!(metadata.get("owner"), Some(&value!("accounts-team")));
+    assert_eq!(metadata.get("version"), Some(&value!(2)));
+    assert_eq!(
+        schema.field_metadata("User", "email").unwrap().get("pii"),
+        Some(&value!(true))
+    );
+    assert!(schema.field_metadata("User", "name").is_none());
+
+    assert_eq!(
+        schema.type_metadata("UserInput").unwrap().get("owner"),
+        Some(&value!("accounts-team"))
+    );
+    assert!(schema
+        .field_metadata("UserInput", "email")
+        .unwrap()
+        .contains_key("pii"));
+
+    assert_eq!(
+        schema.type_metadata("Plan").unwrap().get("owner"),
+        Some(&value!("billing-team"))
+    );
+
+    assert_eq!(
+        schema.type_metadata("Query").unwrap().get("owner"),
+        Some(&value!("platform-team"))
+    );
+    let metadata = schema.field_metadata("Query", "user").unwrap();
+    assert_eq!(
+        metadata.iter().collect::<Vec<_>>(),
+        vec![("owner", &value!("accounts-team")), ("cost", &value!(1.5))]
+    );
+    assert!(schema.field_metadata("Query", "plan").is_none());
+
+    // The metadata is not exported by default.
+    assert!(!schema.sdl().contains("@meta"));
+}
+
+#[tokio::test]
+pub async fn test_metadata_directive() {
+    #[derive(SimpleObject)]
+    #[graphql(meta(owner = "accounts-team"))]
+    struct User {
+        #[graphql(meta(pii = true))]
+        email: String,
+    }
+
+    #[derive(Union)]
+    #[graphql(meta(owner = "accounts-team"))]
+    enum Account {
+        User(User),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn account(&self) -> Account {
+            Account::User(User {
+                email: "sunli@example.com".to_string(),
+            })
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .enable_metadata_directive()
+        .finish();
+    let sdl = schema.sdl();
+    assert!(sdl.contains("type User @meta(owner: \"accounts-team\") {"));
+    assert!(sdl.contains("\temail: String! @meta(pii: true)\n"));
+    assert!(sdl.contains("union Account @meta(owner: \"accounts-team\") = | User"));
+}
+
+#[tokio::test]
+pub async fn test_metadata_in_extension() {
+    struct MyExtensionImpl {
+        owners: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Extension for MyExtensionImpl {
+        async fn execute(
+            &self,
+            ctx: &ExtensionContext<'_>,
+            operation_name: Option<&str>,
+            next: NextExecute<'_>,
+        ) -> Response {
+            if let Some(Value::String(owner)) = ctx
+                .field_metadata("Query", "value")
+                .and_then(|metadata| metadata.get("owner"))
+            {
+                self.owners.lock().unwrap().push(owner.clone());
+            }
+            next.run(ctx, operation_name).await
+        }
+    }
+
+    struct MyExtension {
+        owners: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl ExtensionFactory for MyExtension {
+        fn create(&self) -> Arc<dyn Extension> {
+            Arc::new(MyExtensionImpl {
+                owners: self.owners.clone(),
+            })
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(meta(owner = "payments-team"))]
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    let owners: Arc<Mutex<Vec<String>>> = Default::default();
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .extension(MyExtension {
+            owners: owners.clone(),
+        })
+        .finish();
+    assert_eq!(
+        schema
+            .execute("{ value }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "value": 10 })
+    );
+    assert_eq!(*owners.lock().unwrap(), vec!["payments-team".to_string()]);
+}
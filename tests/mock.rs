@@ -0,0 +1,161 @@
+use async_graphql::mock::MockSchema;
+use async_graphql::*;
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+enum Role {
+    Admin,
+    Guest,
+}
+
+#[derive(SimpleObject)]
+struct User {
+    id: ID,
+    name: String,
+    score: Option<f64>,
+    active: bool,
+    role: Role,
+}
+
+#[derive(SimpleObject)]
+struct Robot {
+    id: ID,
+    model: String,
+}
+
+#[derive(Union)]
+enum Actor {
+    User(User),
+    Robot(Robot),
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn user(&self) -> User {
+        unimplemented!()
+    }
+
+    async fn actors(&self) -> Vec<Actor> {
+        unimplemented!()
+    }
+
+    async fn count(&self) -> i32 {
+        unimplemented!()
+    }
+}
+
+fn mock() -> MockSchema {
+    MockSchema::new(&Schema::new(Query, EmptyMutation, EmptySubscription))
+}
+
+#[test]
+pub fn test_mock_default_values() {
+    assert_eq!(
+        mock()
+            .execute("{ count user { __typename id name score active role } }")
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "count": 42,
+            "user": {
+                "__typename": "User",
+                "id": "1",
+                "name": "Hello World",
+                "score": 4.2,
+                "active": true,
+                "role": "ADMIN",
+            },
+        })
+    );
+}
+
+#[test]
+pub fn test_mock_generators() {
+    let mock = mock()
+        .generator("String", |_| Value::from("mocked"))
+        .generator("User.name", |ctx| {
+            Value::from(format!("{}.{}", ctx.parent_type, ctx.field_name))
+        })
+        .generator("Role", |_| Value::Enum(Name::new("GUEST")));
+    assert_eq!(
+        mock.execute("{ user { name role } actors { ... on Robot { model } } }")
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "user": { "name": "User.name", "role": "GUEST" },
+            "actors": [{}, { "model": "mocked" }],
+        })
+    );
+}
+
+#[test]
+pub fn test_mock_abstract_types() {
+    let query = r#"{
+        actors {
+            __typename
+            ... on User { id name }
+            ...RobotFields
+        }
+    }
+
+    fragment RobotFields on Robot { id model }"#;
+    assert_eq!(
+        mock()
+            .list_length(3)
+            .execute(query)
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "actors": [
+                { "__typename": "User", "id": "1", "name": "Hello World" },
+                { "__typename": "Robot", "id": "2", "model": "Hello World" },
+                { "__typename": "User", "id": "3", "name": "Hello World" },
+            ],
+        })
+    );
+}
+
+#[test]
+pub fn test_mock_directives() {
+    let query = r#"query($withName: Boolean = false) {
+        user {
+            id @skip(if: true)
+            name @include(if: $withName)
+            active
+        }
+    }"#;
+    assert_eq!(
+        mock().execute(query).into_result().unwrap().data,
+        value!({ "user": { "active": true } })
+    );
+    assert_eq!(
+        mock()
+            .execute(Request::new(query).variables(Variables::from_json(
+                serde_json::json!({ "withName": true })
+            )))
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "user": { "name": "Hello World", "active": true } })
+    );
+}
+
+#[test]
+pub fn test_mock_errors() {
+    assert_eq!(
+        mock().execute("{ unknown }").into_result().unwrap_err()[0].message,
+        r#"Unknown field "unknown" on type "Query"."#
+    );
+    assert_eq!(
+        mock()
+            .execute("{ __schema { queryType { name } } }")
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        "Introspection is not supported by the mock schema."
+    );
+}
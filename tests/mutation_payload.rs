@@ -0,0 +1,154 @@
+use async_graphql::*;
+
+#[tokio::test]
+pub async fn test_mutation_payload() {
+    #[derive(SimpleObject)]
+    struct User {
+        name: String,
+    }
+
+    enum CreateUserError {
+        EmptyName,
+        NameTaken(String),
+    }
+
+    impl UserErrorType for CreateUserError {
+        fn user_error(&self) -> UserError {
+            match self {
+                CreateUserError::EmptyName => {
+                    UserError::new("The name is empty.").with_field(["input", "name"])
+                }
+                CreateUserError::NameTaken(name) => {
+                    UserError::new(format!("The name \"{}\" is already taken.", name))
+                        .with_field(["input", "name"])
+                        .with_code("NAME_TAKEN")
+                }
+            }
+        }
+    }
+
+    /// The result of creating a user.
+    #[derive(MutationPayload)]
+    struct CreateUserPayload {
+        user: Option<User>,
+        user_errors: Vec<CreateUserError>,
+    }
+
+    #[derive(MutationPayload)]
+    struct DeleteUserPayload {
+        deleted: bool,
+        #[graphql(user_errors, name = "problems")]
+        errors: Vec<UserError>,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct Mutation;
+
+    #[Object]
+    impl Mutation {
+        async fn create_user(&self, name: String) -> CreateUserPayload {
+            let user_errors = if name.is_empty() {
+                vec![CreateUserError::EmptyName]
+            } else if name == "sunli" {
+                vec![CreateUserError::NameTaken(name.clone())]
+            } else {
+                vec![]
+            };
+            CreateUserPayload {
+                user: if user_errors.is_empty() {
+                    Some(User { name })
+                } else {
+                    None
+                },
+                user_errors,
+            }
+        }
+
+        async fn delete_user(&self) -> DeleteUserPayload {
+            DeleteUserPayload {
+                deleted: false,
+                errors: vec![UserError::new("Not found.").with_code("NOT_FOUND")],
+            }
+        }
+    }
+
+    let schema = Schema::new(Query, Mutation, EmptySubscription);
+
+    let query = r#"mutation {
+        a: createUser(name: "abc") { user { name } userErrors { field message code } }
+        b: createUser(name: "sunli") { user { name } userErrors { field message code } }
+        c: createUser(name: "") { user { name } userErrors { field message } }
+        deleteUser { deleted problems { field message code } }
+    }"#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({
+            "a": {
+                "user": { "name": "abc" },
+                "userErrors": [],
+            },
+            "b": {
+                "user": null,
+                "userErrors": [{
+                    "field": ["input", "name"],
+                    "message": "The name \"sunli\" is already taken.",
+                    "code": "NAME_TAKEN",
+                }],
+            },
+            "c": {
+                "user": null,
+                "userErrors": [{
+                    "field": ["input", "name"],
+                    "message": "The name is empty.",
+                }],
+            },
+            "deleteUser": {
+                "deleted": false,
+                "problems": [{
+                    "field": null,
+                    "message": "Not found.",
+                    "code": "NOT_FOUND",
+                }],
+            },
+        })
+    );
+
+    let query = r#"{
+        __type(name: "CreateUserPayload") {
+            description
+            fields { name type { kind ofType { kind ofType { kind ofType { name } } } } }
+        }
+    }"#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({
+            "__type": {
+                "description": "The result of creating a user.",
+                "fields": [
+                    {
+                        "name": "user",
+                        "type": { "kind": "OBJECT", "ofType": null },
+                    },
+                    {
+                        "name": "userErrors",
+                        "type": {
+                            "kind": "NON_NULL",
+                            "ofType": {
+                                "kind": "LIST",
+                                "ofType": { "kind": "NON_NULL", "ofType": { "name": "UserError" } },
+                            },
+                        },
+                    },
+                ],
+            }
+        })
+    );
+}
@@ -0,0 +1,200 @@
+use async_graphql::*;
+
+#[tokio::test]
+async fn test_number_policy() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn small(&self) -> i64 {
+            -10
+        }
+
+        async fn large(&self) -> i64 {
+            -(1 << 60)
+        }
+
+        async fn large_unsigned(&self) -> Vec<u64> {
+            vec![1, u64::MAX]
+        }
+
+        async fn int32(&self) -> i32 {
+            i32::MAX
+        }
+    }
+
+    let query = "{ small large largeUnsigned int32 }";
+    let execute = |policy| async move {
+        Schema::build(Query, EmptyMutation, EmptySubscription)
+            .number_policy(policy)
+            .finish()
+            .execute(query)
+            .await
+            .into_result()
+            .unwrap()
+            .data
+    };
+
+    assert_eq!(
+        execute(NumberPolicy::Native).await,
+        value!({
+            "small": -10,
+            "large": -1152921504606846976i64,
+            "largeUnsigned": [1, 18446744073709551615u64],
+            "int32": 2147483647,
+        })
+    );
+    assert_eq!(
+        execute(NumberPolicy::String).await,
+        value!({
+            "small": "-10",
+            "large": "-1152921504606846976",
+            "largeUnsigned": ["1", "18446744073709551615"],
+            "int32": 2147483647,
+        })
+    );
+    assert_eq!(
+        execute(NumberPolicy::SafeNumber).await,
+        value!({
+            "small": -10,
+            "large": "-1152921504606846976",
+            "largeUnsigned": [1, "18446744073709551615"],
+            "int32": 2147483647,
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_number_policy_input() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn int32(&self, n: i32) -> i32 {
+            n
+        }
+
+        async fn large(&self, n: i64) -> i64 {
+            n
+        }
+
+        async fn large_unsigned(&self, n: u64) -> u64 {
+            n
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .number_policy(NumberPolicy::String)
+        .finish();
+
+    // The strings returned by the schema are accepted as arguments.
+    let query = r#"query($n: Int!) {
+        a: large(n: "-1152921504606846976")
+        b: large(n: $n)
+        c: largeUnsigned(n: "18446744073709551615")
+    }"#;
+    assert_eq!(
+        schema
+            .execute(Request::new(query).variables(Variables::from_value(value!({ "n": "10" }))))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "a": "-1152921504606846976",
+            "b": "10",
+            "c": "18446744073709551615",
+        })
+    );
+    assert!(schema
+        .execute(r#"{ large(n: "abc") }"#)
+        .await
+        .into_result()
+        .is_err());
+    assert!(schema
+        .execute(r#"{ int32(n: "10") }"#)
+        .await
+        .into_result()
+        .is_err());
+
+    // Strings are only accepted with the policies returning them.
+    assert!(Schema::new(Query, EmptyMutation, EmptySubscription)
+        .execute(r#"{ large(n: "10") }"#)
+        .await
+        .into_result()
+        .is_err());
+}
+
+#[cfg(feature = "decimal")]
+#[tokio::test]
+async fn test_number_policy_decimal() {
+    use std::str::FromStr;
+
+    use rust_decimal::Decimal;
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn values(&self) -> Vec<Decimal> {
+            vec![
+                Decimal::from_str("1.5").unwrap(),
+                Decimal::from_str("12345678901234567890.123").unwrap(),
+            ]
+        }
+    }
+
+    let execute = |policy| async move {
+        Schema::build(Query, EmptyMutation, EmptySubscription)
+            .number_policy(policy)
+            .finish()
+            .execute("{ values }")
+            .await
+            .into_result()
+            .unwrap()
+            .data
+    };
+
+    assert_eq!(
+        execute(NumberPolicy::Native).await,
+        value!({ "values": ["1.5", "12345678901234567890.123"] })
+    );
+    assert_eq!(
+        execute(NumberPolicy::Number).await,
+        value!({ "values": [1.5, 12345678901234567000.0] })
+    );
+    assert_eq!(
+        execute(NumberPolicy::SafeNumber).await,
+        value!({ "values": [1.5, "12345678901234567890.123"] })
+    );
+}
+
+#[cfg(feature = "decimal")]
+#[tokio::test]
+async fn test_number_policy_decimal_input() {
+    use rust_decimal::Decimal;
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self, n: Decimal) -> Decimal {
+            n
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .number_policy(NumberPolicy::Number)
+        .finish();
+
+    // The numbers returned by the schema are accepted as arguments.
+    assert_eq!(
+        schema
+            .execute(r#"{ a: value(n: 1.5) b: value(n: "2.5") }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "a": 1.5, "b": 2.5 })
+    );
+}
@@ -0,0 +1,102 @@
+use async_graphql::*;
+
+struct Obj;
+
+#[Object]
+impl Obj {
+    async fn a(&self) -> i32 {
+        1
+    }
+
+    async fn error(&self) -> Result<i32> {
+        Err("oops".into())
+    }
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self) -> i32 {
+        10
+    }
+
+    async fn obj(&self) -> Option<Obj> {
+        Some(Obj)
+    }
+}
+
+fn oops_error() -> ServerError {
+    ServerError {
+        message: "oops".to_string(),
+        source: None,
+        locations: vec![Pos {
+            line: 1,
+            column: 15,
+        }],
+        path: vec![
+            PathSegment::Field("obj".to_owned()),
+            PathSegment::Field("error".to_owned()),
+        ],
+        extensions: None,
+    }
+}
+
+#[tokio::test]
+pub async fn test_on_error_propagate() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let resp = schema.execute("{ value obj { error } }").await;
+    assert_eq!(resp.data, value!({ "value": 10, "obj": null }));
+    assert_eq!(resp.errors, vec![oops_error()]);
+}
+
+#[tokio::test]
+pub async fn test_on_error_abort() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let resp = schema
+        .execute(Request::new("{ value obj { error } }").on_error(OnError::Abort))
+        .await;
+    assert_eq!(resp.data, Value::Null);
+    assert_eq!(resp.errors, vec![oops_error()]);
+
+    // The request without errors is not affected.
+    assert_eq!(
+        schema
+            .execute(Request::new("{ value obj { a } }").on_error(OnError::Abort))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "value": 10, "obj": { "a": 1 } })
+    );
+}
+
+#[tokio::test]
+pub async fn test_on_error_schema_default() {
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .on_error(OnError::Abort)
+        .finish();
+    let resp = schema.execute("{ value obj { error } }").await;
+    assert_eq!(resp.data, Value::Null);
+    assert_eq!(resp.errors, vec![oops_error()]);
+
+    // The request can override the default of the schema.
+    let resp = schema
+        .execute(Request::new("{ value obj { error } }").on_error(OnError::Propagate))
+        .await;
+    assert_eq!(resp.data, value!({ "value": 10, "obj": null }));
+}
+
+#[tokio::test]
+pub async fn test_on_error_deserialize() {
+    let request: Request =
+        serde_json::from_str(r#"{ "query": "{ value obj { error } }", "onError": "ABORT" }"#)
+            .unwrap();
+    assert_eq!(request.on_error, Some(OnError::Abort));
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(schema.execute(request).await.data, Value::Null);
+
+    let request: Request = serde_json::from_str(r#"{ "query": "{ value }" }"#).unwrap();
+    assert_eq!(request.on_error, None);
+}
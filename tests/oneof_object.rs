@@ -0,0 +1,123 @@
+use async_graphql::*;
+
+#[derive(OneofObject, Debug, PartialEq)]
+enum UserBy {
+    Id(ID),
+    Email(String),
+    #[graphql(name = "login")]
+    Username(String),
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn user(&self, by: UserBy) -> String {
+        format!("{:?}", by)
+    }
+}
+
+#[tokio::test]
+pub async fn test_oneof_object() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    assert_eq!(
+        schema
+            .execute(r#"{ a: user(by: { id: "1" }) b: user(by: { login: "sunli" }) }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "a": r#"Id(ID("1"))"#, "b": r#"Username("sunli")"# })
+    );
+
+    let query = "query($by: UserBy!) { user(by: $by) }";
+    assert_eq!(
+        schema
+            .execute(Request::new(query).variables(Variables::from_json(
+                serde_json::json!({ "by": { "email": "sunli@example.com" } })
+            )))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "user": r#"Email("sunli@example.com")"# })
+    );
+
+    assert_eq!(
+        schema
+            .execute(r#"{ user(by: { id: "1", email: "sunli@example.com" }) }"#)
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        r#"Invalid value for argument "by", oneof input object "UserBy" requires exactly one field"#
+    );
+    assert_eq!(
+        schema
+            .execute(r#"{ user(by: { id: null }) }"#)
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        r#"Invalid value for argument "by", field "id" of oneof input object "UserBy" must not be null"#
+    );
+    assert_eq!(
+        schema
+            .execute(r#"{ user(by: {}) }"#)
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        r#"Invalid value for argument "by", oneof input object "UserBy" requires exactly one field"#
+    );
+    assert_eq!(
+        schema
+            .execute(Request::new(query).variables(Variables::from_json(
+                serde_json::json!({ "by": { "id": "1", "login": "sunli" } })
+            )))
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        r#"Invalid value for argument "by", oneof input object "UserBy" requires exactly one field"#
+    );
+}
+
+#[tokio::test]
+pub async fn test_oneof_object_schema() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert!(schema.sdl().contains(
+        r#"input UserBy @oneOf {
+	id: ID
+	email: String
+	login: String
+}"#
+    ));
+
+    assert_eq!(
+        schema
+            .execute(r#"{ __type(name: "UserBy") { isOneOf } }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "__type": { "isOneOf": true } })
+    );
+
+    let res = schema
+        .execute(
+            r#"{ __type(name: "Query") { isOneOf } __schema { directives { name locations } } }"#,
+        )
+        .await
+        .into_result()
+        .unwrap()
+        .data
+        .into_json()
+        .unwrap();
+    assert_eq!(res["__type"], serde_json::json!({ "isOneOf": null }));
+    assert!(res["__schema"]["directives"]
+        .as_array()
+        .unwrap()
+        .contains(&serde_json::json!({ "name": "oneOf", "locations": ["INPUT_OBJECT"] })));
+}
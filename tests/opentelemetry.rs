@@ -0,0 +1,237 @@
+#![cfg(feature = "opentelemetry")]
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_graphql::dataloader::{DataLoader, Loader};
+use async_graphql::extensions::{OpenTelemetry, OpenTelemetryConfig};
+use async_graphql::*;
+use futures_util::stream::{Stream, StreamExt};
+use opentelemetry::sdk::export::trace::SpanData;
+use opentelemetry::sdk::trace::{Span, SpanProcessor, TracerProvider};
+use opentelemetry::trace::{TraceResult, TracerProvider as _};
+use opentelemetry::Context as OpenTelemetryContext;
+
+#[derive(Debug, Clone, Default)]
+struct Spans(Arc<Mutex<Vec<(String, Vec<String>)>>>);
+
+impl SpanProcessor for Spans {
+    fn on_start(&self, _span: &mut Span, _cx: &OpenTelemetryContext) {}
+
+    fn on_end(&self, span: SpanData) {
+        let events = span
+            .events
+            .iter()
+            .map(|event| event.name.to_string())
+            .collect();
+        self.0
+            .lock()
+            .unwrap()
+            .push((span.name.into_owned(), events));
+    }
+
+    fn force_flush(&self) -> TraceResult<()> {
+        Ok(())
+    }
+
+    fn shutdown(&mut self) -> TraceResult<()> {
+        Ok(())
+    }
+}
+
+impl Spans {
+    fn events(&self, name: &str) -> Vec<String> {
+        self.0
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(span_name, _)| span_name == name)
+            .map(|(_, events)| events.clone())
+            .unwrap_or_default()
+    }
+
+    fn fields(&self) -> Vec<String> {
+        let mut names = self
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(name, _)| name.clone())
+            .filter(|name| name.starts_with("items"))
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+}
+
+struct Item(i32);
+
+#[Object]
+impl Item {
+    async fn value(&self) -> i32 {
+        if self.0 == 1 {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        self.0
+    }
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn items(&self) -> Vec<Item> {
+        (0..4).map(Item).collect()
+    }
+}
+
+async fn execute(config: OpenTelemetryConfig) -> Vec<String> {
+    let spans = Spans::default();
+    let provider = TracerProvider::builder()
+        .with_span_processor(spans.clone())
+        .build();
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .extension(OpenTelemetry::new(provider.tracer("test", None)).config(config))
+        .finish();
+    let resp = schema.execute("{ items { value } }").await;
+    assert_eq!(
+        resp.into_result().unwrap().data,
+        value!({ "items": [{ "value": 0 }, { "value": 1 }, { "value": 2 }, { "value": 3 }] })
+    );
+    spans.fields()
+}
+
+#[tokio::test]
+pub async fn test_opentelemetry_all_spans() {
+    assert_eq!(
+        execute(OpenTelemetryConfig::default()).await,
+        vec![
+            "items",
+            "items.0",
+            "items.0.value",
+            "items.1",
+            "items.1.value",
+            "items.2",
+            "items.2.value",
+            "items.3",
+            "items.3.value"
+        ]
+    );
+}
+
+#[tokio::test]
+pub async fn test_opentelemetry_max_list_items() {
+    assert_eq!(
+        execute(OpenTelemetryConfig::default().max_list_items(2)).await,
+        vec![
+            "items",
+            "items.0",
+            "items.0.value",
+            "items.1",
+            "items.1.value"
+        ]
+    );
+}
+
+#[tokio::test]
+pub async fn test_opentelemetry_min_list_item_duration() {
+    assert_eq!(
+        execute(OpenTelemetryConfig::default().min_list_item_duration(Duration::from_millis(30)))
+            .await,
+        vec!["items", "items.1", "items.1.value"]
+    );
+}
+
+#[tokio::test]
+pub async fn test_opentelemetry_dataloader_events() {
+    struct MyLoader;
+
+    #[async_trait::async_trait]
+    impl Loader<i32> for MyLoader {
+        type Value = i32;
+        type Error = ();
+
+        async fn load(&self, keys: &[i32]) -> Result<HashMap<i32, Self::Value>, Self::Error> {
+            Ok(keys.iter().map(|key| (*key, *key * 10)).collect())
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self, ctx: &Context<'_>) -> Option<i32> {
+            ctx.data_unchecked::<DataLoader<MyLoader>>()
+                .load_one(1)
+                .await
+                .unwrap()
+        }
+    }
+
+    let spans = Spans::default();
+    let provider = TracerProvider::builder()
+        .with_span_processor(spans.clone())
+        .build();
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(DataLoader::new(MyLoader))
+        .extension(OpenTelemetry::new(provider.tracer("test", None)))
+        .finish();
+    assert_eq!(
+        schema
+            .execute("{ value }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "value": 10 })
+    );
+    assert_eq!(
+        spans.events("value"),
+        vec!["dataloader.wait", "dataloader.ready"]
+    );
+}
+
+#[tokio::test]
+pub async fn test_opentelemetry_subscription_events() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct Subscription;
+
+    #[Subscription]
+    impl Subscription {
+        async fn values(&self) -> impl Stream<Item = i32> {
+            futures_util::stream::iter(0..2)
+        }
+    }
+
+    let spans = Spans::default();
+    let provider = TracerProvider::builder()
+        .with_span_processor(spans.clone())
+        .build();
+    let schema = Schema::build(Query, EmptyMutation, Subscription)
+        .extension(OpenTelemetry::new(provider.tracer("test", None)))
+        .finish();
+    let mut stream = schema.execute_stream("subscription { values }");
+    assert!(stream.next().await.is_some());
+    assert_eq!(spans.events("subscribe"), Vec::<String>::new());
+    assert!(stream.next().await.is_some());
+    assert!(stream.next().await.is_none());
+    drop(stream);
+    assert_eq!(
+        spans.events("subscribe"),
+        vec![
+            "subscription.enqueued",
+            "subscription.flushed",
+            "subscription.enqueued",
+            "subscription.flushed"
+        ]
+    );
+}
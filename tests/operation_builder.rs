@@ -0,0 +1,109 @@
+use async_graphql::*;
+
+#[tokio::test]
+pub async fn test_operation_builder() {
+    #[derive(SimpleObject)]
+    struct Address {
+        city: String,
+    }
+
+    #[derive(SimpleObject)]
+    struct User {
+        id: i32,
+        name: String,
+        address: Address,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn user(&self, id: i32) -> User {
+            User {
+                id,
+                name: format!("user{}", id),
+                address: Address {
+                    city: "Beijing".to_string(),
+                },
+            }
+        }
+
+        async fn version(&self) -> &str {
+            "1.0"
+        }
+    }
+
+    struct Mutation;
+
+    #[Object]
+    impl Mutation {
+        async fn add(&self, a: i32, b: i32) -> i32 {
+            a + b
+        }
+    }
+
+    let schema = Schema::new(Query, Mutation, EmptySubscription);
+
+    let builder = schema
+        .query()
+        .field("user", value!({ "id": 10 }))
+        .select([
+            FieldBuilder::new("id"),
+            FieldBuilder::new("name").alias("displayName"),
+            FieldBuilder::new("address").select(["city"]),
+        ])
+        .field("version", Value::Null);
+    assert_eq!(
+        builder.to_query_string(),
+        "query { user(id: 10) { id displayName: name address { city } } version }"
+    );
+    assert_eq!(
+        builder.execute().await.into_result().unwrap().data,
+        value!({
+            "user": {
+                "id": 10,
+                "displayName": "user10",
+                "address": { "city": "Beijing" },
+            },
+            "version": "1.0",
+        })
+    );
+
+    assert_eq!(
+        schema
+            .mutation()
+            .field_with(FieldBuilder::new("add").argument("a", 10).argument("b", 20))
+            .execute()
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "add": 30 })
+    );
+}
+
+#[tokio::test]
+pub async fn test_operation_builder_escape_string() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn echo(&self, s: String) -> String {
+            s
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let s = "a \"quoted\"\nstring";
+    assert_eq!(
+        schema
+            .query()
+            .field("echo", value!({ "s": s }))
+            .execute()
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "echo": s })
+    );
+}
@@ -0,0 +1,243 @@
+use async_graphql::extensions::{OperationPolicy, OperationWhitelist};
+use async_graphql::*;
+
+#[derive(InputObject)]
+struct Page {
+    first: i32,
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn users(&self, first: i32) -> Vec<i32> {
+        (0..first).collect()
+    }
+
+    async fn pages(&self, pages: Vec<Page>) -> i32 {
+        pages.iter().map(|page| page.first).sum()
+    }
+
+    async fn sum(&self, values: Vec<i32>) -> i32 {
+        values.iter().sum()
+    }
+
+    async fn value(&self) -> i32 {
+        10
+    }
+}
+
+fn not_allowed_error(message: &str, pos: Pos) -> ServerError {
+    let mut extensions = ErrorExtensionValues::default();
+    extensions.set("code", "OPERATION_NOT_ALLOWED");
+    ServerError {
+        message: message.to_string(),
+        source: None,
+        locations: vec![pos],
+        path: vec![],
+        extensions: Some(extensions),
+    }
+}
+
+fn schema(whitelist: OperationWhitelist) -> Schema<Query, EmptyMutation, EmptySubscription> {
+    whitelist
+        .register(
+            "listUsers",
+            r#"
+            query List($n: Int = 2) { ...F }
+            fragment F on Query { users(first: $n) }
+            query Literal { users(first: 4) }
+            query Nested($pages: [Page!]!, $values: [Int!]!) {
+                pages(pages: $pages)
+                sum(values: $values)
+            }
+            "#,
+            OperationPolicy::new().max_argument("first", 3),
+        )
+        .unwrap();
+    whitelist
+        .register("getValue", "{ value }", OperationPolicy::new())
+        .unwrap();
+    Schema::build(Query, EmptyMutation, EmptySubscription)
+        .extension(whitelist)
+        .finish()
+}
+
+#[tokio::test]
+pub async fn test_operation_whitelist_directive() {
+    let schema = schema(OperationWhitelist::new());
+
+    assert_eq!(
+        schema
+            .execute(r#"query @operation(id: "getValue") { value }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "value": 10 })
+    );
+
+    assert_eq!(
+        schema.execute("{ value }").await.errors,
+        vec![not_allowed_error(
+            "The operation id is required.",
+            Pos { line: 1, column: 1 }
+        )]
+    );
+
+    assert_eq!(
+        schema
+            .execute(r#"query @operation(id: "unknown") { value }"#)
+            .await
+            .errors,
+        vec![not_allowed_error(
+            r#"Unknown operation id "unknown"."#,
+            Pos { line: 1, column: 7 }
+        )]
+    );
+
+    // The id is read from the executed operation.
+    let query = r#"
+        query A @operation(id: "unknown") { value }
+        query B @operation(id: "getValue") { value }
+    "#;
+    assert!(schema
+        .execute(Request::new(query).operation_name("B"))
+        .await
+        .is_ok());
+    assert!(schema
+        .execute(Request::new(query).operation_name("A"))
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+pub async fn test_operation_whitelist_extension() {
+    let schema = schema(OperationWhitelist::new());
+
+    let mut request = Request::new("{ value }");
+    request
+        .extensions
+        .insert("operationId".to_string(), value!("getValue"));
+    assert!(schema.execute(request).await.is_ok());
+
+    let mut request = Request::new("{ value }");
+    request
+        .extensions
+        .insert("operationId".to_string(), value!(1));
+    assert_eq!(
+        schema.execute(request).await.errors,
+        vec![not_allowed_error(
+            "The operation id must be a string.",
+            Pos { line: 1, column: 1 }
+        )]
+    );
+}
+
+#[tokio::test]
+pub async fn test_operation_whitelist_document() {
+    let schema = schema(OperationWhitelist::new());
+
+    // The formatting and the name of the operation are ignored.
+    assert!(schema
+        .execute(
+            r#"query GetValue @operation(id: "getValue") {
+            value
+        }"#
+        )
+        .await
+        .is_ok());
+
+    assert_eq!(
+        schema
+            .execute(r#"query @operation(id: "getValue") { value users(first: 1) }"#)
+            .await
+            .errors,
+        vec![not_allowed_error(
+            r#"The operation is not the registered operation "getValue"."#,
+            Pos { line: 1, column: 1 }
+        )]
+    );
+    assert!(schema
+        .execute(r#"query @operation(id: "getValue") { users(first: 1) }"#)
+        .await
+        .is_err());
+    assert!(schema
+        .execute(r#"query @operation(id: "getValue") { value @skip(if: true) }"#)
+        .await
+        .is_err());
+
+    // The fragments are compared too.
+    let query = r#"query($n: Int = 2) @operation(id: "listUsers") { ...F }
+        fragment F on Query { users(first: $n) value }"#;
+    assert!(schema.execute(query).await.is_err());
+}
+
+#[tokio::test]
+pub async fn test_operation_whitelist_arguments() {
+    let whitelist = OperationWhitelist::new();
+    let schema = schema(whitelist.clone());
+
+    let query = r#"query($n: Int = 2) @operation(id: "listUsers") { ...F }
+        fragment F on Query { users(first: $n) }"#;
+    assert_eq!(
+        schema
+            .execute(Request::new(query).variables(Variables::from_value(value!({ "n": 3 }))))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "users": [0, 1, 2] })
+    );
+    assert!(schema.execute(query).await.is_ok());
+    assert_eq!(
+        schema
+            .execute(Request::new(query).variables(Variables::from_value(value!({ "n": 5 }))))
+            .await
+            .errors,
+        vec![not_allowed_error(
+            r#"The argument "first" must not be greater than 3 in the operation "listUsers"."#,
+            Pos {
+                line: 2,
+                column: 44
+            }
+        )]
+    );
+
+    // The literal values are checked too.
+    assert!(schema
+        .execute(r#"query @operation(id: "listUsers") { users(first: 4) }"#)
+        .await
+        .is_err());
+
+    // The values in input objects and lists are checked too.
+    let query = r#"query Nested($pages: [Page!]!, $values: [Int!]!) @operation(id: "listUsers") {
+        pages(pages: $pages)
+        sum(values: $values)
+    }"#;
+    let request = |variables| Request::new(query).variables(Variables::from_value(variables));
+    assert_eq!(
+        schema
+            .execute(request(
+                value!({ "pages": [{ "first": 1 }, { "first": 3 }], "values": [10] })
+            ))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "pages": 4, "sum": 10 })
+    );
+    assert!(schema
+        .execute(request(
+            value!({ "pages": [{ "first": 1 }, { "first": 4 }], "values": [10] })
+        ))
+        .await
+        .is_err());
+
+    whitelist.unregister("getValue");
+    assert!(!whitelist.is_registered("getValue"));
+    assert!(schema
+        .execute(r#"query @operation(id: "getValue") { value }"#)
+        .await
+        .is_err());
+}
@@ -0,0 +1,156 @@
+#![cfg(feature = "timeout")]
+
+use std::time::Duration;
+
+use async_graphql::*;
+
+#[tokio::test]
+pub async fn test_partial_results_timeout() {
+    struct Slow;
+
+    #[Object]
+    impl Slow {
+        async fn fast(&self) -> i32 {
+            1
+        }
+
+        async fn slow(&self) -> Option<i32> {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            Some(2)
+        }
+
+        async fn slow_non_null(&self) -> i32 {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            3
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+
+        async fn obj(&self) -> Option<Slow> {
+            Some(Slow)
+        }
+
+        async fn list(&self) -> Vec<Option<Slow>> {
+            vec![Some(Slow), Some(Slow)]
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .partial_results_timeout(Duration::from_millis(100))
+        .finish();
+
+    let resp = schema
+        .execute("{ value obj { fast slow } list { fast slowNonNull } }")
+        .await;
+    assert_eq!(
+        resp.data,
+        value!({
+            "value": 10,
+            "obj": { "fast": 1, "slow": null },
+            "list": [null, null],
+        })
+    );
+    let errors = resp
+        .errors
+        .iter()
+        .map(|err| {
+            let err = serde_json::to_value(err).unwrap();
+            (
+                err["path"].clone(),
+                err["message"].clone(),
+                err["extensions"]["code"].clone(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        errors,
+        vec![
+            (
+                serde_json::json!(["obj", "slow"]),
+                serde_json::json!("Execution timed out."),
+                serde_json::json!("TIMEOUT"),
+            ),
+            (
+                serde_json::json!(["list", 0, "slowNonNull"]),
+                serde_json::json!("Execution timed out."),
+                serde_json::json!("TIMEOUT"),
+            ),
+            (
+                serde_json::json!(["list", 1, "slowNonNull"]),
+                serde_json::json!("Execution timed out."),
+                serde_json::json!("TIMEOUT"),
+            ),
+        ]
+    );
+
+    // The timeout of the request overrides the timeout of the schema.
+    let resp = schema
+        .execute(Request::new("{ obj { slow } }").partial_results_timeout(Duration::from_secs(10)))
+        .await;
+    assert_eq!(resp.data, value!({ "obj": { "slow": 2 } }));
+    assert!(resp.errors.is_empty());
+}
+
+#[tokio::test]
+pub async fn test_partial_results_timeout_of_subtree() {
+    // Keeps resolving a field after its subtree is resolved.
+    struct Linger;
+
+    #[async_trait::async_trait]
+    impl CustomDirective for Linger {
+        fn name(&self) -> &'static str {
+            "linger"
+        }
+
+        async fn resolve_field(
+            &self,
+            ctx: &Context<'_>,
+            _args: &DirectiveArgs,
+            next: NextDirective<'_>,
+        ) -> ServerResult<Option<Value>> {
+            let value = next.run(ctx).await?;
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            Ok(value)
+        }
+    }
+
+    struct Slow;
+
+    #[Object]
+    impl Slow {
+        async fn slow(&self) -> Option<i32> {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            Some(2)
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn obj(&self) -> Option<Slow> {
+            Some(Slow)
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .directive(Linger)
+        .partial_results_timeout(Duration::from_millis(100))
+        .finish();
+
+    let resp = schema.execute("{ obj @linger { slow } }").await;
+    assert_eq!(resp.data, value!({ "obj": null }));
+    assert_eq!(resp.errors.len(), 1);
+    assert_eq!(
+        resp.errors[0].path,
+        vec![PathSegment::Field("obj".to_string())]
+    );
+    assert_eq!(resp.errors[0].message, "Execution timed out.");
+}
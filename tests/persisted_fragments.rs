@@ -0,0 +1,108 @@
+use async_graphql::extensions::PersistedFragments;
+use async_graphql::*;
+
+#[derive(SimpleObject)]
+struct User {
+    id: i32,
+    name: String,
+    friends: Vec<User>,
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn user(&self) -> User {
+        User {
+            id: 1,
+            name: "Alice".to_string(),
+            friends: vec![User {
+                id: 2,
+                name: "Bob".to_string(),
+                friends: vec![],
+            }],
+        }
+    }
+}
+
+fn schema(fragments: PersistedFragments) -> Schema<Query, EmptyMutation, EmptySubscription> {
+    Schema::build(Query, EmptyMutation, EmptySubscription)
+        .extension(fragments)
+        .finish()
+}
+
+#[tokio::test]
+pub async fn test_persisted_fragments() {
+    let fragments = PersistedFragments::new();
+    fragments
+        .register(
+            r#"
+            fragment UserId on User { id }
+            fragment UserFields on User { ...UserId name friends { ...UserId } }
+            "#,
+        )
+        .unwrap();
+    assert!(fragments.contains("UserFields"));
+    let schema = schema(fragments.clone());
+
+    // The fragments spread by the registered fragments are added too.
+    assert_eq!(
+        schema
+            .execute("{ user { ...UserFields } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "user": { "id": 1, "name": "Alice", "friends": [{ "id": 2 }] } })
+    );
+
+    // The fragments of the query are preferred.
+    assert_eq!(
+        schema
+            .execute("{ user { ...UserFields } } fragment UserId on User { name }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "user": { "name": "Alice", "friends": [{ "name": "Bob" }] } })
+    );
+
+    // The fragments spread by the fragments of the query are added.
+    assert_eq!(
+        schema
+            .execute("{ user { ...Fields } } fragment Fields on User { ...UserId }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "user": { "id": 1 } })
+    );
+
+    fragments.unregister("UserFields");
+    assert!(!fragments.contains("UserFields"));
+    assert_eq!(
+        schema
+            .execute("{ user { ...UserFields } }")
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        r#"Unknown fragment: "UserFields""#
+    );
+}
+
+#[tokio::test]
+pub async fn test_persisted_fragments_validation() {
+    let fragments = PersistedFragments::new();
+    fragments
+        .register("fragment QueryFields on Query { unknown }")
+        .unwrap();
+    let schema = schema(fragments.clone());
+
+    // The persisted fragments are validated with the query.
+    assert!(schema.execute("{ ...QueryFields }").await.is_err());
+    // The unused ones are not added.
+    assert!(schema.execute("{ user { id } }").await.is_ok());
+
+    assert!(fragments.register("fragment Invalid on User {").is_err());
+}
@@ -0,0 +1,218 @@
+use std::sync::{Arc, Mutex};
+
+use async_graphql::pubsub::{Broker, PubSub, SimpleBroker, SubscriptionBroker};
+use async_graphql::*;
+use futures_util::stream::{BoxStream, Stream, StreamExt};
+use futures_util::FutureExt;
+
+struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn value(&self) -> i32 {
+        10
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+struct Order {
+    merchant_id: i32,
+    amount: i32,
+}
+
+struct SubscriptionRoot;
+
+#[Subscription]
+impl SubscriptionRoot {
+    #[graphql(topic = "orders:{merchant_id}")]
+    async fn orders(&self, merchant_id: i32) -> impl Stream<Item = Order> {}
+
+    #[graphql(topic = "orders:{merchant_id}:{{{ status }}}")]
+    async fn orders_with_status(
+        &self,
+        ctx: &Context<'_>,
+        merchant_id: i32,
+        status: String,
+    ) -> Result<impl Stream<Item = Order>> {
+    }
+}
+
+#[tokio::test]
+pub async fn test_topic_subscription() {
+    let pubsub = PubSub::default();
+    let schema = Schema::build(QueryRoot, EmptyMutation, SubscriptionRoot)
+        .data(pubsub.clone())
+        .finish();
+
+    let mut stream1 =
+        schema.execute_stream("subscription { orders(merchantId: 1) { merchantId amount } }");
+    let mut stream2 =
+        schema.execute_stream("subscription { orders(merchantId: 2) { merchantId amount } }");
+    assert!(stream1.next().now_or_never().is_none());
+    assert!(stream2.next().now_or_never().is_none());
+    assert_eq!(pubsub.subscriber_count::<Order>("orders:1"), 1);
+
+    assert_eq!(
+        pubsub.publish(
+            "orders:1",
+            Order {
+                merchant_id: 1,
+                amount: 100
+            }
+        ),
+        1
+    );
+    assert_eq!(
+        stream1.next().await.unwrap().into_result().unwrap().data,
+        value!({ "orders": { "merchantId": 1, "amount": 100 } })
+    );
+    assert!(stream2.next().now_or_never().is_none());
+
+    drop(stream1);
+    assert_eq!(pubsub.subscriber_count::<Order>("orders:1"), 0);
+    assert_eq!(pubsub.subscriber_count::<Order>("orders:2"), 1);
+}
+
+#[tokio::test]
+pub async fn test_topic_placeholders() {
+    let pubsub = PubSub::default();
+    let schema = Schema::build(QueryRoot, EmptyMutation, SubscriptionRoot)
+        .data(pubsub.clone())
+        .finish();
+
+    let mut stream = schema.execute_stream(
+        r#"subscription { ordersWithStatus(merchantId: 1, status: "paid") { amount } }"#,
+    );
+    assert!(stream.next().now_or_never().is_none());
+    assert_eq!(pubsub.subscriber_count::<Order>("orders:1:{paid}"), 1);
+}
+
+#[tokio::test]
+pub async fn test_topic_without_pubsub() {
+    let schema = Schema::new(QueryRoot, EmptyMutation, SubscriptionRoot);
+    let resp = schema
+        .execute_stream("subscription { orders(merchantId: 1) { amount } }")
+        .next()
+        .await
+        .unwrap();
+    assert_eq!(
+        resp.errors,
+        vec![ServerError {
+            message: "Data `async_graphql::pubsub::PubSub` does not exist.".to_string(),
+            source: None,
+            locations: vec![Pos {
+                line: 1,
+                column: 16
+            }],
+            path: vec![PathSegment::Field("orders".to_owned())],
+            extensions: None,
+        }]
+    );
+}
+
+#[derive(SimpleObject, serde::Serialize, serde::Deserialize)]
+struct Payment {
+    merchant_id: i32,
+    currency: String,
+    amount: i32,
+}
+
+struct BrokerSubscriptionRoot;
+
+#[Subscription]
+impl BrokerSubscriptionRoot {
+    async fn payments(
+        &self,
+        ctx: &Context<'_>,
+        merchant_id: i32,
+        currency: String,
+    ) -> Result<impl Stream<Item = Payment>> {
+        ctx.data_unchecked::<Broker>()
+            .subscribe_keyed("payments", &(merchant_id, currency))
+            .await
+    }
+}
+
+/// A backend which records the published topics.
+#[derive(Clone, Default)]
+struct RecordingBroker {
+    inner: SimpleBroker,
+    published: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait::async_trait]
+impl SubscriptionBroker for RecordingBroker {
+    async fn publish(&self, topic: &str, payload: String) -> Result<()> {
+        self.published.lock().unwrap().push(topic.to_string());
+        self.inner.publish(topic, payload).await
+    }
+
+    async fn subscribe(&self, topic: &str) -> Result<BoxStream<'static, String>> {
+        self.inner.subscribe(topic).await
+    }
+}
+
+#[tokio::test]
+pub async fn test_broker_subscription() {
+    let backend = RecordingBroker::default();
+    let broker = Broker::new(backend.clone());
+    let schema = Schema::build(QueryRoot, EmptyMutation, BrokerSubscriptionRoot)
+        .data(broker.clone())
+        .finish();
+
+    let mut stream = schema.execute_stream(
+        r#"subscription { payments(merchantId: 1, currency: "EUR") { merchantId currency amount } }"#,
+    );
+    assert!(stream.next().now_or_never().is_none());
+
+    for (merchant_id, currency, amount) in [(1, "USD", 10), (2, "EUR", 20), (1, "EUR", 30)] {
+        broker
+            .publish_keyed(
+                "payments",
+                &(merchant_id, currency),
+                &Payment {
+                    merchant_id,
+                    currency: currency.to_string(),
+                    amount,
+                },
+            )
+            .await
+            .unwrap();
+    }
+    // A message of another type is skipped.
+    broker
+        .publish_keyed("payments", &(1, "EUR"), "refund")
+        .await
+        .unwrap();
+    broker
+        .publish_keyed(
+            "payments",
+            &(1, "EUR"),
+            &Payment {
+                merchant_id: 1,
+                currency: "EUR".to_string(),
+                amount: 40,
+            },
+        )
+        .await
+        .unwrap();
+
+    for amount in [30, 40] {
+        assert_eq!(
+            stream.next().await.unwrap().into_result().unwrap().data,
+            value!({ "payments": { "merchantId": 1, "currency": "EUR", "amount": amount } })
+        );
+    }
+    assert!(stream.next().now_or_never().is_none());
+
+    assert_eq!(
+        *backend.published.lock().unwrap(),
+        vec![
+            r#"payments:[1,"USD"]"#,
+            r#"payments:[2,"EUR"]"#,
+            r#"payments:[1,"EUR"]"#,
+            r#"payments:[1,"EUR"]"#,
+            r#"payments:[1,"EUR"]"#,
+        ]
+    );
+}
@@ -136,3 +136,73 @@ pub async fn test_subscription() {
         value!({"CREATE_OBJECT": 100})
     );
 }
+
+#[tokio::test]
+pub async fn test_rename_unknown_fields() {
+    #[derive(SimpleObject)]
+    #[graphql(rename_fields = "snake_case")]
+    struct User {
+        user_id: i32,
+        display_name: String,
+    }
+
+    struct Query;
+
+    #[Object(rename_fields = "snake_case")]
+    impl Query {
+        async fn current_user(&self) -> User {
+            User {
+                user_id: 1,
+                display_name: "sunli".to_string(),
+            }
+        }
+    }
+
+    fn to_snake_case(name: &str) -> String {
+        let mut s = String::new();
+        for c in name.chars() {
+            if c.is_ascii_uppercase() {
+                s.push('_');
+            }
+            s.push(c.to_ascii_lowercase());
+        }
+        s
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .rename_unknown_fields(to_snake_case)
+        .finish();
+
+    let query = r#"{
+        currentUser {
+            __typename
+            userId
+            name: displayName
+            ... on User { display_name }
+            ...UserFields
+        }
+    }
+
+    fragment UserFields on User { id: userId }"#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({
+            "currentUser": {
+                "__typename": "User",
+                "userId": 1,
+                "name": "sunli",
+                "display_name": "sunli",
+                "id": 1,
+            }
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute("{ currentUser { unknownField } }")
+            .await
+            .errors[0]
+            .message,
+        r#"Unknown field "unknownField" on type "User"."#
+    );
+}
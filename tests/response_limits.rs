@@ -0,0 +1,121 @@
+use async_graphql::*;
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn values(&self, count: i32) -> Vec<i32> {
+        (0..count).collect()
+    }
+
+    async fn nested(&self) -> Option<Vec<Vec<i32>>> {
+        Some(vec![vec![1, 2], vec![3, 4]])
+    }
+
+    async fn text(&self) -> &str {
+        "abcdefghij"
+    }
+
+    async fn long_text(&self) -> String {
+        "a".repeat(100)
+    }
+
+    async fn pending(&self) -> i32 {
+        futures_util::future::pending().await
+    }
+}
+
+fn too_large_error(message: &str) -> ServerError {
+    let mut extensions = ErrorExtensionValues::default();
+    extensions.set("code", "RESPONSE_TOO_LARGE");
+    ServerError {
+        message: message.to_string(),
+        source: None,
+        locations: Vec::new(),
+        path: Vec::new(),
+        extensions: Some(extensions),
+    }
+}
+
+#[tokio::test]
+pub async fn test_limit_list_elements() {
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_list_elements(6)
+        .finish();
+
+    assert_eq!(
+        schema
+            .execute("{ values(count: 5) }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "values": [0, 1, 2, 3, 4] })
+    );
+
+    // The nested lists count 2 + 2 + 2 elements.
+    assert_eq!(
+        schema
+            .execute("{ nested }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "nested": [[1, 2], [3, 4]] })
+    );
+
+    let resp = schema
+        .execute("{ a: values(count: 4) b: values(count: 4) }")
+        .await;
+    assert_eq!(resp.data, Value::Null);
+    assert_eq!(
+        resp.errors,
+        vec![too_large_error(
+            "The response contains more than 6 list elements."
+        )]
+    );
+
+    // The error is not hidden by a nullable field.
+    let resp = schema.execute("{ values(count: 5) nested }").await;
+    assert_eq!(resp.data, Value::Null);
+    assert_eq!(resp.errors.len(), 1);
+}
+
+#[tokio::test]
+pub async fn test_limit_response_size() {
+    // {"data":{"text":"abcdefghij"}}
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_response_size(30)
+        .finish();
+    assert_eq!(
+        schema.execute("{ text }").await.into_result().unwrap().data,
+        value!({ "text": "abcdefghij" })
+    );
+
+    let resp = schema.execute("{ text a: text }").await;
+    assert_eq!(resp.data, Value::Null);
+    assert_eq!(
+        resp.errors,
+        vec![too_large_error("The response is larger than 30 bytes.")]
+    );
+}
+
+#[tokio::test]
+pub async fn test_limit_response_size_aborts_execution() {
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_response_size(30)
+        .finish();
+
+    // The execution is aborted without waiting for the other fields.
+    let resp = tokio::time::timeout(
+        std::time::Duration::from_secs(5),
+        schema.execute("{ longText pending }"),
+    )
+    .await
+    .unwrap();
+    assert_eq!(resp.data, Value::Null);
+    assert_eq!(
+        resp.errors,
+        vec![too_large_error("The response is larger than 30 bytes.")]
+    );
+}
@@ -50,6 +50,9 @@ pub async fn test_fieldresult() {
                     extensions: None,
                 },
             ],
+            path: Default::default(),
+            label: None,
+            has_next: None,
             http_headers: Default::default(),
         }
     );
@@ -204,6 +207,9 @@ pub async fn test_error_propagation() {
                 ],
                 extensions: None,
             }],
+            path: Default::default(),
+            label: None,
+            has_next: None,
             http_headers: Default::default(),
         }
     );
@@ -232,6 +238,9 @@ pub async fn test_error_propagation() {
                 ],
                 extensions: None,
             }],
+            path: Default::default(),
+            label: None,
+            has_next: None,
             http_headers: Default::default(),
         }
     );
@@ -256,6 +265,9 @@ pub async fn test_error_propagation() {
                 ],
                 extensions: None,
             }],
+            path: Default::default(),
+            label: None,
+            has_next: None,
             http_headers: Default::default(),
         }
     );
@@ -286,6 +298,9 @@ pub async fn test_error_propagation() {
                 ],
                 extensions: None,
             }],
+            path: Default::default(),
+            label: None,
+            has_next: None,
             http_headers: Default::default(),
         }
     );
@@ -1,4 +1,6 @@
+use async_graphql::parser::types::OperationType;
 use async_graphql::*;
+use futures_util::stream::{Stream, StreamExt};
 
 #[tokio::test]
 pub async fn test_schema_default() {
@@ -42,3 +44,222 @@ pub async fn test_http_headers() {
     let resp = schema.execute("{ err }").await;
     assert_eq!(resp.http_headers.get("A").map(|s| &**s), Some("1"));
 }
+
+#[tokio::test]
+pub async fn test_schema_hash() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct Query2;
+
+    #[Object(name = "Query")]
+    impl Query2 {
+        async fn value(&self) -> String {
+            "10".to_string()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let schema_hash = schema.schema_hash().to_string();
+    assert_eq!(schema_hash.len(), 16);
+    assert_eq!(
+        Schema::new(Query, EmptyMutation, EmptySubscription).schema_hash(),
+        schema_hash
+    );
+    assert_ne!(
+        Schema::new(Query2, EmptyMutation, EmptySubscription).schema_hash(),
+        schema_hash
+    );
+    assert!(schema.execute("{ value }").await.extensions.is_empty());
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .enable_schema_hash_field()
+        .enable_schema_hash_extension()
+        .finish();
+    assert_eq!(schema.schema_hash(), schema_hash);
+
+    let resp = schema.execute("{ value _schemaHash }").await;
+    assert_eq!(
+        resp.data,
+        value!({ "value": 10, "_schemaHash": schema_hash.clone() })
+    );
+    assert_eq!(
+        resp.extensions.get("schemaHash"),
+        Some(&Value::String(schema_hash.clone()))
+    );
+
+    let resp = schema.execute("{ unknown }").await;
+    assert!(resp.is_err());
+    assert_eq!(
+        resp.extensions.get("schemaHash"),
+        Some(&Value::String(schema_hash))
+    );
+}
+
+#[tokio::test]
+pub async fn test_schema_hash_with_interfaces() {
+    #[derive(SimpleObject)]
+    struct MyObj {
+        id: i32,
+    }
+
+    #[derive(Interface)]
+    #[graphql(field(name = "id", type = "&i32"))]
+    enum Charlie {
+        MyObj(MyObj),
+    }
+
+    #[derive(Interface)]
+    #[graphql(field(name = "id", type = "&i32"))]
+    enum Alpha {
+        MyObj(MyObj),
+    }
+
+    #[derive(Interface)]
+    #[graphql(field(name = "id", type = "&i32"))]
+    enum Bravo {
+        MyObj(MyObj),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn charlie(&self) -> Charlie {
+            MyObj { id: 1 }.into()
+        }
+
+        async fn alpha(&self) -> Alpha {
+            MyObj { id: 1 }.into()
+        }
+
+        async fn bravo(&self) -> Bravo {
+            MyObj { id: 1 }.into()
+        }
+    }
+
+    // The order of the interfaces does not depend on the hash of a set.
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert!(schema
+        .sdl()
+        .contains("type MyObj implements Alpha & Bravo & Charlie {"));
+    for _ in 0..10 {
+        assert_eq!(
+            Schema::new(Query, EmptyMutation, EmptySubscription).schema_hash(),
+            schema.schema_hash()
+        );
+    }
+}
+
+#[tokio::test]
+pub async fn test_schema_check() {
+    #[derive(SimpleObject)]
+    struct MyObj {
+        value: i32,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+
+        async fn obj(&self) -> MyObj {
+            MyObj { value: 10 }
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_depth(2)
+        .finish();
+
+    let res = schema.check("{ value obj { value } }");
+    assert!(res.valid);
+    assert_eq!(res.depth, 2);
+    assert_eq!(res.complexity, 3);
+    assert!(res.errors.is_empty());
+
+    let res = schema.check("{ value obj { value }");
+    assert!(!res.valid);
+    assert_eq!(res.depth, 0);
+    assert_eq!(res.errors.len(), 1);
+
+    let res = schema.check("{ value obj { unknown } }");
+    assert!(!res.valid);
+    assert_eq!(
+        res.errors[0].message,
+        r#"Unknown field "unknown" on type "MyObj"."#
+    );
+
+    let res = schema.check("query A { obj { value } } query B { value }");
+    assert!(!res.valid);
+    assert_eq!(res.errors[0].message, "Operation name required in request.");
+    assert!(
+        schema
+            .check(Request::new("query A { obj { value } } query B { value }").operation_name("B"))
+            .valid
+    );
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_depth(1)
+        .finish();
+    let res = schema.check("{ value obj { value } }");
+    assert!(!res.valid);
+    assert_eq!(res.depth, 2);
+    assert_eq!(res.errors[0].message, "Query is nested too deep.");
+}
+
+#[tokio::test]
+pub async fn test_operation_data() {
+    struct Pool(&'static str);
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn pool(&self, ctx: &Context<'_>) -> &str {
+            ctx.data_unchecked::<Pool>().0
+        }
+    }
+
+    struct Subscription;
+
+    #[Subscription]
+    impl Subscription {
+        async fn pool(&self, ctx: &Context<'_>) -> impl Stream<Item = &'static str> {
+            futures_util::stream::once(futures_util::future::ready(ctx.data_unchecked::<Pool>().0))
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, Subscription)
+        .data(Pool("primary"))
+        .operation_data(OperationType::Subscription, Pool("replica"))
+        .finish();
+    assert_eq!(
+        schema.execute("{ pool }").await.data,
+        value!({ "pool": "primary" })
+    );
+    assert_eq!(
+        schema
+            .execute(Request::new("{ pool }").data(Pool("request")))
+            .await
+            .data,
+        value!({ "pool": "request" })
+    );
+    assert_eq!(
+        schema
+            .execute_stream("subscription { pool }")
+            .map(|resp| resp.data)
+            .collect::<Vec<_>>()
+            .await,
+        vec![value!({ "pool": "replica" })]
+    );
+}
@@ -0,0 +1,137 @@
+use async_graphql::*;
+
+#[derive(SimpleObject)]
+struct Post {
+    title: String,
+}
+
+struct PostsQuery;
+
+#[Object]
+impl PostsQuery {
+    async fn posts(&self, ctx: &Context<'_>) -> Vec<Post> {
+        ctx.data_unchecked::<Vec<String>>()
+            .iter()
+            .map(|title| Post {
+                title: title.clone(),
+            })
+            .collect()
+    }
+}
+
+struct PostsMutation;
+
+#[Object]
+impl PostsMutation {
+    async fn create_post(&self, title: String) -> Post {
+        Post { title }
+    }
+}
+
+#[derive(SimpleObject)]
+struct Comment {
+    text: String,
+}
+
+fn posts_module() -> SchemaModule {
+    SchemaModule::new("posts")
+        .query(PostsQuery)
+        .mutation(PostsMutation)
+        .register_type::<Comment>()
+        .data(vec!["a".to_string(), "b".to_string()])
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn version(&self) -> i32 {
+        1
+    }
+}
+
+#[tokio::test]
+pub async fn test_schema_module() {
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .module(posts_module())
+        .finish();
+
+    assert_eq!(
+        schema
+            .execute("{ version posts { title } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "version": 1,
+            "posts": [{ "title": "a" }, { "title": "b" }],
+        })
+    );
+    assert_eq!(
+        schema
+            .execute(r#"mutation { __typename createPost(title: "c") { title } }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "__typename": "Mutation",
+            "createPost": { "title": "c" },
+        })
+    );
+
+    let sdl = schema.sdl();
+    assert!(sdl.contains("type Comment"));
+    assert!(!sdl.contains("PostsQuery"));
+    assert!(!sdl.contains("PostsMutation"));
+}
+
+#[test]
+#[should_panic(expected = "The module `posts` defines the field `Query.posts`")]
+pub fn test_schema_module_duplicate_field() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn posts(&self) -> i32 {
+            1
+        }
+    }
+
+    Schema::build(Query, EmptyMutation, EmptySubscription).module(posts_module());
+}
+
+#[tokio::test]
+pub async fn test_schema_module_referenced_type() {
+    struct ViewerQuery;
+
+    #[Object]
+    impl ViewerQuery {
+        async fn name(&self) -> &str {
+            "sunli"
+        }
+
+        async fn viewer(&self) -> ViewerQuery {
+            ViewerQuery
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .module(SchemaModule::new("viewer").query(ViewerQuery))
+        .finish();
+
+    assert_eq!(
+        schema
+            .execute("{ name viewer { name viewer { name } } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "name": "sunli",
+            "viewer": { "name": "sunli", "viewer": { "name": "sunli" } },
+        })
+    );
+    assert!(schema.sdl().contains("type ViewerQuery"));
+}
@@ -0,0 +1,59 @@
+use async_graphql::guard::Scopes;
+use async_graphql::*;
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn orders(&self, ctx: &Context<'_>) -> Result<Vec<i32>> {
+        ctx.require_scope("orders:read")?;
+        Ok(vec![1, 2])
+    }
+}
+
+#[tokio::test]
+pub async fn test_require_scope() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    let request =
+        Request::new("{ orders }").data(Scopes::from_space_separated("users:read  orders:read"));
+    assert_eq!(
+        schema.execute(request).await.into_result().unwrap().data,
+        value!({ "orders": [1, 2] })
+    );
+
+    let mut extensions = ErrorExtensionValues::default();
+    extensions.set("code", "UNAUTHORIZED");
+    extensions.set("scope", "orders:read");
+    let expected = vec![ServerError {
+        message: r#"The scope "orders:read" is required."#.to_string(),
+        source: None,
+        locations: vec![Pos { line: 1, column: 3 }],
+        path: vec![PathSegment::Field("orders".to_string())],
+        extensions: Some(extensions),
+    }];
+
+    let request = Request::new("{ orders }").data(Scopes::from_iter(["users:read"]));
+    assert_eq!(
+        schema.execute(request).await.into_result().unwrap_err(),
+        expected
+    );
+    // A request without scopes is not granted any scope.
+    assert_eq!(
+        schema
+            .execute("{ orders }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        expected
+    );
+}
+
+#[test]
+pub fn test_scopes() {
+    let mut scopes = Scopes::from_space_separated("b a");
+    scopes.insert("c");
+    assert!(scopes.contains("a"));
+    assert!(!scopes.contains("d"));
+    assert_eq!(scopes.iter().collect::<Vec<_>>(), vec!["a", "b", "c"]);
+}
@@ -0,0 +1,97 @@
+use async_graphql::*;
+
+#[tokio::test]
+pub async fn test_tuple_struct() {
+    /// A point on a plane.
+    #[derive(SimpleObject)]
+    struct Point(
+        #[graphql(name = "x")] f64,
+        #[graphql(name = "y")] f64,
+        #[graphql(skip)] i32,
+        /// The name of the point.
+        #[graphql(name = "pointName")]
+        String,
+        #[graphql(name = "type")] i32,
+    );
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn point(&self) -> Point {
+            Point(1.0, 2.5, 0, "a".to_string(), 1)
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ point { x y pointName type } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "point": { "x": 1.0, "y": 2.5, "pointName": "a", "type": 1 }
+        })
+    );
+    assert_eq!(
+        schema
+            .execute(r#"{ __type(name: "Point") { description fields { name description } } }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "__type": {
+                "description": "A point on a plane.",
+                "fields": [
+                    { "name": "x", "description": null },
+                    { "name": "y", "description": null },
+                    { "name": "pointName", "description": "The name of the point." },
+                    { "name": "type", "description": null },
+                ],
+            }
+        })
+    );
+}
+
+#[tokio::test]
+pub async fn test_unit_struct() {
+    #[derive(SimpleObject)]
+    #[graphql(complex)]
+    struct Version;
+
+    #[ComplexObject]
+    impl Version {
+        async fn major(&self) -> i32 {
+            1
+        }
+
+        async fn minor(&self) -> i32 {
+            2
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn version(&self) -> Version {
+            Version
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ version { major minor } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "version": { "major": 1, "minor": 2 }
+        })
+    );
+}
@@ -0,0 +1,127 @@
+#![cfg(feature = "unblock")]
+
+use std::thread;
+
+use async_graphql::*;
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self, ctx: &Context<'_>) -> Result<String> {
+        let current = thread::current().id();
+        ctx.spawn_blocking(move |ctx| {
+            assert_ne!(thread::current().id(), current);
+            Ok(format!(
+                "{} {}",
+                ctx.data::<i32>()?,
+                ctx.data_unchecked::<String>()
+            ))
+        })
+        .await
+    }
+
+    async fn missing(&self, ctx: &Context<'_>) -> Result<bool> {
+        ctx.spawn_blocking(|ctx| ctx.data::<u64>().map(|_| true))
+            .await
+    }
+}
+
+#[tokio::test]
+pub async fn test_spawn_blocking() {
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(10i32)
+        .data("schema".to_string())
+        .finish();
+
+    assert_eq!(
+        schema
+            .execute("{ value }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "value": "10 schema" })
+    );
+    // The data of the request takes priority over the data of the schema.
+    assert_eq!(
+        schema
+            .execute(Request::new("{ value }").data("request".to_string()))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "value": "10 request" })
+    );
+    assert_eq!(
+        schema
+            .execute("{ missing }")
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        "Data `u64` does not exist."
+    );
+}
+
+#[tokio::test]
+pub async fn test_blocking_attribute() {
+    #[derive(SimpleObject, Clone)]
+    #[graphql(complex)]
+    struct Numbers {
+        base: i32,
+    }
+
+    #[ComplexObject]
+    impl Numbers {
+        #[graphql(blocking)]
+        fn multiplied(&self, ctx: &BlockingContext, by: i32) -> Result<i32> {
+            Ok(self.base * by * ctx.data::<i32>()?)
+        }
+    }
+
+    #[derive(Clone)]
+    struct Query {
+        main_thread: thread::ThreadId,
+    }
+
+    #[Object]
+    impl Query {
+        #[graphql(blocking)]
+        fn sum(&self, mut values: Vec<i32>) -> i32 {
+            assert_ne!(thread::current().id(), self.main_thread);
+            values.sort_unstable();
+            values.iter().sum()
+        }
+
+        #[graphql(blocking)]
+        fn data(&self, ctx: &BlockingContext) -> String {
+            ctx.data_unchecked::<String>().clone()
+        }
+
+        async fn numbers(&self) -> Numbers {
+            Numbers { base: 2 }
+        }
+    }
+
+    let schema = Schema::build(
+        Query {
+            main_thread: thread::current().id(),
+        },
+        EmptyMutation,
+        EmptySubscription,
+    )
+    .data(10i32)
+    .data("schema".to_string())
+    .finish();
+
+    assert_eq!(
+        schema
+            .execute("{ sum(values: [3, 1, 2]) data numbers { multiplied(by: 3) } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "sum": 6, "data": "schema", "numbers": { "multiplied": 60 } })
+    );
+}
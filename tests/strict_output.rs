@@ -0,0 +1,88 @@
+#![cfg(feature = "strict_output")]
+
+use async_graphql::*;
+
+struct BadScalar;
+
+#[Scalar]
+impl ScalarType for BadScalar {
+    fn parse(_value: Value) -> InputValueResult<Self> {
+        Ok(BadScalar)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Null
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+enum MyEnum {
+    A,
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self) -> i32 {
+        10
+    }
+
+    async fn optional(&self) -> Option<i32> {
+        None
+    }
+
+    async fn list(&self) -> Vec<Option<MyEnum>> {
+        vec![Some(MyEnum::A), None]
+    }
+
+    async fn bad(&self) -> BadScalar {
+        BadScalar
+    }
+
+    async fn bad_optional(&self) -> Option<BadScalar> {
+        Some(BadScalar)
+    }
+
+    async fn bad_list(&self) -> Vec<BadScalar> {
+        vec![BadScalar]
+    }
+}
+
+#[tokio::test]
+pub async fn test_strict_output_valid() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ value optional list badOptional __typename }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "value": 10,
+            "optional": null,
+            "list": ["A", null],
+            "badOptional": null,
+            "__typename": "Query",
+        })
+    );
+}
+
+#[tokio::test]
+#[should_panic(
+    expected = r#"Field "bad" of type "BadScalar!" resolved to an invalid value null: expected non-null type "BadScalar!""#
+)]
+pub async fn test_strict_output_null() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    schema.execute("{ bad }").await;
+}
+
+#[tokio::test]
+#[should_panic(
+    expected = r#"Field "badList" of type "[BadScalar!]!" resolved to an invalid value [null]: at index 0, expected non-null type "BadScalar!""#
+)]
+pub async fn test_strict_output_list() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    schema.execute("{ badList }").await;
+}
@@ -399,6 +399,9 @@ pub async fn test_subscription_fieldresult() {
                 path: vec![PathSegment::Field("values".to_owned())],
                 extensions: None,
             }],
+            path: Vec::new(),
+            label: None,
+            has_next: None,
             http_headers: Default::default()
         },
         stream.next().await.unwrap(),
@@ -406,3 +409,56 @@ pub async fn test_subscription_fieldresult() {
 
     assert!(stream.next().await.is_none());
 }
+
+#[tokio::test]
+pub async fn test_execute_stream_query_and_mutation() {
+    use std::sync::Arc;
+
+    use async_graphql::extensions::{Extension, ExtensionContext, ExtensionFactory, NextExecute};
+
+    struct MyExtensionImpl;
+
+    #[async_trait::async_trait]
+    impl Extension for MyExtensionImpl {
+        async fn execute(
+            &self,
+            ctx: &ExtensionContext<'_>,
+            operation_name: Option<&str>,
+            next: NextExecute<'_>,
+        ) -> Response {
+            next.run(ctx, operation_name)
+                .await
+                .extension("executed", Value::Boolean(true))
+        }
+    }
+
+    struct MyExtension;
+
+    impl ExtensionFactory for MyExtension {
+        fn create(&self) -> Arc<dyn Extension> {
+            Arc::new(MyExtensionImpl)
+        }
+    }
+
+    struct MutationRoot;
+
+    #[Object]
+    impl MutationRoot {
+        async fn add(&self, a: i32, b: i32) -> i32 {
+            a + b
+        }
+    }
+
+    let schema = Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+        .extension(MyExtension)
+        .finish();
+
+    for query in ["{ value }", "mutation { add(a: 1, b: 2) }"] {
+        let responses = schema.execute_stream(query).collect::<Vec<_>>().await;
+        assert_eq!(responses, vec![schema.execute(query).await]);
+        assert_eq!(
+            responses[0].extensions.get("executed"),
+            Some(&Value::Boolean(true))
+        );
+    }
+}
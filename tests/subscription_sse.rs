@@ -0,0 +1,68 @@
+use async_graphql::http::create_sse_stream;
+use async_graphql::*;
+use futures_util::stream::{Stream, StreamExt};
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self) -> i32 {
+        10
+    }
+}
+
+struct Subscription;
+
+#[Subscription]
+impl Subscription {
+    async fn values(&self, count: i32) -> Result<impl Stream<Item = i32>> {
+        if count < 0 {
+            return Err("Invalid count".into());
+        }
+        Ok(futures_util::stream::iter(0..count))
+    }
+}
+
+#[tokio::test]
+pub async fn test_sse_subscription() {
+    let schema = Schema::new(Query, EmptyMutation, Subscription);
+    let events = create_sse_stream(&schema, "subscription { values(count: 2) }")
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(
+        events,
+        vec![
+            "event: next\ndata: {\"data\":{\"values\":0}}\n\n",
+            "event: next\ndata: {\"data\":{\"values\":1}}\n\n",
+            "event: complete\ndata:\n\n",
+        ]
+    );
+}
+
+#[tokio::test]
+pub async fn test_sse_query() {
+    let schema = Schema::new(Query, EmptyMutation, Subscription);
+    let events = create_sse_stream(&schema, "{ value }")
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(
+        events,
+        vec![
+            "event: next\ndata: {\"data\":{\"value\":10}}\n\n",
+            "event: complete\ndata:\n\n",
+        ]
+    );
+}
+
+#[tokio::test]
+pub async fn test_sse_error() {
+    let schema = Schema::new(Query, EmptyMutation, Subscription);
+    let events = create_sse_stream(&schema, "subscription { values(count: -1) }")
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(events.len(), 2);
+    assert!(events[0].starts_with(
+        "event: next\ndata: {\"data\":null,\"errors\":[{\"message\":\"Invalid count\""
+    ));
+    assert_eq!(events[1], "event: complete\ndata:\n\n");
+}
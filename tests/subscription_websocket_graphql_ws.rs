@@ -313,7 +313,7 @@ pub async fn test_subscription_init_error() {
     .unwrap();
 
     assert_eq!(
-        (1002, "Error!".to_string()),
+        (4403, "Error!".to_string()),
         dbg!(stream.next().await.unwrap()).unwrap_close()
     );
 }
@@ -678,3 +678,251 @@ pub async fn test_ping_pong() {
             .is_err()
     );
 }
+
+#[tokio::test]
+pub async fn test_subscriber_already_exists() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct SubscriptionRoot;
+
+    #[Subscription]
+    impl SubscriptionRoot {
+        async fn values(&self) -> impl Stream<Item = i32> {
+            futures_util::stream::pending()
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, SubscriptionRoot);
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(schema, rx, WebSocketProtocols::GraphQLWS);
+
+    tx.send(
+        serde_json::to_string(&value!({
+            "type": "connection_init",
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+            .unwrap(),
+        serde_json::json!({
+            "type": "connection_ack",
+        }),
+    );
+
+    for _ in 0..2 {
+        tx.send(
+            serde_json::to_string(&value!({
+                "type": "subscribe",
+                "id": "1",
+                "payload": {
+                    "query": "subscription { values }"
+                },
+            }))
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+    }
+
+    assert_eq!(
+        stream.next().await.unwrap().unwrap_close(),
+        (4409, "Subscriber for 1 already exists".to_string())
+    );
+}
+
+#[tokio::test]
+pub async fn test_invalid_message() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(schema, rx, WebSocketProtocols::GraphQLWS);
+
+    tx.send(r#"{"type": "unknown"}"#.to_string()).await.unwrap();
+    assert_eq!(stream.next().await.unwrap().unwrap_close().0, 4400);
+}
+
+#[tokio::test]
+pub async fn test_subscription_init_authentication() {
+    struct Token(String);
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct SubscriptionRoot;
+
+    #[Subscription]
+    impl SubscriptionRoot {
+        async fn token(&self, ctx: &Context<'_>) -> impl Stream<Item = String> {
+            futures_util::stream::once(futures_util::future::ready(
+                ctx.data_unchecked::<Token>().0.clone(),
+            ))
+        }
+    }
+
+    let create_stream = || {
+        let schema = Schema::new(QueryRoot, EmptyMutation, SubscriptionRoot);
+        let (tx, rx) = mpsc::unbounded();
+        let stream = http::WebSocket::with_data(
+            schema,
+            rx,
+            |payload: serde_json::Value| async move {
+                match payload.get("token").and_then(|token| token.as_str()) {
+                    Some(token) => {
+                        let mut data = Data::default();
+                        data.insert(Token(token.to_string()));
+                        Ok(data)
+                    }
+                    None => Err("Forbidden".into()),
+                }
+            },
+            WebSocketProtocols::GraphQLWS,
+        );
+        (tx, stream)
+    };
+
+    let (mut tx, mut stream) = create_stream();
+    tx.send(
+        serde_json::to_string(&value!({
+            "type": "connection_init",
+            "payload": { "token": "123456" }
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        Some(value!({ "type": "connection_ack" })),
+        serde_json::from_str(&stream.next().await.unwrap().unwrap_text()).unwrap()
+    );
+    tx.send(
+        serde_json::to_string(&value!({
+            "type": "subscribe",
+            "id": "1",
+            "payload": { "query": "subscription { token }" },
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        Some(value!({
+            "type": "next",
+            "id": "1",
+            "payload": { "data": { "token": "123456" } },
+        })),
+        serde_json::from_str(&stream.next().await.unwrap().unwrap_text()).unwrap()
+    );
+
+    let (mut tx, mut stream) = create_stream();
+    tx.send(serde_json::to_string(&value!({ "type": "connection_init" })).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(
+        (4403, "Forbidden".to_string()),
+        stream.next().await.unwrap().unwrap_close()
+    );
+}
+
+#[cfg(feature = "timeout")]
+#[tokio::test]
+pub async fn test_subscription_keepalive_and_idle_timeout() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct SubscriptionRoot;
+
+    #[Subscription]
+    impl SubscriptionRoot {
+        async fn events(&self) -> impl Stream<Item = i32> {
+            futures_util::stream::once(async move {
+                tokio::time::sleep(Duration::from_millis(250)).await;
+                10
+            })
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, SubscriptionRoot);
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(schema, rx, WebSocketProtocols::GraphQLWS)
+        .keepalive_interval(Duration::from_millis(100))
+        .idle_timeout(Duration::from_millis(250));
+
+    tx.send(serde_json::to_string(&value!({ "type": "connection_init" })).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(
+        Some(value!({ "type": "connection_ack" })),
+        serde_json::from_str(&stream.next().await.unwrap().unwrap_text()).unwrap()
+    );
+    tx.send(
+        serde_json::to_string(&value!({
+            "type": "subscribe",
+            "id": "1",
+            "payload": { "query": "subscription { events }" },
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    // The connection is not idle while the subscription is active.
+    for _ in 0..2 {
+        assert_eq!(
+            Some(value!({ "type": "ping" })),
+            serde_json::from_str(&stream.next().await.unwrap().unwrap_text()).unwrap()
+        );
+    }
+    assert_eq!(
+        Some(value!({ "type": "next", "id": "1", "payload": { "data": { "events": 10 } } })),
+        serde_json::from_str(&stream.next().await.unwrap().unwrap_text()).unwrap()
+    );
+    assert_eq!(
+        Some(value!({ "type": "complete", "id": "1" })),
+        serde_json::from_str(&stream.next().await.unwrap().unwrap_text()).unwrap()
+    );
+
+    // The connection is closed after the idle timeout without active subscriptions.
+    let mut messages = Vec::new();
+    loop {
+        match stream.next().await.unwrap() {
+            http::WsMessage::Close(code, reason) => {
+                assert_eq!((code, reason.as_str()), (1000, "Idle timeout."));
+                break;
+            }
+            http::WsMessage::Text(text) => messages.push(text),
+        }
+    }
+    assert!(!messages.is_empty());
+    assert!(messages.iter().all(|text| text == r#"{"type":"ping"}"#));
+}
@@ -425,3 +425,46 @@ pub async fn test_start_before_connection_init() {
         (1011, "The handshake is not completed.".to_string())
     );
 }
+
+#[cfg(feature = "timeout")]
+#[tokio::test]
+pub async fn test_keepalive() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn value(&self) -> i32 {
+            999
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(schema, rx, WebSocketProtocols::SubscriptionsTransportWS)
+        .keepalive_interval(std::time::Duration::from_millis(50));
+
+    tx.send(
+        serde_json::to_string(&value!({
+            "type": "connection_init",
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        Some(value!({
+        "type": "connection_ack",
+        })),
+        serde_json::from_str(&stream.next().await.unwrap().unwrap_text()).unwrap()
+    );
+
+    for _ in 0..2 {
+        assert_eq!(
+            Some(value!({
+            "type": "ka",
+            })),
+            serde_json::from_str(&stream.next().await.unwrap().unwrap_text()).unwrap()
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use async_graphql::http::WebSocketProtocols;
+use async_graphql::test::{WsClient, WsEvent};
+use async_graphql::*;
+use futures_util::stream::{self, Stream};
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self) -> i32 {
+        10
+    }
+}
+
+struct Token(String);
+
+struct Subscription;
+
+#[Subscription]
+impl Subscription {
+    async fn values(&self, count: i32) -> impl Stream<Item = i32> {
+        stream::iter(0..count)
+    }
+
+    async fn token(&self, ctx: &Context<'_>) -> impl Stream<Item = String> {
+        stream::iter(Some(ctx.data_unchecked::<Token>().0.clone()))
+    }
+
+    async fn forever(&self) -> impl Stream<Item = i32> {
+        stream::pending()
+    }
+}
+
+const PROTOCOLS: [WebSocketProtocols; 2] = [
+    WebSocketProtocols::SubscriptionsTransportWS,
+    WebSocketProtocols::GraphQLWS,
+];
+
+#[tokio::test]
+pub async fn test_ws_client_subscribe() {
+    for protocol in PROTOCOLS {
+        let mut client = WsClient::new(Schema::new(Query, EmptyMutation, Subscription), protocol);
+        client.init(None);
+        assert_eq!(client.next_event().await, Some(WsEvent::ConnectionAck));
+
+        client.subscribe(
+            "1",
+            Request::new("subscription($count: Int!) { values(count: $count) }")
+                .variables(Variables::from_json(serde_json::json!({ "count": 2 }))),
+        );
+        assert_eq!(
+            client.next_payloads("1").await,
+            vec![
+                serde_json::json!({ "data": { "values": 0 } }),
+                serde_json::json!({ "data": { "values": 1 } }),
+            ]
+        );
+
+        client.subscribe("2", "{ value }");
+        assert_eq!(
+            client.next_payloads("2").await,
+            vec![serde_json::json!({ "data": { "value": 10 } })]
+        );
+
+        client.subscribe("3", "subscription { forever }");
+        client.stop("3");
+        assert_eq!(
+            client.next_event().await,
+            Some(WsEvent::Complete {
+                id: "3".to_string()
+            })
+        );
+
+        client.ping();
+        assert_eq!(client.next_event().await, Some(WsEvent::Pong));
+
+        client.close();
+        assert_eq!(client.next_event().await, None);
+    }
+}
+
+#[tokio::test]
+pub async fn test_ws_client_with_data() {
+    for protocol in PROTOCOLS {
+        let mut client = WsClient::with_data(
+            Schema::new(Query, EmptyMutation, Subscription),
+            protocol,
+            |payload| async move {
+                let token = payload["token"].as_str().ok_or("Missing token")?;
+                let mut data = Data::default();
+                data.insert(Token(token.to_string()));
+                Ok(data)
+            },
+        );
+        client.init(Some(serde_json::json!({ "token": "123456" })));
+        assert_eq!(client.next_event().await, Some(WsEvent::ConnectionAck));
+
+        client.subscribe("1", "subscription { token }");
+        assert_eq!(
+            client.next_payloads("1").await,
+            vec![serde_json::json!({ "data": { "token": "123456" } })]
+        );
+    }
+}
+
+#[tokio::test]
+pub async fn test_ws_client_connection_error() {
+    let init = |_| async move { Err::<Data, _>("Missing token".into()) };
+
+    let mut client = WsClient::with_data(
+        Schema::new(Query, EmptyMutation, Subscription),
+        WebSocketProtocols::SubscriptionsTransportWS,
+        init,
+    );
+    client.init(None);
+    assert_eq!(
+        client.next_event().await,
+        Some(WsEvent::ConnectionError {
+            payload: serde_json::json!({ "message": "Missing token" }),
+        })
+    );
+
+    let mut client = WsClient::with_data(
+        Schema::new(Query, EmptyMutation, Subscription),
+        WebSocketProtocols::GraphQLWS,
+        init,
+    );
+    client.init(None);
+    assert_eq!(
+        client.next_event().await,
+        Some(WsEvent::Close {
+            code: 4403,
+            reason: "Missing token".to_string(),
+        })
+    );
+}
@@ -367,3 +367,128 @@ pub async fn test_union_flatten() {
         })
     );
 }
+
+#[tokio::test]
+pub async fn test_union_resolve_type() {
+    #[derive(SimpleObject)]
+    struct Dog {
+        name: String,
+    }
+
+    #[derive(SimpleObject)]
+    struct Cat {
+        name: String,
+    }
+
+    // Values are always decoded as `Dog` and the real type is decided from the data.
+    #[derive(Union)]
+    #[graphql(resolve_type = "Pet::resolve_type")]
+    enum Pet {
+        Dog(Dog),
+        Cat(Cat),
+    }
+
+    impl Pet {
+        fn resolve_type(&self) -> Option<&'static str> {
+            match self {
+                Pet::Dog(dog) if dog.name.starts_with("cat:") => Some("Cat"),
+                _ => None,
+            }
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn pets(&self) -> Vec<Pet> {
+            vec![
+                Dog {
+                    name: "cat:tom".to_string(),
+                }
+                .into(),
+                Dog {
+                    name: "rex".to_string(),
+                }
+                .into(),
+            ]
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let query = r#"
+    {
+        pets {
+            __typename
+            ... on Cat {
+                name
+            }
+        }
+    }"#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({
+            "pets": [
+                { "__typename": "Cat", "name": "cat:tom" },
+                { "__typename": "Dog" },
+            ]
+        })
+    );
+}
+
+#[tokio::test]
+pub async fn test_union_dynamic_members() {
+    struct Circle;
+
+    #[Object]
+    impl Circle {
+        async fn radius(&self) -> i32 {
+            1
+        }
+    }
+
+    #[derive(Union)]
+    enum Shape {
+        Circle(Circle),
+        #[graphql(dynamic)]
+        Dynamic(DynObject),
+    }
+
+    // Defined in another crate.
+    struct Square;
+
+    #[Object]
+    impl Square {
+        async fn side(&self) -> i32 {
+            2
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn shapes(&self) -> Vec<Shape> {
+            vec![Circle.into(), DynObject::new(Square).into()]
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .register_possible_type::<Shape, Square>()
+        .finish();
+    assert_eq!(
+        schema
+            .execute("{ shapes { __typename ... on Circle { radius } ... on Square { side } } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "shapes": [
+                { "__typename": "Circle", "radius": 1 },
+                { "__typename": "Square", "side": 2 },
+            ]
+        })
+    );
+    assert!(schema.sdl().contains("union Shape = | Circle | Square"));
+}
@@ -1,4 +1,4 @@
-use std::{fmt, vec};
+use std::{fmt, slice, vec};
 
 use indexmap::IndexMap;
 
@@ -501,8 +501,383 @@ impl<'de> de::Deserializer<'de> for NameDeserializer {
     }
 }
 
+impl<'de> de::Deserializer<'de> for &'de ConstValue {
+    type Error = DeserializerError;
+
+    #[inline]
+    fn deserialize_any<V>(self, visitor: V) -> Result<<V as Visitor<'de>>::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            ConstValue::Null => visitor.visit_unit(),
+            ConstValue::Number(v) => v
+                .deserialize_any(visitor)
+                .map_err(|err| DeserializerError(err.to_string())),
+            ConstValue::String(v) => visitor.visit_borrowed_str(v),
+            ConstValue::Boolean(v) => visitor.visit_bool(*v),
+            ConstValue::Binary(bytes) => visitor.visit_borrowed_bytes(bytes),
+            ConstValue::Enum(v) => visitor.visit_borrowed_str(v.as_str()),
+            ConstValue::List(v) => {
+                let len = v.len();
+                let mut deserializer = SeqRefDeserializer { iter: v.iter() };
+                let seq = visitor.visit_seq(&mut deserializer)?;
+                if deserializer.iter.len() == 0 {
+                    Ok(seq)
+                } else {
+                    Err(DeserializerError::invalid_length(
+                        len,
+                        &"fewer elements in array",
+                    ))
+                }
+            }
+            ConstValue::Object(v) => {
+                let len = v.len();
+                let mut deserializer = MapRefDeserializer {
+                    iter: v.iter(),
+                    value: None,
+                };
+                let map = visitor.visit_map(&mut deserializer)?;
+                if deserializer.iter.len() == 0 {
+                    Ok(map)
+                } else {
+                    Err(DeserializerError::invalid_length(
+                        len,
+                        &"fewer elements in map",
+                    ))
+                }
+            }
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+
+    #[inline]
+    fn deserialize_option<V>(self, visitor: V) -> Result<<V as Visitor<'de>>::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            ConstValue::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<<V as Visitor<'de>>::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<<V as Visitor<'de>>::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let (variant, value) = match self {
+            ConstValue::Object(value) if value.len() == 1 => {
+                let (variant, value) = value.iter().next().unwrap();
+                (variant.as_str(), Some(value))
+            }
+            ConstValue::Object(_) => {
+                return Err(serde::de::Error::invalid_value(
+                    Unexpected::Map,
+                    &"map with a single key",
+                ));
+            }
+            ConstValue::String(variant) => (variant.as_str(), None),
+            ConstValue::Enum(variant) => (variant.as_str(), None),
+            other => {
+                return Err(DeserializerError::invalid_type(
+                    other.unexpected(),
+                    &"string or map",
+                ));
+            }
+        };
+
+        visitor.visit_enum(EnumRefDeserializer { variant, value })
+    }
+
+    #[inline]
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+struct EnumRefDeserializer<'de> {
+    variant: &'de str,
+    value: Option<&'de ConstValue>,
+}
+
+impl<'de> EnumAccess<'de> for EnumRefDeserializer<'de> {
+    type Error = DeserializerError;
+    type Variant = VariantRefDeserializer<'de>;
+
+    #[inline]
+    fn variant_seed<V>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, VariantRefDeserializer<'de>), DeserializerError>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant = de::value::BorrowedStrDeserializer::new(self.variant);
+        let visitor = VariantRefDeserializer { value: self.value };
+        seed.deserialize(variant).map(|v| (v, visitor))
+    }
+}
+
+struct VariantRefDeserializer<'de> {
+    value: Option<&'de ConstValue>,
+}
+
+impl<'de> VariantAccess<'de> for VariantRefDeserializer<'de> {
+    type Error = DeserializerError;
+
+    #[inline]
+    fn unit_variant(self) -> Result<(), DeserializerError> {
+        match self.value {
+            Some(value) => Deserialize::deserialize(value),
+            None => Ok(()),
+        }
+    }
+
+    #[inline]
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, DeserializerError>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.value {
+            Some(value) => seed.deserialize(value),
+            None => Err(DeserializerError::invalid_type(
+                Unexpected::UnitVariant,
+                &"newtype variant",
+            )),
+        }
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, DeserializerError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Some(value @ ConstValue::List(_)) => {
+                serde::Deserializer::deserialize_any(value, visitor)
+            }
+            Some(other) => Err(serde::de::Error::invalid_type(
+                other.unexpected(),
+                &"tuple variant",
+            )),
+            None => Err(DeserializerError::invalid_type(
+                Unexpected::UnitVariant,
+                &"tuple variant",
+            )),
+        }
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeserializerError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Some(value @ ConstValue::Object(_)) => {
+                serde::Deserializer::deserialize_any(value, visitor)
+            }
+            Some(other) => Err(DeserializerError::invalid_type(
+                other.unexpected(),
+                &"struct variant",
+            )),
+            None => Err(DeserializerError::invalid_type(
+                Unexpected::UnitVariant,
+                &"struct variant",
+            )),
+        }
+    }
+}
+
+struct SeqRefDeserializer<'de> {
+    iter: slice::Iter<'de, ConstValue>,
+}
+
+impl<'de> SeqAccess<'de> for SeqRefDeserializer<'de> {
+    type Error = DeserializerError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, DeserializerError>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some(value) => seed.deserialize(value).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct MapRefDeserializer<'de> {
+    iter: indexmap::map::Iter<'de, Name, ConstValue>,
+    value: Option<&'de ConstValue>,
+}
+
+impl<'de> MapAccess<'de> for MapRefDeserializer<'de> {
+    type Error = DeserializerError;
+
+    fn next_key_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, DeserializerError>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(de::value::BorrowedStrDeserializer::new(key.as_str()))
+                    .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    #[inline]
+    fn next_value_seed<T>(&mut self, seed: T) -> Result<T::Value, DeserializerError>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.value.take() {
+            Some(value) => seed.deserialize(value),
+            None => Err(serde::de::Error::custom("value is missing")),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
 /// Interpret a `ConstValue` as an instance of type `T`.
+///
+/// This is the counterpart of `serde_json::from_value`.
+///
+/// # Examples
+///
+/// ```
+/// use async_graphql_value::{from_value, value};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize, Debug, PartialEq)]
+/// struct User {
+///     name: String,
+///     tags: Vec<String>,
+/// }
+///
+/// let user: User = from_value(value!({ "name": "alice", "tags": ["admin"] })).unwrap();
+/// assert_eq!(
+///     user,
+///     User {
+///         name: "alice".to_string(),
+///         tags: vec!["admin".to_string()],
+///     }
+/// );
+/// ```
+///
+/// # Errors
+///
+/// Fails if the structure of the value does not match the structure expected by `T`.
 #[inline]
 pub fn from_value<T: DeserializeOwned>(value: ConstValue) -> Result<T, DeserializerError> {
     T::deserialize(value)
 }
+
+/// Interpret a reference to a `ConstValue` as an instance of type `T`.
+///
+/// Unlike [`from_value`], this does not take the ownership of the value, and `T` can borrow
+/// strings and bytes from it.
+#[inline]
+pub fn from_value_ref<'a, T: Deserialize<'a>>(
+    value: &'a ConstValue,
+) -> Result<T, DeserializerError> {
+    T::deserialize(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::{to_value, value, ValueSerializer};
+
+    #[test]
+    fn test_from_value_ref() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        enum Kind<'a> {
+            A,
+            B(&'a str),
+            C { x: i32 },
+        }
+
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Borrowed<'a> {
+            name: &'a str,
+            tags: Vec<&'a str>,
+            kinds: Vec<Kind<'a>>,
+            count: Option<i32>,
+        }
+
+        let value = value!({
+            "name": "sunli",
+            "tags": ["a", "b"],
+            "kinds": ["A", { "B": "b" }, { "C": { "x": 1 } }],
+            "count": null,
+        });
+        assert_eq!(
+            from_value_ref::<Borrowed>(&value).unwrap(),
+            Borrowed {
+                name: "sunli",
+                tags: vec!["a", "b"],
+                kinds: vec![Kind::A, Kind::B("b"), Kind::C { x: 1 }],
+                count: None,
+            }
+        );
+        assert!(from_value_ref::<Borrowed>(&value!({ "name": 1 })).is_err());
+    }
+
+    #[test]
+    fn test_value_serializer_round_trip() {
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct Point {
+            x: i32,
+            y: Vec<f64>,
+        }
+
+        let point = Point {
+            x: 1,
+            y: vec![1.5, 2.0],
+        };
+        let value = point.serialize(ValueSerializer).unwrap();
+        assert_eq!(value, to_value(&point).unwrap());
+        assert_eq!(value, value!({ "x": 1, "y": [1.5, 2.0] }));
+        assert_eq!(from_value_ref::<Point>(&value).unwrap(), point);
+        assert_eq!(from_value::<Point>(value).unwrap(), point);
+    }
+}
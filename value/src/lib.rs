@@ -18,11 +18,11 @@ use bytes::Bytes;
 use indexmap::IndexMap;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-pub use deserializer::{from_value, DeserializerError};
+pub use deserializer::{from_value, from_value_ref, DeserializerError};
 #[doc(hidden)]
 pub use indexmap;
 pub use serde_json::Number;
-pub use serializer::{to_value, SerializerError};
+pub use serializer::{to_value, SerializerError, ValueSerializer};
 
 pub use variables::Variables;
 
@@ -480,7 +480,7 @@ fn write_quoted(s: &str, f: &mut Formatter<'_>) -> fmt::Result {
             '\t' => f.write_str("\\t"),
             '"' => f.write_str("\\\""),
             '\\' => f.write_str("\\\\"),
-            c if c.is_control() => write!(f, "\\u{:04}", c as u32),
+            c if c.is_control() => write!(f, "\\u{:04X}", c as u32),
             c => f.write_char(c),
         }?
     }
@@ -494,7 +494,7 @@ fn write_binary(bytes: &[u8], f: &mut Formatter<'_>) -> fmt::Result {
         value.fmt(f)?;
     }
     for value in iter {
-        f.write_char(',')?;
+        f.write_str(", ")?;
         value.fmt(f)?;
     }
     f.write_char(']')
@@ -507,7 +507,7 @@ fn write_list<T: Display>(list: impl IntoIterator<Item = T>, f: &mut Formatter<'
         item.fmt(f)?;
     }
     for item in iter {
-        f.write_char(',')?;
+        f.write_str(", ")?;
         item.fmt(f)?;
     }
     f.write_char(']')
@@ -523,8 +523,7 @@ fn write_object<K: Display, V: Display>(
         write!(f, "{}: {}", name, value)?;
     }
     for (name, value) in iter {
-        f.write_char(',')?;
-        write!(f, "{}: {}", name, value)?;
+        write!(f, ", {}: {}", name, value)?;
     }
     f.write_char('}')
 }
@@ -1,4 +1,20 @@
 /// Construct a `ConstValue`.
+///
+/// The syntax is the same as JSON, with two extensions:
+///
+/// - `..expr` inside an object inserts every entry of `expr`, which must serialize to an object.
+///   Keys written explicitly always take precedence over spread keys, so `{ "a": 1, ..defaults }`
+///   fills in the missing keys from `defaults`.
+/// - `..expr` inside a list inserts every item of `expr`, which can be any iterator of
+///   serializable values.
+///
+/// ```
+/// use async_graphql_value::value;
+///
+/// let defaults = value!({ "a": 1, "b": 2 });
+/// assert_eq!(value!({ "b": 10, ..defaults }), value!({ "a": 1, "b": 10 }));
+/// assert_eq!(value!([0, ..(1..3), 3]), value!([0, 1, 2, 3]));
+/// ```
 #[macro_export]
 macro_rules! value {
     ($($json:tt)+) => {
@@ -6,6 +22,28 @@ macro_rules! value {
     };
 }
 
+/// Construct `Variables` from key-value pairs, using the same syntax as the entries of an object
+/// in [`value!`].
+///
+/// ```
+/// use async_graphql_value::{value, vars};
+///
+/// let variables = vars! {
+///     "id": 10,
+///     "filter": { "tags": ["a", "b"] },
+/// };
+/// assert_eq!(variables.into_value(), value!({ "id": 10, "filter": { "tags": ["a", "b"] } }));
+/// ```
+#[macro_export]
+macro_rules! vars {
+    () => {
+        $crate::Variables::default()
+    };
+    ($($tt:tt)+) => {
+        $crate::Variables::from_value($crate::value_internal!({ $($tt)+ }))
+    };
+}
+
 #[macro_export]
 #[doc(hidden)]
 macro_rules! value_internal {
@@ -44,6 +82,13 @@ macro_rules! value_internal {
         $crate::value_internal!(@array [$($elems,)* $crate::value_internal!({$($map)*})] $($rest)*)
     };
 
+    // Next element is a spread. Switch to pushing the remaining elements into a list.
+    (@array [$($elems:expr,)*] .. $($rest:tt)*) => {{
+        let mut list = $crate::value_internal_vec![$($elems,)*];
+        $crate::value_internal!(@list list (.. $($rest)*));
+        list
+    }};
+
     // Next element is an expression followed by comma.
     (@array [$($elems:expr,)*] $next:expr, $($rest:tt)*) => {
         $crate::value_internal!(@array [$($elems,)* $crate::value_internal!($next),] $($rest)*)
@@ -64,9 +109,86 @@ macro_rules! value_internal {
         $crate::value_unexpected!($unexpected)
     };
 
+    // Done with the remaining list elements.
+    (@list $list:ident ()) => {};
+
+    // Comma after the most recent list element.
+    (@list $list:ident (, $($rest:tt)*)) => {
+        $crate::value_internal!(@list $list ($($rest)*));
+    };
+
+    // Next list element is a spread followed by comma.
+    (@list $list:ident (.. $iter:expr , $($rest:tt)*)) => {
+        $crate::value_internal!(@list $list (.. $iter));
+        $crate::value_internal!(@list $list ($($rest)*));
+    };
+
+    // Last list element is a spread with no trailing comma.
+    (@list $list:ident (.. $iter:expr)) => {
+        $list.extend(
+            ::std::iter::IntoIterator::into_iter($iter).map(|item| $crate::to_value(&item).unwrap()),
+        );
+    };
+
+    // Next list element is `null`, `true`, `false`, an array or a map.
+    (@list $list:ident (null $($rest:tt)*)) => {
+        $list.push($crate::value_internal!(null));
+        $crate::value_internal!(@list $list ($($rest)*));
+    };
+    (@list $list:ident (true $($rest:tt)*)) => {
+        $list.push($crate::value_internal!(true));
+        $crate::value_internal!(@list $list ($($rest)*));
+    };
+    (@list $list:ident (false $($rest:tt)*)) => {
+        $list.push($crate::value_internal!(false));
+        $crate::value_internal!(@list $list ($($rest)*));
+    };
+    (@list $list:ident ([$($array:tt)*] $($rest:tt)*)) => {
+        $list.push($crate::value_internal!([$($array)*]));
+        $crate::value_internal!(@list $list ($($rest)*));
+    };
+    (@list $list:ident ({$($map:tt)*} $($rest:tt)*)) => {
+        $list.push($crate::value_internal!({$($map)*}));
+        $crate::value_internal!(@list $list ($($rest)*));
+    };
+
+    // Next list element is an expression followed by comma.
+    (@list $list:ident ($next:expr , $($rest:tt)*)) => {
+        $list.push($crate::value_internal!($next));
+        $crate::value_internal!(@list $list ($($rest)*));
+    };
+
+    // Last list element is an expression with no trailing comma.
+    (@list $list:ident ($last:expr)) => {
+        $list.push($crate::value_internal!($last));
+    };
+
+    // Insert all entries of a spread object which are not already present.
+    (@spread $object:ident ($spread:expr)) => {
+        match $crate::to_value(&$spread).unwrap() {
+            $crate::ConstValue::Object(map) => {
+                for (key, value) in map {
+                    $object.entry(key).or_insert(value);
+                }
+            }
+            _ => panic!("only objects can be spread into an object"),
+        }
+    };
+
     // Done.
     (@object $object:ident () () ()) => {};
 
+    // Next entry is a spread followed by comma.
+    (@object $object:ident () (.. $spread:expr , $($rest:tt)*) $copy:tt) => {
+        $crate::value_internal!(@spread $object ($spread));
+        $crate::value_internal!(@object $object () ($($rest)*) ($($rest)*));
+    };
+
+    // Last entry is a spread with no trailing comma.
+    (@object $object:ident () (.. $spread:expr) $copy:tt) => {
+        $crate::value_internal!(@spread $object ($spread));
+    };
+
     // Insert the current entry followed by trailing comma.
     (@object $object:ident [$($key:tt)+] ($value:expr) , $($rest:tt)*) => {
         let _ = $object.insert($crate::Name::new($($key)+), $value);
@@ -260,4 +382,41 @@ mod tests {
             ConstValue::Object(map)
         });
     }
+
+    #[test]
+    fn test_macro_spread_object() {
+        let base = value!({ "a": 1, "b": 2 });
+        assert_eq!(value!({ ..base }), base);
+        assert_eq!(
+            value!({ "c": 3, ..base.clone(), "d": [base.clone()] }),
+            value!({ "c": 3, "a": 1, "b": 2, "d": [{ "a": 1, "b": 2 }] })
+        );
+        assert_eq!(value!({ ..base, "a": 10 }), value!({ "a": 10, "b": 2 }));
+        assert_eq!(value!({ "a": 10, ..base }), value!({ "a": 10, "b": 2 }));
+        assert_eq!(
+            value!({ "a": 10, ..base, ..value!({ "b": 20, "c": 30 }), }),
+            value!({ "a": 10, "b": 2, "c": 30 })
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_macro_spread_object_not_object() {
+        let _ = value!({ ..[1, 2] });
+    }
+
+    #[test]
+    fn test_macro_spread_list() {
+        assert_eq!(value!([..(1..4)]), value!([1, 2, 3]));
+        assert_eq!(value!([..Vec::<i32>::new()]), value!([]));
+        assert_eq!(
+            value!([null, true, ..vec!["a", "b"], false, [1], { "a": 1 }, 5, ..(1..3),]),
+            value!([null, true, "a", "b", false, [1], { "a": 1 }, 5, 1, 2])
+        );
+        let names = ["x", "y"];
+        assert_eq!(
+            value!([..names.iter().map(|name| value!({ "name": name }))]),
+            value!([{ "name": "x" }, { "name": "y" }])
+        );
+    }
 }
@@ -32,14 +32,46 @@ impl ser::Error for SerializerError {
 }
 
 /// Convert a `T` into `ConstValue` which is an enum that can represent any valid GraphQL data.
+///
+/// This is the counterpart of `serde_json::to_value`.
+///
+/// # Examples
+///
+/// ```
+/// use async_graphql_value::{to_value, value};
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct User {
+///     name: String,
+///     tags: Vec<&'static str>,
+/// }
+///
+/// let user = User {
+///     name: "alice".to_string(),
+///     tags: vec!["admin"],
+/// };
+/// assert_eq!(
+///     to_value(&user).unwrap(),
+///     value!({ "name": "alice", "tags": ["admin"] })
+/// );
+/// ```
+///
+/// # Errors
+///
+/// Fails if `T` is a map with non-string keys, or if its `Serialize` implementation fails.
 #[inline]
 pub fn to_value<T: ser::Serialize>(value: T) -> Result<ConstValue, SerializerError> {
-    value.serialize(Serializer)
+    value.serialize(ValueSerializer)
 }
 
-struct Serializer;
+/// A serializer that converts a value into a `ConstValue`.
+///
+/// [`to_value`] is a shortcut of `value.serialize(ValueSerializer)`.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct ValueSerializer;
 
-impl ser::Serializer for Serializer {
+impl ser::Serializer for ValueSerializer {
     type Ok = ConstValue;
     type Error = SerializerError;
     type SerializeSeq = SerializeSeq;
@@ -253,7 +285,7 @@ impl ser::Serializer for Serializer {
     }
 }
 
-struct SerializeSeq(Vec<ConstValue>);
+pub struct SerializeSeq(Vec<ConstValue>);
 
 impl ser::SerializeSeq for SerializeSeq {
     type Ok = ConstValue;
@@ -264,7 +296,7 @@ impl ser::SerializeSeq for SerializeSeq {
     where
         T: ser::Serialize,
     {
-        let value = value.serialize(Serializer)?;
+        let value = value.serialize(ValueSerializer)?;
         self.0.push(value);
         Ok(())
     }
@@ -275,7 +307,7 @@ impl ser::SerializeSeq for SerializeSeq {
     }
 }
 
-struct SerializeTuple(Vec<ConstValue>);
+pub struct SerializeTuple(Vec<ConstValue>);
 
 impl ser::SerializeTuple for SerializeTuple {
     type Ok = ConstValue;
@@ -286,7 +318,7 @@ impl ser::SerializeTuple for SerializeTuple {
     where
         T: ser::Serialize,
     {
-        let value = value.serialize(Serializer)?;
+        let value = value.serialize(ValueSerializer)?;
         self.0.push(value);
         Ok(())
     }
@@ -297,7 +329,7 @@ impl ser::SerializeTuple for SerializeTuple {
     }
 }
 
-struct SerializeTupleStruct(Vec<ConstValue>);
+pub struct SerializeTupleStruct(Vec<ConstValue>);
 
 impl ser::SerializeTupleStruct for SerializeTupleStruct {
     type Ok = ConstValue;
@@ -308,7 +340,7 @@ impl ser::SerializeTupleStruct for SerializeTupleStruct {
     where
         T: ser::Serialize,
     {
-        let value = value.serialize(Serializer)?;
+        let value = value.serialize(ValueSerializer)?;
         self.0.push(value);
         Ok(())
     }
@@ -319,7 +351,7 @@ impl ser::SerializeTupleStruct for SerializeTupleStruct {
     }
 }
 
-struct SerializeTupleVariant(Name, Vec<ConstValue>);
+pub struct SerializeTupleVariant(Name, Vec<ConstValue>);
 
 impl ser::SerializeTupleVariant for SerializeTupleVariant {
     type Ok = ConstValue;
@@ -330,7 +362,7 @@ impl ser::SerializeTupleVariant for SerializeTupleVariant {
     where
         T: ser::Serialize,
     {
-        let value = value.serialize(Serializer)?;
+        let value = value.serialize(ValueSerializer)?;
         self.1.push(value);
         Ok(())
     }
@@ -343,7 +375,7 @@ impl ser::SerializeTupleVariant for SerializeTupleVariant {
     }
 }
 
-struct SerializeMap {
+pub struct SerializeMap {
     map: IndexMap<Name, ConstValue>,
     key: Option<Name>,
 }
@@ -367,7 +399,7 @@ impl ser::SerializeMap for SerializeMap {
     where
         T: ser::Serialize,
     {
-        let value = value.serialize(Serializer)?;
+        let value = value.serialize(ValueSerializer)?;
         self.map.insert(self.key.take().unwrap(), value);
         Ok(())
     }
@@ -378,7 +410,7 @@ impl ser::SerializeMap for SerializeMap {
     }
 }
 
-struct SerializeStruct(IndexMap<Name, ConstValue>);
+pub struct SerializeStruct(IndexMap<Name, ConstValue>);
 
 impl ser::SerializeStruct for SerializeStruct {
     type Ok = ConstValue;
@@ -394,7 +426,7 @@ impl ser::SerializeStruct for SerializeStruct {
         T: ser::Serialize,
     {
         let key = Name::new(key);
-        let value = value.serialize(Serializer)?;
+        let value = value.serialize(ValueSerializer)?;
         self.0.insert(key, value);
         Ok(())
     }
@@ -405,7 +437,7 @@ impl ser::SerializeStruct for SerializeStruct {
     }
 }
 
-struct SerializeStructVariant(Name, IndexMap<Name, ConstValue>);
+pub struct SerializeStructVariant(Name, IndexMap<Name, ConstValue>);
 
 impl ser::SerializeStructVariant for SerializeStructVariant {
     type Ok = ConstValue;
@@ -421,7 +453,7 @@ impl ser::SerializeStructVariant for SerializeStructVariant {
         T: ser::Serialize,
     {
         let key = Name::new(key);
-        let value = value.serialize(Serializer)?;
+        let value = value.serialize(ValueSerializer)?;
         self.1.insert(key, value);
         Ok(())
     }
@@ -2,9 +2,10 @@ use std::collections::BTreeMap;
 use std::fmt::{self, Display, Formatter};
 use std::ops::{Deref, DerefMut};
 
+use serde::de::{DeserializeOwned, Error as DeError};
 use serde::{Deserialize, Deserializer, Serialize};
 
-use crate::{ConstValue, Name};
+use crate::{from_value, to_value, ConstValue, DeserializerError, Name, SerializerError};
 
 /// Variables of a query.
 #[derive(Debug, Clone, Default, Serialize)]
@@ -52,6 +53,34 @@ impl Variables {
             .unwrap_or_default()
     }
 
+    /// Get the variables from a type that implements `serde::Serialize`.
+    ///
+    /// If the value does not serialize to a map, then no variables will be returned.
+    ///
+    /// # Errors
+    ///
+    /// Fails if serialization fails.
+    pub fn from_serialize<T: Serialize>(value: T) -> Result<Self, SerializerError> {
+        to_value(value).map(Self::from_value)
+    }
+
+    /// Get a variable and deserialize it into a type that implements `serde::Deserialize`.
+    ///
+    /// A missing variable is treated as `null`, so `Option<T>` can be used for optional
+    /// variables.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the variable cannot be deserialized into `T`, or if it is missing and `T` does not
+    /// accept `null`.
+    pub fn get_typed<T: DeserializeOwned>(&self, name: &str) -> Result<T, DeserializerError> {
+        match self.0.get(name) {
+            Some(value) => from_value(value.clone()),
+            None => from_value(ConstValue::Null)
+                .map_err(|_| DeserializerError::custom(format!("missing variable \"{}\"", name))),
+        }
+    }
+
     /// Get the variables as a GraphQL value.
     #[must_use]
     pub fn into_value(self) -> ConstValue {
@@ -78,3 +107,53 @@ impl DerefMut for Variables {
         &mut self.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::{value, vars};
+
+    #[test]
+    fn test_from_serialize() {
+        #[derive(Serialize)]
+        struct MyVariables {
+            id: i32,
+            name: &'static str,
+        }
+
+        let variables = Variables::from_serialize(MyVariables {
+            id: 10,
+            name: "abc",
+        })
+        .unwrap();
+        assert_eq!(variables.into_value(), value!({ "id": 10, "name": "abc" }));
+        assert!(Variables::from_serialize(10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_get_typed() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Filter {
+            limit: i32,
+        }
+
+        let variables = vars! {
+            "id": 10,
+            "filter": { "limit": 5 },
+        };
+        assert_eq!(variables.get_typed::<i32>("id").unwrap(), 10);
+        assert_eq!(
+            variables.get_typed::<Filter>("filter").unwrap(),
+            Filter { limit: 5 }
+        );
+        assert_eq!(variables.get_typed::<Option<i32>>("a").unwrap(), None);
+        assert_eq!(
+            variables.get_typed::<i32>("a").unwrap_err().to_string(),
+            r#"missing variable "a""#
+        );
+        assert!(variables.get_typed::<String>("id").is_err());
+        assert!(vars!().is_empty());
+    }
+}